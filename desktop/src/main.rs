@@ -10,7 +10,9 @@ use dioxus::prelude::*;
 use ui::components::app_navbar::{register_nav, NavBuilder};
 use ui::components::AppNavbar;
 
-use ui::views::{Glucose, Home, NBack2, Pvt, Results};
+use ui::views::{
+    Dsst, Flanker, Glucose, Home, Import, Journal, NBack2, Pvt, Results, TaskSwitch, WordRecall,
+};
 
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
@@ -22,10 +24,22 @@ enum Route {
     Pvt {},
     #[route("/test/nback")]
     NBack2 {},
+    #[route("/test/flanker")]
+    Flanker {},
+    #[route("/test/dsst")]
+    Dsst {},
+    #[route("/test/word-recall")]
+    WordRecall {},
+    #[route("/test/task-switch")]
+    TaskSwitch {},
     #[route("/results")]
     Results {},
     #[route("/glucose")]
     Glucose {},
+    #[route("/journal")]
+    Journal {},
+    #[route("/import")]
+    Import {},
 }
 
 const MAIN_CSS_INLINE: &str = include_str!(concat!(
@@ -76,7 +90,7 @@ fn main() {
 #[cfg(feature = "desktop")]
 fn init_health_store() {
     use looplace_store::migrate::{run_upgrade, MigrationOutcome, MigrationPlan};
-    use looplace_store::ParquetStore;
+    use looplace_store::{ParquetStore, StoreError};
 
     let data_dir = match ui::core::storage::data_dir() {
         Ok(dir) => dir,
@@ -88,6 +102,11 @@ fn init_health_store() {
 
     let mut store = match ParquetStore::open(data_dir.join("looplace.parquet")) {
         Ok(store) => store,
+        // Encrypted stores were already migrated before the user could encrypt.
+        Err(StoreError::Encrypted) => {
+            eprintln!("[store] health store is encrypted; skipping migration");
+            return;
+        }
         Err(err) => {
             eprintln!("[store] could not open health store: {err}");
             return;
@@ -127,12 +146,30 @@ fn nav_pvt(label: &str) -> Element {
 fn nav_nback(label: &str) -> Element {
     rsx!(Link { class: "navbar__link", to: Route::NBack2 {}, "{label}" })
 }
+fn nav_flanker(label: &str) -> Element {
+    rsx!(Link { class: "navbar__link", to: Route::Flanker {}, "{label}" })
+}
+fn nav_dsst(label: &str) -> Element {
+    rsx!(Link { class: "navbar__link", to: Route::Dsst {}, "{label}" })
+}
+fn nav_word_recall(label: &str) -> Element {
+    rsx!(Link { class: "navbar__link", to: Route::WordRecall {}, "{label}" })
+}
+fn nav_task_switch(label: &str) -> Element {
+    rsx!(Link { class: "navbar__link", to: Route::TaskSwitch {}, "{label}" })
+}
 fn nav_results(label: &str) -> Element {
     rsx!(Link { class: "navbar__link", to: Route::Results {}, "{label}" })
 }
 fn nav_glucose(label: &str) -> Element {
     rsx!(Link { class: "navbar__link", to: Route::Glucose {}, "{label}" })
 }
+fn nav_journal(label: &str) -> Element {
+    rsx!(Link { class: "navbar__link", to: Route::Journal {}, "{label}" })
+}
+fn nav_import(label: &str) -> Element {
+    rsx!(Link { class: "navbar__link", to: Route::Import {}, "{label}" })
+}
 
 #[component]
 fn App() -> Element {
@@ -144,13 +181,23 @@ fn App() -> Element {
     let lang_code = use_signal(|| "en-US".to_string());
     use_context_provider(|| lang_code);
 
+    // One long-lived handle on the local store (and its change feed) for
+    // every view; the store stays open for the app's lifetime.
+    ui::core::store::use_store_provider();
+
     // Register localized navigation builder (desktop)
     register_nav(NavBuilder {
         home: nav_home,
         pvt: nav_pvt,
         nback: nav_nback,
+        flanker: nav_flanker,
+        dsst: nav_dsst,
+        word_recall: nav_word_recall,
+        task_switch: nav_task_switch,
         results: nav_results,
         glucose: nav_glucose,
+        journal: nav_journal,
+        import: nav_import,
     });
 
     // Runtime maximize fallback (in case initial builder maximize is ignored by WM)
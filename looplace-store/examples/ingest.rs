@@ -15,10 +15,13 @@ fn main() {
     use std::collections::BTreeMap;
     use std::path::PathBuf;
 
+    use looplace_store::convert::cgm::skip_known_readings;
     use looplace_store::convert::reading_to_observation;
     use looplace_store::migrate::{
         run_upgrade, MigrationOutcome, MigrationPlan, LEGACY_FILE, MARKER_FILE,
     };
+    use looplace_store::provenance::{self, IngestBatch, READER_SYNC};
+    use looplace_store::snapshot::SNAPSHOT_DIR;
     use looplace_store::{ParquetStore, Query, Store};
 
     let out_path: PathBuf = std::env::args()
@@ -53,6 +56,8 @@ fn main() {
             legacy_summaries: legacy_summaries.clone(),
             backup_path: out_dir.join(format!("{LEGACY_FILE}.pre-store-backup-demo")),
             marker: out_dir.join(MARKER_FILE),
+            snapshot_dir: Some(out_dir.join(SNAPSHOT_DIR)),
+            prefs: Vec::new(),
         };
         match run_upgrade(&plan, &mut store) {
             Ok(MigrationOutcome::Migrated(r)) => eprintln!(
@@ -77,6 +82,10 @@ fn main() {
     match open_and_connect() {
         Ok(mut device) => {
             let serial = device.serial_number().unwrap_or_else(|_| "unknown".into());
+            let app = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"), " ingest");
+            let mut batch = IngestBatch::new(READER_SYNC, app);
+            batch.device_firmware = device.software_version().ok();
+            batch.detail = Some(serial.clone());
             let tz = iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".into());
             match device.read_all() {
                 Ok(readings) => {
@@ -84,7 +93,8 @@ fn main() {
                         .iter()
                         .filter_map(|r| reading_to_observation(r, &serial, &tz))
                         .collect();
-                    match store.upsert(&observations) {
+                    let fresh = skip_known_readings(&store, observations.clone());
+                    match fresh.and_then(|fresh| provenance::ingest(&mut store, &batch, fresh)) {
                         Ok(new) => eprintln!(
                             "✓ ingested {} glucose observations ({new} new) from reader {serial}",
                             observations.len()
@@ -0,0 +1,481 @@
+//! Aggregation and resampling over observations.
+//!
+//! One tested implementation of bucketing for every consumer: resample a
+//! stream to fixed intervals (5/15/60 min, day), fold days together into a
+//! time-of-day profile, or summarize everything at once — optionally grouped
+//! by a tag — computing count/mean/median/min/max/percentiles per bucket.
+//!
+//! Results are themselves [`Observation`]s, one derived series per input
+//! stream and statistic, named `<stream>.<stat>_<bucket>`
+//! (`glucose.mg_dl.mean_15m`, `glucose.mg_dl.p90_tod1h`, `pvt.median_rt_ms.count_all`).
+//! Each row is stamped at its bucket's start, carries the number of inputs in
+//! an `n` tag (and the group value under the grouping tag's key), and has
+//! [`AGGREGATE_SOURCE`] as its source — so it can be charted or joined like any
+//! other series. Derived series are computed on demand, not stored: the
+//! [`streams`](crate::streams) registry doesn't know their names, so a store
+//! refuses them with [`StoreError::UnknownStream`].
+//!
+//! Timestamps are bucketed on their wall-clock value as stored (see the crate
+//! docs on the time basis); no zone conversion happens here.
+//!
+//! [`StoreError::UnknownStream`]: crate::StoreError::UnknownStream
+
+use std::collections::BTreeMap;
+
+use time::macros::date;
+use time::{Duration, PrimitiveDateTime, Time};
+
+use crate::error::Result;
+use crate::observation::{Observation, Query};
+use crate::store::Store;
+
+/// `source` of every aggregated row.
+pub const AGGREGATE_SOURCE: &str = "looplace.aggregate";
+
+/// Date that time-of-day buckets are anchored to (only the time is meaningful).
+pub const TIME_OF_DAY_ANCHOR: time::Date = date!(1970 - 01 - 01);
+
+/// A fixed bucket width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    /// Whole minutes, aligned to the Unix epoch (so 15-minute buckets start on
+    /// :00/:15/:30/:45). `0` is treated as `1`.
+    Minutes(u32),
+    /// Calendar days, midnight to midnight.
+    Day,
+}
+
+impl Interval {
+    pub const FIVE_MINUTES: Interval = Interval::Minutes(5);
+    pub const FIFTEEN_MINUTES: Interval = Interval::Minutes(15);
+    pub const HOUR: Interval = Interval::Minutes(60);
+
+    /// Short label used in derived stream names: `5m`, `15m`, `1h`, `1d`.
+    pub fn label(self) -> String {
+        match self {
+            Interval::Minutes(m) if m > 0 && m % 60 == 0 => format!("{}h", m / 60),
+            Interval::Minutes(m) => format!("{}m", m.max(1)),
+            Interval::Day => "1d".into(),
+        }
+    }
+
+    fn minutes(self) -> i64 {
+        match self {
+            Interval::Minutes(m) => i64::from(m.max(1)),
+            Interval::Day => 24 * 60,
+        }
+    }
+}
+
+/// How observations are assigned to buckets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bucket {
+    /// Consecutive fixed intervals along the timeline.
+    Interval(Interval),
+    /// Intervals of the day, with every day folded together (e.g. hourly
+    /// profile across a fortnight). Stamped on [`TIME_OF_DAY_ANCHOR`].
+    TimeOfDay(Interval),
+    /// Everything in one bucket, stamped at the earliest input.
+    All,
+}
+
+impl Bucket {
+    fn label(self) -> String {
+        match self {
+            Bucket::Interval(i) => i.label(),
+            Bucket::TimeOfDay(i) => format!("tod{}", i.label()),
+            Bucket::All => "all".into(),
+        }
+    }
+
+    /// Start of the bucket holding `t` (`None` for [`Bucket::All`]).
+    fn start(self, t: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        match self {
+            Bucket::Interval(Interval::Day) => Some(t.date().midnight()),
+            Bucket::Interval(interval) => {
+                let width = interval.minutes() * 60;
+                let secs = t.assume_utc().unix_timestamp();
+                let floored = secs.div_euclid(width) * width;
+                let epoch = PrimitiveDateTime::new(TIME_OF_DAY_ANCHOR, Time::MIDNIGHT);
+                Some(epoch + Duration::seconds(floored))
+            }
+            Bucket::TimeOfDay(interval) => {
+                let minute = i64::from(t.hour()) * 60 + i64::from(t.minute());
+                let width = interval.minutes().min(24 * 60);
+                let floored = minute / width * width;
+                let anchor = PrimitiveDateTime::new(TIME_OF_DAY_ANCHOR, Time::MIDNIGHT);
+                Some(anchor + Duration::minutes(floored))
+            }
+            Bucket::All => None,
+        }
+    }
+}
+
+/// A per-bucket statistic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stat {
+    Count,
+    Mean,
+    Median,
+    Min,
+    Max,
+    /// Linearly interpolated percentile, `0.0..=100.0` (clamped).
+    Percentile(f64),
+}
+
+impl Stat {
+    /// Short label used in derived stream names: `mean`, `p90`, `p2_5`.
+    pub fn label(self) -> String {
+        match self {
+            Stat::Count => "count".into(),
+            Stat::Mean => "mean".into(),
+            Stat::Median => "median".into(),
+            Stat::Min => "min".into(),
+            Stat::Max => "max".into(),
+            Stat::Percentile(p) => format!("p{}", p.clamp(0.0, 100.0)).replace('.', "_"),
+        }
+    }
+
+    /// Apply to `sorted` (ascending, finite, non-empty).
+    fn apply(self, sorted: &[f64]) -> f64 {
+        match self {
+            Stat::Count => sorted.len() as f64,
+            Stat::Mean => sorted.iter().sum::<f64>() / sorted.len() as f64,
+            Stat::Median => percentile(sorted, 50.0),
+            Stat::Min => sorted[0],
+            Stat::Max => sorted[sorted.len() - 1],
+            Stat::Percentile(p) => percentile(sorted, p),
+        }
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+/// What to compute: a bucketing, the statistics per bucket, and optionally a
+/// tag to split each bucket by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregation {
+    pub bucket: Bucket,
+    pub stats: Vec<Stat>,
+    /// Tag key to group by; rows without the tag form their own group.
+    pub group_by: Option<String>,
+}
+
+impl Aggregation {
+    /// Resample to fixed intervals along the timeline.
+    pub fn resample(interval: Interval, stats: &[Stat]) -> Self {
+        Self::new(Bucket::Interval(interval), stats)
+    }
+
+    /// Fold days together into a time-of-day profile.
+    pub fn time_of_day(interval: Interval, stats: &[Stat]) -> Self {
+        Self::new(Bucket::TimeOfDay(interval), stats)
+    }
+
+    /// One summary over everything.
+    pub fn overall(stats: &[Stat]) -> Self {
+        Self::new(Bucket::All, stats)
+    }
+
+    fn new(bucket: Bucket, stats: &[Stat]) -> Self {
+        Self {
+            bucket,
+            stats: stats.to_vec(),
+            group_by: None,
+        }
+    }
+
+    /// Split each bucket by the value of tag `key` (e.g. `kind`, `food`).
+    pub fn group_by(mut self, key: impl Into<String>) -> Self {
+        self.group_by = Some(key.into());
+        self
+    }
+
+    /// Name of the derived series for `stream` and `stat`.
+    pub fn series_name(&self, stream: &str, stat: Stat) -> String {
+        format!("{stream}.{}_{}", stat.label(), self.bucket.label())
+    }
+}
+
+/// Aggregate `rows` (any mix of streams; each is aggregated separately).
+/// Non-finite values are ignored. Output is ordered by timestamp, then series
+/// name, then group value.
+pub fn aggregate(rows: &[Observation], aggregation: &Aggregation) -> Vec<Observation> {
+    struct Cell<'a> {
+        start: PrimitiveDateTime,
+        unit: &'a str,
+        values: Vec<f64>,
+    }
+
+    // (stream, group value, bucket start) → values. For `All`, the start is
+    // the earliest timestamp seen, so the key's bucket slot is `None`.
+    let mut cells: BTreeMap<(&str, Option<&str>, Option<PrimitiveDateTime>), Cell> =
+        BTreeMap::new();
+    for row in rows.iter().filter(|o| o.value.is_finite()) {
+        let group = aggregation
+            .group_by
+            .as_deref()
+            .and_then(|key| row.tags.get(key))
+            .map(String::as_str);
+        let start = aggregation.bucket.start(row.timestamp);
+        let cell = cells
+            .entry((row.stream.as_str(), group, start))
+            .or_insert_with(|| Cell {
+                start: start.unwrap_or(row.timestamp),
+                unit: &row.unit,
+                values: Vec::new(),
+            });
+        cell.start = cell.start.min(start.unwrap_or(row.timestamp));
+        cell.values.push(row.value);
+    }
+
+    let mut out = Vec::with_capacity(cells.len() * aggregation.stats.len());
+    for ((stream, group, _), mut cell) in cells {
+        cell.values.sort_by(f64::total_cmp);
+        for &stat in &aggregation.stats {
+            let unit = if stat == Stat::Count {
+                "count"
+            } else {
+                cell.unit
+            };
+            let mut o = Observation::new(
+                aggregation.series_name(stream, stat),
+                cell.start,
+                stat.apply(&cell.values),
+                unit,
+                AGGREGATE_SOURCE,
+            );
+            o.tags.insert("n".into(), cell.values.len().to_string());
+            if let (Some(key), Some(value)) = (&aggregation.group_by, group) {
+                o.tags.insert(key.clone(), value.to_string());
+            }
+            out.push(o);
+        }
+    }
+    let group_value = |o: &Observation| {
+        aggregation
+            .group_by
+            .as_ref()
+            .and_then(|key| o.tags.get(key))
+            .cloned()
+    };
+    out.sort_by(|a, b| {
+        (a.timestamp, &a.stream)
+            .cmp(&(b.timestamp, &b.stream))
+            .then_with(|| group_value(a).cmp(&group_value(b)))
+    });
+    out
+}
+
+/// [`aggregate`] the rows of `store` matching `query`.
+pub fn aggregate_query(
+    store: &dyn Store,
+    query: &Query,
+    aggregation: &Aggregation,
+) -> Result<Vec<Observation>> {
+    Ok(aggregate(&store.query(query)?, aggregation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::StoreError;
+    use crate::store::MemoryStore;
+    use time::macros::datetime;
+
+    fn glucose(t: PrimitiveDateTime, value: f64) -> Observation {
+        Observation::new("glucose.mg_dl", t, value, "mg/dL", "dev")
+    }
+
+    fn values(rows: &[Observation], stream: &str) -> Vec<f64> {
+        rows.iter()
+            .filter(|o| o.stream == stream)
+            .map(|o| o.value)
+            .collect()
+    }
+
+    #[test]
+    fn resamples_to_aligned_fixed_intervals() {
+        let rows = [
+            glucose(datetime!(2026-06-19 08:01:00), 100.0),
+            glucose(datetime!(2026-06-19 08:14:59), 110.0),
+            glucose(datetime!(2026-06-19 08:15:00), 120.0),
+            glucose(datetime!(2026-06-19 08:44:00), 90.0),
+        ];
+        let agg = Aggregation::resample(Interval::FIFTEEN_MINUTES, &[Stat::Mean, Stat::Count]);
+        let out = aggregate(&rows, &agg);
+
+        let means: Vec<_> = out
+            .iter()
+            .filter(|o| o.stream == "glucose.mg_dl.mean_15m")
+            .map(|o| (o.timestamp, o.value))
+            .collect();
+        // Empty buckets are absent, not zero.
+        assert_eq!(
+            means,
+            vec![
+                (datetime!(2026-06-19 08:00:00), 105.0),
+                (datetime!(2026-06-19 08:15:00), 120.0),
+                (datetime!(2026-06-19 08:30:00), 90.0),
+            ]
+        );
+        assert_eq!(values(&out, "glucose.mg_dl.count_15m"), vec![2.0, 1.0, 1.0]);
+
+        // Same bucket: series sort by name, so `count` precedes `mean`.
+        let (count, mean) = (&out[0], &out[1]);
+        assert_eq!((count.unit.as_str(), mean.unit.as_str()), ("count", "mg/dL"));
+        assert_eq!(mean.source, AGGREGATE_SOURCE);
+        assert_eq!(mean.tags.get("n").map(String::as_str), Some("2"));
+    }
+
+    #[test]
+    fn hourly_and_daily_buckets() {
+        let rows = [
+            glucose(datetime!(2026-06-19 08:59:00), 100.0),
+            glucose(datetime!(2026-06-19 09:00:00), 140.0),
+            glucose(datetime!(2026-06-20 00:00:00), 80.0),
+        ];
+        let hourly = aggregate(&rows, &Aggregation::resample(Interval::HOUR, &[Stat::Max]));
+        assert_eq!(hourly.len(), 3);
+        assert_eq!(hourly[1].stream, "glucose.mg_dl.max_1h");
+        assert_eq!(hourly[1].timestamp, datetime!(2026-06-19 09:00:00));
+
+        let daily = aggregate(
+            &rows,
+            &Aggregation::resample(Interval::Day, &[Stat::Min, Stat::Max]),
+        );
+        assert_eq!(values(&daily, "glucose.mg_dl.min_1d"), vec![100.0, 80.0]);
+        assert_eq!(values(&daily, "glucose.mg_dl.max_1d"), vec![140.0, 80.0]);
+        assert_eq!(daily[0].timestamp, datetime!(2026-06-19 00:00:00));
+    }
+
+    #[test]
+    fn median_and_percentiles_interpolate() {
+        let rows: Vec<_> = [1.0, 2.0, 3.0, 4.0]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                glucose(
+                    datetime!(2026-06-19 08:00:00) + Duration::minutes(i as i64),
+                    *v,
+                )
+            })
+            .collect();
+        let stats = [
+            Stat::Median,
+            Stat::Percentile(0.0),
+            Stat::Percentile(90.0),
+            Stat::Percentile(2.5),
+        ];
+        let out = aggregate(&rows, &Aggregation::overall(&stats));
+        assert_eq!(values(&out, "glucose.mg_dl.median_all"), vec![2.5]);
+        assert_eq!(values(&out, "glucose.mg_dl.p0_all"), vec![1.0]);
+        assert!((values(&out, "glucose.mg_dl.p90_all")[0] - 3.7).abs() < 1e-9);
+        assert_eq!(
+            out.iter()
+                .filter(|o| o.stream == "glucose.mg_dl.p2_5_all")
+                .count(),
+            1
+        );
+        // `All` is stamped at the earliest input.
+        assert!(out
+            .iter()
+            .all(|o| o.timestamp == datetime!(2026-06-19 08:00:00)));
+    }
+
+    #[test]
+    fn time_of_day_folds_days_together() {
+        let rows = [
+            glucose(datetime!(2026-06-19 08:10:00), 100.0),
+            glucose(datetime!(2026-06-20 08:50:00), 120.0),
+            glucose(datetime!(2026-06-20 13:00:00), 150.0),
+        ];
+        let out = aggregate(
+            &rows,
+            &Aggregation::time_of_day(Interval::HOUR, &[Stat::Mean]),
+        );
+        let profile: Vec<_> = out.iter().map(|o| (o.timestamp.time(), o.value)).collect();
+        assert_eq!(
+            profile,
+            vec![
+                (Time::from_hms(8, 0, 0).unwrap(), 110.0),
+                (Time::from_hms(13, 0, 0).unwrap(), 150.0)
+            ]
+        );
+        assert!(out.iter().all(|o| o.timestamp.date() == TIME_OF_DAY_ANCHOR));
+        assert_eq!(out[0].stream, "glucose.mg_dl.mean_tod1h");
+    }
+
+    #[test]
+    fn groups_by_tag_and_keeps_streams_apart() {
+        let tagged = |t, v, kind: Option<&str>| {
+            let mut o = glucose(t, v);
+            if let Some(kind) = kind {
+                o.tags.insert("kind".into(), kind.into());
+            }
+            o
+        };
+        let rows = [
+            tagged(datetime!(2026-06-19 08:00:00), 100.0, Some("scan")),
+            tagged(datetime!(2026-06-19 08:05:00), 90.0, Some("sensor")),
+            tagged(datetime!(2026-06-19 08:10:00), 94.0, Some("sensor")),
+            tagged(datetime!(2026-06-19 08:20:00), 50.0, None),
+            Observation::new(
+                "pvt.median_rt_ms",
+                datetime!(2026-06-19 08:30:00),
+                300.0,
+                "ms",
+                "app",
+            ),
+            glucose(datetime!(2026-06-19 08:40:00), f64::NAN),
+        ];
+        let out = aggregate(&rows, &Aggregation::overall(&[Stat::Mean]).group_by("kind"));
+
+        let by_kind: Vec<_> = out
+            .iter()
+            .filter(|o| o.stream == "glucose.mg_dl.mean_all")
+            .map(|o| (o.tags.get("kind").cloned(), o.value))
+            .collect();
+        assert_eq!(
+            by_kind,
+            vec![
+                (Some("scan".into()), 100.0),
+                (Some("sensor".into()), 92.0),
+                (None, 50.0),
+            ]
+        );
+        assert_eq!(values(&out, "pvt.median_rt_ms.mean_all"), vec![300.0]);
+    }
+
+    #[test]
+    fn aggregates_straight_from_a_store() {
+        let mut store = MemoryStore::new();
+        store
+            .upsert(&[
+                glucose(datetime!(2026-06-19 08:00:00), 100.0),
+                glucose(datetime!(2026-06-19 08:03:00), 104.0),
+                Observation::new(
+                    "pvt.median_rt_ms",
+                    datetime!(2026-06-19 08:01:00),
+                    300.0,
+                    "ms",
+                    "app",
+                ),
+            ])
+            .unwrap();
+        let agg = Aggregation::resample(Interval::FIVE_MINUTES, &[Stat::Mean]);
+        let out = aggregate_query(&store, &Query::stream("glucose.mg_dl"), &agg).unwrap();
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].stream, "glucose.mg_dl.mean_5m");
+        assert_eq!(out[0].value, 102.0);
+
+        // Derived series aren't registered streams, so they can't be stored.
+        let refused = store.upsert(&out).unwrap_err();
+        assert!(matches!(refused, StoreError::UnknownStream(s) if s == "glucose.mg_dl.mean_5m"));
+    }
+}
@@ -0,0 +1,882 @@
+//! Portable export bundles: the whole store (or a subset of it) in one zip
+//! that other tools can read and another Looplace can import (behind the
+//! `parquet-store` feature).
+//!
+//! ```text
+//! looplace-bundle.zip
+//!   manifest.json          format, selection, row counts, column schemas,
+//!                          the stream registry, per-file size + SHA-256
+//!   observations.parquet   the observations table (same schema as the store)
+//!   observations.csv       the same rows, flat, for spreadsheets
+//!   sessions.parquet       cognition session records
+//!   trials.parquet         per-trial data behind those sessions, if kept
+//!   batches.parquet        the syncs and imports that wrote the rows
+//!   prefs/…                the app's preference files
+//! ```
+//!
+//! Journal entries are observations on the `journal.*` streams, so they travel
+//! with the table. Unlike a [`crate::snapshot`], which is a same-machine
+//! safety net, a bundle is meant to leave the machine: it is documented by its
+//! own manifest, never sealed, and can be cut down by stream and time range
+//! (say, the last three months of glucose for a clinician). [`import`]
+//! verifies every checksum and parses both tables before writing anything,
+//! then upserts, so re-importing a bundle is a no-op.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, PrimitiveDateTime};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::error::{Result, StoreError};
+use crate::observation::{Observation, Query};
+use crate::parquet_store::{
+    batches_from_parquet, batches_to_parquet, observations_from_parquet, observations_to_parquet,
+    sessions_from_parquet, sessions_to_parquet, trials_from_parquet, trials_to_parquet,
+};
+use crate::provenance::IngestBatch;
+use crate::session::SessionRecord;
+use crate::snapshot::{format_ts, RestoreReport, MANIFEST_FILE};
+use crate::store::Store;
+use crate::streams::{StreamSpec, STREAMS};
+use crate::trial::TrialRecord;
+
+/// Bundle layout version; [`read`] refuses anything newer. Format 2 added
+/// `batch_id` and the batches table, format 3 the trials table, format 4 the
+/// sessions' pause columns.
+pub const BUNDLE_FORMAT: u32 = 4;
+/// Conventional file extension for bundles.
+pub const BUNDLE_EXTENSION: &str = "zip";
+
+const OBSERVATIONS_PARQUET: &str = "observations.parquet";
+const OBSERVATIONS_CSV: &str = "observations.csv";
+const SESSIONS_PARQUET: &str = "sessions.parquet";
+const BATCHES_PARQUET: &str = "batches.parquet";
+const TRIALS_PARQUET: &str = "trials.parquet";
+const PREFS_DIR: &str = "prefs";
+
+/// What to put in a bundle. The default is everything but prefs.
+#[derive(Debug, Clone)]
+pub struct BundleOptions {
+    /// Only these streams; every stream if empty.
+    pub streams: Vec<String>,
+    /// Only observations at or after this (source-local) time.
+    pub since: Option<PrimitiveDateTime>,
+    /// Only observations at or before this time.
+    pub until: Option<PrimitiveDateTime>,
+    /// Include cognition sessions and their trials. With a stream or time
+    /// filter, only the sessions an exported observation belongs to are
+    /// included.
+    pub sessions: bool,
+    /// Preference files to include; missing ones are skipped.
+    pub prefs: Vec<std::path::PathBuf>,
+}
+
+impl Default for BundleOptions {
+    fn default() -> Self {
+        Self {
+            streams: Vec::new(),
+            since: None,
+            until: None,
+            sessions: true,
+            prefs: Vec::new(),
+        }
+    }
+}
+
+impl BundleOptions {
+    fn is_subset(&self) -> bool {
+        !self.streams.is_empty() || self.since.is_some() || self.until.is_some()
+    }
+}
+
+/// The self-description written into every bundle as `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: u32,
+    /// When the bundle was written (RFC3339, UTC).
+    pub created_at: String,
+    /// Crate name and version that wrote it.
+    pub generator: String,
+    pub selection: Selection,
+    pub observations: usize,
+    pub sessions: usize,
+    /// Ingest batches; absent from format 1 bundles.
+    #[serde(default)]
+    pub batches: usize,
+    /// Per-trial rows; absent before format 3.
+    #[serde(default)]
+    pub trials: usize,
+    /// Columns of each table, shared by its Parquet and CSV forms.
+    pub tables: Vec<TableSchema>,
+    /// The stream registry of the build that wrote the bundle.
+    pub streams: Vec<StreamEntry>,
+    pub files: Vec<BundleFile>,
+}
+
+/// The filter a bundle was cut with (empty and `None` mean everything).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Selection {
+    pub streams: Vec<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<ColumnSchema>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub nullable: bool,
+    pub description: String,
+}
+
+/// One [`StreamSpec`], as written to the manifest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamEntry {
+    pub name: String,
+    pub unit: String,
+    pub min: f64,
+    pub max: f64,
+    pub precision: u8,
+    pub higher_is_better: Option<bool>,
+    pub label_key: String,
+}
+
+impl From<&StreamSpec> for StreamEntry {
+    fn from(s: &StreamSpec) -> Self {
+        Self {
+            name: s.name.to_string(),
+            unit: s.unit.to_string(),
+            min: s.min,
+            max: s.max,
+            precision: s.precision,
+            higher_is_better: s.higher_is_better,
+            label_key: s.label_key.to_string(),
+        }
+    }
+}
+
+/// One file in a bundle, with the checksum [`read`] holds it to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// Path inside the zip.
+    pub path: String,
+    pub kind: BundleFileKind,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleFileKind {
+    Observations,
+    /// The observations table again, as CSV; [`import`] reads the Parquet.
+    ObservationsCsv,
+    Sessions,
+    Trials,
+    Batches,
+    Pref,
+}
+
+/// A bundle read back and verified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    pub manifest: BundleManifest,
+    pub observations: Vec<Observation>,
+    pub sessions: Vec<SessionRecord>,
+    pub batches: Vec<IngestBatch>,
+    pub trials: Vec<TrialRecord>,
+    /// Preference files by name, with their contents.
+    pub prefs: Vec<(String, Vec<u8>)>,
+}
+
+/// Write a bundle of `store` to `dest` (replacing any file there). Written to
+/// a sibling `.partial` file and renamed into place, so `dest` is never left
+/// half-written.
+pub fn export(store: &dyn Store, dest: &Path, options: &BundleOptions) -> Result<BundleManifest> {
+    let query = Query {
+        stream: None,
+        since: options.since,
+        until: options.until,
+        batch_id: None,
+    };
+    let observations: Vec<Observation> = store
+        .query(&query)?
+        .into_iter()
+        .filter(|o| options.streams.is_empty() || options.streams.contains(&o.stream))
+        .collect();
+    let sessions: Vec<SessionRecord> = if !options.sessions {
+        Vec::new()
+    } else if options.is_subset() {
+        let wanted: std::collections::HashSet<&str> = observations
+            .iter()
+            .filter_map(|o| o.session_id.as_deref())
+            .collect();
+        let all = store.sessions()?;
+        all.into_iter()
+            .filter(|s| wanted.contains(s.id.as_str()))
+            .collect()
+    } else {
+        store.sessions()?
+    };
+    let trials: Vec<TrialRecord> = if sessions.is_empty() {
+        Vec::new()
+    } else {
+        let wanted: std::collections::HashSet<&str> =
+            sessions.iter().map(|s| s.id.as_str()).collect();
+        let all = store.trials()?;
+        all.into_iter()
+            .filter(|t| wanted.contains(t.session_id.as_str()))
+            .collect()
+    };
+    // Like sessions, a subset carries only the batches its rows came from.
+    let batches: Vec<IngestBatch> = if options.is_subset() {
+        let wanted: std::collections::HashSet<&str> = observations
+            .iter()
+            .filter_map(|o| o.batch_id.as_deref())
+            .collect();
+        let all = store.batches()?;
+        all.into_iter()
+            .filter(|b| wanted.contains(b.id.as_str()))
+            .collect()
+    } else {
+        store.batches()?
+    };
+
+    let mut parts = vec![
+        (
+            OBSERVATIONS_PARQUET.to_string(),
+            BundleFileKind::Observations,
+            observations_to_parquet(&observations)?,
+        ),
+        (
+            OBSERVATIONS_CSV.to_string(),
+            BundleFileKind::ObservationsCsv,
+            observations_csv(&observations)?,
+        ),
+    ];
+    if options.sessions {
+        parts.push((
+            SESSIONS_PARQUET.to_string(),
+            BundleFileKind::Sessions,
+            sessions_to_parquet(&sessions)?,
+        ));
+        parts.push((
+            TRIALS_PARQUET.to_string(),
+            BundleFileKind::Trials,
+            trials_to_parquet(&trials)?,
+        ));
+    }
+    parts.push((
+        BATCHES_PARQUET.to_string(),
+        BundleFileKind::Batches,
+        batches_to_parquet(&batches)?,
+    ));
+    for source in &options.prefs {
+        if !source.exists() {
+            continue;
+        }
+        let name = source
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| StoreError::Io(format!("unnamed pref file: {}", source.display())))?;
+        parts.push((
+            format!("{PREFS_DIR}/{name}"),
+            BundleFileKind::Pref,
+            fs::read(source)?,
+        ));
+    }
+
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        created_at: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "1970-01-01T00:00:00Z".into()),
+        generator: concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")).to_string(),
+        selection: Selection {
+            streams: options.streams.clone(),
+            since: options.since.map(format_ts),
+            until: options.until.map(format_ts),
+        },
+        observations: observations.len(),
+        sessions: sessions.len(),
+        batches: batches.len(),
+        trials: trials.len(),
+        tables: table_schemas(),
+        streams: STREAMS.iter().map(StreamEntry::from).collect(),
+        files: parts
+            .iter()
+            .map(|(path, kind, bytes)| describe(path, *kind, bytes))
+            .collect(),
+    };
+
+    let partial = dest.with_extension(format!("{BUNDLE_EXTENSION}.partial"));
+    let mut zip = ZipWriter::new(fs::File::create(&partial)?);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file(MANIFEST_FILE, deflated).map_err(zip_error)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    for (path, _, bytes) in &parts {
+        zip.start_file(path.as_str(), deflated).map_err(zip_error)?;
+        zip.write_all(bytes)?;
+    }
+    zip.finish().map_err(zip_error)?.sync_all()?;
+    fs::rename(&partial, dest)?;
+    Ok(manifest)
+}
+
+/// Read and check a bundle: a supported format, every listed file present
+/// with its recorded size and checksum, and table row counts matching the
+/// manifest. Nothing is written.
+pub fn read(path: &Path) -> Result<Bundle> {
+    let mut zip = ZipArchive::new(fs::File::open(path)?).map_err(zip_error)?;
+    let manifest: BundleManifest = serde_json::from_slice(&entry(&mut zip, MANIFEST_FILE)?)
+        .map_err(|e| StoreError::Parse(format!("bundle manifest unreadable: {e}")))?;
+    if manifest.format > BUNDLE_FORMAT {
+        return Err(StoreError::UnsupportedSchema {
+            found: manifest.format,
+            supported: BUNDLE_FORMAT,
+        });
+    }
+    if !manifest
+        .files
+        .iter()
+        .any(|f| f.kind == BundleFileKind::Observations)
+    {
+        return Err(StoreError::Parse(
+            "bundle is missing its observations table".into(),
+        ));
+    }
+
+    let mut bundle = Bundle {
+        manifest: manifest.clone(),
+        observations: Vec::new(),
+        sessions: Vec::new(),
+        batches: Vec::new(),
+        trials: Vec::new(),
+        prefs: Vec::new(),
+    };
+    for file in &manifest.files {
+        let bytes = entry(&mut zip, &file.path)?;
+        if describe(&file.path, file.kind, &bytes) != *file {
+            return Err(StoreError::Parse(format!(
+                "bundle checksum mismatch: {}",
+                file.path
+            )));
+        }
+        match file.kind {
+            BundleFileKind::Observations => bundle
+                .observations
+                .extend(observations_from_parquet(bytes)?),
+            BundleFileKind::Sessions => bundle.sessions.extend(sessions_from_parquet(bytes)?),
+            BundleFileKind::Trials => bundle.trials.extend(trials_from_parquet(bytes)?),
+            BundleFileKind::Batches => bundle.batches.extend(batches_from_parquet(bytes)?),
+            BundleFileKind::Pref => {
+                // Only ever a bare file name, whatever the manifest says.
+                let name = Path::new(&file.path)
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .ok_or_else(|| StoreError::Parse(format!("bad pref path: {}", file.path)))?;
+                bundle.prefs.push((name.to_string(), bytes));
+            }
+            BundleFileKind::ObservationsCsv => {}
+        }
+    }
+    if bundle.observations.len() != manifest.observations
+        || bundle.sessions.len() != manifest.sessions
+        || bundle.batches.len() != manifest.batches
+        || bundle.trials.len() != manifest.trials
+    {
+        return Err(StoreError::Parse(format!(
+            "bundle row counts disagree with manifest \
+             ({} observations, {} sessions, {} batches, {} trials)",
+            bundle.observations.len(),
+            bundle.sessions.len(),
+            bundle.batches.len(),
+            bundle.trials.len()
+        )));
+    }
+    Ok(bundle)
+}
+
+/// Import a bundle into `store` (upserting, so rows already there are left
+/// alone), and copy its prefs into `prefs_dir` if given. The bundle is
+/// [`read`] whole before anything is written.
+pub fn import(
+    path: &Path,
+    store: &mut dyn Store,
+    prefs_dir: Option<&Path>,
+) -> Result<RestoreReport> {
+    let bundle = read(path)?;
+    let mut report = RestoreReport {
+        sessions_inserted: store.upsert_sessions(&bundle.sessions)?,
+        batches_inserted: store.upsert_batches(&bundle.batches)?,
+        trials_inserted: store.upsert_trials(&bundle.trials)?,
+        observations_inserted: store.upsert(&bundle.observations)?,
+        prefs_restored: 0,
+    };
+    if let Some(prefs_dir) = prefs_dir {
+        fs::create_dir_all(prefs_dir)?;
+        for (name, bytes) in &bundle.prefs {
+            fs::write(prefs_dir.join(name), bytes)?;
+            report.prefs_restored += 1;
+        }
+    }
+    Ok(report)
+}
+
+fn entry(zip: &mut ZipArchive<fs::File>, name: &str) -> Result<Vec<u8>> {
+    let mut file = zip
+        .by_name(name)
+        .map_err(|e| StoreError::Parse(format!("bundle entry {name}: {e}")))?;
+    let mut out = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut out)?;
+    Ok(out)
+}
+
+fn observations_csv(rows: &[Observation]) -> Result<Vec<u8>> {
+    let mut out = csv::Writer::from_writer(Vec::new());
+    out.write_record(OBSERVATION_COLUMNS.iter().map(|c| c.0))
+        .map_err(csv_error)?;
+    for o in rows {
+        out.write_record([
+            o.stream.clone(),
+            format_ts(o.timestamp),
+            o.value.to_string(),
+            o.unit.clone(),
+            o.source.clone(),
+            o.session_id.clone().unwrap_or_default(),
+            serde_json::to_string(&o.tags)?,
+            o.batch_id.clone().unwrap_or_default(),
+        ])
+        .map_err(csv_error)?;
+    }
+    out.into_inner().map_err(|e| StoreError::Io(e.to_string()))
+}
+
+/// Observation columns: name, type, nullable, description.
+const OBSERVATION_COLUMNS: &[(&str, &str, bool, &str)] = &[
+    (
+        "stream",
+        "string",
+        false,
+        "Registered stream name (see `streams`), or `custom.*`",
+    ),
+    (
+        "timestamp",
+        "timestamp[us]",
+        false,
+        "Source-local wall-clock time, no offset; resolve with the `tz` tag if present",
+    ),
+    ("value", "float64", false, "The measurement, in `unit`"),
+    ("unit", "string", false, "The stream's canonical unit"),
+    (
+        "source",
+        "string",
+        false,
+        "Device or importer that produced the row",
+    ),
+    (
+        "session_id",
+        "string",
+        true,
+        "Cognition session the row belongs to",
+    ),
+    (
+        "tags_json",
+        "string",
+        false,
+        "JSON object of string tags (tz, kind, device, …)",
+    ),
+    (
+        "batch_id",
+        "string",
+        true,
+        "Ingest batch (see `batches`) that wrote the row",
+    ),
+];
+
+const SESSION_COLUMNS: &[(&str, &str, bool, &str)] = &[
+    (
+        "id",
+        "string",
+        false,
+        "Session id, referenced by observations' `session_id`",
+    ),
+    (
+        "task",
+        "string",
+        false,
+        "Cognitive task (`pvt`, `nback2`, …)",
+    ),
+    ("created_at", "timestamp[us]", false, "Session start, UTC"),
+    ("client_platform", "string", false, "web, desktop or mobile"),
+    ("client_tz", "string", false, "IANA zone of the device"),
+    (
+        "metrics_json",
+        "string",
+        false,
+        "The task's full metrics, as JSON",
+    ),
+    (
+        "qc_visibility_blur_events",
+        "int64",
+        false,
+        "Times the window was hidden",
+    ),
+    (
+        "qc_focus_lost_events",
+        "int64",
+        false,
+        "Times the window lost focus",
+    ),
+    (
+        "qc_min_trials_met",
+        "bool",
+        false,
+        "Enough trials for the metrics to be trusted",
+    ),
+    (
+        "qc_device_platform",
+        "string",
+        false,
+        "Platform reported by quality control",
+    ),
+    (
+        "qc_device_user_agent",
+        "string",
+        true,
+        "Browser user agent, on web",
+    ),
+    ("notes", "string", true, "The user's note on the session"),
+    (
+        "qc_pause_events",
+        "int64",
+        false,
+        "Times the run was paused",
+    ),
+    (
+        "qc_paused_ms",
+        "int64",
+        false,
+        "Total time the run spent paused, ms",
+    ),
+];
+
+const BATCH_COLUMNS: &[(&str, &str, bool, &str)] = &[
+    (
+        "id",
+        "string",
+        false,
+        "Batch id, referenced by observations' `batch_id`",
+    ),
+    (
+        "created_at",
+        "timestamp[us]",
+        false,
+        "When the sync or import ran, UTC",
+    ),
+    (
+        "kind",
+        "string",
+        false,
+        "What produced it (`reader_sync`, `apple_health`, …)",
+    ),
+    ("app_version", "string", false, "App name and version"),
+    (
+        "device_firmware",
+        "string",
+        true,
+        "Reader firmware, for syncs",
+    ),
+    ("file_hash", "string", true, "SHA-256 of the imported file"),
+    ("detail", "string", true, "File name or reader serial"),
+    (
+        "rolled_back_at",
+        "timestamp[us]",
+        true,
+        "When its rows were removed, UTC",
+    ),
+];
+
+const TRIAL_COLUMNS: &[(&str, &str, bool, &str)] = &[
+    ("session_id", "string", false, "Session (see `sessions`) the trial belongs to"),
+    ("index", "int64", false, "Position in the run, from 0"),
+    ("iti_ms", "float64", true, "Scheduled wait before the stimulus, ms"),
+    (
+        "onset_ms",
+        "float64",
+        true,
+        "Stimulus onset since the run (PVT) or first stimulus (2-back), ms",
+    ),
+    ("response_ms", "float64", true, "Response, on the same clock, ms"),
+    ("rt_ms", "float64", true, "Response time after onset, ms"),
+    (
+        "outcome",
+        "string",
+        false,
+        "`reaction`/`lapse`/`false_start` (PVT), `hit`/`miss`/`false_alarm`/`correct_rejection` (2-back)",
+    ),
+    ("stimulus", "string", true, "What was shown (2-back letter)"),
+    ("target", "bool", true, "2-back: matched the stimulus n back"),
+    ("lure", "bool", true, "2-back: a lure, matching the stimulus n − 1 back"),
+];
+
+fn table_schemas() -> Vec<TableSchema> {
+    let table = |name: &str, columns: &[(&str, &str, bool, &str)]| TableSchema {
+        name: name.to_string(),
+        columns: columns
+            .iter()
+            .map(|&(name, data_type, nullable, description)| ColumnSchema {
+                name: name.to_string(),
+                data_type: data_type.to_string(),
+                nullable,
+                description: description.to_string(),
+            })
+            .collect(),
+    };
+    vec![
+        table("observations", OBSERVATION_COLUMNS),
+        table("sessions", SESSION_COLUMNS),
+        table("trials", TRIAL_COLUMNS),
+        table("batches", BATCH_COLUMNS),
+    ]
+}
+
+fn describe(path: &str, kind: BundleFileKind, bytes: &[u8]) -> BundleFile {
+    BundleFile {
+        path: path.to_string(),
+        kind,
+        bytes: bytes.len() as u64,
+        sha256: Sha256::digest(bytes)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect(),
+    }
+}
+
+fn zip_error(e: zip::result::ZipError) -> StoreError {
+    StoreError::Io(format!("bundle zip: {e}"))
+}
+
+fn csv_error(e: csv::Error) -> StoreError {
+    StoreError::Io(format!("bundle csv: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+    use time::macros::datetime;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("looplace_bundle_{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn populated() -> MemoryStore {
+        let mut store = MemoryStore::new();
+        let t = datetime!(2025-09-21 16:21:54.093347);
+        let mut reading = Observation::new("glucose.mg_dl", t, 94.0, "mg/dL", "dev");
+        reading.tags.insert("tz".into(), "Europe/Paris".into());
+        let later = Observation::new(
+            "glucose.mg_dl",
+            datetime!(2026-01-05 07:00),
+            131.0,
+            "mg/dL",
+            "dev",
+        );
+        let t = datetime!(2026-06-19 08:00:00);
+        let mut metric = Observation::new("pvt.median_rt_ms", t, 312.5, "ms", "looplace");
+        metric.session_id = Some("pvt-1".into());
+        let sync = IngestBatch::new(crate::provenance::READER_SYNC, "test");
+        sync.stamp(std::slice::from_mut(&mut reading));
+        store.upsert(&[reading, later, metric]).unwrap();
+        store.upsert_batches(&[sync]).unwrap();
+        store
+            .upsert_trials(&[TrialRecord {
+                session_id: "pvt-1".into(),
+                index: 0,
+                iti_ms: Some(3_250.0),
+                onset_ms: Some(3_250.0),
+                response_ms: Some(3_562.5),
+                rt_ms: Some(312.5),
+                outcome: "reaction".into(),
+                stimulus: None,
+                target: None,
+                lure: None,
+            }])
+            .unwrap();
+        store
+            .upsert_sessions(&[SessionRecord {
+                id: "pvt-1".into(),
+                task: "pvt".into(),
+                created_at: datetime!(2026-06-19 08:00:00),
+                client_platform: "desktop".into(),
+                client_tz: "UTC".into(),
+                metrics: serde_json::json!({"median_rt_ms": 312.5}),
+                qc_visibility_blur_events: 0,
+                qc_focus_lost_events: 1,
+                qc_min_trials_met: true,
+                qc_device_platform: "desktop".into(),
+                qc_device_user_agent: None,
+                qc_pause_events: 0,
+                qc_paused_ms: 0,
+                notes: Some("felt sharp".into()),
+            }])
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn export_then_import_is_lossless_and_idempotent() {
+        let dir = temp_dir("roundtrip");
+        let prefs = dir.join("glucose_prefs.json");
+        fs::write(&prefs, r#"{"low":70,"high":180}"#).unwrap();
+        let store = populated();
+        let dest = dir.join("all.zip");
+        let options = BundleOptions {
+            prefs: vec![prefs, dir.join("absent.json")],
+            ..Default::default()
+        };
+
+        let manifest = export(&store, &dest, &options).unwrap();
+        assert_eq!(
+            (manifest.observations, manifest.sessions, manifest.batches, manifest.trials),
+            (3, 1, 1, 1)
+        );
+        // parquet + csv + sessions + trials + batches + one pref
+        assert_eq!(manifest.files.len(), 6);
+        assert_eq!(manifest.streams.len(), STREAMS.len());
+        let bundle = read(&dest).unwrap();
+        assert_eq!(bundle.manifest, manifest);
+        let csv = String::from_utf8(
+            entry(
+                &mut ZipArchive::new(fs::File::open(&dest).unwrap()).unwrap(),
+                OBSERVATIONS_CSV,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(
+            csv.starts_with("stream,timestamp,value,unit,source,session_id,tags_json,batch_id\n")
+        );
+        assert!(csv.contains("glucose.mg_dl,2025-09-21T16:21:54.093347,94,mg/dL,dev,"));
+
+        let mut restored = MemoryStore::new();
+        let restored_prefs = dir.join("restored");
+        let report = import(&dest, &mut restored, Some(&restored_prefs)).unwrap();
+        assert_eq!(
+            report,
+            RestoreReport {
+                observations_inserted: 3,
+                sessions_inserted: 1,
+                batches_inserted: 1,
+                trials_inserted: 1,
+                prefs_restored: 1,
+            }
+        );
+        assert_eq!(
+            restored.query(&Query::default()).unwrap(),
+            store.query(&Query::default()).unwrap()
+        );
+        assert_eq!(restored.sessions().unwrap(), store.sessions().unwrap());
+        assert_eq!(restored.batches().unwrap(), store.batches().unwrap());
+        assert_eq!(restored.trials().unwrap(), store.trials().unwrap());
+        assert!(restored_prefs.join("glucose_prefs.json").exists());
+
+        let again = import(&dest, &mut restored, None).unwrap();
+        assert_eq!(
+            (again.observations_inserted, again.sessions_inserted),
+            (0, 0)
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn subset_keeps_only_selected_streams_range_and_their_sessions() {
+        let dir = temp_dir("subset");
+        let store = populated();
+        let dest = dir.join("glucose.zip");
+        let options = BundleOptions {
+            streams: vec!["glucose.mg_dl".into()],
+            since: Some(datetime!(2026-01-01 00:00)),
+            ..Default::default()
+        };
+
+        let manifest = export(&store, &dest, &options).unwrap();
+        // The 2026 reading came from no batch; the stamped 2025 one is cut.
+        assert_eq!(
+            (manifest.observations, manifest.sessions, manifest.batches, manifest.trials),
+            (1, 0, 0, 0)
+        );
+        assert_eq!(
+            manifest.selection.since.as_deref(),
+            Some("2026-01-01T00:00:00.000000")
+        );
+        let bundle = read(&dest).unwrap();
+        assert_eq!(bundle.observations[0].value, 131.0);
+        assert!(bundle.sessions.is_empty());
+
+        let pvt = BundleOptions {
+            streams: vec!["pvt.median_rt_ms".into()],
+            ..Default::default()
+        };
+        let manifest = export(&store, &dest, &pvt).unwrap();
+        assert_eq!(
+            (manifest.observations, manifest.sessions, manifest.trials),
+            (1, 1, 1)
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn tampered_bundle_is_rejected_before_any_write() {
+        let dir = temp_dir("tampered");
+        let dest = dir.join("all.zip");
+        let mut manifest = export(&populated(), &dest, &BundleOptions::default()).unwrap();
+
+        // Rewrite the zip with a manifest whose checksum no longer matches.
+        manifest.files[0].sha256 = "0".repeat(64);
+        let mut source = ZipArchive::new(fs::File::open(&dest).unwrap()).unwrap();
+        let tables: Vec<(String, Vec<u8>)> = manifest
+            .files
+            .iter()
+            .map(|f| (f.path.clone(), entry(&mut source, &f.path).unwrap()))
+            .collect();
+        let mut zip = ZipWriter::new(fs::File::create(&dest).unwrap());
+        zip.start_file(MANIFEST_FILE, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&serde_json::to_vec(&manifest).unwrap())
+            .unwrap();
+        for (path, bytes) in tables {
+            zip.start_file(path, SimpleFileOptions::default()).unwrap();
+            zip.write_all(&bytes).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let mut store = MemoryStore::new();
+        assert!(matches!(
+            import(&dest, &mut store, None),
+            Err(StoreError::Parse(_))
+        ));
+        assert!(store.is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,326 @@
+//! Backend conformance suite (test-only).
+//!
+//! [`MemoryStore`] is the reference semantics; every backend runs the same
+//! checks from its own test module via [`run`], so a new backend is proven
+//! interchangeable by one call.
+
+use std::collections::BTreeMap;
+
+use time::macros::datetime;
+use time::PrimitiveDateTime;
+
+use crate::observation::{Observation, Query};
+use crate::error::StoreError;
+use crate::provenance::{IngestBatch, READER_SYNC};
+use crate::session::SessionRecord;
+use crate::store::{copy_store, CopyReport, MemoryStore, Store};
+use crate::trial::TrialRecord;
+
+/// Run every check, each against a fresh, empty store from `fresh(name)`.
+pub(crate) fn run<S: Store>(mut fresh: impl FnMut(&str) -> S) {
+    upsert_overwrites_by_key(&mut fresh("upsert_key"));
+    kind_tag_is_part_of_the_key(&mut fresh("kind_key"));
+    query_filters_and_sorts(&mut fresh("query"));
+    observations_round_trip_losslessly(&mut fresh("lossless"));
+    sessions_overwrite_by_id_and_sort(&mut fresh("sessions"));
+    unregistered_streams_are_refused(&mut fresh("registry"));
+    delete_removes_by_key(&mut fresh("delete"));
+    sessions_delete_by_id(&mut fresh("delete_sessions"));
+    batches_overwrite_by_id_and_sort(&mut fresh("batches"));
+    unchanged_rewrites_keep_their_batch(&mut fresh("provenance"));
+    trials_overwrite_by_key_and_delete_by_session(&mut fresh("trials"));
+    copies_losslessly_both_ways(&mut fresh("copy"));
+}
+
+fn glucose(t: PrimitiveDateTime, value: f64) -> Observation {
+    Observation::new("glucose.mg_dl", t, value, "mg/dL", "dev")
+}
+
+fn session(id: &str, created_at: PrimitiveDateTime, notes: Option<&str>) -> SessionRecord {
+    SessionRecord {
+        id: id.into(),
+        task: "pvt".into(),
+        created_at,
+        client_platform: "desktop".into(),
+        client_tz: "America/Chicago".into(),
+        metrics: serde_json::json!({"median_rt_ms": 312.5, "lapses_ge_500ms": 2}),
+        qc_visibility_blur_events: 1,
+        qc_focus_lost_events: 0,
+        qc_min_trials_met: true,
+        qc_device_platform: "desktop".into(),
+        qc_device_user_agent: None,
+        qc_pause_events: 0,
+        qc_paused_ms: 0,
+        notes: notes.map(str::to_string),
+    }
+}
+
+fn upsert_overwrites_by_key(store: &mut dyn Store) {
+    let a = glucose(datetime!(2026-06-19 08:00:00), 100.0);
+    let b = glucose(datetime!(2026-06-19 08:05:00), 101.0);
+    assert_eq!(store.upsert(&[a.clone(), b]).unwrap(), 2);
+
+    let mut a2 = a;
+    a2.value = 105.0;
+    a2.tags.insert("food".into(), "true".into());
+    assert_eq!(store.upsert(std::slice::from_ref(&a2)).unwrap(), 0);
+
+    let rows = store.query(&Query::default()).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0], a2);
+}
+
+fn kind_tag_is_part_of_the_key(store: &mut dyn Store) {
+    let t = datetime!(2026-06-19 08:00:00);
+    let mut scan = glucose(t, 100.0);
+    scan.tags.insert("kind".into(), "scan".into());
+    let mut sensor = glucose(t, 98.0);
+    sensor.tags.insert("kind".into(), "sensor".into());
+    assert_eq!(store.upsert(&[scan, sensor]).unwrap(), 2);
+
+    // A different source at the same instant is a different reading too.
+    let mut other_device = glucose(t, 97.0);
+    other_device.source = "dev-2".into();
+    assert_eq!(store.upsert(&[other_device]).unwrap(), 1);
+    assert_eq!(store.query(&Query::default()).unwrap().len(), 3);
+}
+
+fn query_filters_and_sorts(store: &mut dyn Store) {
+    store
+        .upsert(&[
+            glucose(datetime!(2026-06-19 09:00:00), 110.0),
+            glucose(datetime!(2026-06-19 08:00:00), 100.0),
+            Observation::new("pvt.median_rt_ms", datetime!(2026-06-19 08:30:00), 300.0, "ms", "app"),
+            glucose(datetime!(2026-06-19 10:00:00), 120.0),
+        ])
+        .unwrap();
+
+    let all = store.query(&Query::default()).unwrap();
+    let times: Vec<_> = all.iter().map(|o| o.timestamp).collect();
+    let mut sorted = times.clone();
+    sorted.sort();
+    assert_eq!(times, sorted);
+
+    let glucose_rows = store.query(&Query::stream("glucose.mg_dl")).unwrap();
+    let values: Vec<f64> = glucose_rows.iter().map(|o| o.value).collect();
+    assert_eq!(values, vec![100.0, 110.0, 120.0]);
+
+    // Bounds are inclusive on both ends.
+    let window = Query {
+        stream: Some("glucose.mg_dl".into()),
+        since: Some(datetime!(2026-06-19 09:00:00)),
+        until: Some(datetime!(2026-06-19 10:00:00)),
+        batch_id: None,
+    };
+    let values: Vec<f64> = store.query(&window).unwrap().iter().map(|o| o.value).collect();
+    assert_eq!(values, vec![110.0, 120.0]);
+
+    assert!(store.query(&Query::stream("nback2.d_prime")).unwrap().is_empty());
+}
+
+fn observations_round_trip_losslessly(store: &mut dyn Store) {
+    let mut reading = glucose(datetime!(2025-09-21 16:21:54.093347), 94.0);
+    reading.tags = BTreeMap::from([
+        ("kind".to_string(), "scan".to_string()),
+        ("food_carbs_grams".to_string(), "15".to_string()),
+        ("tz".to_string(), "America/Denver".to_string()),
+    ]);
+    reading.batch_id = Some("reader_sync-1".into());
+    let mut metric =
+        Observation::new("nback2.d_prime", datetime!(2026-06-19 08:00:00), 1.8, "", "looplace");
+    metric.session_id = Some("nback2-1".into());
+
+    store.upsert(&[reading.clone(), metric.clone()]).unwrap();
+    assert_eq!(store.query(&Query::default()).unwrap(), vec![reading, metric]);
+}
+
+fn sessions_overwrite_by_id_and_sort(store: &mut dyn Store) {
+    let later = session("b", datetime!(2026-06-20 08:00:00), None);
+    let earlier = session("a", datetime!(2026-06-19 08:00:00), Some("felt sharp"));
+    assert_eq!(store.upsert_sessions(&[later.clone(), earlier.clone()]).unwrap(), 2);
+
+    let mut edited = earlier;
+    edited.notes = Some("felt foggy".into());
+    edited.qc_device_user_agent = Some("Mozilla/5.0".into());
+    edited.qc_pause_events = 2;
+    edited.qc_paused_ms = 41_500;
+    assert_eq!(store.upsert_sessions(std::slice::from_ref(&edited)).unwrap(), 0);
+
+    assert_eq!(store.sessions().unwrap(), vec![edited, later]);
+}
+
+fn unregistered_streams_are_refused(store: &mut dyn Store) {
+    let t = datetime!(2026-06-19 08:00:00);
+    let typo = Observation::new("glucose.mgdl", t, 100.0, "mg/dL", "dev");
+    assert!(matches!(
+        store.upsert(&[glucose(t, 100.0), typo]),
+        Err(StoreError::UnknownStream(s)) if s == "glucose.mgdl"
+    ));
+    assert!(matches!(
+        store.upsert(&[glucose(t, -5.0)]),
+        Err(StoreError::InvalidValue { .. })
+    ));
+    // A refused batch writes nothing, not even its valid rows.
+    assert!(store.query(&Query::default()).unwrap().is_empty());
+
+    let custom = Observation::new("custom.sleep_hours", t, 7.5, "h", "manual");
+    assert_eq!(store.upsert(&[custom]).unwrap(), 1);
+}
+
+fn delete_removes_by_key(store: &mut dyn Store) {
+    let a = glucose(datetime!(2026-06-19 08:00:00), 100.0);
+    let b = glucose(datetime!(2026-06-19 08:05:00), 101.0);
+    store.upsert(&[a.clone(), b.clone()]).unwrap();
+
+    // Matched by key alone: a stale value still deletes the stored row.
+    let mut stale = a;
+    stale.value = 250.0;
+    assert_eq!(store.delete(&[stale.clone()]).unwrap(), 1);
+    assert_eq!(store.delete(&[stale]).unwrap(), 0);
+    assert_eq!(store.query(&Query::default()).unwrap(), vec![b]);
+}
+
+fn sessions_delete_by_id(store: &mut dyn Store) {
+    let a = session("a", datetime!(2026-06-19 07:00:00), None);
+    let b = session("b", datetime!(2026-06-19 08:00:00), None);
+    store.upsert_sessions(&[a, b.clone()]).unwrap();
+
+    let ids = ["a".to_string(), "missing".to_string()];
+    assert_eq!(store.delete_sessions(&ids).unwrap(), 1);
+    assert_eq!(store.delete_sessions(&ids).unwrap(), 0);
+    assert_eq!(store.sessions().unwrap(), vec![b]);
+}
+
+fn batch(id: &str, created_at: PrimitiveDateTime) -> IngestBatch {
+    IngestBatch {
+        id: id.into(),
+        created_at,
+        kind: READER_SYNC.into(),
+        app_version: "looplace-ui 0.1.0".into(),
+        device_firmware: Some("2.4.3".into()),
+        file_hash: None,
+        detail: Some("MPGF176-T4167".into()),
+        rolled_back_at: None,
+    }
+}
+
+fn batches_overwrite_by_id_and_sort(store: &mut dyn Store) {
+    let later = batch("b", datetime!(2026-06-20 08:00:00));
+    let earlier = batch("a", datetime!(2026-06-19 08:00:00));
+    assert_eq!(
+        store
+            .upsert_batches(&[later.clone(), earlier.clone()])
+            .unwrap(),
+        2
+    );
+
+    let mut undone = earlier;
+    undone.rolled_back_at = Some(datetime!(2026-06-21 10:30:00.5));
+    undone.file_hash = Some("ab".repeat(32));
+    assert_eq!(
+        store.upsert_batches(std::slice::from_ref(&undone)).unwrap(),
+        0
+    );
+
+    assert_eq!(store.batches().unwrap(), vec![undone, later]);
+}
+
+fn unchanged_rewrites_keep_their_batch(store: &mut dyn Store) {
+    let mut first = glucose(datetime!(2026-06-19 08:00:00), 100.0);
+    first.batch_id = Some("sync-1".into());
+    store.upsert(std::slice::from_ref(&first)).unwrap();
+
+    // Re-read unchanged by a later sync: still the first sync's row.
+    let mut again = first.clone();
+    again.batch_id = Some("sync-2".into());
+    assert_eq!(store.upsert(&[again]).unwrap(), 0);
+    assert_eq!(store.query(&Query::default()).unwrap(), vec![first.clone()]);
+
+    // Changed by it: now the later sync's.
+    let mut changed = first;
+    changed.value = 104.0;
+    changed.batch_id = Some("sync-2".into());
+    store.upsert(std::slice::from_ref(&changed)).unwrap();
+    let all = store.query(&Query::default()).unwrap();
+    assert_eq!(all, vec![changed]);
+    assert_eq!(store.query(&Query::batch("sync-2")).unwrap(), all);
+    assert!(store.query(&Query::batch("sync-1")).unwrap().is_empty());
+}
+
+fn trial(session_id: &str, index: i64, outcome: &str) -> TrialRecord {
+    TrialRecord {
+        session_id: session_id.into(),
+        index,
+        iti_ms: Some(2_500.0),
+        onset_ms: Some(2_500.0 * (index + 1) as f64),
+        response_ms: None,
+        rt_ms: None,
+        outcome: outcome.into(),
+        stimulus: Some("K".into()),
+        target: Some(index % 2 == 0),
+        lure: None,
+    }
+}
+
+fn trials_overwrite_by_key_and_delete_by_session(store: &mut dyn Store) {
+    let first = trial("b", 0, "miss");
+    let second = trial("a", 1, "correct_rejection");
+    let third = trial("a", 0, "miss");
+    assert_eq!(
+        store
+            .upsert_trials(&[first.clone(), second.clone(), third])
+            .unwrap(),
+        3
+    );
+
+    let mut hit = trial("a", 0, "hit");
+    hit.response_ms = Some(3_012.25);
+    hit.rt_ms = Some(512.25);
+    hit.lure = Some(false);
+    assert_eq!(store.upsert_trials(std::slice::from_ref(&hit)).unwrap(), 0);
+    assert_eq!(store.trials().unwrap(), vec![hit, second, first.clone()]);
+
+    assert_eq!(store.delete_trials(&["a".into(), "zzz".into()]).unwrap(), 2);
+    assert_eq!(store.trials().unwrap(), vec![first]);
+}
+
+fn copies_losslessly_both_ways(store: &mut dyn Store) {
+    let mut source = MemoryStore::new();
+    let mut reading = glucose(datetime!(2026-06-19 08:00:00), 100.0);
+    reading.tags.insert("kind".into(), "sensor".into());
+    source.upsert(&[reading, glucose(datetime!(2026-06-19 08:15:00), 104.0)]).unwrap();
+    source
+        .upsert_sessions(&[session("a", datetime!(2026-06-19 07:00:00), Some("n"))])
+        .unwrap();
+    source
+        .upsert_batches(&[batch("sync-1", datetime!(2026-06-19 08:20:00))])
+        .unwrap();
+    source.upsert_trials(&[trial("a", 0, "hit")]).unwrap();
+
+    let report = copy_store(&source, store).unwrap();
+    assert_eq!(
+        report,
+        CopyReport {
+            observations: 2,
+            sessions: 1,
+            batches: 1,
+            trials: 1,
+            observations_inserted: 2,
+            sessions_inserted: 1,
+            batches_inserted: 1,
+            trials_inserted: 1,
+        }
+    );
+    // Re-running is a no-op: the copy is an upsert.
+    assert_eq!(copy_store(&source, store).unwrap().observations_inserted, 0);
+
+    let mut back = MemoryStore::new();
+    copy_store(store, &mut back).unwrap();
+    assert_eq!(
+        back.query(&Query::default()).unwrap(),
+        source.query(&Query::default()).unwrap()
+    );
+    assert_eq!(back.sessions().unwrap(), source.sessions().unwrap());
+    assert_eq!(back.batches().unwrap(), source.batches().unwrap());
+    assert_eq!(back.trials().unwrap(), source.trials().unwrap());
+}
@@ -1,5 +1,11 @@
 //! Convert domain data (Libre readings, Looplace cognition summaries) into the
-//! tidy [`Observation`] shape.
+//! tidy [`Observation`] shape. Third-party CGM exports are parsed in [`cgm`], Apple Health
+//! exports in [`health`], and hand-kept spreadsheets through a user mapping in
+//! [`mapped`].
+
+pub mod cgm;
+pub mod health;
+pub mod mapped;
 
 use std::collections::BTreeMap;
 
@@ -12,13 +18,15 @@ use looplace_libre::records::{Annotations, GlucoseSource, Reading};
 use crate::error::{Result, StoreError};
 use crate::observation::Observation;
 use crate::session::SessionRecord;
+use crate::streams::{self, StreamSpec};
 
 /// Convert a Libre [`Reading`] into an [`Observation`]. `source` is the device
 /// serial (e.g. from `$sn?`); `tz` is the host IANA timezone *name* (e.g.
 /// `America/Denver`) at sync time, recorded so the reader's local wall-clock can
 /// later be resolved to UTC by that zone's *historical* DST rules — never by a
 /// single current offset, which would re-key the same reading across a DST flip.
-/// Time-adjustment events return `None` (metadata, not a measurement).
+/// Time-adjustment events return `None` (metadata, not a measurement), as do
+/// values the [`streams`] registry would refuse (e.g. a sensor error reading).
 pub fn reading_to_observation(reading: &Reading, source: &str, tz: &str) -> Option<Observation> {
     match reading {
         Reading::Glucose {
@@ -29,12 +37,13 @@ pub fn reading_to_observation(reading: &Reading, source: &str, tz: &str) -> Opti
             annotations,
             ..
         } => {
-            let mut obs = Observation::new("glucose.mg_dl", *timestamp, *value_mg_dl as f64, "mg/dL", source);
+            let spec = registered(streams::GLUCOSE);
+            let mut obs = Observation::new(spec.name, *timestamp, *value_mg_dl as f64, spec.unit, source);
             obs.tags.insert("kind".into(), glucose_kind(*kind).into());
             obs.tags.insert("record_seq".into(), device_id.to_string());
             obs.tags.insert("tz".into(), tz.to_string());
             annotate(&mut obs.tags, annotations);
-            Some(obs)
+            spec.accepts(obs.value).then_some(obs)
         }
         Reading::Ketone {
             timestamp,
@@ -43,16 +52,21 @@ pub fn reading_to_observation(reading: &Reading, source: &str, tz: &str) -> Opti
             annotations,
             ..
         } => {
-            let mut obs = Observation::new("ketone.mmol_l", *timestamp, *value_mmol_l, "mmol/L", source);
+            let spec = registered(streams::KETONE);
+            let mut obs = Observation::new(spec.name, *timestamp, *value_mmol_l, spec.unit, source);
             obs.tags.insert("record_seq".into(), device_id.to_string());
             obs.tags.insert("tz".into(), tz.to_string());
             annotate(&mut obs.tags, annotations);
-            Some(obs)
+            spec.accepts(obs.value).then_some(obs)
         }
         Reading::TimeAdjustment { .. } => None,
     }
 }
 
+fn registered(stream: &str) -> &'static StreamSpec {
+    streams::lookup(stream).expect("built-in stream is registered")
+}
+
 fn glucose_kind(source: GlucoseSource) -> &'static str {
     match source {
         GlucoseSource::SensorHistory => "sensor",
@@ -117,6 +131,10 @@ pub struct LegacyQc {
     pub min_trials_met: bool,
     #[serde(default)]
     pub device: LegacyDevice,
+    #[serde(default)]
+    pub pause_events: i64,
+    #[serde(default)]
+    pub paused_ms: i64,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -143,6 +161,8 @@ pub fn summary_to_session(summary: &CognitionSummary) -> Option<SessionRecord> {
         qc_min_trials_met: summary.qc.min_trials_met,
         qc_device_platform: summary.qc.device.platform.clone(),
         qc_device_user_agent: summary.qc.device.user_agent.clone(),
+        qc_pause_events: summary.qc.pause_events,
+        qc_paused_ms: summary.qc.paused_ms,
         notes: summary.notes.clone(),
     })
 }
@@ -174,7 +194,18 @@ pub fn summaries_from_json(json: &str) -> Result<ParsedSummaries> {
     Ok(ParsedSummaries { summaries, skipped })
 }
 
-/// Flatten one cognition summary into one observation per numeric metric.
+/// Flatten one cognition summary into one observation per numeric metric,
+/// in the metric's registered unit. Metrics without a registered stream, or with
+/// a value the registry refuses, are left out — the session record keeps them.
+///
+/// Metrics that tell runs apart become tags on each of the run's observations:
+///
+/// - `preset` (the PVT protocol) → `preset`
+/// - `n` (the N-back level) → `n`; runs without it predate levels and were
+///   all 2-back
+/// - `symbol_set` (the DSST key) → `symbol_set`
+/// - `form_id` (the word-recall list) → `form`
+/// - `response_mode` (typed or word bank) → `response_mode`
 pub fn summary_to_observations(summary: &CognitionSummary) -> Vec<Observation> {
     let Some(timestamp) = parse_rfc3339(&summary.created_at) else {
         return Vec::new();
@@ -182,35 +213,42 @@ pub fn summary_to_observations(summary: &CognitionSummary) -> Vec<Observation> {
     let Some(metrics) = summary.metrics.as_object() else {
         return Vec::new();
     };
+    let preset = metrics.get("preset").and_then(|value| value.as_str());
+    let level = metrics.get("n").and_then(|value| value.as_u64());
+    let symbol_set = metrics.get("symbol_set").and_then(|value| value.as_u64());
+    let form = metrics.get("form_id").and_then(|value| value.as_str());
+    let response_mode = metrics.get("response_mode").and_then(|value| value.as_str());
 
     metrics
         .iter()
         .filter_map(|(key, value)| {
             let num = value.as_f64()?;
-            let mut obs = Observation::new(
-                format!("{}.{}", summary.task, key),
-                timestamp,
-                num,
-                unit_for_metric(key),
-                "looplace",
-            );
+            let spec = streams::lookup(&streams::metric_stream(&summary.task, key))?;
+            if !spec.accepts(num) {
+                return None;
+            }
+            let mut obs = Observation::new(spec.name, timestamp, num, spec.unit, "looplace");
             obs.session_id = Some(summary.id.clone());
+            if let Some(preset) = preset {
+                obs.tags.insert("preset".into(), preset.into());
+            }
+            if let Some(level) = level {
+                obs.tags.insert("n".into(), level.to_string());
+            }
+            if let Some(symbol_set) = symbol_set {
+                obs.tags.insert("symbol_set".into(), symbol_set.to_string());
+            }
+            if let Some(form) = form {
+                obs.tags.insert("form".into(), form.into());
+            }
+            if let Some(response_mode) = response_mode {
+                obs.tags.insert("response_mode".into(), response_mode.into());
+            }
             Some(obs)
         })
         .collect()
 }
 
-/// Best-effort unit from a metric key suffix.
-fn unit_for_metric(key: &str) -> &'static str {
-    if key.ends_with("_ms") || key.ends_with("_ms_per_min") {
-        "ms"
-    } else if key.ends_with("_pct") || key.contains("accuracy") {
-        "%"
-    } else {
-        ""
-    }
-}
-
 /// Parse an RFC3339 instant into a naive local-equivalent timestamp.
 ///
 /// NOTE: this currently keeps the UTC wall-clock. Aligning cognition (UTC) with
@@ -269,13 +307,61 @@ mod tests {
         assert_eq!(median.session_id.as_deref(), Some("pvt-1"));
     }
 
+    #[test]
+    fn pvt_preset_tags_every_observation() {
+        let json = r#"[{"id":"a","task":"pvt","created_at":"2026-06-19T08:00:00Z",
+            "metrics":{"median_rt_ms":301.5,"false_starts":2,"preset":"pvt_b_3min"}}]"#;
+        let parsed = summaries_from_json(json).unwrap();
+        let obs = summary_to_observations(&parsed.summaries[0]);
+        assert_eq!(obs.len(), 2);
+        assert!(obs
+            .iter()
+            .all(|o| o.tags.get("preset").map(String::as_str) == Some("pvt_b_3min")));
+    }
+
+    #[test]
+    fn nback_level_tags_every_observation_and_is_its_own_stream() {
+        let json = r#"[{"id":"a","task":"nback2","created_at":"2026-06-19T08:00:00Z",
+            "metrics":{"accuracy":0.9,"d_prime":2.1,"n":3,"adaptive":true}}]"#;
+        let parsed = summaries_from_json(json).unwrap();
+        let obs = summary_to_observations(&parsed.summaries[0]);
+        assert_eq!(obs.len(), 3);
+        assert!(obs.iter().all(|o| o.tags.get("n").map(String::as_str) == Some("3")));
+        let level = obs.iter().find(|o| o.stream == "nback2.n").unwrap();
+        assert_eq!(level.value, 3.0);
+    }
+
+    #[test]
+    fn dsst_symbol_set_is_a_tag_not_a_stream() {
+        let json = r#"[{"id":"a","task":"dsst","created_at":"2026-06-19T08:00:00Z",
+            "metrics":{"correct_per_minute":41.3,"errors":2,"symbol_set":1}}]"#;
+        let parsed = summaries_from_json(json).unwrap();
+        let obs = summary_to_observations(&parsed.summaries[0]);
+        assert_eq!(obs.len(), 2);
+        assert!(obs
+            .iter()
+            .all(|o| o.tags.get("symbol_set").map(String::as_str) == Some("1")));
+    }
+
+    #[test]
+    fn word_recall_form_and_mode_are_tags() {
+        let json = r#"[{"id":"a","task":"word_recall","created_at":"2026-06-19T08:00:00Z",
+            "metrics":{"form_id":"fr-FR/v1/C","response_mode":"typed","immediate_correct":9,
+            "delayed_correct":null}}]"#;
+        let parsed = summaries_from_json(json).unwrap();
+        let obs = summary_to_observations(&parsed.summaries[0]);
+        assert_eq!(obs.len(), 1);
+        assert_eq!(obs[0].tags.get("form").map(String::as_str), Some("fr-FR/v1/C"));
+        assert_eq!(obs[0].tags.get("response_mode").map(String::as_str), Some("typed"));
+    }
+
     #[test]
     fn lenient_parse_skips_malformed_records() {
         // good, malformed (missing required `created_at`), good.
         let json = r#"[
             {"id":"a","task":"pvt","created_at":"2026-06-19T08:00:00Z","metrics":{"median_rt_ms":300}},
             {"id":"b","task":"pvt","metrics":{"median_rt_ms":310}},
-            {"id":"c","task":"nback2","created_at":"2026-06-19T09:00:00Z","metrics":{"dprime":1.5}}
+            {"id":"c","task":"nback2","created_at":"2026-06-19T09:00:00Z","metrics":{"d_prime":1.5}}
         ]"#;
         let parsed = summaries_from_json(json).unwrap();
         assert_eq!(parsed.summaries.len(), 2);
@@ -315,10 +401,21 @@ mod tests {
     #[test]
     fn only_numeric_metrics_become_observations() {
         let json = r#"[{"id":"a","task":"pvt","created_at":"2026-06-19T08:00:00Z",
-            "metrics":{"num":1.5,"int":2,"text":"hi","flag":true,"nothing":null}}]"#;
+            "metrics":{"mean_rt_ms":301.5,"false_starts":2,"text":"hi",
+                       "meets_min_trial_requirement":true,"sd_rt_ms":null}}]"#;
+        let parsed = summaries_from_json(json).unwrap();
+        let obs = summary_to_observations(&parsed.summaries[0]);
+        assert_eq!(obs.len(), 2); // float + int; text/flag/null skipped
+    }
+
+    #[test]
+    fn unregistered_or_out_of_range_metrics_are_left_out() {
+        let json = r#"[{"id":"a","task":"nback2","created_at":"2026-06-19T08:00:00Z",
+            "metrics":{"accuracy":0.85,"hits":12,"dprime":1.5,"hit_rate":1.4}}]"#;
         let parsed = summaries_from_json(json).unwrap();
         let obs = summary_to_observations(&parsed.summaries[0]);
-        assert_eq!(obs.len(), 2); // num + int; text/flag/null skipped
+        let streams: Vec<_> = obs.iter().map(|o| (o.stream.as_str(), o.unit.as_str())).collect();
+        assert_eq!(streams, [("nback2.accuracy", "ratio"), ("nback2.hits", "count")]);
     }
 
     #[test]
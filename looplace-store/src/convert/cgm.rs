@@ -0,0 +1,541 @@
+//! Importers for third-party CGM exports: LibreView CSV, Dexcom Clarity CSV,
+//! and Nightscout `entries.json`.
+//!
+//! Each maps glucose rows onto the same shape as a reader sync: the
+//! `glucose.mg_dl` stream (mmol/L exports are converted), local wall-clock
+//! timestamps, a `kind` tag (`sensor`, `scan`, or `blood`), and a `tz` tag.
+//! Sources identify the device so re-imports are idempotent: a LibreView row
+//! keeps the reader's serial (the same source a reader sync writes), Dexcom
+//! rows are `dexcom:<transmitter>`, Nightscout rows `nightscout:<device>`.
+//! Every row also gets an `imported_from` tag naming the format.
+//!
+//! Exports only carry minute-resolution times, while the reader records
+//! seconds, so the same reading can arrive under two keys. Pass a batch
+//! through [`skip_known_readings`] before upserting to drop those.
+//!
+//! Parsing is lenient like [`super::summaries_from_json`]: a row that looks
+//! like a reading but can't be parsed is skipped and counted, never fatal.
+//! Rows that aren't glucose or ketone readings at all (insulin, carbs, alerts,
+//! notes) are ignored without counting.
+
+use std::collections::{BTreeMap, HashSet};
+
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::error::{Result, StoreError};
+use crate::observation::{Observation, Query};
+use crate::store::Store;
+use crate::streams::{self, StreamSpec};
+
+/// mg/dL per mmol/L of glucose.
+const MG_DL_PER_MMOL_L: f64 = 18.0182;
+
+/// Outcome of leniently parsing an export.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedReadings {
+    pub observations: Vec<Observation>,
+    /// Reading rows that were present but unparseable.
+    pub skipped: usize,
+}
+
+/// Parse a LibreView glucose export. `tz` is the IANA zone the device clock
+/// was set to (recorded as the `tz` tag, as a reader sync does).
+///
+/// The header row is found by name (LibreView puts a title row above it), and
+/// the glucose unit is read from the column names. LibreView writes dates in
+/// the account's locale, so day-first vs month-first is inferred from the file.
+pub fn libreview_csv(csv: &str, tz: &str) -> Result<ParsedReadings> {
+    let records = csv_records(csv)?;
+    let header_at = records
+        .iter()
+        .position(|r| r.iter().any(|f| f == "Device Timestamp"))
+        .ok_or_else(|| StoreError::Parse("no LibreView header row".into()))?;
+    let header = &records[header_at];
+    let col = |prefix: &str| header.iter().position(|h| h.starts_with(prefix));
+    let at = |name: &str| header.iter().position(|h| h == name);
+    let stamp = at("Device Timestamp").expect("header row has it");
+    let serial = at("Serial Number");
+    let glucose_columns: Vec<(usize, &str, f64)> = [
+        ("Historic Glucose", "sensor"),
+        ("Scan Glucose", "scan"),
+        ("Strip Glucose", "blood"),
+    ]
+    .into_iter()
+    .filter_map(|(prefix, kind)| {
+        let i = col(prefix)?;
+        Some((i, kind, glucose_factor(&header[i])))
+    })
+    .collect();
+    let ketone = col("Ketone");
+
+    let rows = &records[header_at + 1..];
+    let day_first = infer_day_first(rows.iter().filter_map(|r| r.get(stamp)).map(String::as_str));
+    let mut parsed = ParsedReadings::default();
+    for row in rows {
+        let field = |i: usize| row.get(i).map(|s| s.trim()).filter(|s| !s.is_empty());
+        let source = serial.and_then(field).unwrap_or("libreview");
+        let glucose = glucose_columns
+            .iter()
+            .find_map(|&(i, kind, factor)| field(i).map(|v| (v, kind, factor)));
+        let (raw, spec, kind, factor) = match (glucose, ketone.and_then(field)) {
+            (Some((v, kind, factor)), _) => (v, registered(streams::GLUCOSE), Some(kind), factor),
+            (None, Some(v)) => (v, registered(streams::KETONE), None, 1.0),
+            (None, None) => continue,
+        };
+        let timestamp = field(stamp).and_then(|s| parse_wall_clock(s, day_first));
+        let value = parse_number(raw).map(|v| to_unit(v * factor, spec));
+        match (timestamp, value) {
+            (Some(t), Some(v)) if spec.accepts(v) => {
+                let obs = reading(spec, t, v, source, kind, tz, "libreview");
+                parsed.observations.push(obs);
+            }
+            _ => parsed.skipped += 1,
+        }
+    }
+    Ok(parsed)
+}
+
+/// Parse a Dexcom Clarity CSV export. Sensor readings (`EGV`) and fingerstick
+/// calibrations are imported; `Low`/`High` placeholders have no value and
+/// are skipped.
+pub fn dexcom_csv(csv: &str, tz: &str) -> Result<ParsedReadings> {
+    let records = csv_records(csv)?;
+    let header_at = records
+        .iter()
+        .position(|r| r.iter().any(|f| f.starts_with("Timestamp (")))
+        .ok_or_else(|| StoreError::Parse("no Dexcom Clarity header row".into()))?;
+    let header = &records[header_at];
+    let col = |prefix: &str| header.iter().position(|h| h.starts_with(prefix));
+    let (Some(stamp), Some(event), Some(value_col)) = (
+        col("Timestamp ("),
+        col("Event Type"),
+        col("Glucose Value ("),
+    ) else {
+        return Err(StoreError::Parse(
+            "Dexcom export lacks timestamp/event/glucose columns".into(),
+        ));
+    };
+    let factor = glucose_factor(&header[value_col]);
+    let transmitter = col("Transmitter ID");
+    let spec = registered(streams::GLUCOSE);
+
+    let mut parsed = ParsedReadings::default();
+    for row in &records[header_at + 1..] {
+        let field = |i: usize| row.get(i).map(|s| s.trim()).filter(|s| !s.is_empty());
+        let kind = match field(event) {
+            Some("EGV") => "sensor",
+            Some("Calibration") => "blood",
+            _ => continue,
+        };
+        let source = match transmitter.and_then(field) {
+            Some(id) => format!("dexcom:{id}"),
+            None => "dexcom".into(),
+        };
+        let timestamp = field(stamp).and_then(parse_iso_wall_clock);
+        let value = field(value_col)
+            .and_then(parse_number)
+            .map(|v| to_unit(v * factor, spec));
+        match (timestamp, value) {
+            (Some(t), Some(v)) if spec.accepts(v) => {
+                let obs = reading(spec, t, v, &source, Some(kind), tz, "dexcom");
+                parsed.observations.push(obs);
+            }
+            _ => parsed.skipped += 1,
+        }
+    }
+    Ok(parsed)
+}
+
+/// Parse a Nightscout `entries.json` array. `sgv` entries are sensor readings
+/// and `mbg` entries meter (blood) readings; other types are ignored.
+///
+/// Local wall-clock comes from the entry's `utcOffset` or `dateString` offset;
+/// an entry with neither is kept at its UTC time, tagged `tz` = `UTC`.
+/// `fallback_tz` is the `tz` tag for entries that do carry an offset.
+pub fn nightscout_json(json: &str, fallback_tz: &str) -> Result<ParsedReadings> {
+    let values: Vec<serde_json::Value> = serde_json::from_str(json)
+        .map_err(|e| StoreError::Parse(format!("entries.json is not a JSON array: {e}")))?;
+    let spec = registered(streams::GLUCOSE);
+
+    let mut parsed = ParsedReadings::default();
+    for entry in &values {
+        let (kind, key) = match entry.get("type").and_then(|t| t.as_str()) {
+            Some("sgv") => ("sensor", "sgv"),
+            Some("mbg") => ("blood", "mbg"),
+            _ => continue,
+        };
+        let value = entry
+            .get(key)
+            .and_then(|v| v.as_f64())
+            .map(|v| to_unit(v, spec));
+        let source = match entry.get("device").and_then(|d| d.as_str()) {
+            Some(device) if !device.is_empty() => format!("nightscout:{device}"),
+            _ => "nightscout".into(),
+        };
+        match (nightscout_time(entry), value) {
+            (Some((t, has_offset)), Some(v)) if spec.accepts(v) => {
+                let tz = if has_offset { fallback_tz } else { "UTC" };
+                let mut obs = reading(spec, t, v, &source, Some(kind), tz, "nightscout");
+                if let Some(direction) = entry.get("direction").and_then(|d| d.as_str()) {
+                    obs.tags.insert("trend".into(), direction.to_string());
+                }
+                parsed.observations.push(obs);
+            }
+            _ => parsed.skipped += 1,
+        }
+    }
+    Ok(parsed)
+}
+
+/// Drop readings the store already holds under a different key: the same
+/// stream, source, and `kind` within the same minute, but not the exact same
+/// timestamp (an export's minute-resolution copy of a reader-synced reading,
+/// or the reverse). Exact key matches are kept so their upsert stays a plain
+/// idempotent overwrite.
+pub fn skip_known_readings(
+    store: &dyn Store,
+    observations: Vec<Observation>,
+) -> Result<Vec<Observation>> {
+    let mut spans: BTreeMap<&str, (PrimitiveDateTime, PrimitiveDateTime)> = BTreeMap::new();
+    for o in &observations {
+        let t = minute(o.timestamp);
+        let span = spans.entry(o.stream.as_str()).or_insert((t, t));
+        span.0 = span.0.min(t);
+        span.1 = span.1.max(t);
+    }
+    let mut exact = HashSet::new();
+    let mut near = HashSet::new();
+    for (stream, (since, until)) in spans {
+        let query = Query {
+            stream: Some(stream.into()),
+            since: Some(since),
+            until: Some(until + Duration::minutes(1)),
+            batch_id: None,
+        };
+        for row in store.query(&query)? {
+            let (stream, timestamp, source, kind) = row.key();
+            near.insert(near_key(stream, minute(timestamp), source, kind));
+            exact.insert(near_key(stream, timestamp, source, kind));
+        }
+    }
+    Ok(observations
+        .into_iter()
+        .filter(|o| {
+            let (stream, timestamp, source, kind) = o.key();
+            exact.contains(&near_key(stream, timestamp, source, kind))
+                || !near.contains(&near_key(stream, minute(timestamp), source, kind))
+        })
+        .collect())
+}
+
+type NearKey = (String, PrimitiveDateTime, String, Option<String>);
+
+fn near_key(stream: &str, t: PrimitiveDateTime, source: &str, kind: Option<&str>) -> NearKey {
+    (stream.into(), t, source.into(), kind.map(Into::into))
+}
+
+fn minute(t: PrimitiveDateTime) -> PrimitiveDateTime {
+    let time = Time::from_hms(t.hour(), t.minute(), 0).expect("valid time");
+    PrimitiveDateTime::new(t.date(), time)
+}
+
+fn registered(stream: &str) -> &'static StreamSpec {
+    streams::lookup(stream).expect("built-in stream is registered")
+}
+
+fn reading(
+    spec: &StreamSpec,
+    timestamp: PrimitiveDateTime,
+    value: f64,
+    source: &str,
+    kind: Option<&str>,
+    tz: &str,
+    format: &str,
+) -> Observation {
+    let mut obs = Observation::new(spec.name, timestamp, value, spec.unit, source);
+    if let Some(kind) = kind {
+        obs.tags.insert("kind".into(), kind.into());
+    }
+    obs.tags.insert("tz".into(), tz.into());
+    obs.tags.insert("imported_from".into(), format.into());
+    obs
+}
+
+/// All records of a CSV file, ragged rows allowed (exports put a title row
+/// of a different width above the header).
+fn csv_records(csv: &str) -> Result<Vec<Vec<String>>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(csv.trim_start_matches('\u{feff}').as_bytes());
+    reader
+        .records()
+        .map(|r| {
+            r.map(|r| r.iter().map(str::to_string).collect())
+                .map_err(|e| StoreError::Parse(format!("malformed CSV: {e}")))
+        })
+        .collect()
+}
+
+/// Multiplier from a column's unit (named in its header) to mg/dL.
+fn glucose_factor(header: &str) -> f64 {
+    if header.contains("mmol/L") {
+        MG_DL_PER_MMOL_L
+    } else {
+        1.0
+    }
+}
+
+/// Round to the stream's display precision, so converted values are stable.
+fn to_unit(value: f64, spec: &StreamSpec) -> f64 {
+    let scale = 10f64.powi(i32::from(spec.precision));
+    (value * scale).round() / scale
+}
+
+/// A number in either decimal convention (`5,6` as well as `5.6`).
+pub(super) fn parse_number(s: &str) -> Option<f64> {
+    s.trim()
+        .replace(',', ".")
+        .parse()
+        .ok()
+        .filter(|v: &f64| v.is_finite())
+}
+
+/// Whether `DD-MM` dates are more plausible than `MM-DD` for these samples:
+/// true as soon as one leading component can't be a month.
+fn infer_day_first<'a>(samples: impl Iterator<Item = &'a str>) -> bool {
+    for s in samples {
+        let parts = date_parts(s);
+        if let [a, b, _] = parts[..] {
+            if a > 12 {
+                return true;
+            }
+            if b > 12 {
+                return false;
+            }
+        }
+    }
+    false
+}
+
+/// The numeric components of a timestamp's date (`06-19-2026` → `[6, 19, 2026]`).
+fn date_parts(s: &str) -> Vec<u32> {
+    s.split_whitespace()
+        .next()
+        .unwrap_or("")
+        .split(['-', '/', '.'])
+        .map(|p| p.parse().unwrap_or(0))
+        .collect()
+}
+
+/// A locale-formatted wall-clock time: `MM-DD-YYYY HH:MM` (or day-first), any
+/// of `-`/`/`/`.` as the date separator, optional seconds and AM/PM, or ISO
+/// `YYYY-MM-DD HH:MM`.
+fn parse_wall_clock(s: &str, day_first: bool) -> Option<PrimitiveDateTime> {
+    let mut words = s.split_whitespace();
+    let (date, clock) = (words.next()?, words.next()?);
+    let meridiem = words.next().map(str::to_ascii_uppercase);
+
+    let parts = date_parts(date);
+    let (year, month, day) = match parts[..] {
+        [y, m, d] if y > 999 => (y, m, d),
+        [a, b, y] if day_first => (y, b, a),
+        [a, b, y] => (y, a, b),
+        _ => return None,
+    };
+    let mut hms = clock.split(':').map(|p| p.parse::<u8>().ok());
+    let (mut hour, minute) = (hms.next()??, hms.next()??);
+    let second = hms.next().flatten().unwrap_or(0);
+    match meridiem.as_deref() {
+        Some("PM") if hour < 12 => hour += 12,
+        Some("AM") if hour == 12 => hour = 0,
+        _ => {}
+    }
+    let month = Month::try_from(u8::try_from(month).ok()?).ok()?;
+    let date = Date::from_calendar_date(i32::try_from(year).ok()?, month, u8::try_from(day).ok()?);
+    Some(PrimitiveDateTime::new(
+        date.ok()?,
+        Time::from_hms(hour, minute, second).ok()?,
+    ))
+}
+
+/// `YYYY-MM-DDThh:mm:ss` (or with a space), as Dexcom writes it.
+fn parse_iso_wall_clock(s: &str) -> Option<PrimitiveDateTime> {
+    let t = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
+    let space = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+    PrimitiveDateTime::parse(s, &t)
+        .or_else(|_| PrimitiveDateTime::parse(s, &space))
+        .ok()
+}
+
+/// An entry's local wall-clock, and whether it came with a UTC offset.
+fn nightscout_time(entry: &serde_json::Value) -> Option<(PrimitiveDateTime, bool)> {
+    let epoch_ms = entry.get("date").and_then(|d| d.as_i64());
+    let offset_min = entry.get("utcOffset").and_then(|o| o.as_i64());
+    if let (Some(ms), Some(offset)) = (epoch_ms, offset_min) {
+        let offset = UtcOffset::from_whole_seconds(i32::try_from(offset * 60).ok()?).ok()?;
+        let odt = from_epoch_ms(ms)?.to_offset(offset);
+        return Some((PrimitiveDateTime::new(odt.date(), odt.time()), true));
+    }
+    if let Some(s) = entry.get("dateString").and_then(|d| d.as_str()) {
+        let compact = format_description!(
+            "[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]][offset_hour sign:mandatory][offset_minute]"
+        );
+        if let Ok(odt) =
+            OffsetDateTime::parse(s, &Rfc3339).or_else(|_| OffsetDateTime::parse(s, &compact))
+        {
+            return Some((PrimitiveDateTime::new(odt.date(), odt.time()), true));
+        }
+    }
+    let utc = from_epoch_ms(epoch_ms?)?;
+    Some((PrimitiveDateTime::new(utc.date(), utc.time()), false))
+}
+
+fn from_epoch_ms(ms: i64) -> Option<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp_nanos(i128::from(ms) * 1_000_000).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+    use time::macros::datetime;
+
+    const LIBREVIEW: &str = "\u{feff}Glucose Data,Generated on,06-20-2026 09:00 UTC,Generated by,Pat\n\
+Device,Serial Number,Device Timestamp,Record Type,Historic Glucose mg/dL,Scan Glucose mg/dL,Non-numeric Rapid-Acting Insulin,Rapid-Acting Insulin (units),Notes,Strip Glucose mg/dL,Ketone mmol/L\n\
+FreeStyle Libre 2,MPGF176-T4167,06-19-2026 08:00,0,102,,,,,,\n\
+FreeStyle Libre 2,MPGF176-T4167,06-19-2026 08:07,1,,110,,,\"after coffee, black\",,\n\
+FreeStyle Libre 2,MPGF176-T4167,06-19-2026 08:10,4,,,,2,,,\n\
+FreeStyle Libre 2,MPGF176-T4167,06-19-2026 08:20,2,,,,,,98,\n\
+FreeStyle Libre 2,MPGF176-T4167,06-19-2026 08:25,3,,,,,,,0.4\n\
+FreeStyle Libre 2,MPGF176-T4167,not a time,0,104,,,,,,\n";
+
+    #[test]
+    fn libreview_rows_map_to_kinds_and_the_reader_serial() {
+        let parsed = libreview_csv(LIBREVIEW, "America/Denver").unwrap();
+        assert_eq!(parsed.skipped, 1);
+        let rows: Vec<_> = parsed
+            .observations
+            .iter()
+            .map(|o| {
+                (
+                    o.stream.as_str(),
+                    o.value,
+                    o.tags.get("kind").map(String::as_str),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (streams::GLUCOSE, 102.0, Some("sensor")),
+                (streams::GLUCOSE, 110.0, Some("scan")),
+                (streams::GLUCOSE, 98.0, Some("blood")),
+                (streams::KETONE, 0.4, None),
+            ]
+        );
+        let first = &parsed.observations[0];
+        assert_eq!(first.timestamp, datetime!(2026-06-19 08:00:00));
+        assert_eq!(first.source, "MPGF176-T4167");
+        assert_eq!(
+            first.tags.get("tz").map(String::as_str),
+            Some("America/Denver")
+        );
+        assert_eq!(
+            first.tags.get("imported_from").map(String::as_str),
+            Some("libreview")
+        );
+    }
+
+    #[test]
+    fn libreview_mmol_and_day_first_exports_are_normalised() {
+        let csv = "Device,Serial Number,Device Timestamp,Record Type,Historic Glucose mmol/L,Scan Glucose mmol/L\n\
+FreeStyle LibreLink,ab12,19-06-2026 08:00,0,\"5,6\",\n\
+FreeStyle LibreLink,ab12,02/07/2026 9:15 PM,1,,7.2\n";
+        let parsed = libreview_csv(csv, "Europe/Madrid").unwrap();
+        assert_eq!(parsed.skipped, 0);
+        assert_eq!(parsed.observations[0].value, 101.0);
+        assert_eq!(parsed.observations[1].value, 130.0);
+        assert_eq!(
+            parsed.observations[1].timestamp,
+            datetime!(2026-07-02 21:15:00)
+        );
+        assert!(libreview_csv("a,b\n1,2\n", "UTC").is_err());
+    }
+
+    #[test]
+    fn dexcom_imports_egvs_and_calibrations() {
+        let csv = "Index,Timestamp (YYYY-MM-DDThh:mm:ss),Event Type,Event Subtype,Patient Info,Device Info,Source Device ID,Glucose Value (mg/dL),Insulin Value (u),Carb Value (grams),Duration (hh:mm:ss),Glucose Rate of Change (mg/dL/min),Transmitter Time (Long Integer),Transmitter ID\n\
+1,,FirstName,,Pat,,,,,,,,,\n\
+2,,Device,,,\"G6 Mobile App\",iOS G6,,,,,,,\n\
+3,2026-06-19T08:00:12,EGV,,,,iOS G6,115,,,,,1000,8XXXXX\n\
+4,2026-06-19T08:05:12,EGV,,,,iOS G6,Low,,,,,1300,8XXXXX\n\
+5,2026-06-19T08:07:00,Calibration,,,,iOS G6,120,,,,,,8XXXXX\n\
+6,2026-06-19T08:09:00,Carbs,,,,iOS G6,,,30,,,,\n";
+        let parsed = dexcom_csv(csv, "America/Chicago").unwrap();
+        assert_eq!(parsed.skipped, 1);
+        assert_eq!(parsed.observations.len(), 2);
+        let egv = &parsed.observations[0];
+        assert_eq!(egv.timestamp, datetime!(2026-06-19 08:00:12));
+        assert_eq!(egv.source, "dexcom:8XXXXX");
+        assert_eq!(egv.tags.get("kind").map(String::as_str), Some("sensor"));
+        assert_eq!(
+            parsed.observations[1].tags.get("kind").map(String::as_str),
+            Some("blood")
+        );
+    }
+
+    #[test]
+    fn nightscout_entries_use_their_local_offset() {
+        let json = r#"[
+            {"type":"sgv","sgv":120,"date":1781877600000,"utcOffset":-360,"device":"xDrip-DexcomG6","direction":"Flat"},
+            {"type":"mbg","mbg":131,"dateString":"2026-06-19T08:10:00.000-0600","device":"meter"},
+            {"type":"sgv","sgv":99,"date":1781878200000},
+            {"type":"cal","slope":900},
+            {"type":"sgv","sgv":"bad","date":1781878500000}
+        ]"#;
+        let parsed = nightscout_json(json, "America/Denver").unwrap();
+        assert_eq!(parsed.skipped, 1);
+        let [sgv, mbg, bare] = &parsed.observations[..] else {
+            panic!("expected three readings");
+        };
+        assert_eq!(sgv.timestamp, datetime!(2026-06-19 08:00:00));
+        assert_eq!(sgv.source, "nightscout:xDrip-DexcomG6");
+        assert_eq!(sgv.tags.get("trend").map(String::as_str), Some("Flat"));
+        assert_eq!(mbg.timestamp, datetime!(2026-06-19 08:10:00));
+        assert_eq!(mbg.tags.get("kind").map(String::as_str), Some("blood"));
+        assert_eq!(bare.timestamp, datetime!(2026-06-19 14:10:00));
+        assert_eq!(bare.tags.get("tz").map(String::as_str), Some("UTC"));
+        assert!(nightscout_json("{}", "UTC").is_err());
+    }
+
+    #[test]
+    fn known_readings_are_not_imported_twice() {
+        let mut store = MemoryStore::new();
+        // A reader sync stored this sensor reading with seconds.
+        let mut synced = Observation::new(
+            streams::GLUCOSE,
+            datetime!(2026-06-19 08:00:41),
+            102.0,
+            "mg/dL",
+            "MPGF176-T4167",
+        );
+        synced.tags.insert("kind".into(), "sensor".into());
+        store.upsert(&[synced]).unwrap();
+
+        let parsed = libreview_csv(LIBREVIEW, "America/Denver").unwrap();
+        let fresh = skip_known_readings(&store, parsed.observations.clone()).unwrap();
+        assert_eq!(fresh.len(), parsed.observations.len() - 1);
+        assert!(fresh
+            .iter()
+            .all(|o| o.timestamp != datetime!(2026-06-19 08:00:00)));
+
+        // Re-importing the same file keeps exact matches (a no-op upsert).
+        store.upsert(&fresh).unwrap();
+        let again = skip_known_readings(&store, fresh.clone()).unwrap();
+        assert_eq!(again, fresh);
+        assert_eq!(store.upsert(&again).unwrap(), 0);
+    }
+}
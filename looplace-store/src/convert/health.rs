@@ -0,0 +1,434 @@
+//! Streaming importer for Apple Health's `export.xml`.
+//!
+//! Exports run to gigabytes, so the file is read event by event and handed
+//! back in batches of at most [`BATCH`] observations; nothing but the current
+//! batch is held in memory. A caller writes each batch as it arrives; against
+//! a `ParquetStore` it does so inside a bulk write (`begin_bulk_write`), so the
+//! store's files are rewritten once at the end rather than per batch.
+//! Mapped records:
+//!
+//! | Apple Health type                                  | Stream                      |
+//! |----------------------------------------------------|-----------------------------|
+//! | `HKQuantityTypeIdentifierHeartRate`                | `health.heart_rate_bpm`     |
+//! | `HKQuantityTypeIdentifierHeartRateVariabilitySDNN` | `health.hrv_sdnn_ms`        |
+//! | `HKQuantityTypeIdentifierStepCount`                | `health.steps`              |
+//! | `HKQuantityTypeIdentifierActiveEnergyBurned`       | `health.active_energy_kcal` |
+//! | `HKCategoryTypeIdentifierSleepAnalysis`            | `health.sleep_h`            |
+//! | `Workout` elements                                 | `health.workout_min`        |
+//!
+//! Sleep and workouts store the interval's duration at its start, with the
+//! sleep stage (`in_bed`, `asleep`, `awake`, `core`, `deep`, `rem`) or the
+//! workout activity (`running`, `traditional_strength_training`, …) as `kind`.
+//! The source is `apple_health:<sourceName>` (the app or watch that recorded
+//! it), so the phone's and the watch's step counts stay separate rows; the
+//! recording hardware goes in `device` / `device_hardware` tags. Timestamps
+//! keep the wall-clock the export wrote, tagged with the caller's `tz`, and
+//! every row is tagged `imported_from` = `apple_health`.
+//!
+//! Other record types are ignored. A mapped record with a missing date, an
+//! unknown unit, or a value the [`streams`] registry refuses is skipped and
+//! counted.
+
+use std::io::BufRead;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::error::{Result, StoreError};
+use crate::observation::Observation;
+use crate::streams::{self, StreamSpec};
+
+/// Largest batch handed to the caller at once.
+pub const BATCH: usize = 5_000;
+
+const KJ_PER_KCAL: f64 = 4.184;
+
+/// Running totals, reported with every batch and returned at the end.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HealthProgress {
+    /// Bytes of the export consumed so far (compare with the file size).
+    pub bytes: u64,
+    /// Observations mapped so far.
+    pub imported: usize,
+    /// Mapped records that couldn't be converted.
+    pub skipped: usize,
+}
+
+/// Stream `export.xml` from `input`, calling `on_batch` with each batch of
+/// observations and the progress so far. An error from `on_batch` (e.g. a
+/// failed upsert) stops the import and is returned as is.
+pub fn apple_health_xml<R: BufRead>(
+    input: R,
+    tz: &str,
+    mut on_batch: impl FnMut(Vec<Observation>, HealthProgress) -> Result<()>,
+) -> Result<HealthProgress> {
+    let mut reader = Reader::from_reader(input);
+    let mut buf = Vec::new();
+    let mut batch = Vec::new();
+    let mut progress = HealthProgress::default();
+    loop {
+        let event = reader.read_event_into(&mut buf).map_err(|e| {
+            StoreError::Parse(format!(
+                "export.xml at byte {}: {e}",
+                reader.error_position()
+            ))
+        })?;
+        let mapped = match &event {
+            Event::Start(e) | Event::Empty(e) => match e.name().as_ref() {
+                b"Record" => record(e, tz),
+                b"Workout" => workout(e, tz),
+                _ => None,
+            },
+            Event::Eof => break,
+            _ => None,
+        };
+        match mapped {
+            Some(Some(obs)) => {
+                progress.imported += 1;
+                batch.push(obs);
+            }
+            Some(None) => progress.skipped += 1,
+            None => {}
+        }
+        buf.clear();
+        if batch.len() >= BATCH {
+            progress.bytes = reader.buffer_position();
+            on_batch(std::mem::take(&mut batch), progress)?;
+        }
+    }
+    progress.bytes = reader.buffer_position();
+    if !batch.is_empty() {
+        on_batch(batch, progress)?;
+    }
+    Ok(progress)
+}
+
+/// `None` for an unmapped type; `Some(None)` for a mapped record that failed.
+fn record(e: &BytesStart, tz: &str) -> Option<Option<Observation>> {
+    let kind = attr(e, "type")?;
+    let stream = match kind.as_str() {
+        "HKQuantityTypeIdentifierHeartRate" => "health.heart_rate_bpm",
+        "HKQuantityTypeIdentifierHeartRateVariabilitySDNN" => "health.hrv_sdnn_ms",
+        "HKQuantityTypeIdentifierStepCount" => "health.steps",
+        "HKQuantityTypeIdentifierActiveEnergyBurned" => "health.active_energy_kcal",
+        "HKCategoryTypeIdentifierSleepAnalysis" => return Some(sleep(e, tz)),
+        _ => return None,
+    };
+    let factor = match attr(e, "unit").as_deref() {
+        Some("count/min" | "ms" | "count" | "kcal" | "Cal") => 1.0,
+        Some("kJ") => 1.0 / KJ_PER_KCAL,
+        _ => return Some(None),
+    };
+    let value = attr(e, "value").and_then(|v| v.parse::<f64>().ok());
+    let start = attr(e, "startDate").and_then(|d| parse_date(&d));
+    Some(
+        start
+            .zip(value)
+            .and_then(|(t, v)| observation(e, spec(stream), t, v * factor, None, tz)),
+    )
+}
+
+fn sleep(e: &BytesStart, tz: &str) -> Option<Observation> {
+    let stage = match attr(e, "value")?.strip_prefix("HKCategoryValueSleepAnalysis")? {
+        "InBed" => "in_bed",
+        "Asleep" | "AsleepUnspecified" => "asleep",
+        "Awake" => "awake",
+        "AsleepCore" => "core",
+        "AsleepDeep" => "deep",
+        "AsleepREM" => "rem",
+        _ => return None,
+    };
+    let (start, hours) = interval(e)?;
+    observation(e, spec("health.sleep_h"), start, hours, Some(stage), tz)
+}
+
+fn workout(e: &BytesStart, tz: &str) -> Option<Option<Observation>> {
+    let activity = attr(e, "workoutActivityType")?;
+    let activity = snake_case(
+        activity
+            .strip_prefix("HKWorkoutActivityType")
+            .unwrap_or(&activity),
+    );
+    let Some((start, hours)) = interval(e) else {
+        return Some(None);
+    };
+    let minutes = match (attr(e, "duration"), attr(e, "durationUnit").as_deref()) {
+        (Some(d), Some("min")) => d.parse().ok(),
+        (Some(d), Some("hr")) => d.parse::<f64>().ok().map(|h| h * 60.0),
+        (Some(d), Some("s")) => d.parse::<f64>().ok().map(|s| s / 60.0),
+        _ => Some(hours * 60.0),
+    };
+    let minutes = minutes.filter(|m: &f64| m.is_finite());
+    Some(
+        minutes.and_then(|m| {
+            observation(e, spec("health.workout_min"), start, m, Some(&activity), tz)
+        }),
+    )
+}
+
+/// Start of the element's `startDate`..`endDate`, and its length in hours.
+fn interval(e: &BytesStart) -> Option<(PrimitiveDateTime, f64)> {
+    let start = attr(e, "startDate").and_then(|d| parse_offset_date(&d))?;
+    let end = attr(e, "endDate").and_then(|d| parse_offset_date(&d))?;
+    let hours = (end - start).as_seconds_f64() / 3600.0;
+    Some((PrimitiveDateTime::new(start.date(), start.time()), hours))
+}
+
+fn observation(
+    e: &BytesStart,
+    spec: &StreamSpec,
+    timestamp: PrimitiveDateTime,
+    value: f64,
+    kind: Option<&str>,
+    tz: &str,
+) -> Option<Observation> {
+    let scale = 10f64.powi(i32::from(spec.precision));
+    let value = (value * scale).round() / scale;
+    if !spec.accepts(value) {
+        return None;
+    }
+    let source = attr(e, "sourceName").unwrap_or_else(|| "unknown".into());
+    let mut obs = Observation::new(
+        spec.name,
+        timestamp,
+        value,
+        spec.unit,
+        format!("apple_health:{source}"),
+    );
+    if let Some(kind) = kind {
+        obs.tags.insert("kind".into(), kind.into());
+    }
+    if let Some(device) = attr(e, "device") {
+        if let Some(name) = device_field(&device, "name") {
+            obs.tags.insert("device".into(), name.into());
+        }
+        if let Some(hardware) = device_field(&device, "hardware") {
+            obs.tags.insert("device_hardware".into(), hardware.into());
+        }
+    }
+    obs.tags.insert("tz".into(), tz.into());
+    obs.tags
+        .insert("imported_from".into(), "apple_health".into());
+    Some(obs)
+}
+
+fn spec(stream: &str) -> &'static StreamSpec {
+    streams::lookup(stream).expect("Apple Health streams are registered")
+}
+
+fn attr(e: &BytesStart, name: &str) -> Option<String> {
+    let attr = e.try_get_attribute(name).ok()??;
+    Some(attr.unescape_value().ok()?.into_owned())
+}
+
+/// One field of an `HKDevice` description:
+/// `<<HKDevice: 0x…>, name:Apple Watch, manufacturer:Apple Inc., hardware:Watch6,1, …>`.
+fn device_field<'a>(device: &'a str, key: &str) -> Option<&'a str> {
+    device
+        .trim_end_matches('>')
+        .split(", ")
+        .find_map(|part| part.strip_prefix(key)?.strip_prefix(':'))
+        .filter(|v| !v.is_empty())
+}
+
+/// `2026-06-19 08:00:00 -0600`.
+fn parse_offset_date(s: &str) -> Option<OffsetDateTime> {
+    let format = format_description!(
+        "[year]-[month]-[day] [hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"
+    );
+    OffsetDateTime::parse(s, &format).ok()
+}
+
+/// The wall-clock an export date was written in.
+fn parse_date(s: &str) -> Option<PrimitiveDateTime> {
+    let t = parse_offset_date(s)?;
+    Some(PrimitiveDateTime::new(t.date(), t.time()))
+}
+
+/// `TraditionalStrengthTraining` → `traditional_strength_training`.
+fn snake_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 4);
+    for (i, c) in s.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    const EXPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE HealthData [
+<!ELEMENT HealthData (ExportDate,Me,(Record|Workout)*)>
+]>
+<HealthData locale="en_US">
+ <ExportDate value="2026-06-20 09:00:00 -0600"/>
+ <Me HKCharacteristicTypeIdentifierDateOfBirth=""/>
+ <Record type="HKQuantityTypeIdentifierHeartRate" sourceName="Pat&#x2019;s Apple Watch" sourceVersion="9.0" device="&lt;&lt;HKDevice: 0x283a2e7b0&gt;, name:Apple Watch, manufacturer:Apple Inc., model:Watch, hardware:Watch6,1, software:9.0&gt;" unit="count/min" creationDate="2026-06-19 08:01:10 -0600" startDate="2026-06-19 08:00:41 -0600" endDate="2026-06-19 08:00:41 -0600" value="62">
+  <MetadataEntry key="HKMetadataKeyHeartRateMotionContext" value="0"/>
+ </Record>
+ <Record type="HKQuantityTypeIdentifierHeartRateVariabilitySDNN" sourceName="Pat&#x2019;s Apple Watch" unit="ms" startDate="2026-06-19 03:10:00 -0600" endDate="2026-06-19 03:11:00 -0600" value="48.6"/>
+ <Record type="HKQuantityTypeIdentifierStepCount" sourceName="Pat&#x2019;s iPhone" unit="count" startDate="2026-06-19 08:00:00 -0600" endDate="2026-06-19 08:10:00 -0600" value="812"/>
+ <Record type="HKQuantityTypeIdentifierActiveEnergyBurned" sourceName="Pat&#x2019;s Apple Watch" unit="kJ" startDate="2026-06-19 08:00:00 -0600" endDate="2026-06-19 08:05:00 -0600" value="41.84"/>
+ <Record type="HKQuantityTypeIdentifierBodyMass" sourceName="Scale" unit="kg" startDate="2026-06-19 07:00:00 -0600" endDate="2026-06-19 07:00:00 -0600" value="70"/>
+ <Record type="HKCategoryTypeIdentifierSleepAnalysis" sourceName="Pat&#x2019;s Apple Watch" startDate="2026-06-18 23:30:00 -0600" endDate="2026-06-19 01:00:00 -0600" value="HKCategoryValueSleepAnalysisAsleepCore"/>
+ <Record type="HKCategoryTypeIdentifierSleepAnalysis" sourceName="Pat&#x2019;s Apple Watch" startDate="2026-06-19 01:00:00 -0600" endDate="2026-06-19 01:45:00 -0600" value="HKCategoryValueSleepAnalysisAsleepREM"/>
+ <Record type="HKQuantityTypeIdentifierHeartRate" sourceName="Pat&#x2019;s Apple Watch" unit="count/min" startDate="2026-06-19 08:02:00 -0600" endDate="2026-06-19 08:02:00 -0600" value="900"/>
+ <Record type="HKQuantityTypeIdentifierStepCount" sourceName="Pat&#x2019;s iPhone" unit="count" startDate="yesterday" endDate="yesterday" value="10"/>
+ <Workout workoutActivityType="HKWorkoutActivityTypeTraditionalStrengthTraining" duration="32.5" durationUnit="min" sourceName="Pat&#x2019;s Apple Watch" startDate="2026-06-19 17:00:00 -0600" endDate="2026-06-19 17:32:30 -0600">
+  <WorkoutEvent type="HKWorkoutEventTypeSegment" date="2026-06-19 17:00:00 -0600"/>
+ </Workout>
+</HealthData>
+"#;
+
+    fn import(xml: &str) -> (Vec<Observation>, HealthProgress, usize) {
+        let mut all = Vec::new();
+        let mut batches = 0;
+        let progress = apple_health_xml(xml.as_bytes(), "America/Denver", |batch, _| {
+            batches += 1;
+            all.extend(batch);
+            Ok(())
+        })
+        .unwrap();
+        (all, progress, batches)
+    }
+
+    fn find<'a>(rows: &'a [Observation], stream: &str) -> Vec<&'a Observation> {
+        rows.iter().filter(|o| o.stream == stream).collect()
+    }
+
+    #[test]
+    fn records_map_to_health_streams_with_source_and_device() {
+        let (rows, progress, _) = import(EXPORT);
+        assert_eq!(progress.imported, 7);
+        assert_eq!(progress.skipped, 2);
+        assert_eq!(progress.bytes, EXPORT.len() as u64);
+        rows.iter().try_for_each(streams::validate).unwrap();
+
+        let hr = find(&rows, "health.heart_rate_bpm")[0];
+        assert_eq!(hr.timestamp, datetime!(2026-06-19 08:00:41));
+        assert_eq!(hr.value, 62.0);
+        assert_eq!(hr.source, "apple_health:Pat\u{2019}s Apple Watch");
+        assert_eq!(
+            hr.tags.get("device").map(String::as_str),
+            Some("Apple Watch")
+        );
+        assert_eq!(
+            hr.tags.get("device_hardware").map(String::as_str),
+            Some("Watch6,1")
+        );
+        assert_eq!(
+            hr.tags.get("tz").map(String::as_str),
+            Some("America/Denver")
+        );
+        assert_eq!(
+            hr.tags.get("imported_from").map(String::as_str),
+            Some("apple_health")
+        );
+
+        assert_eq!(find(&rows, "health.hrv_sdnn_ms")[0].value, 49.0);
+        assert_eq!(
+            find(&rows, "health.steps")[0].source,
+            "apple_health:Pat\u{2019}s iPhone"
+        );
+        assert_eq!(find(&rows, "health.active_energy_kcal")[0].value, 10.0);
+    }
+
+    #[test]
+    fn sleep_and_workouts_are_durations_with_a_kind() {
+        let (rows, _, _) = import(EXPORT);
+        let sleep: Vec<_> = find(&rows, "health.sleep_h")
+            .iter()
+            .map(|o| (o.timestamp, o.value, o.tags["kind"].as_str()))
+            .collect();
+        assert_eq!(
+            sleep,
+            [
+                (datetime!(2026-06-18 23:30:00), 1.5, "core"),
+                (datetime!(2026-06-19 01:00:00), 0.75, "rem"),
+            ]
+        );
+        let workout = find(&rows, "health.workout_min")[0];
+        assert_eq!(workout.value, 33.0);
+        assert_eq!(workout.tags["kind"], "traditional_strength_training");
+    }
+
+    #[cfg(feature = "parquet-store")]
+    #[test]
+    fn a_year_of_heart_rate_streams_into_one_bulk_write() {
+        use crate::provenance::{self, IngestBatch, APPLE_HEALTH};
+        use crate::{ParquetStore, Query, Store};
+
+        // Every five minutes for a year: a modest watch export.
+        let start = datetime!(2025-06-19 00:00:00);
+        let minutes = 365 * 24 * 60;
+        let format = format_description!("[year]-[month]-[day] [hour]:[minute]:[second] +0000");
+        let mut xml = String::from("<HealthData>");
+        for minute in (0..minutes).step_by(5) {
+            let t = start + time::Duration::minutes(minute);
+            let date = t.format(&format).unwrap();
+            xml.push_str(&format!(
+                r#"<Record type="HKQuantityTypeIdentifierHeartRate" sourceName="Watch" unit="count/min" startDate="{date}" endDate="{date}" value="{}"/>"#,
+                60 + minute % 40
+            ));
+        }
+        xml.push_str("</HealthData>");
+
+        let dir = std::env::temp_dir().join("looplace_health_year");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("store.parquet");
+        let mut store = ParquetStore::open(&path).unwrap();
+        let ingest = IngestBatch::new(APPLE_HEALTH, "test");
+        store.begin_bulk_write().unwrap();
+        let (mut batches, mut added) = (0, 0);
+        let progress = apple_health_xml(xml.as_bytes(), "UTC", |batch, _| {
+            batches += 1;
+            added += provenance::ingest(&mut store, &ingest, batch)?;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(progress.imported, 105_120);
+        assert_eq!(batches, 22);
+        assert_eq!(added, 105_120);
+        assert!(!path.exists());
+        store.finish_bulk_write().unwrap();
+
+        let reopened = ParquetStore::open(&path).unwrap();
+        let hr = reopened
+            .query(&Query::stream("health.heart_rate_bpm"))
+            .unwrap();
+        assert_eq!(hr.len(), 105_120);
+        let id = Some(ingest.id.as_str());
+        assert!(hr.iter().all(|o| o.batch_id.as_deref() == id));
+        assert_eq!(reopened.batches().unwrap().len(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn large_exports_arrive_in_bounded_batches() {
+        let row = r#"<Record type="HKQuantityTypeIdentifierStepCount" sourceName="Phone" unit="count" startDate="2026-06-19 08:00:00 +0000" endDate="2026-06-19 08:01:00 +0000" value="5"/>"#;
+        let xml = format!("<HealthData>{}</HealthData>", row.repeat(BATCH * 2 + 1));
+        let mut sizes = Vec::new();
+        let progress = apple_health_xml(xml.as_bytes(), "UTC", |batch, _| {
+            sizes.push(batch.len());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(sizes, [BATCH, BATCH, 1]);
+        assert_eq!(progress.imported, BATCH * 2 + 1);
+
+        let stop = apple_health_xml(xml.as_bytes(), "UTC", |_, _| {
+            Err(StoreError::Backend("full".into()))
+        });
+        assert!(matches!(stop, Err(StoreError::Backend(_))));
+        assert!(apple_health_xml("<HealthData><Record".as_bytes(), "UTC", |_, _| Ok(())).is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! Generic CSV/JSON import driven by a user-defined [`ImportMapping`]: which
+//! column holds the time (and how it's written), which the value, and which
+//! stream, unit, and timezone the values belong to. For the spreadsheets
+//! people keep by hand (weight, blood pressure, mood) rather than a named
+//! export format.
+//!
+//! [`parse`] is the dry run: it maps every row without writing anything and,
+//! like [`super::summaries_from_json`], skips and counts rows it can't use (a
+//! bad time, a non-numeric value, a value the [`streams`] registry refuses)
+//! instead of failing. Only a file without the mapped columns, or a mapping
+//! that can't work at all ([`ImportMapping::check`]), is an error.
+//!
+//! Rows get `import:<mapping name>` as their source, so importing the same
+//! file again overwrites rather than duplicates, plus `tz` and
+//! `imported_from` (`csv`/`json`) tags. Mappings are plain serde values; the
+//! app saves them by name so a recurring import is one step.
+
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::format_description::OwnedFormatItem;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+use super::cgm::{parse_number, ParsedReadings};
+use crate::error::{Result, StoreError};
+use crate::observation::Observation;
+use crate::streams;
+
+/// How a mapped file is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MappedFormat {
+    /// A header row, then one row per observation. The delimiter (`,`, `;`,
+    /// or tab) is detected from the header.
+    Csv,
+    /// An array of flat objects; keys are the columns.
+    Json,
+}
+
+impl MappedFormat {
+    /// The format for a file name's extension (`.json`, else CSV).
+    pub fn for_file_name(name: &str) -> Self {
+        if name.to_ascii_lowercase().ends_with(".json") {
+            MappedFormat::Json
+        } else {
+            MappedFormat::Csv
+        }
+    }
+}
+
+/// How the timestamp column is written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "pattern", rename_all = "snake_case")]
+pub enum TimestampFormat {
+    /// RFC 3339 / ISO 8601 with an offset; the wall-clock is kept as written.
+    Rfc3339,
+    /// Seconds since the Unix epoch; stored as UTC wall-clock, tagged `UTC`.
+    UnixSeconds,
+    /// Milliseconds since the Unix epoch; as [`TimestampFormat::UnixSeconds`].
+    UnixMillis,
+    /// A `time` format description, e.g. `[day]/[month]/[year] [hour]:[minute]`.
+    /// A date-only pattern puts the value at midnight.
+    Pattern(String),
+}
+
+/// A saved recipe for turning one file layout into observations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportMapping {
+    /// Name the mapping is saved under; also the rows' source (`import:<name>`).
+    pub name: String,
+    pub timestamp_column: String,
+    pub timestamp_format: TimestampFormat,
+    pub value_column: String,
+    /// A registered stream, or a `custom.` one.
+    pub stream: String,
+    /// Must be the stream's canonical unit if it's registered.
+    pub unit: String,
+    /// IANA zone the wall-clock times are in (the `tz` tag).
+    pub tz: String,
+}
+
+impl ImportMapping {
+    /// Refuse a mapping that can't produce a single valid row: blank fields,
+    /// an unknown stream, the wrong unit for a registered stream, or an
+    /// unparseable timestamp pattern.
+    pub fn check(&self) -> Result<()> {
+        let blank = [
+            ("name", &self.name),
+            ("timestamp column", &self.timestamp_column),
+            ("value column", &self.value_column),
+            ("timezone", &self.tz),
+        ]
+        .into_iter()
+        .find(|(_, v)| v.trim().is_empty());
+        if let Some((field, _)) = blank {
+            return Err(StoreError::Parse(format!("mapping needs a {field}")));
+        }
+        if !streams::is_custom(&self.stream) {
+            let spec = streams::lookup(&self.stream)
+                .ok_or_else(|| StoreError::UnknownStream(self.stream.clone()))?;
+            if spec.unit != self.unit {
+                return Err(StoreError::Parse(format!(
+                    "{} is stored in {:?}, not {:?}",
+                    spec.name, spec.unit, self.unit
+                )));
+            }
+        }
+        self.pattern().map(drop)
+    }
+
+    fn pattern(&self) -> Result<Option<OwnedFormatItem>> {
+        match &self.timestamp_format {
+            TimestampFormat::Pattern(p) => time::format_description::parse_owned::<2>(p)
+                .map(Some)
+                .map_err(|e| StoreError::Parse(format!("bad timestamp pattern {p:?}: {e}"))),
+            _ => Ok(None),
+        }
+    }
+
+    fn source(&self) -> String {
+        format!("import:{}", self.name.trim())
+    }
+}
+
+/// The column names of a file, for choosing a mapping's columns.
+pub fn columns(text: &str, format: MappedFormat) -> Result<Vec<String>> {
+    match format {
+        MappedFormat::Csv => Ok(csv_reader(text)
+            .headers()
+            .map_err(csv_error)?
+            .iter()
+            .map(str::to_string)
+            .collect()),
+        MappedFormat::Json => {
+            let mut names: Vec<String> = Vec::new();
+            for row in json_rows(text)? {
+                for key in row.as_object().into_iter().flat_map(|o| o.keys()) {
+                    if !names.contains(key) {
+                        names.push(key.clone());
+                    }
+                }
+            }
+            Ok(names)
+        }
+    }
+}
+
+/// Map every row of `text` through `mapping` without writing anything.
+pub fn parse(text: &str, format: MappedFormat, mapping: &ImportMapping) -> Result<ParsedReadings> {
+    mapping.check()?;
+    let pattern = mapping.pattern()?;
+    let (cells, from) = match format {
+        MappedFormat::Csv => (csv_cells(text, mapping)?, "csv"),
+        MappedFormat::Json => (json_cells(text, mapping)?, "json"),
+    };
+    let mut parsed = ParsedReadings::default();
+    for (time, value) in cells {
+        let timestamp =
+            time.and_then(|t| parse_time(t, &mapping.timestamp_format, pattern.as_ref()));
+        let Some(((t, utc), v)) = timestamp.zip(value.and_then(Cell::number)) else {
+            parsed.skipped += 1;
+            continue;
+        };
+        let mut obs = Observation::new(&mapping.stream, t, v, &mapping.unit, mapping.source());
+        let tz = if utc { "UTC" } else { mapping.tz.trim() };
+        obs.tags.insert("tz".into(), tz.into());
+        obs.tags.insert("imported_from".into(), from.into());
+        match streams::validate(&obs) {
+            Ok(()) => parsed.observations.push(obs),
+            Err(_) => parsed.skipped += 1,
+        }
+    }
+    Ok(parsed)
+}
+
+/// Each row's (time, value) cells; a malformed row has neither.
+type RowCells = Vec<(Option<Cell>, Option<Cell>)>;
+
+fn csv_cells(text: &str, mapping: &ImportMapping) -> Result<RowCells> {
+    let mut reader = csv_reader(text);
+    let header = reader.headers().map_err(csv_error)?.clone();
+    let at = |name: &str| {
+        header
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| StoreError::Parse(format!("no column {name:?}")))
+    };
+    let (time_at, value_at) = (at(&mapping.timestamp_column)?, at(&mapping.value_column)?);
+    let mut cells = Vec::new();
+    for record in reader.records() {
+        let Ok(record) = record else {
+            cells.push((None, None));
+            continue;
+        };
+        if record.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+        let cell = |i: usize| record.get(i).map(|s| Cell::Text(s.trim().to_string()));
+        cells.push((cell(time_at), cell(value_at)));
+    }
+    Ok(cells)
+}
+
+fn json_cells(text: &str, mapping: &ImportMapping) -> Result<RowCells> {
+    let rows = json_rows(text)?;
+    for name in [&mapping.timestamp_column, &mapping.value_column] {
+        if !rows.is_empty() && !rows.iter().any(|r| r.get(name).is_some()) {
+            return Err(StoreError::Parse(format!("no column {name:?}")));
+        }
+    }
+    let cell = |row: &serde_json::Value, name: &str| row.get(name).and_then(Cell::from_json);
+    Ok(rows
+        .iter()
+        .map(|row| {
+            (
+                cell(row, &mapping.timestamp_column),
+                cell(row, &mapping.value_column),
+            )
+        })
+        .collect())
+}
+
+/// One field, as text (CSV, JSON strings) or a JSON number.
+enum Cell {
+    Text(String),
+    Number(f64),
+}
+
+impl Cell {
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        match value {
+            serde_json::Value::String(s) => Some(Cell::Text(s.trim().to_string())),
+            serde_json::Value::Number(n) => n.as_f64().map(Cell::Number),
+            _ => None,
+        }
+    }
+
+    fn number(self) -> Option<f64> {
+        match self {
+            Cell::Text(s) => parse_number(&s),
+            Cell::Number(n) => Some(n).filter(|n| n.is_finite()),
+        }
+    }
+}
+
+/// A row's wall-clock time, and whether it was an epoch (so UTC).
+fn parse_time(
+    cell: Cell,
+    format: &TimestampFormat,
+    pattern: Option<&OwnedFormatItem>,
+) -> Option<(PrimitiveDateTime, bool)> {
+    let wall = |t: OffsetDateTime| PrimitiveDateTime::new(t.date(), t.time());
+    match (format, cell) {
+        (TimestampFormat::Rfc3339, Cell::Text(s)) => OffsetDateTime::parse(&s, &Rfc3339)
+            .ok()
+            .map(|t| (wall(t), false)),
+        (TimestampFormat::UnixSeconds | TimestampFormat::UnixMillis, cell) => {
+            let n = cell.number()?;
+            let seconds = if *format == TimestampFormat::UnixMillis {
+                n / 1000.0
+            } else {
+                n
+            };
+            let nanos = (seconds * 1e9).round() as i128;
+            OffsetDateTime::from_unix_timestamp_nanos(nanos)
+                .ok()
+                .map(|t| (wall(t), true))
+        }
+        (TimestampFormat::Pattern(_), Cell::Text(s)) => {
+            let pattern = pattern?;
+            OffsetDateTime::parse(&s, pattern)
+                .map(wall)
+                .or_else(|_| PrimitiveDateTime::parse(&s, pattern))
+                .or_else(|_| {
+                    Date::parse(&s, pattern).map(|d| PrimitiveDateTime::new(d, Time::MIDNIGHT))
+                })
+                .ok()
+                .map(|t| (t, false))
+        }
+        _ => None,
+    }
+}
+
+fn csv_reader(text: &str) -> csv::Reader<&[u8]> {
+    let text = text.trim_start_matches('\u{feff}');
+    let header = text.lines().next().unwrap_or("");
+    let delimiter = [b',', b';', b'\t']
+        .into_iter()
+        .max_by_key(|d| header.bytes().filter(|b| b == d).count())
+        .filter(|d| header.as_bytes().contains(d))
+        .unwrap_or(b',');
+    csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(text.as_bytes())
+}
+
+fn csv_error(e: csv::Error) -> StoreError {
+    StoreError::Parse(format!("malformed CSV: {e}"))
+}
+
+fn json_rows(text: &str) -> Result<Vec<serde_json::Value>> {
+    serde_json::from_str(text)
+        .map_err(|e| StoreError::Parse(format!("expected a JSON array of objects: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn weight() -> ImportMapping {
+        ImportMapping {
+            name: "Scale sheet".into(),
+            timestamp_column: "Date".into(),
+            timestamp_format: TimestampFormat::Pattern(
+                "[day]/[month]/[year] [hour]:[minute]".into(),
+            ),
+            value_column: "Weight".into(),
+            stream: "custom.weight_kg".into(),
+            unit: "kg".into(),
+            tz: "Europe/Madrid".into(),
+        }
+    }
+
+    #[test]
+    fn csv_rows_map_and_bad_rows_are_counted() {
+        let csv = "Date;Weight;Note\n19/06/2026 07:30;71,4;after run\n20/06/2026 07:35;;\nnot a date;70.9;\n\n21/06/2026 07:20;70.8;\n";
+        assert_eq!(
+            columns(csv, MappedFormat::Csv).unwrap(),
+            ["Date", "Weight", "Note"]
+        );
+        let parsed = parse(csv, MappedFormat::Csv, &weight()).unwrap();
+        assert_eq!(parsed.skipped, 2);
+        let rows: Vec<_> = parsed
+            .observations
+            .iter()
+            .map(|o| (o.timestamp, o.value))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (datetime!(2026-06-19 07:30:00), 71.4),
+                (datetime!(2026-06-21 07:20:00), 70.8)
+            ]
+        );
+        let first = &parsed.observations[0];
+        assert_eq!(first.source, "import:Scale sheet");
+        assert_eq!(first.unit, "kg");
+        assert_eq!(
+            first.tags.get("tz").map(String::as_str),
+            Some("Europe/Madrid")
+        );
+        assert_eq!(
+            first.tags.get("imported_from").map(String::as_str),
+            Some("csv")
+        );
+    }
+
+    #[test]
+    fn json_rows_accept_epochs_and_registered_streams() {
+        let json = r#"[
+            {"at": 1781877600, "steps": 5400},
+            {"at": "1781964000", "steps": "6100"},
+            {"at": 1782050400, "steps": -3},
+            {"at": null, "steps": 10}
+        ]"#;
+        let mapping = ImportMapping {
+            name: "pedometer".into(),
+            timestamp_column: "at".into(),
+            timestamp_format: TimestampFormat::UnixSeconds,
+            value_column: "steps".into(),
+            stream: "health.steps".into(),
+            unit: "count".into(),
+            tz: "America/Denver".into(),
+        };
+        let parsed = parse(json, MappedFormat::Json, &mapping).unwrap();
+        assert_eq!(parsed.skipped, 2);
+        assert_eq!(
+            parsed.observations[0].timestamp,
+            datetime!(2026-06-19 14:00:00)
+        );
+        assert_eq!(parsed.observations[1].value, 6100.0);
+        assert_eq!(
+            parsed.observations[0].tags.get("tz").map(String::as_str),
+            Some("UTC")
+        );
+        assert_eq!(columns(json, MappedFormat::Json).unwrap(), ["at", "steps"]);
+    }
+
+    #[test]
+    fn unusable_mappings_and_files_are_refused() {
+        let mut wrong_unit = weight();
+        wrong_unit.stream = "health.steps".into();
+        assert!(wrong_unit.check().is_err());
+        let mut unknown = weight();
+        unknown.stream = "weight".into();
+        assert!(matches!(unknown.check(), Err(StoreError::UnknownStream(_))));
+        let mut bad_pattern = weight();
+        bad_pattern.timestamp_format = TimestampFormat::Pattern("[nope]".into());
+        assert!(bad_pattern.check().is_err());
+
+        let missing = parse(
+            "When,Weight\n19/06/2026 07:30,71\n",
+            MappedFormat::Csv,
+            &weight(),
+        );
+        assert!(missing.is_err());
+        assert!(parse("{}", MappedFormat::Json, &weight()).is_err());
+    }
+
+    #[test]
+    fn mappings_round_trip_through_json() {
+        let mappings = vec![
+            weight(),
+            ImportMapping {
+                timestamp_format: TimestampFormat::Rfc3339,
+                ..weight()
+            },
+        ];
+        let json = serde_json::to_string(&mappings).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<ImportMapping>>(&json).unwrap(),
+            mappings
+        );
+
+        let iso = "Date,Weight\n2026-06-19T07:30:00+02:00,71.0\n";
+        let parsed = parse(iso, MappedFormat::Csv, &mappings[1]).unwrap();
+        assert_eq!(
+            parsed.observations[0].timestamp,
+            datetime!(2026-06-19 07:30:00)
+        );
+    }
+}
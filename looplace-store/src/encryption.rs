@@ -0,0 +1,454 @@
+//! Passphrase encryption at rest (behind the `encryption` feature).
+//!
+//! Envelope scheme: every sealed file is encrypted with a random 256-bit data
+//! key under XChaCha20-Poly1305 (authenticated, so tampering or a wrong key is
+//! detected, never mis-decoded). The data keys are themselves sealed with a
+//! key-encryption key derived from the user's passphrase by Argon2id, and kept
+//! in a small sibling key file (`<store>.key.json`). That makes a passphrase
+//! change a cheap rewrap of the key file, and a data-key rotation a re-seal of
+//! the store files — crash-safe because the key file holds both keys until
+//! every file has moved to the new one.
+//!
+//! Sealed layout: `MAGIC (8) | key id (u32 LE) | nonce (24) | ciphertext+tag`.
+//! The file's role (e.g. `observations`) is bound in as associated data, so
+//! sealed files can't be swapped for one another.
+
+use std::fs;
+use std::path::Path;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, StoreError};
+use crate::snapshot::Sealer;
+
+/// Leading bytes of every sealed file.
+pub const MAGIC: &[u8; 8] = b"LPSEAL\x00\x01";
+const KEY_FILE_FORMAT: u32 = 1;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = MAGIC.len() + 4 + NONCE_LEN;
+const SALT_LEN: usize = 16;
+
+/// Whether a store is encrypted and, if so, whether this handle holds its key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionState {
+    /// No key file: data is stored in plaintext.
+    Plaintext,
+    /// Encrypted, and no passphrase has been supplied.
+    Locked,
+    /// Encrypted and unlocked for this handle.
+    Unlocked,
+}
+
+/// `true` if `bytes` is a sealed file (as opposed to plaintext Parquet).
+pub fn is_sealed(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Argon2id cost parameters, recorded in the key file so they can be raised
+/// later without stranding existing stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    /// OWASP's baseline Argon2id recommendation.
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeyFile {
+    format: u32,
+    kdf: KdfParams,
+    /// Hex salt for the passphrase KDF.
+    salt: String,
+    /// Data keys sealed under the passphrase key; the last is current.
+    keys: Vec<WrappedKey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WrappedKey {
+    id: u32,
+    /// Hex `nonce | ciphertext+tag` of the 32-byte data key.
+    sealed: String,
+}
+
+/// Unwrapped data keys for one store. Seals with the current key; opens with
+/// whichever key a file names.
+pub struct Cipher {
+    keys: Vec<(u32, Key)>,
+}
+
+impl std::fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print key material.
+        let ids: Vec<u32> = self.keys.iter().map(|(id, _)| *id).collect();
+        f.debug_struct("Cipher").field("key_ids", &ids).finish()
+    }
+}
+
+impl Cipher {
+    fn current(&self) -> (u32, &Key) {
+        let (id, key) = self.keys.last().expect("a cipher always holds a key");
+        (*id, key)
+    }
+
+    /// Encrypt `plaintext` for the file role `role`.
+    pub fn seal(&self, role: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let (id, key) = self.current();
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = XChaCha20Poly1305::new(key)
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: role.as_bytes(),
+                },
+            )
+            .map_err(|_| StoreError::Backend("encryption failed".into()))?;
+        let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&id.to_le_bytes());
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    /// Decrypt a sealed file for the file role `role`.
+    pub fn open(&self, role: &str, sealed: &[u8]) -> Result<Vec<u8>> {
+        if !is_sealed(sealed) || sealed.len() < HEADER_LEN {
+            return Err(StoreError::Parse(format!("{role}: not a sealed file")));
+        }
+        let id = u32::from_le_bytes(sealed[MAGIC.len()..MAGIC.len() + 4].try_into().unwrap());
+        let nonce = XNonce::from_slice(&sealed[MAGIC.len() + 4..HEADER_LEN]);
+        let key = self
+            .keys
+            .iter()
+            .find(|(k, _)| *k == id)
+            .map(|(_, key)| key)
+            .ok_or_else(|| StoreError::Parse(format!("{role}: sealed with unknown key {id}")))?;
+        XChaCha20Poly1305::new(key)
+            .decrypt(
+                nonce,
+                Payload {
+                    msg: &sealed[HEADER_LEN..],
+                    aad: role.as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                StoreError::Parse(format!(
+                    "{role}: authentication failed (corrupt or tampered)"
+                ))
+            })
+    }
+
+    /// Whether this cipher still holds more than one data key — i.e. a
+    /// rotation started but hasn't been [`finish_rotation`]-ed.
+    pub fn is_rotating(&self) -> bool {
+        self.keys.len() > 1
+    }
+}
+
+impl Sealer for Cipher {
+    fn seal(&self, role: &str, plaintext: &[u8]) -> Result<Vec<u8>> {
+        Cipher::seal(self, role, plaintext)
+    }
+
+    fn open(&self, role: &str, sealed: &[u8]) -> Result<Vec<u8>> {
+        Cipher::open(self, role, sealed)
+    }
+}
+
+/// Create a key file at `key_path` with a fresh data key, sealed under
+/// `passphrase`. Fails if one already exists.
+pub fn create(key_path: &Path, passphrase: &str) -> Result<Cipher> {
+    create_with(key_path, passphrase, KdfParams::default())
+}
+
+pub(crate) fn create_with(key_path: &Path, passphrase: &str, kdf: KdfParams) -> Result<Cipher> {
+    if key_path.exists() {
+        return Err(StoreError::Io(format!(
+            "key file already exists: {}",
+            key_path.display()
+        )));
+    }
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let kek = derive(passphrase, &salt, kdf)?;
+    let data_key = XChaCha20Poly1305::generate_key(&mut OsRng);
+    let file = KeyFile {
+        format: KEY_FILE_FORMAT,
+        kdf,
+        salt: to_hex(&salt),
+        keys: vec![wrap(&kek, 1, &data_key)?],
+    };
+    write_key_file(key_path, &file)?;
+    Ok(Cipher {
+        keys: vec![(1, data_key)],
+    })
+}
+
+/// Unlock the data keys in `key_path` with `passphrase`.
+pub fn unlock(key_path: &Path, passphrase: &str) -> Result<Cipher> {
+    let file = read_key_file(key_path)?;
+    let kek = derive(passphrase, &from_hex(&file.salt)?, file.kdf)?;
+    let keys = file
+        .keys
+        .iter()
+        .map(|wrapped| Ok((wrapped.id, unwrap(&kek, wrapped)?)))
+        .collect::<Result<Vec<_>>>()?;
+    if keys.is_empty() {
+        return Err(StoreError::Parse("key file holds no keys".into()));
+    }
+    Ok(Cipher { keys })
+}
+
+/// Re-seal the data keys under a new passphrase (and a fresh salt). The store
+/// files are untouched.
+pub fn change_passphrase(key_path: &Path, old: &str, new: &str) -> Result<()> {
+    let cipher = unlock(key_path, old)?;
+    let file = read_key_file(key_path)?;
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let kek = derive(new, &salt, file.kdf)?;
+    let keys = cipher
+        .keys
+        .iter()
+        .map(|(id, key)| wrap(&kek, *id, key))
+        .collect::<Result<Vec<_>>>()?;
+    write_key_file(
+        key_path,
+        &KeyFile {
+            salt: to_hex(&salt),
+            keys,
+            ..file
+        },
+    )
+}
+
+/// Step one of a data-key rotation: add a new current key to the key file
+/// (keeping the old one, so every file stays readable) and return the cipher
+/// holding both. Re-seal every file, then call [`finish_rotation`].
+pub fn begin_rotation(key_path: &Path, passphrase: &str) -> Result<Cipher> {
+    let mut cipher = unlock(key_path, passphrase)?;
+    let mut file = read_key_file(key_path)?;
+    let kek = derive(passphrase, &from_hex(&file.salt)?, file.kdf)?;
+    let id = cipher.current().0 + 1;
+    let data_key = XChaCha20Poly1305::generate_key(&mut OsRng);
+    file.keys.push(wrap(&kek, id, &data_key)?);
+    write_key_file(key_path, &file)?;
+    cipher.keys.push((id, data_key));
+    Ok(cipher)
+}
+
+/// Step two of a rotation: drop every key but the current one, from both the
+/// key file and `cipher`. Only call once every file is sealed with it.
+pub fn finish_rotation(key_path: &Path, cipher: &mut Cipher) -> Result<()> {
+    let current = cipher.current().0;
+    let mut file = read_key_file(key_path)?;
+    file.keys.retain(|k| k.id == current);
+    write_key_file(key_path, &file)?;
+    cipher.keys.retain(|(id, _)| *id == current);
+    Ok(())
+}
+
+fn derive(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<Key> {
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(32))
+        .map_err(|e| StoreError::Parse(format!("invalid key derivation parameters: {e}")))?;
+    let mut out = Key::default();
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut out)
+        .map_err(|e| StoreError::Backend(format!("key derivation failed: {e}")))?;
+    Ok(out)
+}
+
+fn wrap(kek: &Key, id: u32, data_key: &Key) -> Result<WrappedKey> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = XChaCha20Poly1305::new(kek)
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: data_key,
+                aad: &id.to_le_bytes(),
+            },
+        )
+        .map_err(|_| StoreError::Backend("key wrap failed".into()))?;
+    let mut bytes = nonce.to_vec();
+    bytes.extend_from_slice(&sealed);
+    Ok(WrappedKey {
+        id,
+        sealed: to_hex(&bytes),
+    })
+}
+
+fn unwrap(kek: &Key, wrapped: &WrappedKey) -> Result<Key> {
+    let bytes = from_hex(&wrapped.sealed)?;
+    if bytes.len() < NONCE_LEN {
+        return Err(StoreError::Parse("key file entry truncated".into()));
+    }
+    let (nonce, sealed) = bytes.split_at(NONCE_LEN);
+    let key = XChaCha20Poly1305::new(kek)
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: sealed,
+                aad: &wrapped.id.to_le_bytes(),
+            },
+        )
+        // The only key material checked here is the passphrase's.
+        .map_err(|_| StoreError::WrongPassphrase)?;
+    Key::from_exact_iter(key)
+        .ok_or_else(|| StoreError::Parse("data key has the wrong length".into()))
+}
+
+fn read_key_file(path: &Path) -> Result<KeyFile> {
+    let file: KeyFile = serde_json::from_slice(&fs::read(path)?)
+        .map_err(|e| StoreError::Parse(format!("key file unreadable: {e}")))?;
+    if file.format > KEY_FILE_FORMAT {
+        return Err(StoreError::UnsupportedSchema {
+            found: file.format,
+            supported: KEY_FILE_FORMAT,
+        });
+    }
+    Ok(file)
+}
+
+/// Key files are replaced atomically: losing one loses the store.
+fn write_key_file(path: &Path, file: &KeyFile) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(file)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return Err(StoreError::Parse("odd-length hex in key file".into()));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|_| StoreError::Parse("invalid hex in key file".into()))
+        })
+        .collect()
+}
+
+/// Cheap KDF settings so tests don't spend seconds in Argon2.
+#[cfg(test)]
+pub(crate) const TEST_KDF: KdfParams = KdfParams {
+    memory_kib: 64,
+    iterations: 1,
+    parallelism: 1,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_key(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("looplace_store_keys");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{name}.key.json"));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn seal_open_round_trip_and_role_binding() {
+        let path = temp_key("roundtrip");
+        let cipher = create_with(&path, "correct horse", TEST_KDF).unwrap();
+        let sealed = cipher.seal("observations", b"PAR1 plaintext").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.windows(9).any(|w| w == b"plaintext"));
+        assert_eq!(
+            cipher.open("observations", &sealed).unwrap(),
+            b"PAR1 plaintext"
+        );
+        // Same bytes presented as a different file fail authentication.
+        assert!(cipher.open("sessions", &sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(cipher.open("observations", &tampered).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_reported_as_such() {
+        let path = temp_key("wrong");
+        create_with(&path, "correct horse", TEST_KDF).unwrap();
+        assert!(matches!(
+            unlock(&path, "battery staple"),
+            Err(StoreError::WrongPassphrase)
+        ));
+        assert!(unlock(&path, "correct horse").is_ok());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn passphrase_change_keeps_data_readable() {
+        let path = temp_key("change");
+        let sealed = create_with(&path, "old", TEST_KDF)
+            .unwrap()
+            .seal("t", b"data")
+            .unwrap();
+        change_passphrase(&path, "old", "new").unwrap();
+        assert!(matches!(
+            unlock(&path, "old"),
+            Err(StoreError::WrongPassphrase)
+        ));
+        assert_eq!(
+            unlock(&path, "new").unwrap().open("t", &sealed).unwrap(),
+            b"data"
+        );
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn rotation_keeps_old_files_readable_until_finished() {
+        let path = temp_key("rotate");
+        let old_sealed = create_with(&path, "pw", TEST_KDF)
+            .unwrap()
+            .seal("t", b"old")
+            .unwrap();
+
+        let mut cipher = begin_rotation(&path, "pw").unwrap();
+        assert!(cipher.is_rotating());
+        // Mid-rotation (e.g. after a crash), a fresh unlock reads both generations.
+        assert_eq!(
+            unlock(&path, "pw").unwrap().open("t", &old_sealed).unwrap(),
+            b"old"
+        );
+        let new_sealed = cipher.seal("t", b"new").unwrap();
+
+        finish_rotation(&path, &mut cipher).unwrap();
+        assert!(!cipher.is_rotating());
+        let fresh = unlock(&path, "pw").unwrap();
+        assert_eq!(fresh.open("t", &new_sealed).unwrap(), b"new");
+        assert!(fresh.open("t", &old_sealed).is_err());
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -1,6 +1,7 @@
 //! Store error type (std-only, mirroring the pattern used across the workspace).
 
 use std::fmt;
+use std::path::PathBuf;
 
 pub type Result<T> = std::result::Result<T, StoreError>;
 
@@ -9,6 +10,18 @@ pub enum StoreError {
     Io(String),
     Parse(String),
     Backend(String),
+    /// A store file was written by a newer build with a schema this one can't read.
+    UnsupportedSchema { found: u32, supported: u32 },
+    /// Another process is writing the store and didn't release its lock in time.
+    Locked(PathBuf),
+    /// The store is encrypted and this handle hasn't been given its passphrase.
+    Encrypted,
+    /// The passphrase didn't unlock the store's key file.
+    WrongPassphrase,
+    /// An observation names a stream that isn't in the [`crate::streams`] registry.
+    UnknownStream(String),
+    /// An observation's value is non-finite or outside its stream's range.
+    InvalidValue { stream: String, value: f64 },
 }
 
 impl fmt::Display for StoreError {
@@ -17,6 +30,19 @@ impl fmt::Display for StoreError {
             StoreError::Io(m) => write!(f, "io error: {m}"),
             StoreError::Parse(m) => write!(f, "parse error: {m}"),
             StoreError::Backend(m) => write!(f, "backend error: {m}"),
+            StoreError::UnsupportedSchema { found, supported } => write!(
+                f,
+                "store schema v{found} is newer than this build supports (v{supported})"
+            ),
+            StoreError::Locked(path) => {
+                write!(f, "store is locked by another process: {}", path.display())
+            }
+            StoreError::Encrypted => write!(f, "store is encrypted; a passphrase is required"),
+            StoreError::WrongPassphrase => write!(f, "wrong passphrase for the encrypted store"),
+            StoreError::UnknownStream(stream) => write!(f, "unknown stream: {stream}"),
+            StoreError::InvalidValue { stream, value } => {
+                write!(f, "value {value} is out of range for stream {stream}")
+            }
         }
     }
 }
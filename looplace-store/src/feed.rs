@@ -0,0 +1,421 @@
+//! Change feed: subscribe to what a store's writes touched.
+//!
+//! [`WatchedStore`] wraps any [`Store`] and, after each successful upsert or
+//! delete, emits a [`Change`] naming the affected keys to every subscriber of
+//! its [`Feed`]. A long-lived handle can then refresh views incrementally (by
+//! re-querying just the changed streams and time span) instead of reopening and
+//! rereading the backend.
+//!
+//! Only writes made *through* the wrapper are seen; another process writing the
+//! same files is not. Subscribers are called synchronously on the writing
+//! thread, so they should be quick (forward to a channel) and must not write to
+//! the store or subscribe from inside the callback.
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use time::PrimitiveDateTime;
+
+use crate::error::Result;
+use crate::observation::{Observation, Query};
+use crate::provenance::IngestBatch;
+use crate::session::SessionRecord;
+use crate::store::Store;
+use crate::trial::TrialRecord;
+
+/// An owned [`Observation::key`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObservationKey {
+    pub stream: String,
+    pub timestamp: PrimitiveDateTime,
+    pub source: String,
+    pub kind: Option<String>,
+}
+
+impl ObservationKey {
+    pub fn of(o: &Observation) -> Self {
+        let (stream, timestamp, source, kind) = o.key();
+        Self {
+            stream: stream.into(),
+            timestamp,
+            source: source.into(),
+            kind: kind.map(Into::into),
+        }
+    }
+}
+
+/// Whether a change wrote or removed rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Upserted,
+    Deleted,
+}
+
+/// One write's worth of changed keys.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Observations written or removed, by key (sorted, deduplicated). An
+    /// upsert lists every key in the batch, new or overwritten.
+    Observations {
+        kind: ChangeKind,
+        keys: Vec<ObservationKey>,
+    },
+    /// Session records written or removed, by id (sorted, deduplicated).
+    Sessions { kind: ChangeKind, ids: Vec<String> },
+    /// Anything may have changed: the store was reopened, unlocked, or
+    /// restored. Subscribers should reload what they show.
+    Reset,
+}
+
+impl Change {
+    fn sessions<'a>(kind: ChangeKind, ids: impl IntoIterator<Item = &'a String>) -> Self {
+        let ids: BTreeSet<_> = ids.into_iter().cloned().collect();
+        Change::Sessions {
+            kind,
+            ids: ids.into_iter().collect(),
+        }
+    }
+
+    fn observations(kind: ChangeKind, observations: &[Observation]) -> Self {
+        let keys: BTreeSet<_> = observations.iter().map(ObservationKey::of).collect();
+        Change::Observations {
+            kind,
+            keys: keys.into_iter().collect(),
+        }
+    }
+
+    /// Distinct streams this change named (none for session changes or a
+    /// [`Change::Reset`]).
+    pub fn streams(&self) -> BTreeSet<&str> {
+        match self {
+            Change::Observations { keys, .. } => keys.iter().map(|k| k.stream.as_str()).collect(),
+            Change::Sessions { .. } | Change::Reset => BTreeSet::new(),
+        }
+    }
+
+    /// Whether this change may have touched session records.
+    pub fn touches_sessions(&self) -> bool {
+        matches!(self, Change::Sessions { .. } | Change::Reset)
+    }
+
+    /// Whether this change may have touched `stream`.
+    pub fn touches(&self, stream: &str) -> bool {
+        match self {
+            Change::Observations { keys, .. } => keys.iter().any(|k| k.stream == stream),
+            Change::Sessions { .. } => false,
+            Change::Reset => true,
+        }
+    }
+
+    /// The query that re-reads everything this change touched on `stream`:
+    /// the stream between its earliest and latest changed timestamps (the
+    /// whole stream after a reset). `None` if the change didn't touch `stream`.
+    pub fn requery(&self, stream: &str) -> Option<Query> {
+        let keys = match self {
+            Change::Observations { keys, .. } => keys,
+            Change::Sessions { .. } => return None,
+            Change::Reset => return Some(Query::stream(stream)),
+        };
+        let mut stamps = keys
+            .iter()
+            .filter(|k| k.stream == stream)
+            .map(|k| k.timestamp);
+        let first = stamps.next()?;
+        let (since, until) = stamps.fold((first, first), |(lo, hi), t| (lo.min(t), hi.max(t)));
+        Some(Query {
+            stream: Some(stream.into()),
+            since: Some(since),
+            until: Some(until),
+            batch_id: None,
+        })
+    }
+}
+
+type Callback = Box<dyn Fn(&Change) + Send>;
+
+#[derive(Default)]
+struct Subscribers {
+    next_id: AtomicU64,
+    list: Mutex<Vec<(u64, Callback)>>,
+}
+
+/// A set of change subscribers. Cheap to clone; clones share subscribers, so
+/// one feed can outlive (and be handed to) successive [`WatchedStore`]s — e.g.
+/// when the app reopens its store after unlocking it.
+#[derive(Clone, Default)]
+pub struct Feed {
+    subscribers: Arc<Subscribers>,
+}
+
+impl std::fmt::Debug for Feed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.subscribers.list.lock().map(|l| l.len()).unwrap_or(0);
+        f.debug_struct("Feed").field("subscribers", &count).finish()
+    }
+}
+
+impl Feed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `callback` with every change from now on, until the returned
+    /// [`Subscription`] is dropped.
+    pub fn subscribe(&self, callback: impl Fn(&Change) + Send + 'static) -> Subscription {
+        let id = self.subscribers.next_id.fetch_add(1, Ordering::Relaxed);
+        self.list().push((id, Box::new(callback)));
+        Subscription {
+            id,
+            subscribers: Arc::downgrade(&self.subscribers),
+        }
+    }
+
+    /// Deliver `change` to every current subscriber.
+    pub fn emit(&self, change: &Change) {
+        for (_, callback) in self.list().iter() {
+            callback(change);
+        }
+    }
+
+    fn list(&self) -> std::sync::MutexGuard<'_, Vec<(u64, Callback)>> {
+        // A panicking subscriber can't leave the list half-updated.
+        self.subscribers
+            .list
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Keeps a [`Feed`] subscription alive; dropping it unsubscribes.
+#[must_use = "dropping the subscription unsubscribes immediately"]
+pub struct Subscription {
+    id: u64,
+    subscribers: Weak<Subscribers>,
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            Feed { subscribers }.list().retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+
+/// A [`Store`] that reports its writes to a [`Feed`].
+///
+/// Reads pass straight through. A write that fails, or a delete that removed
+/// nothing, emits no change. Writes made via [`WatchedStore::get_mut`] bypass
+/// the feed — use it for backend-specific maintenance (re-encryption and the
+/// like) that doesn't change the data.
+#[derive(Debug)]
+pub struct WatchedStore<S> {
+    inner: S,
+    feed: Feed,
+}
+
+impl<S: Store> WatchedStore<S> {
+    /// Watch `inner` with a fresh feed.
+    pub fn new(inner: S) -> Self {
+        Self::with_feed(inner, Feed::new())
+    }
+
+    /// Watch `inner`, reporting to an existing `feed`.
+    pub fn with_feed(inner: S, feed: Feed) -> Self {
+        Self { inner, feed }
+    }
+
+    pub fn feed(&self) -> &Feed {
+        &self.feed
+    }
+
+    /// Shorthand for `self.feed().subscribe(callback)`.
+    pub fn subscribe(&self, callback: impl Fn(&Change) + Send + 'static) -> Subscription {
+        self.feed.subscribe(callback)
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Store> Store for WatchedStore<S> {
+    fn upsert(&mut self, observations: &[Observation]) -> Result<usize> {
+        let added = self.inner.upsert(observations)?;
+        if !observations.is_empty() {
+            self.feed
+                .emit(&Change::observations(ChangeKind::Upserted, observations));
+        }
+        Ok(added)
+    }
+
+    fn query(&self, query: &Query) -> Result<Vec<Observation>> {
+        self.inner.query(query)
+    }
+
+    fn delete(&mut self, observations: &[Observation]) -> Result<usize> {
+        let removed = self.inner.delete(observations)?;
+        if removed > 0 {
+            // Keys that matched nothing are harmless to a subscriber re-querying.
+            self.feed
+                .emit(&Change::observations(ChangeKind::Deleted, observations));
+        }
+        Ok(removed)
+    }
+
+    fn upsert_sessions(&mut self, sessions: &[SessionRecord]) -> Result<usize> {
+        let added = self.inner.upsert_sessions(sessions)?;
+        if !sessions.is_empty() {
+            let ids = sessions.iter().map(|s| &s.id);
+            self.feed.emit(&Change::sessions(ChangeKind::Upserted, ids));
+        }
+        Ok(added)
+    }
+
+    fn delete_sessions(&mut self, ids: &[String]) -> Result<usize> {
+        let removed = self.inner.delete_sessions(ids)?;
+        if removed > 0 {
+            self.feed.emit(&Change::sessions(ChangeKind::Deleted, ids));
+        }
+        Ok(removed)
+    }
+
+    fn sessions(&self) -> Result<Vec<SessionRecord>> {
+        self.inner.sessions()
+    }
+
+    // Batch records only describe rows; the rows' own writes are what's reported.
+    fn upsert_batches(&mut self, batches: &[IngestBatch]) -> Result<usize> {
+        self.inner.upsert_batches(batches)
+    }
+
+    fn batches(&self) -> Result<Vec<IngestBatch>> {
+        self.inner.batches()
+    }
+
+    // Trials are part of their session, so they're reported as its change.
+    fn upsert_trials(&mut self, trials: &[TrialRecord]) -> Result<usize> {
+        let added = self.inner.upsert_trials(trials)?;
+        if !trials.is_empty() {
+            let ids = trials.iter().map(|t| &t.session_id);
+            self.feed.emit(&Change::sessions(ChangeKind::Upserted, ids));
+        }
+        Ok(added)
+    }
+
+    fn delete_trials(&mut self, session_ids: &[String]) -> Result<usize> {
+        let removed = self.inner.delete_trials(session_ids)?;
+        if removed > 0 {
+            self.feed
+                .emit(&Change::sessions(ChangeKind::Upserted, session_ids));
+        }
+        Ok(removed)
+    }
+
+    fn trials(&self) -> Result<Vec<TrialRecord>> {
+        self.inner.trials()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+    use crate::streams::GLUCOSE;
+    use std::sync::mpsc;
+    use time::macros::datetime;
+
+    fn reading(t: PrimitiveDateTime, value: f64) -> Observation {
+        Observation::new(GLUCOSE, t, value, "mg/dL", "dev")
+    }
+
+    fn watched() -> (
+        WatchedStore<MemoryStore>,
+        mpsc::Receiver<Change>,
+        Subscription,
+    ) {
+        let store = WatchedStore::new(MemoryStore::new());
+        let (tx, rx) = mpsc::channel();
+        let sub = store.subscribe(move |c| {
+            let _ = tx.send(c.clone());
+        });
+        (store, rx, sub)
+    }
+
+    #[test]
+    fn writes_emit_their_keys() {
+        let (mut store, rx, _sub) = watched();
+        let a = reading(datetime!(2026-06-19 08:00:00), 100.0);
+        let b = reading(datetime!(2026-06-19 08:15:00), 110.0);
+        store.upsert(&[b.clone(), a.clone(), b.clone()]).unwrap();
+
+        let change = rx.try_recv().unwrap();
+        assert_eq!(
+            change,
+            Change::Observations {
+                kind: ChangeKind::Upserted,
+                keys: vec![ObservationKey::of(&a), ObservationKey::of(&b)],
+            }
+        );
+        let query = change.requery(GLUCOSE).unwrap();
+        assert_eq!(query.since, Some(a.timestamp));
+        assert_eq!(query.until, Some(b.timestamp));
+        assert!(change.requery("ketone.mmol_l").is_none());
+        assert_eq!(Change::Reset.requery(GLUCOSE).unwrap().since, None);
+
+        assert_eq!(store.delete(&[a]).unwrap(), 1);
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            Change::Observations { kind: ChangeKind::Deleted, keys } if keys.len() == 1
+        ));
+    }
+
+    #[test]
+    fn no_ops_and_failures_stay_quiet() {
+        let (mut store, rx, _sub) = watched();
+        store.upsert(&[]).unwrap();
+        assert_eq!(
+            store
+                .delete(&[reading(datetime!(2026-06-19 08:00:00), 1.0)])
+                .unwrap(),
+            0
+        );
+        assert!(store
+            .upsert(&[reading(datetime!(2026-06-19 08:00:00), 0.0)])
+            .is_err());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn dropping_the_subscription_unsubscribes() {
+        let (mut store, rx, sub) = watched();
+        // A second store on the same feed reports to the same subscribers.
+        let mut other = WatchedStore::with_feed(MemoryStore::new(), store.feed().clone());
+        other
+            .upsert(&[reading(datetime!(2026-06-19 09:00:00), 95.0)])
+            .unwrap();
+        assert!(rx.try_recv().is_ok());
+
+        drop(sub);
+        store
+            .upsert(&[reading(datetime!(2026-06-19 08:00:00), 100.0)])
+            .unwrap();
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,278 @@
+//! Manual journal entries: meals, medication, exercise, sleep, and free-text
+//! events the user logs alongside device data.
+//!
+//! Entries are stored as observations on the `journal.<category>` streams, so
+//! they share the glucose/cognition timeline (and ride along in snapshots,
+//! copies, and encryption) without a table of their own. One entry is one row:
+//!
+//! - `timestamp` is the start, in local wall-clock (like glucose readings);
+//! - `value` is the optional dose, in the category's registered unit;
+//! - the entry id is the `kind` tag, so it's part of the row's key;
+//! - `end`, `notes`, and `no_dose` (when there's no dose) are tags.
+//!
+//! Because the start time is part of the key, [`save`] replaces an edited entry
+//! by id rather than by key.
+
+use time::macros::format_description;
+use time::PrimitiveDateTime;
+
+use crate::error::{Result, StoreError};
+use crate::observation::{Observation, Query};
+use crate::store::Store;
+use crate::streams;
+
+/// `source` of every journal row.
+pub const JOURNAL_SOURCE: &str = "journal";
+
+/// Format of the `end` tag.
+const END_FORMAT: &[time::format_description::FormatItem<'static>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
+
+/// What kind of event an entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Meal,
+    Medication,
+    Exercise,
+    Sleep,
+    Note,
+}
+
+impl Category {
+    pub const ALL: [Category; 5] = [
+        Category::Meal,
+        Category::Medication,
+        Category::Exercise,
+        Category::Sleep,
+        Category::Note,
+    ];
+
+    /// The stream this category's entries are stored on.
+    pub fn stream(self) -> &'static str {
+        match self {
+            Category::Meal => "journal.meal",
+            Category::Medication => "journal.medication",
+            Category::Exercise => "journal.exercise",
+            Category::Sleep => "journal.sleep",
+            Category::Note => "journal.note",
+        }
+    }
+
+    pub fn from_stream(stream: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.stream() == stream)
+    }
+
+    /// The registry entry for this category's stream (dose unit, range, label).
+    pub fn spec(self) -> &'static streams::StreamSpec {
+        streams::lookup(self.stream()).expect("journal streams are registered")
+    }
+}
+
+/// One logged event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// Caller-assigned, unique across the journal (e.g. a UUID).
+    pub id: String,
+    pub category: Category,
+    pub start: PrimitiveDateTime,
+    /// `None` for instantaneous events.
+    pub end: Option<PrimitiveDateTime>,
+    /// Amount in the category's unit (carbs in g, medication units, …).
+    pub dose: Option<f64>,
+    pub notes: Option<String>,
+}
+
+impl JournalEntry {
+    pub fn new(id: impl Into<String>, category: Category, start: PrimitiveDateTime) -> Self {
+        Self {
+            id: id.into(),
+            category,
+            start,
+            end: None,
+            dose: None,
+            notes: None,
+        }
+    }
+
+    pub fn to_observation(&self) -> Observation {
+        let spec = self.category.spec();
+        let mut obs = Observation::new(
+            spec.name,
+            self.start,
+            self.dose.unwrap_or(0.0),
+            spec.unit,
+            JOURNAL_SOURCE,
+        );
+        obs.tags.insert("kind".into(), self.id.clone());
+        if self.dose.is_none() {
+            obs.tags.insert("no_dose".into(), "true".into());
+        }
+        if let Some(end) = self.end.and_then(|end| end.format(END_FORMAT).ok()) {
+            obs.tags.insert("end".into(), end);
+        }
+        if let Some(notes) = self.notes.as_ref().filter(|n| !n.is_empty()) {
+            obs.tags.insert("notes".into(), notes.clone());
+        }
+        obs
+    }
+
+    /// The entry a journal row encodes; `None` for any other row.
+    pub fn from_observation(o: &Observation) -> Option<Self> {
+        if o.source != JOURNAL_SOURCE {
+            return None;
+        }
+        let category = Category::from_stream(&o.stream)?;
+        Some(Self {
+            id: o.tags.get("kind")?.clone(),
+            category,
+            start: o.timestamp,
+            end: o
+                .tags
+                .get("end")
+                .and_then(|end| PrimitiveDateTime::parse(end, END_FORMAT).ok()),
+            dose: (!o.tags.contains_key("no_dose")).then_some(o.value),
+            notes: o.tags.get("notes").cloned(),
+        })
+    }
+}
+
+/// Entries starting within `[since, until]` (either bound optional), sorted
+/// by start.
+pub fn entries(
+    store: &dyn Store,
+    since: Option<PrimitiveDateTime>,
+    until: Option<PrimitiveDateTime>,
+) -> Result<Vec<JournalEntry>> {
+    let mut out = Vec::new();
+    for category in Category::ALL {
+        let query = Query {
+            stream: Some(category.stream().into()),
+            since,
+            until,
+            batch_id: None,
+        };
+        out.extend(
+            store
+                .query(&query)?
+                .iter()
+                .filter_map(JournalEntry::from_observation),
+        );
+    }
+    out.sort_by_key(|e| e.start);
+    Ok(out)
+}
+
+/// Add `entry`, or replace the stored entry with the same id (even if its
+/// category or start time changed). Validated before anything is removed, so a
+/// refused edit leaves the old entry in place.
+pub fn save(store: &mut dyn Store, entry: &JournalEntry) -> Result<()> {
+    if entry.id.is_empty() {
+        return Err(StoreError::Parse("journal entry has no id".into()));
+    }
+    let obs = entry.to_observation();
+    streams::validate(&obs)?;
+    let previous = rows_with_id(store, &entry.id)?;
+    store.delete(&previous)?;
+    store.upsert(&[obs])?;
+    Ok(())
+}
+
+/// Remove the entry with `id`. Returns whether there was one.
+pub fn delete(store: &mut dyn Store, id: &str) -> Result<bool> {
+    let rows = rows_with_id(store, id)?;
+    Ok(store.delete(&rows)? > 0)
+}
+
+fn rows_with_id(store: &dyn Store, id: &str) -> Result<Vec<Observation>> {
+    let mut rows = Vec::new();
+    for category in Category::ALL {
+        rows.extend(
+            store
+                .query(&Query::stream(category.stream()))?
+                .into_iter()
+                .filter(|o| {
+                    o.source == JOURNAL_SOURCE && o.tags.get("kind").map(String::as_str) == Some(id)
+                }),
+        );
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+    use time::macros::datetime;
+
+    fn lunch() -> JournalEntry {
+        let mut entry = JournalEntry::new("e1", Category::Meal, datetime!(2026-06-19 12:30:00));
+        entry.dose = Some(45.0);
+        entry.notes = Some("pasta".into());
+        entry
+    }
+
+    #[test]
+    fn entries_round_trip_through_observations() {
+        let mut run = JournalEntry::new("e2", Category::Exercise, datetime!(2026-06-19 18:00:00));
+        run.end = Some(datetime!(2026-06-19 18:40:00));
+        for entry in [lunch(), run] {
+            let obs = entry.to_observation();
+            assert!(streams::validate(&obs).is_ok());
+            assert_eq!(JournalEntry::from_observation(&obs), Some(entry));
+        }
+        let glucose = Observation::new(
+            streams::GLUCOSE,
+            datetime!(2026-06-19 12:00:00),
+            99.0,
+            "mg/dL",
+            "dev",
+        );
+        assert_eq!(JournalEntry::from_observation(&glucose), None);
+    }
+
+    #[test]
+    fn same_instant_entries_stay_distinct() {
+        let mut store = MemoryStore::new();
+        let mut second = lunch();
+        second.id = "e3".into();
+        save(&mut store, &lunch()).unwrap();
+        save(&mut store, &second).unwrap();
+        assert_eq!(entries(&store, None, None).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn editing_moves_the_entry_and_delete_removes_it() {
+        let mut store = MemoryStore::new();
+        save(&mut store, &lunch()).unwrap();
+
+        let mut edited = lunch();
+        edited.start = datetime!(2026-06-19 13:00:00);
+        edited.category = Category::Note;
+        edited.dose = None;
+        save(&mut store, &edited).unwrap();
+        assert_eq!(entries(&store, None, None).unwrap(), vec![edited]);
+
+        // An invalid edit is refused and leaves the entry alone.
+        let mut bad = lunch();
+        bad.dose = Some(-1.0);
+        assert!(matches!(
+            save(&mut store, &bad),
+            Err(StoreError::InvalidValue { .. })
+        ));
+        assert_eq!(entries(&store, None, None).unwrap().len(), 1);
+
+        assert!(delete(&mut store, "e1").unwrap());
+        assert!(!delete(&mut store, "e1").unwrap());
+        assert!(store.query(&Query::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn entries_are_windowed_by_start() {
+        let mut store = MemoryStore::new();
+        save(&mut store, &lunch()).unwrap();
+        let sleep = JournalEntry::new("e4", Category::Sleep, datetime!(2026-06-19 23:00:00));
+        save(&mut store, &sleep).unwrap();
+        let evening = entries(&store, Some(datetime!(2026-06-19 20:00:00)), None).unwrap();
+        assert_eq!(evening, vec![sleep]);
+    }
+}
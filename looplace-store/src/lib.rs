@@ -1,14 +1,26 @@
 //! Local health + cognition data store for Looplace (native-only).
 //!
 //! A single [`Store`] trait is the storage protocol; backends are swappable
-//! implementations. Today: an in-memory backend (always available) and a Parquet
-//! backend (behind `parquet-store`). A Lance backend can be added later as
-//! another impl without touching callers — Arrow/Parquet interchange keeps that
-//! swap cheap.
+//! implementations. Today: an in-memory backend (always available), a Parquet
+//! backend (behind `parquet-store`), and a SQLite backend (behind
+//! `sqlite-store`). A Lance backend can be added later as another impl without
+//! touching callers — Arrow/Parquet interchange keeps that swap cheap, and
+//! [`copy_store`] moves data between any two backends. The Parquet backend can
+//! also be encrypted at rest under a passphrase (behind `encryption`), and
+//! exported to (or imported from) a portable zip [`bundle`] of Parquet, CSV
+//! and a self-describing manifest.
 //!
 //! All streams are stored in a uniform tidy shape ([`Observation`]): one row per
 //! scalar measurement, so glucose, cognition metrics, and future Apple Health
-//! data share a timeline and join trivially.
+//! data share a timeline and join trivially; manual [`journal`] entries (meals,
+//! medication, exercise, sleep, notes) are observations too. Every stream is
+//! registered in [`streams`] with its unit, valid range, and display metadata.
+//! Bucketing and summary statistics over them live in [`aggregate`], and
+//! produce observation series too. [`feed::WatchedStore`] wraps any backend to
+//! report what each write changed, so a long-lived handle can refresh
+//! incrementally. Each row synced or imported names the [`provenance`] batch
+//! that wrote it, so a bad import can be listed and rolled back. Cognition
+//! sessions can keep their raw per-[`trial`] data too, when the user opts in.
 //!
 //! Time basis: timestamps are [`time::PrimitiveDateTime`] in the *source's local
 //! wall-clock* (what the Libre reader reports) — stored raw, never offset-adjusted,
@@ -17,20 +29,43 @@
 //! by the zone's historical DST *rules* at read time; cognition `created_at` is
 //! already UTC. That cross-source unification lands with the correlation surface.
 
+pub mod aggregate;
+#[cfg(test)]
+mod conformance;
 pub mod convert;
 pub mod error;
+pub mod feed;
+pub mod journal;
 pub mod migrate;
 pub mod observation;
+pub mod provenance;
 pub mod session;
+pub mod snapshot;
 pub mod store;
+pub mod streams;
+pub mod trial;
 
+#[cfg(feature = "parquet-store")]
+pub mod bundle;
+#[cfg(feature = "encryption")]
+pub mod encryption;
+#[cfg(feature = "parquet-store")]
+mod lock;
 #[cfg(feature = "parquet-store")]
 pub mod parquet_store;
+#[cfg(feature = "parquet-store")]
+pub mod schema;
+#[cfg(feature = "sqlite-store")]
+pub mod sqlite_store;
 
 pub use error::{Result, StoreError};
 pub use observation::{Observation, Query};
+pub use provenance::IngestBatch;
 pub use session::SessionRecord;
-pub use store::{MemoryStore, Store};
+pub use store::{copy_store, CopyReport, MemoryStore, Store};
+pub use trial::TrialRecord;
 
 #[cfg(feature = "parquet-store")]
 pub use parquet_store::ParquetStore;
+#[cfg(feature = "sqlite-store")]
+pub use sqlite_store::SqliteStore;
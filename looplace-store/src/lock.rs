@@ -0,0 +1,103 @@
+//! Advisory cross-process locking for file-backed stores.
+//!
+//! The desktop app, the `ingest` example, and any future CLI may all open the
+//! same store. Each write takes an exclusive OS advisory lock on a sibling
+//! `*.lock` file for the few milliseconds it needs to reload, merge, and
+//! rewrite, so concurrent writers serialize instead of clobbering each other.
+//! A bulk write (a long import) holds it from start to finish instead.
+//! Readers never lock: writes land by atomic rename, so a reader always sees a
+//! whole file.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::{Result, StoreError};
+
+/// How long a writer waits for another process to finish before giving up
+/// with [`StoreError::Locked`].
+pub const LOCK_WAIT: Duration = Duration::from_secs(1);
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// An exclusive lock on a store, released on drop.
+#[derive(Debug)]
+pub(crate) struct StoreLock {
+    _file: File,
+}
+
+impl StoreLock {
+    /// Acquire the lock at `path`, waiting up to [`LOCK_WAIT`].
+    pub(crate) fn acquire(path: &Path) -> Result<Self> {
+        Self::acquire_within(path, LOCK_WAIT)
+    }
+
+    /// Acquire the lock at `path`, waiting up to `wait` for a holder to release it.
+    pub(crate) fn acquire_within(path: &Path, wait: Duration) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        let deadline = Instant::now() + wait;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    thread::sleep(RETRY_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(StoreError::Locked(path.to_path_buf()))
+                }
+                Err(TryLockError::Error(e)) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+/// The lock file guarding the store whose observations live at `path`.
+pub(crate) fn lock_path_for(path: &Path) -> PathBuf {
+    path.with_extension("lock")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_lock(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("looplace_store_lock");
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join(format!("{name}.lock"))
+    }
+
+    #[test]
+    fn second_holder_is_refused_until_release() {
+        let path = temp_lock("exclusive");
+        let held = StoreLock::acquire_within(&path, Duration::ZERO).unwrap();
+        assert!(matches!(
+            StoreLock::acquire_within(&path, Duration::ZERO),
+            Err(StoreError::Locked(p)) if p == path
+        ));
+
+        drop(held);
+        assert!(StoreLock::acquire_within(&path, Duration::ZERO).is_ok());
+    }
+
+    #[test]
+    fn waits_for_a_brief_holder() {
+        let path = temp_lock("wait");
+        let held = StoreLock::acquire_within(&path, Duration::ZERO).unwrap();
+        let releaser = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(held);
+        });
+        assert!(StoreLock::acquire_within(&path, Duration::from_secs(2)).is_ok());
+        releaser.join().unwrap();
+    }
+}
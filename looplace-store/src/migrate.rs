@@ -2,14 +2,16 @@
 //!
 //! The app calls [`run_upgrade`] on **every** startup; it migrates exactly once
 //! (gated by a marker file), is safe to re-run, and never deletes the original —
-//! it backs it up first. This is the flow that upgrades existing users when they
-//! first launch the storage-crate version.
+//! it backs it up first, and snapshots the store itself before importing into
+//! it. This is the flow that upgrades existing users when they first launch the
+//! storage-crate version.
 
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::convert::{summaries_from_json, summary_to_observations, summary_to_session};
 use crate::error::Result;
+use crate::snapshot::{snapshot_rotating, Attachments, SNAPSHOT_DIR};
 use crate::store::Store;
 
 /// Legacy cognition store filename (web localStorage key `looplace_summaries`;
@@ -17,10 +19,18 @@ use crate::store::Store;
 pub const LEGACY_FILE: &str = "summaries.json";
 /// Marker recording that the cognition migration has completed.
 pub const MARKER_FILE: &str = ".cognition-migrated";
+/// Small preference files in the app data dir, captured in every snapshot.
+pub const PREFS_FILES: &[&str] = &[
+    "glucose_prefs.json",
+    "import_mappings.json",
+    "cognition_prefs.json",
+];
 
 /// Bump when the migration's *output schema* changes (e.g. adding the sessions
 /// table), so already-migrated users re-run the idempotent import and pick it up.
-const MIGRATION_VERSION: u32 = 1;
+/// v2: the app now writes runs to the store directly and stops appending to
+/// `summaries.json`, so one last import picks up runs appended since v1.
+const MIGRATION_VERSION: u32 = 2;
 
 /// The concrete paths a migration operates on. Decoupled from any specific app
 /// layout so it stays testable; use [`MigrationPlan::for_data_dir`] for the
@@ -33,6 +43,10 @@ pub struct MigrationPlan {
     pub backup_path: PathBuf,
     /// Idempotency marker; when present, [`run_upgrade`] is a no-op.
     pub marker: PathBuf,
+    /// Where to take a rotating store snapshot before importing (`None` skips it).
+    pub snapshot_dir: Option<PathBuf>,
+    /// Preference files to include in that snapshot.
+    pub prefs: Vec<PathBuf>,
 }
 
 impl MigrationPlan {
@@ -43,6 +57,8 @@ impl MigrationPlan {
             legacy_summaries: data_dir.join(LEGACY_FILE),
             backup_path: data_dir.join(format!("{LEGACY_FILE}.pre-store-backup-{tag}")),
             marker: data_dir.join(MARKER_FILE),
+            snapshot_dir: Some(data_dir.join(SNAPSHOT_DIR)),
+            prefs: PREFS_FILES.iter().map(|name| data_dir.join(name)).collect(),
         }
     }
 }
@@ -51,6 +67,8 @@ impl MigrationPlan {
 #[derive(Debug, Clone, Default)]
 pub struct MigrationReport {
     pub backup_path: Option<PathBuf>,
+    /// Store snapshot taken before the import, if the plan asked for one.
+    pub snapshot_path: Option<PathBuf>,
     /// Cognition sessions successfully read from the legacy file.
     pub sessions: usize,
     /// Records present but unparseable — skipped (the backup retains them).
@@ -73,8 +91,9 @@ pub enum MigrationOutcome {
 /// Run the one-time legacy → store upgrade. Safe and idempotent to call on every
 /// app startup.
 ///
-/// Order is crash-safe: back up first, then import (idempotent upsert), then
-/// write the marker. A crash before the marker simply re-runs harmlessly.
+/// Order is crash-safe: back up and snapshot first, then import (idempotent
+/// upsert), then write the marker. A crash before the marker simply re-runs
+/// harmlessly.
 pub fn run_upgrade(plan: &MigrationPlan, store: &mut dyn Store) -> Result<MigrationOutcome> {
     // A marker at the current version means we're done. An older-version marker
     // falls through and re-runs the idempotent import to apply newer schema (e.g.
@@ -94,10 +113,21 @@ pub fn run_upgrade(plan: &MigrationPlan, store: &mut dyn Store) -> Result<Migrat
         fs::create_dir_all(parent)?;
     }
     fs::copy(&plan.legacy_summaries, &plan.backup_path)?;
+    let snapshot_path = match &plan.snapshot_dir {
+        Some(dir) => {
+            let attachments = Attachments {
+                prefs: plan.prefs.clone(),
+                raw: Vec::new(),
+            };
+            Some(snapshot_rotating(&*store, dir, "pre-migrate", &attachments, None)?)
+        }
+        None => None,
+    };
 
     // 2. Import (idempotent upsert).
     let mut report = import_summaries(&plan.legacy_summaries, store)?;
     report.backup_path = Some(plan.backup_path.clone());
+    report.snapshot_path = snapshot_path;
 
     // 3. Mark done so future launches skip straight to AlreadyDone.
     write_marker(
@@ -133,6 +163,7 @@ pub fn import_summaries(path: &Path, store: &mut dyn Store) -> Result<MigrationR
 
     Ok(MigrationReport {
         backup_path: None,
+        snapshot_path: None,
         sessions: sessions.len(),
         skipped_records: parsed.skipped,
         observations_inserted,
@@ -176,7 +207,7 @@ mod tests {
         {"id":"pvt-1","task":"pvt","created_at":"2026-06-19T08:00:00Z",
          "metrics":{"median_rt_ms":312.5,"lapses_ge_500ms":2}},
         {"id":"nback2-1","task":"nback2","created_at":"2026-06-19T09:00:00Z",
-         "metrics":{"dprime":1.8,"accuracy_pct":92.0}}
+         "metrics":{"d_prime":1.8,"accuracy":0.92}}
     ]"#;
 
     #[test]
@@ -194,11 +225,12 @@ mod tests {
             }
             other => panic!("expected Migrated, got {other:?}"),
         }
-        // Original untouched, backup + marker written, data queryable.
+        // Original untouched, backup + snapshot + marker written, data queryable.
         assert!(plan.legacy_summaries.exists());
         assert!(plan.backup_path.exists());
+        assert_eq!(crate::snapshot::list(&dir.join(SNAPSHOT_DIR)).unwrap().len(), 1);
         assert!(plan.marker.exists());
-        assert_eq!(store.query(&Query::stream("nback2.dprime")).unwrap()[0].value, 1.8);
+        assert_eq!(store.query(&Query::stream("nback2.d_prime")).unwrap()[0].value, 1.8);
 
         // Second launch: marker present → no-op (no double import).
         assert!(matches!(
@@ -263,7 +295,7 @@ mod tests {
         let json = r#"[
             {"id":"a","task":"pvt","created_at":"2026-06-19T08:00:00Z","metrics":{"median_rt_ms":300}},
             {"garbage":true},
-            {"id":"c","task":"nback2","created_at":"2026-06-19T09:00:00Z","metrics":{"dprime":1.5,"hits":9}}
+            {"id":"c","task":"nback2","created_at":"2026-06-19T09:00:00Z","metrics":{"d_prime":1.5,"hits":9}}
         ]"#;
         let dir = temp_dir("partial");
         fs::write(dir.join(LEGACY_FILE), json).unwrap();
@@ -314,7 +346,7 @@ mod tests {
                  "device":{"platform":"desktop"}},
            "notes":null},
           {"id":"nback2-1","task":"nback2","created_at":"2025-09-25T20:00:00Z",
-           "metrics":{"dprime":1.8,"criterion":0.2,"hits":10,"misses":2,"accuracy_pct":92.0},
+           "metrics":{"d_prime":1.8,"criterion":0.2,"hits":10,"misses":2,"accuracy":0.92},
            "qc":{"visibility_blur_events":1,"focus_lost_events":0,"min_trials_met":true,
                  "device":{"platform":"desktop"}},
            "notes":"felt good"}
@@ -7,8 +7,10 @@ use time::PrimitiveDateTime;
 /// One scalar measurement on one timeline.
 ///
 /// `stream` is a dotted name (`glucose.mg_dl`, `pvt.median_rt_ms`,
-/// `nback2.dprime`). Contextual annotations (food, exercise, reading kind, the
-/// device record sequence) live in `tags`.
+/// `nback2.d_prime`). Contextual annotations (food, exercise, reading kind, the
+/// device record sequence) live in `tags`. `batch_id` names the sync or import
+/// that last changed the row (see [`crate::provenance`]); rows written by the
+/// app itself have none.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Observation {
     pub stream: String,
@@ -18,6 +20,7 @@ pub struct Observation {
     pub source: String,
     pub session_id: Option<String>,
     pub tags: BTreeMap<String, String>,
+    pub batch_id: Option<String>,
 }
 
 impl Observation {
@@ -36,6 +39,7 @@ impl Observation {
             source: source.into(),
             session_id: None,
             tags: BTreeMap::new(),
+            batch_id: None,
         }
     }
 
@@ -44,7 +48,9 @@ impl Observation {
     /// `kind` is the reading-kind tag (`scan`/`sensor`/`blood` for glucose); it
     /// keeps a manual scan and a sensor-trace point that fall in the same minute
     /// from colliding. Streams without a `kind` tag (e.g. cognition) collapse to
-    /// `None`, so their dedup behaviour is unchanged. The key is deliberately the
+    /// `None`, so their dedup behaviour is unchanged. Journal entries put their
+    /// entry id in `kind`, so two entries logged at the same instant stay
+    /// distinct (see [`crate::journal`]). The key is deliberately the
     /// device's *raw* local timestamp — never an offset-adjusted value — so it
     /// stays stable across DST changes and re-syncs.
     pub fn key(&self) -> (&str, PrimitiveDateTime, &str, Option<&str>) {
@@ -63,6 +69,8 @@ pub struct Query {
     pub stream: Option<String>,
     pub since: Option<PrimitiveDateTime>,
     pub until: Option<PrimitiveDateTime>,
+    /// Only rows the [`crate::provenance`] batch with this id wrote.
+    pub batch_id: Option<String>,
 }
 
 impl Query {
@@ -73,6 +81,13 @@ impl Query {
         }
     }
 
+    pub fn batch(id: impl Into<String>) -> Self {
+        Self {
+            batch_id: Some(id.into()),
+            ..Default::default()
+        }
+    }
+
     pub fn matches(&self, o: &Observation) -> bool {
         if let Some(s) = &self.stream {
             if &o.stream != s {
@@ -89,6 +104,11 @@ impl Query {
                 return false;
             }
         }
+        if let Some(id) = &self.batch_id {
+            if o.batch_id.as_ref() != Some(id) {
+                return false;
+            }
+        }
         true
     }
 }
@@ -4,8 +4,28 @@
 //! semantics exactly) and persists the whole table to one Parquet file on each
 //! upsert — fine at this data scale, and the file is the portable, DuckDB- and
 //! Lance-readable artifact. Writes are atomic (temp file + rename).
+//!
+//! Each file is stamped with its schema version; older files are migrated
+//! forward on [`ParquetStore::open`] (see [`crate::schema`]), after a rotating
+//! snapshot of the store — raw files included — lands in the sibling
+//! `snapshots/` directory.
+//!
+//! Several processes may hold the same store open. Every write takes the
+//! store's advisory lock, reloads the table if another process has written it
+//! since (tracked by a generation number in the file metadata), applies the
+//! upsert on top, and only then rewrites — so concurrent upserts merge rather
+//! than overwrite each other. A long import can instead run as one bulk write
+//! ([`ParquetStore::begin_bulk_write`]): the lock is held throughout, writes
+//! stay in memory, and each changed table is rewritten once at the end.
+//!
+//! With the `encryption` feature a store can be sealed under a passphrase
+//! (see [`crate::encryption`]). A sibling `*.key.json` marks it encrypted:
+//! [`ParquetStore::open`] then refuses with [`StoreError::Encrypted`] and
+//! [`ParquetStore::open_encrypted`] is the way in. Files are serialized in
+//! memory and sealed before anything touches disk, so neither the tables, the
+//! temp files they're renamed from, nor the store's snapshots exist in
+//! plaintext.
 
-use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -14,46 +34,343 @@ use arrow_array::{
     TimestampMicrosecondArray,
 };
 use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use bytes::Bytes;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use parquet::arrow::ArrowWriter;
-use time::{OffsetDateTime, PrimitiveDateTime};
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
 
+#[cfg(feature = "encryption")]
+use crate::encryption::{self, Cipher, EncryptionState, KdfParams};
 use crate::error::{Result, StoreError};
+use crate::lock::{lock_path_for, StoreLock};
 use crate::observation::{Observation, Query};
+use crate::provenance::IngestBatch;
+use crate::schema::{self, Table, SCHEMA_VERSION};
 use crate::session::SessionRecord;
-use crate::store::{query_rows, sorted_sessions, upsert_into, upsert_sessions_into, Store};
+use crate::streams;
+use crate::snapshot::{snapshot_rotating, Attachments, Sealer, SNAPSHOT_DIR};
+use crate::store::{
+    delete_from, delete_sessions_from, delete_trials_from, key_index, micros_to_pdt, pdt_to_micros,
+    query_rows, sorted_batches, sorted_sessions, sorted_trials, upsert_batches_into,
+    upsert_indexed, upsert_into, upsert_sessions_into, upsert_trials_into, KeyIndex, Store,
+};
+use crate::trial::TrialRecord;
+
+/// Parquet key-value metadata key holding a table file's write generation.
+const GENERATION_KEY: &str = "looplace.generation";
+
+/// Stands in for [`crate::encryption::Cipher`] when the `encryption` feature
+/// is off: uninhabited, so every handle is plaintext.
+#[cfg(not(feature = "encryption"))]
+enum Cipher {}
+
+#[cfg(not(feature = "encryption"))]
+impl Sealer for Cipher {
+    fn seal(&self, _role: &str, _plaintext: &[u8]) -> Result<Vec<u8>> {
+        match *self {}
+    }
+
+    fn open(&self, _role: &str, _sealed: &[u8]) -> Result<Vec<u8>> {
+        match *self {}
+    }
+}
 
-/// A [`Store`] persisted to Parquet: observations at `path`, sessions in a
-/// sibling `*.sessions.parquet` file.
+/// What a table file's metadata says about it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct FileStamp {
+    /// Schema version the file was written at.
+    version: u32,
+    /// Incremented on every write; 0 for a missing or pre-generation file.
+    generation: u64,
+    /// The file on disk is sealed (encrypted).
+    sealed: bool,
+}
+
+/// A bulk write in progress (see [`ParquetStore::begin_bulk_write`]).
+struct BulkWrite {
+    /// Held from start to finish, so no other process writes underneath.
+    _lock: StoreLock,
+    /// The observations' key index, kept between upserts; a delete drops it.
+    index: Option<KeyIndex>,
+    /// Tables changed since the bulk write began.
+    dirty: Vec<Table>,
+}
+
+/// The key file whose presence marks the store at `path` as encrypted.
+fn key_path_for(path: &Path) -> PathBuf {
+    path.with_extension("key.json")
+}
+
+/// A [`Store`] persisted to Parquet: observations at `path`; sessions, ingest
+/// batches and trials in sibling `*.sessions.parquet`, `*.batches.parquet` and
+/// `*.trials.parquet` files; and a sibling `*.lock` for writers.
 pub struct ParquetStore {
     path: PathBuf,
     sessions_path: PathBuf,
+    batches_path: PathBuf,
+    trials_path: PathBuf,
+    lock_path: PathBuf,
     rows: Vec<Observation>,
     sessions: Vec<SessionRecord>,
+    batches: Vec<IngestBatch>,
+    trials: Vec<TrialRecord>,
+    /// Generation of each file our mirror reflects.
+    rows_generation: u64,
+    sessions_generation: u64,
+    batches_generation: u64,
+    trials_generation: u64,
+    /// Data keys, for an encrypted store.
+    cipher: Option<Cipher>,
+    bulk: Option<BulkWrite>,
 }
 
 impl ParquetStore {
     /// Open (or create-on-first-write) a store whose observations live at `path`.
-    /// The sessions table is the sibling `<path>.sessions.parquet`.
+    /// The sessions table is the sibling `<path>.sessions.parquet`, the
+    /// batches table `<path>.batches.parquet`, the trials table
+    /// `<path>.trials.parquet`.
+    ///
+    /// Files at an older schema version are migrated and rewritten in place;
+    /// files from a newer build fail with [`StoreError::UnsupportedSchema`].
+    /// Fails with [`StoreError::Locked`] if another process is mid-write, and
+    /// with [`StoreError::Encrypted`] if the store is encrypted.
     pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
         let path = path.into();
+        if key_path_for(&path).exists() {
+            return Err(StoreError::Encrypted);
+        }
+        Self::open_with(path, None)
+    }
+
+    fn open_with(path: PathBuf, cipher: Option<Cipher>) -> Result<Self> {
         let sessions_path = path.with_extension("sessions.parquet");
-        let rows = if path.exists() {
-            read_parquet(&path)?
-        } else {
-            Vec::new()
-        };
-        let sessions = if sessions_path.exists() {
-            read_sessions_parquet(&sessions_path)?
-        } else {
-            Vec::new()
-        };
-        Ok(Self {
+        let batches_path = path.with_extension("batches.parquet");
+        let trials_path = path.with_extension("trials.parquet");
+        let lock_path = lock_path_for(&path);
+        let _lock = StoreLock::acquire(&lock_path)?;
+
+        let (rows, rows_stamp) = read_parquet(&path, cipher.as_ref())?;
+        let (sessions, sessions_stamp) = read_sessions_parquet(&sessions_path, cipher.as_ref())?;
+        let (batches, batches_stamp) = read_ingest_parquet(&batches_path, cipher.as_ref())?;
+        let (trials, trials_stamp) = read_trials_parquet(&trials_path, cipher.as_ref())?;
+        let mut store = Self {
             path,
             sessions_path,
+            batches_path,
+            trials_path,
+            lock_path,
             rows,
             sessions,
-        })
+            batches,
+            trials,
+            rows_generation: rows_stamp.generation,
+            sessions_generation: sessions_stamp.generation,
+            batches_generation: batches_stamp.generation,
+            trials_generation: trials_stamp.generation,
+            cipher,
+            bulk: None,
+        };
+
+        let upgrade_rows = rows_stamp.version < SCHEMA_VERSION;
+        let upgrade_sessions = sessions_stamp.version < SCHEMA_VERSION;
+        let upgrade_batches = batches_stamp.version < SCHEMA_VERSION;
+        let upgrade_trials = trials_stamp.version < SCHEMA_VERSION;
+        if upgrade_rows || upgrade_sessions || upgrade_batches || upgrade_trials {
+            let attachments = Attachments {
+                prefs: Vec::new(),
+                raw: vec![
+                    store.path.clone(),
+                    store.sessions_path.clone(),
+                    store.batches_path.clone(),
+                    store.trials_path.clone(),
+                ],
+            };
+            let dir = store.snapshot_dir();
+            snapshot_rotating(&store, &dir, "pre-schema-upgrade", &attachments, store.sealer())?;
+        }
+        // An encrypted store with a plaintext table was interrupted while
+        // turning encryption on; finish the job.
+        let encrypted = store.cipher.is_some();
+        if upgrade_rows || (encrypted && !rows_stamp.sealed) {
+            store.write_rows()?;
+        }
+        if upgrade_sessions || (encrypted && !sessions_stamp.sealed) {
+            store.write_sessions()?;
+        }
+        if upgrade_batches || (encrypted && !batches_stamp.sealed) {
+            store.write_batches()?;
+        }
+        if upgrade_trials || (encrypted && !trials_stamp.sealed) {
+            store.write_trials()?;
+        }
+        #[cfg(feature = "encryption")]
+        store.finish_interrupted_encryption()?;
+        Ok(store)
+    }
+
+    fn write_rows(&mut self) -> Result<()> {
+        let generation = self.rows_generation + 1;
+        write_parquet(&self.path, &self.rows, generation, self.cipher.as_ref())?;
+        self.rows_generation = generation;
+        Ok(())
+    }
+
+    fn write_sessions(&mut self) -> Result<()> {
+        let generation = self.sessions_generation + 1;
+        let cipher = self.cipher.as_ref();
+        write_sessions_parquet(&self.sessions_path, &self.sessions, generation, cipher)?;
+        self.sessions_generation = generation;
+        Ok(())
+    }
+
+    fn write_batches(&mut self) -> Result<()> {
+        let generation = self.batches_generation + 1;
+        let cipher = self.cipher.as_ref();
+        write_ingest_parquet(&self.batches_path, &self.batches, generation, cipher)?;
+        self.batches_generation = generation;
+        Ok(())
+    }
+
+    fn write_trials(&mut self) -> Result<()> {
+        let generation = self.trials_generation + 1;
+        let cipher = self.cipher.as_ref();
+        write_trials_parquet(&self.trials_path, &self.trials, generation, cipher)?;
+        self.trials_generation = generation;
+        Ok(())
+    }
+
+    fn sealer(&self) -> Option<&dyn Sealer> {
+        self.cipher.as_ref().map(|c| c as &dyn Sealer)
+    }
+
+    /// Where this store's rotating snapshots live.
+    fn snapshot_dir(&self) -> PathBuf {
+        self.path.with_file_name(SNAPSHOT_DIR)
+    }
+
+    /// Reload any table another process has written since we last read or
+    /// wrote it. Returns whether anything changed.
+    pub fn refresh(&mut self) -> Result<bool> {
+        let rows = self.refresh_rows()?;
+        let sessions = self.refresh_sessions()?;
+        let batches = self.refresh_batches()?;
+        let trials = self.refresh_trials()?;
+        Ok(rows || sessions || batches || trials)
+    }
+
+    fn refresh_rows(&mut self) -> Result<bool> {
+        let cipher = self.cipher.as_ref();
+        if file_stamp(&self.path, Table::Observations, cipher)?.generation == self.rows_generation {
+            return Ok(false);
+        }
+        let (rows, stamp) = read_parquet(&self.path, cipher)?;
+        self.rows = rows;
+        self.rows_generation = stamp.generation;
+        Ok(true)
+    }
+
+    fn refresh_sessions(&mut self) -> Result<bool> {
+        let cipher = self.cipher.as_ref();
+        let stamp = file_stamp(&self.sessions_path, Table::Sessions, cipher)?;
+        if stamp.generation == self.sessions_generation {
+            return Ok(false);
+        }
+        let (sessions, stamp) = read_sessions_parquet(&self.sessions_path, cipher)?;
+        self.sessions = sessions;
+        self.sessions_generation = stamp.generation;
+        Ok(true)
+    }
+
+    fn refresh_batches(&mut self) -> Result<bool> {
+        let cipher = self.cipher.as_ref();
+        let stamp = file_stamp(&self.batches_path, Table::Batches, cipher)?;
+        if stamp.generation == self.batches_generation {
+            return Ok(false);
+        }
+        let (batches, stamp) = read_ingest_parquet(&self.batches_path, cipher)?;
+        self.batches = batches;
+        self.batches_generation = stamp.generation;
+        Ok(true)
+    }
+
+    fn refresh_trials(&mut self) -> Result<bool> {
+        let cipher = self.cipher.as_ref();
+        let stamp = file_stamp(&self.trials_path, Table::Trials, cipher)?;
+        if stamp.generation == self.trials_generation {
+            return Ok(false);
+        }
+        let (trials, stamp) = read_trials_parquet(&self.trials_path, cipher)?;
+        self.trials = trials;
+        self.trials_generation = stamp.generation;
+        Ok(true)
+    }
+
+    /// Start a bulk write: until [`finish_bulk_write`](Self::finish_bulk_write)
+    /// writes change only the in-memory tables, and each changed table is
+    /// written once at the end — so an import written chunk by chunk doesn't
+    /// rewrite the files per chunk. The store's lock is held meanwhile, so
+    /// other processes' writes fail with [`StoreError::Locked`]; dropping the
+    /// store before finishing discards the bulk write's changes.
+    pub fn begin_bulk_write(&mut self) -> Result<()> {
+        if self.bulk.is_some() {
+            return Ok(());
+        }
+        let lock = StoreLock::acquire(&self.lock_path)?;
+        self.refresh()?;
+        self.bulk = Some(BulkWrite {
+            _lock: lock,
+            index: None,
+            dirty: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Write every table the bulk write changed, and release the lock. A
+    /// no-op outside a bulk write.
+    pub fn finish_bulk_write(&mut self) -> Result<()> {
+        let Some(bulk) = self.bulk.take() else {
+            return Ok(());
+        };
+        for table in bulk.dirty {
+            self.write_table(table)?;
+        }
+        Ok(())
+    }
+
+    /// Take the lock and catch `table` up before changing it — unless a bulk
+    /// write holds the lock already, in which case nothing else has written.
+    fn lock_table(&mut self, table: Table) -> Result<Option<StoreLock>> {
+        if self.bulk.is_some() {
+            return Ok(None);
+        }
+        let lock = StoreLock::acquire(&self.lock_path)?;
+        match table {
+            Table::Observations => self.refresh_rows(),
+            Table::Sessions => self.refresh_sessions(),
+            Table::Batches => self.refresh_batches(),
+            Table::Trials => self.refresh_trials(),
+        }?;
+        Ok(Some(lock))
+    }
+
+    /// Write a changed `table` — or, in a bulk write, note it for the end.
+    fn save(&mut self, table: Table) -> Result<()> {
+        match &mut self.bulk {
+            Some(bulk) if !bulk.dirty.contains(&table) => bulk.dirty.push(table),
+            Some(_) => {}
+            None => self.write_table(table)?,
+        }
+        Ok(())
+    }
+
+    fn write_table(&mut self, table: Table) -> Result<()> {
+        match table {
+            Table::Observations => self.write_rows(),
+            Table::Sessions => self.write_sessions(),
+            Table::Batches => self.write_batches(),
+            Table::Trials => self.write_trials(),
+        }
     }
 
     pub fn path(&self) -> &Path {
@@ -69,10 +386,141 @@ impl ParquetStore {
     }
 }
 
+#[cfg(feature = "encryption")]
+impl ParquetStore {
+    /// Open an encrypted store with its passphrase. A store that isn't
+    /// encrypted yet is encrypted under `passphrase` first (see
+    /// [`enable_encryption`](Self::enable_encryption)). Fails with
+    /// [`StoreError::WrongPassphrase`] if the passphrase doesn't unlock it.
+    pub fn open_encrypted(path: impl Into<PathBuf>, passphrase: &str) -> Result<Self> {
+        let path = path.into();
+        let key_path = key_path_for(&path);
+        if key_path.exists() {
+            let cipher = encryption::unlock(&key_path, passphrase)?;
+            return Self::open_with(path, Some(cipher));
+        }
+        let mut store = Self::open_with(path, None)?;
+        store.enable_encryption(passphrase)?;
+        Ok(store)
+    }
+
+    /// Whether the store at `path` is encrypted, without needing its
+    /// passphrase: [`EncryptionState::Locked`] or [`EncryptionState::Plaintext`].
+    pub fn encryption_state_at(path: &Path) -> EncryptionState {
+        if key_path_for(path).exists() {
+            EncryptionState::Locked
+        } else {
+            EncryptionState::Plaintext
+        }
+    }
+
+    /// This handle's state: [`EncryptionState::Unlocked`] or
+    /// [`EncryptionState::Plaintext`].
+    pub fn encryption_state(&self) -> EncryptionState {
+        if self.cipher.is_some() {
+            EncryptionState::Unlocked
+        } else {
+            EncryptionState::Plaintext
+        }
+    }
+
+    /// Encrypt this plaintext store under `passphrase`: create its key file,
+    /// rewrite every table sealed, and seal every existing snapshot. A no-op
+    /// if this handle is already unlocked.
+    pub fn enable_encryption(&mut self, passphrase: &str) -> Result<()> {
+        self.enable_encryption_with(passphrase, KdfParams::default())
+    }
+
+    /// [`enable_encryption`](Self::enable_encryption) with explicit Argon2id costs.
+    pub fn enable_encryption_with(&mut self, passphrase: &str, kdf: KdfParams) -> Result<()> {
+        if self.cipher.is_some() {
+            return Ok(());
+        }
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        self.refresh()?;
+        // Fails if another process encrypted the store in the meantime.
+        self.cipher = Some(encryption::create_with(&key_path_for(&self.path), passphrase, kdf)?);
+        self.write_rows()?;
+        self.write_sessions()?;
+        self.write_batches()?;
+        self.write_trials()?;
+        self.reseal_snapshots()
+    }
+
+    /// Re-wrap the store's data keys under a new passphrase. Cheap: no store
+    /// file is rewritten, and other open handles are unaffected.
+    pub fn change_passphrase(&self, old: &str, new: &str) -> Result<()> {
+        if self.cipher.is_none() {
+            return Err(StoreError::Backend("store is not encrypted".into()));
+        }
+        encryption::change_passphrase(&key_path_for(&self.path), old, new)
+    }
+
+    /// Replace the store's data key: every table and snapshot is re-sealed
+    /// under a fresh key and the old one is discarded. Crash-safe — until the
+    /// old key is dropped, the key file holds both, and the next
+    /// [`open_encrypted`](Self::open_encrypted) finishes the rotation. Other
+    /// handles must reopen afterwards.
+    pub fn rotate_key(&mut self, passphrase: &str) -> Result<()> {
+        if self.cipher.is_none() {
+            return Err(StoreError::Backend("store is not encrypted".into()));
+        }
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        self.refresh()?;
+        self.cipher = Some(encryption::begin_rotation(&key_path_for(&self.path), passphrase)?);
+        self.finish_rotation()
+    }
+
+    /// Called under the lock: complete a rotation this handle's cipher is
+    /// part-way through, or seal snapshots an interrupted
+    /// [`enable_encryption`](Self::enable_encryption) left in plaintext.
+    fn finish_interrupted_encryption(&mut self) -> Result<()> {
+        match &self.cipher {
+            Some(cipher) if cipher.is_rotating() => self.finish_rotation(),
+            Some(_) => self.reseal_snapshots(),
+            None => Ok(()),
+        }
+    }
+
+    fn finish_rotation(&mut self) -> Result<()> {
+        self.write_rows()?;
+        self.write_sessions()?;
+        self.write_batches()?;
+        self.write_trials()?;
+        self.reseal_snapshots()?;
+        if let Some(cipher) = self.cipher.as_mut() {
+            encryption::finish_rotation(&key_path_for(&self.path), cipher)?;
+        }
+        Ok(())
+    }
+
+    /// Seal every snapshot that is plaintext or (mid-rotation) not yet under
+    /// the current key.
+    fn reseal_snapshots(&self) -> Result<()> {
+        let Some(cipher) = self.cipher.as_ref() else {
+            return Ok(());
+        };
+        for archive in crate::snapshot::list(&self.snapshot_dir())? {
+            if !crate::snapshot::manifest(&archive)?.sealed || cipher.is_rotating() {
+                crate::snapshot::reseal(&archive, Some(cipher), cipher)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl Store for ParquetStore {
     fn upsert(&mut self, observations: &[Observation]) -> Result<usize> {
-        let inserted = upsert_into(&mut self.rows, observations);
-        write_parquet(&self.path, &self.rows)?;
+        streams::validate_all(observations)?;
+        let _lock = self.lock_table(Table::Observations)?;
+        let inserted = match &mut self.bulk {
+            Some(bulk) => {
+                let index = bulk.index.get_or_insert_with(|| key_index(&self.rows));
+                upsert_indexed(&mut self.rows, index, observations)
+            }
+            None => upsert_into(&mut self.rows, observations),
+        };
+        self.save(Table::Observations)?;
         Ok(inserted)
     }
 
@@ -80,15 +528,68 @@ impl Store for ParquetStore {
         Ok(query_rows(&self.rows, query))
     }
 
+    fn delete(&mut self, observations: &[Observation]) -> Result<usize> {
+        let _lock = self.lock_table(Table::Observations)?;
+        let removed = delete_from(&mut self.rows, observations);
+        if removed > 0 {
+            if let Some(bulk) = &mut self.bulk {
+                bulk.index = None;
+            }
+            self.save(Table::Observations)?;
+        }
+        Ok(removed)
+    }
+
     fn upsert_sessions(&mut self, sessions: &[SessionRecord]) -> Result<usize> {
+        let _lock = self.lock_table(Table::Sessions)?;
         let inserted = upsert_sessions_into(&mut self.sessions, sessions);
-        write_sessions_parquet(&self.sessions_path, &self.sessions)?;
+        self.save(Table::Sessions)?;
         Ok(inserted)
     }
 
+    fn delete_sessions(&mut self, ids: &[String]) -> Result<usize> {
+        let _lock = self.lock_table(Table::Sessions)?;
+        let removed = delete_sessions_from(&mut self.sessions, ids);
+        if removed > 0 {
+            self.save(Table::Sessions)?;
+        }
+        Ok(removed)
+    }
+
     fn sessions(&self) -> Result<Vec<SessionRecord>> {
         Ok(sorted_sessions(&self.sessions))
     }
+
+    fn upsert_batches(&mut self, batches: &[IngestBatch]) -> Result<usize> {
+        let _lock = self.lock_table(Table::Batches)?;
+        let inserted = upsert_batches_into(&mut self.batches, batches);
+        self.save(Table::Batches)?;
+        Ok(inserted)
+    }
+
+    fn batches(&self) -> Result<Vec<IngestBatch>> {
+        Ok(sorted_batches(&self.batches))
+    }
+
+    fn upsert_trials(&mut self, trials: &[TrialRecord]) -> Result<usize> {
+        let _lock = self.lock_table(Table::Trials)?;
+        let inserted = upsert_trials_into(&mut self.trials, trials);
+        self.save(Table::Trials)?;
+        Ok(inserted)
+    }
+
+    fn delete_trials(&mut self, session_ids: &[String]) -> Result<usize> {
+        let _lock = self.lock_table(Table::Trials)?;
+        let removed = delete_trials_from(&mut self.trials, session_ids);
+        if removed > 0 {
+            self.save(Table::Trials)?;
+        }
+        Ok(removed)
+    }
+
+    fn trials(&self) -> Result<Vec<TrialRecord>> {
+        Ok(sorted_trials(&self.trials))
+    }
 }
 
 fn schema() -> Arc<Schema> {
@@ -104,20 +605,33 @@ fn schema() -> Arc<Schema> {
         Field::new("source", DataType::Utf8, false),
         Field::new("session_id", DataType::Utf8, true),
         Field::new("tags_json", DataType::Utf8, false),
+        Field::new("batch_id", DataType::Utf8, true),
     ]))
 }
 
-fn pdt_to_micros(t: PrimitiveDateTime) -> i64 {
-    (t.assume_utc().unix_timestamp_nanos() / 1_000) as i64
+fn write_parquet(
+    path: &Path,
+    rows: &[Observation],
+    generation: u64,
+    cipher: Option<&Cipher>,
+) -> Result<()> {
+    let batch = observations_batch(rows)?;
+    write_batch(path, Table::Observations, schema(), &batch, generation, cipher)
 }
 
-fn micros_to_pdt(micros: i64) -> PrimitiveDateTime {
-    let odt = OffsetDateTime::from_unix_timestamp_nanos(micros as i128 * 1_000)
-        .unwrap_or(OffsetDateTime::UNIX_EPOCH);
-    PrimitiveDateTime::new(odt.date(), odt.time())
+/// The observations table as standalone Parquet bytes (stamped with the
+/// current schema version, generation 0), for bundles.
+pub(crate) fn observations_to_parquet(rows: &[Observation]) -> Result<Vec<u8>> {
+    encode(schema(), &observations_batch(rows)?, 0)
 }
 
-fn write_parquet(path: &Path, rows: &[Observation]) -> Result<()> {
+/// Observations from standalone Parquet bytes, upgraded to the current schema.
+pub(crate) fn observations_from_parquet(bytes: Vec<u8>) -> Result<Vec<Observation>> {
+    let (batches, _) = decode(Bytes::from(bytes), Table::Observations, false)?;
+    observations_from(batches)
+}
+
+fn observations_batch(rows: &[Observation]) -> Result<RecordBatch> {
     let schema = schema();
 
     let stream = StringArray::from_iter_values(rows.iter().map(|r| r.stream.as_str()));
@@ -132,9 +646,10 @@ fn write_parquet(path: &Path, rows: &[Observation]) -> Result<()> {
         rows.iter()
             .map(|r| serde_json::to_string(&r.tags).unwrap_or_else(|_| "{}".to_string())),
     );
+    let batch_id = StringArray::from_iter(rows.iter().map(|r| r.batch_id.as_deref()));
 
-    let batch = RecordBatch::try_new(
-        schema.clone(),
+    RecordBatch::try_new(
+        schema,
         vec![
             Arc::new(stream),
             Arc::new(timestamp),
@@ -143,15 +658,24 @@ fn write_parquet(path: &Path, rows: &[Observation]) -> Result<()> {
             Arc::new(source),
             Arc::new(session_id),
             Arc::new(tags),
+            Arc::new(batch_id),
         ],
     )
-    .map_err(|e| StoreError::Backend(e.to_string()))?;
-
-    write_batch(path, schema, &batch)
+    .map_err(|e| StoreError::Backend(e.to_string()))
 }
 
 /// Atomic Parquet write: ensure the parent dir, write a temp file, then rename.
-fn write_batch(path: &Path, schema: Arc<Schema>, batch: &RecordBatch) -> Result<()> {
+/// The file is stamped with the current [`SCHEMA_VERSION`] and `generation`.
+/// It's serialized in memory and, given a cipher, sealed before the temp file
+/// is created, so no plaintext reaches disk.
+fn write_batch(
+    path: &Path,
+    table: Table,
+    schema: Arc<Schema>,
+    batch: &RecordBatch,
+    generation: u64,
+    cipher: Option<&Cipher>,
+) -> Result<()> {
     if let Some(parent) = path.parent() {
         if !parent.as_os_str().is_empty() {
             std::fs::create_dir_all(parent)?;
@@ -162,27 +686,138 @@ fn write_batch(path: &Path, schema: Arc<Schema>, batch: &RecordBatch) -> Result<
         .and_then(|n| n.to_str())
         .unwrap_or("store.parquet");
     let tmp = path.with_file_name(format!("{file_name}.tmp"));
-    {
-        let file = File::create(&tmp)?;
-        let mut writer =
-            ArrowWriter::try_new(file, schema, None).map_err(|e| StoreError::Backend(e.to_string()))?;
-        writer.write(batch).map_err(|e| StoreError::Backend(e.to_string()))?;
-        writer.close().map_err(|e| StoreError::Backend(e.to_string()))?;
+    let mut bytes = encode(schema, batch, generation)?;
+    if let Some(cipher) = cipher {
+        bytes = cipher.seal(role(table), &bytes)?;
     }
+    std::fs::write(&tmp, &bytes)?;
     std::fs::rename(&tmp, path)?;
     Ok(())
 }
 
-fn read_parquet(path: &Path) -> Result<Vec<Observation>> {
-    let file = File::open(path)?;
-    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
-        .map_err(|e| StoreError::Backend(e.to_string()))?
+/// Serialize one batch to Parquet in memory, stamped with the current
+/// [`SCHEMA_VERSION`] and `generation`.
+fn encode(schema: Arc<Schema>, batch: &RecordBatch, generation: u64) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut metadata = schema::version_metadata();
+    metadata.push(KeyValue::new(GENERATION_KEY.to_string(), generation.to_string()));
+    let props = WriterProperties::builder()
+        .set_key_value_metadata(Some(metadata))
+        .build();
+    let mut writer = ArrowWriter::try_new(&mut bytes, schema, Some(props))
+        .map_err(|e| StoreError::Backend(e.to_string()))?;
+    writer.write(batch).map_err(|e| StoreError::Backend(e.to_string()))?;
+    writer.close().map_err(|e| StoreError::Backend(e.to_string()))?;
+    Ok(bytes)
+}
+
+/// Associated data binding a sealed file to its table.
+fn role(table: Table) -> &'static str {
+    match table {
+        Table::Observations => "observations",
+        Table::Sessions => "sessions",
+        Table::Batches => "batches",
+        Table::Trials => "trials",
+    }
+}
+
+/// Read a whole table file, unsealing it if it's encrypted, and whether it
+/// was. `None` if the file doesn't exist.
+fn read_file(path: &Path, table: Table, cipher: Option<&Cipher>) -> Result<Option<(Bytes, bool)>> {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let (bytes, sealed) = unseal(table, bytes, cipher)?;
+    Ok(Some((Bytes::from(bytes), sealed)))
+}
+
+/// Plaintext files pass through even with a cipher: they're what an
+/// interrupted [`ParquetStore::enable_encryption`] leaves behind.
+#[cfg(feature = "encryption")]
+fn unseal(table: Table, bytes: Vec<u8>, cipher: Option<&Cipher>) -> Result<(Vec<u8>, bool)> {
+    if !encryption::is_sealed(&bytes) {
+        return Ok((bytes, false));
+    }
+    let cipher = cipher.ok_or(StoreError::Encrypted)?;
+    Ok((cipher.open(role(table), &bytes)?, true))
+}
+
+#[cfg(not(feature = "encryption"))]
+fn unseal(_table: Table, bytes: Vec<u8>, _cipher: Option<&Cipher>) -> Result<(Vec<u8>, bool)> {
+    Ok((bytes, false))
+}
+
+/// Read a table file's footer metadata (no row data is decoded, though a
+/// sealed file is decrypted whole). A missing file reads as the default stamp.
+fn file_stamp(path: &Path, table: Table, cipher: Option<&Cipher>) -> Result<FileStamp> {
+    let Some((bytes, sealed)) = read_file(path, table, cipher)? else {
+        return Ok(FileStamp::default());
+    };
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)
+        .map_err(|e| StoreError::Backend(e.to_string()))?;
+    stamp_from(builder.metadata().file_metadata().key_value_metadata(), sealed)
+}
+
+fn stamp_from(metadata: Option<&Vec<KeyValue>>, sealed: bool) -> Result<FileStamp> {
+    let generation = metadata
+        .and_then(|kv| kv.iter().find(|kv| kv.key == GENERATION_KEY))
+        .and_then(|kv| kv.value.as_deref())
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0);
+    Ok(FileStamp {
+        version: schema::file_version(metadata)?,
+        generation,
+        sealed,
+    })
+}
+
+/// Read every batch of a table file, upgraded to the current schema, plus the
+/// file's stamp as written. A missing file reads as empty.
+fn read_batches(
+    path: &Path,
+    table: Table,
+    cipher: Option<&Cipher>,
+) -> Result<(Vec<RecordBatch>, FileStamp)> {
+    let Some((bytes, sealed)) = read_file(path, table, cipher)? else {
+        // Nothing on disk yet: nothing to upgrade, and nothing left in plaintext.
+        let current = FileStamp {
+            version: SCHEMA_VERSION,
+            generation: 0,
+            sealed: true,
+        };
+        return Ok((Vec::new(), current));
+    };
+    decode(bytes, table, sealed)
+}
+
+/// Decode a whole (plaintext) table file, upgrading each batch to the
+/// current schema.
+fn decode(bytes: Bytes, table: Table, sealed: bool) -> Result<(Vec<RecordBatch>, FileStamp)> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)
+        .map_err(|e| StoreError::Backend(e.to_string()))?;
+    let stamp = stamp_from(builder.metadata().file_metadata().key_value_metadata(), sealed)?;
+    let reader = builder
         .build()
         .map_err(|e| StoreError::Backend(e.to_string()))?;
 
-    let mut out = Vec::new();
+    let mut batches = Vec::new();
     for batch in reader {
         let batch = batch.map_err(|e| StoreError::Backend(e.to_string()))?;
+        batches.push(schema::upgrade(table, stamp.version, batch)?);
+    }
+    Ok((batches, stamp))
+}
+
+fn read_parquet(path: &Path, cipher: Option<&Cipher>) -> Result<(Vec<Observation>, FileStamp)> {
+    let (batches, stamp) = read_batches(path, Table::Observations, cipher)?;
+    Ok((observations_from(batches)?, stamp))
+}
+
+fn observations_from(batches: Vec<RecordBatch>) -> Result<Vec<Observation>> {
+    let mut out = Vec::new();
+    for batch in batches {
         let stream = col_str(&batch, "stream")?;
         let timestamp = batch
             .column_by_name("timestamp")
@@ -196,6 +831,7 @@ fn read_parquet(path: &Path) -> Result<Vec<Observation>> {
         let source = col_str(&batch, "source")?;
         let session_id = col_str(&batch, "session_id")?;
         let tags = col_str(&batch, "tags_json")?;
+        let batch_id = col_str(&batch, "batch_id")?;
 
         for i in 0..batch.num_rows() {
             out.push(Observation {
@@ -210,6 +846,7 @@ fn read_parquet(path: &Path) -> Result<Vec<Observation>> {
                     Some(session_id.value(i).to_string())
                 },
                 tags: serde_json::from_str(tags.value(i)).unwrap_or_default(),
+                batch_id: nullable(batch_id, i),
             });
         }
     }
@@ -248,10 +885,33 @@ fn sessions_schema() -> Arc<Schema> {
         Field::new("qc_device_platform", DataType::Utf8, false),
         Field::new("qc_device_user_agent", DataType::Utf8, true),
         Field::new("notes", DataType::Utf8, true),
+        Field::new("qc_pause_events", DataType::Int64, false),
+        Field::new("qc_paused_ms", DataType::Int64, false),
     ]))
 }
 
-fn write_sessions_parquet(path: &Path, sessions: &[SessionRecord]) -> Result<()> {
+fn write_sessions_parquet(
+    path: &Path,
+    sessions: &[SessionRecord],
+    generation: u64,
+    cipher: Option<&Cipher>,
+) -> Result<()> {
+    let batch = sessions_batch(sessions)?;
+    write_batch(path, Table::Sessions, sessions_schema(), &batch, generation, cipher)
+}
+
+/// The sessions table as standalone Parquet bytes, for bundles.
+pub(crate) fn sessions_to_parquet(sessions: &[SessionRecord]) -> Result<Vec<u8>> {
+    encode(sessions_schema(), &sessions_batch(sessions)?, 0)
+}
+
+/// Sessions from standalone Parquet bytes, upgraded to the current schema.
+pub(crate) fn sessions_from_parquet(bytes: Vec<u8>) -> Result<Vec<SessionRecord>> {
+    let (batches, _) = decode(Bytes::from(bytes), Table::Sessions, false)?;
+    sessions_from(batches)
+}
+
+fn sessions_batch(sessions: &[SessionRecord]) -> Result<RecordBatch> {
     let schema = sessions_schema();
 
     let id = StringArray::from_iter_values(sessions.iter().map(|s| s.id.as_str()));
@@ -278,9 +938,13 @@ fn write_sessions_parquet(path: &Path, sessions: &[SessionRecord]) -> Result<()>
     let qc_user_agent =
         StringArray::from_iter(sessions.iter().map(|s| s.qc_device_user_agent.as_deref()));
     let notes = StringArray::from_iter(sessions.iter().map(|s| s.notes.as_deref()));
+    let qc_pauses =
+        Int64Array::from(sessions.iter().map(|s| s.qc_pause_events).collect::<Vec<i64>>());
+    let qc_paused_ms =
+        Int64Array::from(sessions.iter().map(|s| s.qc_paused_ms).collect::<Vec<i64>>());
 
-    let batch = RecordBatch::try_new(
-        schema.clone(),
+    RecordBatch::try_new(
+        schema,
         vec![
             Arc::new(id),
             Arc::new(task),
@@ -294,23 +958,24 @@ fn write_sessions_parquet(path: &Path, sessions: &[SessionRecord]) -> Result<()>
             Arc::new(qc_device),
             Arc::new(qc_user_agent),
             Arc::new(notes),
+            Arc::new(qc_pauses),
+            Arc::new(qc_paused_ms),
         ],
     )
-    .map_err(|e| StoreError::Backend(e.to_string()))?;
-
-    write_batch(path, schema, &batch)
+    .map_err(|e| StoreError::Backend(e.to_string()))
 }
 
-fn read_sessions_parquet(path: &Path) -> Result<Vec<SessionRecord>> {
-    let file = File::open(path)?;
-    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
-        .map_err(|e| StoreError::Backend(e.to_string()))?
-        .build()
-        .map_err(|e| StoreError::Backend(e.to_string()))?;
+fn read_sessions_parquet(
+    path: &Path,
+    cipher: Option<&Cipher>,
+) -> Result<(Vec<SessionRecord>, FileStamp)> {
+    let (batches, stamp) = read_batches(path, Table::Sessions, cipher)?;
+    Ok((sessions_from(batches)?, stamp))
+}
 
+fn sessions_from(batches: Vec<RecordBatch>) -> Result<Vec<SessionRecord>> {
     let mut out = Vec::new();
-    for batch in reader {
-        let batch = batch.map_err(|e| StoreError::Backend(e.to_string()))?;
+    for batch in batches {
         let id = col_str(&batch, "id")?;
         let task = col_str(&batch, "task")?;
         let created_at = col::<TimestampMicrosecondArray>(&batch, "created_at")?;
@@ -323,6 +988,8 @@ fn read_sessions_parquet(path: &Path) -> Result<Vec<SessionRecord>> {
         let qc_device = col_str(&batch, "qc_device_platform")?;
         let qc_user_agent = col_str(&batch, "qc_device_user_agent")?;
         let notes = col_str(&batch, "notes")?;
+        let qc_pauses = col::<Int64Array>(&batch, "qc_pause_events")?;
+        let qc_paused_ms = col::<Int64Array>(&batch, "qc_paused_ms")?;
 
         for i in 0..batch.num_rows() {
             out.push(SessionRecord {
@@ -338,6 +1005,8 @@ fn read_sessions_parquet(path: &Path) -> Result<Vec<SessionRecord>> {
                 qc_min_trials_met: qc_min.value(i),
                 qc_device_platform: qc_device.value(i).to_string(),
                 qc_device_user_agent: nullable(qc_user_agent, i),
+                qc_pause_events: qc_pauses.value(i),
+                qc_paused_ms: qc_paused_ms.value(i),
                 notes: nullable(notes, i),
             });
         }
@@ -345,6 +1014,227 @@ fn read_sessions_parquet(path: &Path) -> Result<Vec<SessionRecord>> {
     Ok(out)
 }
 
+// ---- batches table (ingest batches, not Arrow record batches) -------------
+
+fn ingest_schema() -> Arc<Schema> {
+    let utc = || DataType::Timestamp(TimeUnit::Microsecond, None);
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("created_at", utc(), false),
+        Field::new("kind", DataType::Utf8, false),
+        Field::new("app_version", DataType::Utf8, false),
+        Field::new("device_firmware", DataType::Utf8, true),
+        Field::new("file_hash", DataType::Utf8, true),
+        Field::new("detail", DataType::Utf8, true),
+        Field::new("rolled_back_at", utc(), true),
+    ]))
+}
+
+fn write_ingest_parquet(
+    path: &Path,
+    batches: &[IngestBatch],
+    generation: u64,
+    cipher: Option<&Cipher>,
+) -> Result<()> {
+    let batch = ingest_columns(batches)?;
+    write_batch(
+        path,
+        Table::Batches,
+        ingest_schema(),
+        &batch,
+        generation,
+        cipher,
+    )
+}
+
+/// The batches table as standalone Parquet bytes, for bundles.
+pub(crate) fn batches_to_parquet(batches: &[IngestBatch]) -> Result<Vec<u8>> {
+    encode(ingest_schema(), &ingest_columns(batches)?, 0)
+}
+
+/// Ingest batches from standalone Parquet bytes.
+pub(crate) fn batches_from_parquet(bytes: Vec<u8>) -> Result<Vec<IngestBatch>> {
+    let (record_batches, _) = decode(Bytes::from(bytes), Table::Batches, false)?;
+    ingest_from(record_batches)
+}
+
+fn ingest_columns(batches: &[IngestBatch]) -> Result<RecordBatch> {
+    let text = |f: fn(&IngestBatch) -> Option<&str>| StringArray::from_iter(batches.iter().map(f));
+    let id = StringArray::from_iter_values(batches.iter().map(|b| b.id.as_str()));
+    let created_at = TimestampMicrosecondArray::from(
+        batches
+            .iter()
+            .map(|b| pdt_to_micros(b.created_at))
+            .collect::<Vec<i64>>(),
+    );
+    let kind = StringArray::from_iter_values(batches.iter().map(|b| b.kind.as_str()));
+    let app_version = StringArray::from_iter_values(batches.iter().map(|b| b.app_version.as_str()));
+    let rolled_back_at = TimestampMicrosecondArray::from(
+        batches
+            .iter()
+            .map(|b| b.rolled_back_at.map(pdt_to_micros))
+            .collect::<Vec<Option<i64>>>(),
+    );
+
+    RecordBatch::try_new(
+        ingest_schema(),
+        vec![
+            Arc::new(id),
+            Arc::new(created_at),
+            Arc::new(kind),
+            Arc::new(app_version),
+            Arc::new(text(|b| b.device_firmware.as_deref())),
+            Arc::new(text(|b| b.file_hash.as_deref())),
+            Arc::new(text(|b| b.detail.as_deref())),
+            Arc::new(rolled_back_at),
+        ],
+    )
+    .map_err(|e| StoreError::Backend(e.to_string()))
+}
+
+fn read_ingest_parquet(
+    path: &Path,
+    cipher: Option<&Cipher>,
+) -> Result<(Vec<IngestBatch>, FileStamp)> {
+    let (record_batches, stamp) = read_batches(path, Table::Batches, cipher)?;
+    Ok((ingest_from(record_batches)?, stamp))
+}
+
+fn ingest_from(record_batches: Vec<RecordBatch>) -> Result<Vec<IngestBatch>> {
+    let mut out = Vec::new();
+    for batch in record_batches {
+        let id = col_str(&batch, "id")?;
+        let created_at = col::<TimestampMicrosecondArray>(&batch, "created_at")?;
+        let kind = col_str(&batch, "kind")?;
+        let app_version = col_str(&batch, "app_version")?;
+        let device_firmware = col_str(&batch, "device_firmware")?;
+        let file_hash = col_str(&batch, "file_hash")?;
+        let detail = col_str(&batch, "detail")?;
+        let rolled_back_at = col::<TimestampMicrosecondArray>(&batch, "rolled_back_at")?;
+
+        for i in 0..batch.num_rows() {
+            out.push(IngestBatch {
+                id: id.value(i).to_string(),
+                created_at: micros_to_pdt(created_at.value(i)),
+                kind: kind.value(i).to_string(),
+                app_version: app_version.value(i).to_string(),
+                device_firmware: nullable(device_firmware, i),
+                file_hash: nullable(file_hash, i),
+                detail: nullable(detail, i),
+                rolled_back_at: (!rolled_back_at.is_null(i))
+                    .then(|| micros_to_pdt(rolled_back_at.value(i))),
+            });
+        }
+    }
+    Ok(out)
+}
+
+// ---- trials table ----------------------------------------------------------
+
+fn trials_schema() -> Arc<Schema> {
+    Arc::new(Schema::new(vec![
+        Field::new("session_id", DataType::Utf8, false),
+        Field::new("index", DataType::Int64, false),
+        Field::new("iti_ms", DataType::Float64, true),
+        Field::new("onset_ms", DataType::Float64, true),
+        Field::new("response_ms", DataType::Float64, true),
+        Field::new("rt_ms", DataType::Float64, true),
+        Field::new("outcome", DataType::Utf8, false),
+        Field::new("stimulus", DataType::Utf8, true),
+        Field::new("target", DataType::Boolean, true),
+        Field::new("lure", DataType::Boolean, true),
+    ]))
+}
+
+fn write_trials_parquet(
+    path: &Path,
+    trials: &[TrialRecord],
+    generation: u64,
+    cipher: Option<&Cipher>,
+) -> Result<()> {
+    let batch = trials_batch(trials)?;
+    write_batch(path, Table::Trials, trials_schema(), &batch, generation, cipher)
+}
+
+/// The trials table as standalone Parquet bytes, for bundles.
+pub(crate) fn trials_to_parquet(trials: &[TrialRecord]) -> Result<Vec<u8>> {
+    encode(trials_schema(), &trials_batch(trials)?, 0)
+}
+
+/// Trials from standalone Parquet bytes.
+pub(crate) fn trials_from_parquet(bytes: Vec<u8>) -> Result<Vec<TrialRecord>> {
+    let (batches, _) = decode(Bytes::from(bytes), Table::Trials, false)?;
+    trials_from(batches)
+}
+
+fn trials_batch(trials: &[TrialRecord]) -> Result<RecordBatch> {
+    let ms = |f: fn(&TrialRecord) -> Option<f64>| Float64Array::from_iter(trials.iter().map(f));
+    let flag = |f: fn(&TrialRecord) -> Option<bool>| BooleanArray::from_iter(trials.iter().map(f));
+    let session_id = StringArray::from_iter_values(trials.iter().map(|t| t.session_id.as_str()));
+    let index = Int64Array::from_iter_values(trials.iter().map(|t| t.index));
+    let outcome = StringArray::from_iter_values(trials.iter().map(|t| t.outcome.as_str()));
+    let stimulus = StringArray::from_iter(trials.iter().map(|t| t.stimulus.as_deref()));
+
+    RecordBatch::try_new(
+        trials_schema(),
+        vec![
+            Arc::new(session_id),
+            Arc::new(index),
+            Arc::new(ms(|t| t.iti_ms)),
+            Arc::new(ms(|t| t.onset_ms)),
+            Arc::new(ms(|t| t.response_ms)),
+            Arc::new(ms(|t| t.rt_ms)),
+            Arc::new(outcome),
+            Arc::new(stimulus),
+            Arc::new(flag(|t| t.target)),
+            Arc::new(flag(|t| t.lure)),
+        ],
+    )
+    .map_err(|e| StoreError::Backend(e.to_string()))
+}
+
+fn read_trials_parquet(
+    path: &Path,
+    cipher: Option<&Cipher>,
+) -> Result<(Vec<TrialRecord>, FileStamp)> {
+    let (batches, stamp) = read_batches(path, Table::Trials, cipher)?;
+    Ok((trials_from(batches)?, stamp))
+}
+
+fn trials_from(batches: Vec<RecordBatch>) -> Result<Vec<TrialRecord>> {
+    let mut out = Vec::new();
+    for batch in batches {
+        let session_id = col_str(&batch, "session_id")?;
+        let index = col::<Int64Array>(&batch, "index")?;
+        let iti_ms = col::<Float64Array>(&batch, "iti_ms")?;
+        let onset_ms = col::<Float64Array>(&batch, "onset_ms")?;
+        let response_ms = col::<Float64Array>(&batch, "response_ms")?;
+        let rt_ms = col::<Float64Array>(&batch, "rt_ms")?;
+        let outcome = col_str(&batch, "outcome")?;
+        let stimulus = col_str(&batch, "stimulus")?;
+        let target = col::<BooleanArray>(&batch, "target")?;
+        let lure = col::<BooleanArray>(&batch, "lure")?;
+        let ms = |arr: &Float64Array, i: usize| (!arr.is_null(i)).then(|| arr.value(i));
+        let flag = |arr: &BooleanArray, i: usize| (!arr.is_null(i)).then(|| arr.value(i));
+
+        for i in 0..batch.num_rows() {
+            out.push(TrialRecord {
+                session_id: session_id.value(i).to_string(),
+                index: index.value(i),
+                iti_ms: ms(iti_ms, i),
+                onset_ms: ms(onset_ms, i),
+                response_ms: ms(response_ms, i),
+                rt_ms: ms(rt_ms, i),
+                outcome: outcome.value(i).to_string(),
+                stimulus: nullable(stimulus, i),
+                target: flag(target, i),
+                lure: flag(lure, i),
+            });
+        }
+    }
+    Ok(out)
+}
+
 fn nullable(arr: &StringArray, i: usize) -> Option<String> {
     if arr.is_null(i) {
         None
@@ -356,7 +1246,10 @@ fn nullable(arr: &StringArray, i: usize) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::conformance;
+    use std::fs::File;
     use time::macros::datetime;
+    use time::PrimitiveDateTime;
 
     fn temp_path(name: &str) -> PathBuf {
         let dir = std::env::temp_dir().join("looplace_store_pq");
@@ -366,6 +1259,17 @@ mod tests {
         path
     }
 
+    #[test]
+    fn conforms_to_the_store_contract() {
+        conformance::run(|name| {
+            let path = temp_path(&format!("conformance_{name}"));
+            let _ = std::fs::remove_file(path.with_extension("sessions.parquet"));
+            let _ = std::fs::remove_file(path.with_extension("batches.parquet"));
+            let _ = std::fs::remove_file(path.with_extension("trials.parquet"));
+            ParquetStore::open(path).unwrap()
+        });
+    }
+
     #[test]
     fn round_trips_through_a_parquet_file() {
         let path = temp_path("roundtrip");
@@ -429,6 +1333,90 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    fn stamped_version(path: &Path) -> Option<String> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap()).unwrap();
+        builder
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .and_then(|kv| kv.iter().find(|kv| kv.key == schema::VERSION_KEY))
+            .and_then(|kv| kv.value.clone())
+    }
+
+    /// Write observations the way builds before schema versioning did: the v1
+    /// layout (no `batch_id`), no version stamp.
+    fn write_unversioned(path: &Path, rows: &[Observation]) {
+        write_parquet(path, rows, 1, None).unwrap();
+        let (batches, _) = read_batches(path, Table::Observations, None).unwrap();
+        let v1: Vec<usize> = (0..schema().fields().len() - 1).collect();
+        let v1_schema = Arc::new(schema().project(&v1).unwrap());
+        let mut writer =
+            ArrowWriter::try_new(File::create(path).unwrap(), v1_schema, None).unwrap();
+        for batch in &batches {
+            writer.write(&batch.project(&v1).unwrap()).unwrap();
+        }
+        writer.close().unwrap();
+    }
+
+    #[test]
+    fn writes_stamp_the_schema_version() {
+        let path = temp_path("stamped");
+        let reading =
+            Observation::new("glucose.mg_dl", datetime!(2026-06-19 09:00:00), 100.0, "mg/dL", "dev");
+        let mut store = ParquetStore::open(&path).unwrap();
+        store.upsert(&[reading]).unwrap();
+        assert_eq!(stamped_version(&path), Some(SCHEMA_VERSION.to_string()));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn opens_files_written_before_versioning() {
+        let path = temp_path("unversioned");
+        let mut reading =
+            Observation::new("glucose.mg_dl", datetime!(2026-06-19 09:00:00), 100.0, "mg/dL", "dev");
+        reading.tags.insert("kind".into(), "scan".into());
+        write_unversioned(&path, std::slice::from_ref(&reading));
+        assert_eq!(stamped_version(&path), None);
+
+        let mut store = ParquetStore::open(&path).unwrap();
+        assert_eq!(store.query(&Query::default()).unwrap(), vec![reading.clone()]);
+
+        // The next write stamps the file; history is carried over intact.
+        let mut next = reading.clone();
+        next.timestamp = datetime!(2026-06-19 09:15:00);
+        assert_eq!(store.upsert(&[next]).unwrap(), 1);
+        assert_eq!(stamped_version(&path), Some(SCHEMA_VERSION.to_string()));
+        assert_eq!(ParquetStore::open(&path).unwrap().len(), 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn refuses_files_from_a_newer_schema() {
+        let path = temp_path("newer");
+        write_parquet(&path, &[], 1, None).unwrap();
+        let (batches, _) = read_batches(&path, Table::Observations, None).unwrap();
+        let props = WriterProperties::builder()
+            .set_key_value_metadata(Some(vec![parquet::file::metadata::KeyValue::new(
+                schema::VERSION_KEY.to_string(),
+                (SCHEMA_VERSION + 1).to_string(),
+            )]))
+            .build();
+        let mut writer =
+            ArrowWriter::try_new(File::create(&path).unwrap(), super::schema(), Some(props)).unwrap();
+        for batch in &batches {
+            writer.write(batch).unwrap();
+        }
+        writer.close().unwrap();
+
+        assert!(matches!(
+            ParquetStore::open(&path),
+            Err(StoreError::UnsupportedSchema { found, supported })
+                if found == SCHEMA_VERSION + 1 && supported == SCHEMA_VERSION
+        ));
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn sessions_round_trip_losslessly_through_parquet() {
         let path = temp_path("sessions");
@@ -447,6 +1435,8 @@ mod tests {
             qc_min_trials_met: true,
             qc_device_platform: "desktop".into(),
             qc_device_user_agent: None,
+            qc_pause_events: 0,
+            qc_paused_ms: 0,
             notes: Some("felt sharp".into()),
         };
         let web = SessionRecord {
@@ -455,12 +1445,14 @@ mod tests {
             created_at: datetime!(2025-09-25 20:00:00),
             client_platform: "web".into(),
             client_tz: "UTC".into(),
-            metrics: serde_json::json!({"dprime": 1.8}),
+            metrics: serde_json::json!({"d_prime": 1.8}),
             qc_visibility_blur_events: 2,
             qc_focus_lost_events: 0,
             qc_min_trials_met: false,
             qc_device_platform: "web".into(),
             qc_device_user_agent: Some("Mozilla/5.0".into()),
+            qc_pause_events: 0,
+            qc_paused_ms: 0,
             notes: None,
         };
 
@@ -479,4 +1471,253 @@ mod tests {
         let _ = std::fs::remove_file(&path);
         let _ = std::fs::remove_file(&sessions_file);
     }
+
+    fn session(id: &str, created_at: PrimitiveDateTime) -> SessionRecord {
+        SessionRecord {
+            id: id.into(),
+            task: "pvt".into(),
+            created_at,
+            client_platform: "desktop".into(),
+            client_tz: "UTC".into(),
+            metrics: serde_json::json!({}),
+            qc_visibility_blur_events: 0,
+            qc_focus_lost_events: 0,
+            qc_min_trials_met: true,
+            qc_device_platform: "desktop".into(),
+            qc_device_user_agent: None,
+            qc_pause_events: 0,
+            qc_paused_ms: 0,
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn concurrent_handles_merge_instead_of_overwriting() {
+        let path = temp_path("concurrent");
+        let sessions_file = path.with_extension("sessions.parquet");
+        let _ = std::fs::remove_file(&sessions_file);
+
+        // Two processes (here: two handles) open the same, still-empty store.
+        let mut app = ParquetStore::open(&path).unwrap();
+        let mut ingest = ParquetStore::open(&path).unwrap();
+
+        let glucose = |t, v| Observation::new("glucose.mg_dl", t, v, "mg/dL", "dev");
+        let a = glucose(datetime!(2026-06-19 09:00:00), 100.0);
+        let b = glucose(datetime!(2026-06-19 09:15:00), 110.0);
+        assert_eq!(app.upsert(std::slice::from_ref(&a)).unwrap(), 1);
+        // `ingest`'s mirror is stale; its write must reload and keep `a`.
+        assert_eq!(ingest.upsert(std::slice::from_ref(&b)).unwrap(), 1);
+        assert_eq!(ingest.len(), 2);
+
+        app.upsert_sessions(&[session("s-1", datetime!(2026-06-19 08:00:00))]).unwrap();
+        ingest.upsert_sessions(&[session("s-2", datetime!(2026-06-19 08:30:00))]).unwrap();
+
+        // The stale reader catches up on demand.
+        assert!(app.refresh().unwrap());
+        assert_eq!(app.query(&Query::default()).unwrap(), vec![a, b]);
+        assert_eq!(app.sessions().unwrap().len(), 2);
+        assert!(!app.refresh().unwrap());
+
+        let reopened = ParquetStore::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.sessions().unwrap().len(), 2);
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&sessions_file);
+    }
+
+    #[test]
+    fn write_while_another_process_holds_the_lock_is_refused() {
+        let path = temp_path("locked");
+        let mut store = ParquetStore::open(&path).unwrap();
+
+        let held = StoreLock::acquire(&lock_path_for(&path)).unwrap();
+        let reading =
+            Observation::new("glucose.mg_dl", datetime!(2026-06-19 09:00:00), 100.0, "mg/dL", "dev");
+        assert!(matches!(
+            store.upsert(std::slice::from_ref(&reading)),
+            Err(StoreError::Locked(_))
+        ));
+        assert!(matches!(ParquetStore::open(&path), Err(StoreError::Locked(_))));
+
+        drop(held);
+        assert_eq!(store.upsert(&[reading]).unwrap(), 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn bulk_write_holds_the_files_until_finished() {
+        let path = temp_path("bulk");
+        let mut store = ParquetStore::open(&path).unwrap();
+        let glucose = |minute, v| {
+            let t = datetime!(2026-06-19 09:00:00) + time::Duration::minutes(minute);
+            Observation::new("glucose.mg_dl", t, v, "mg/dL", "dev")
+        };
+        store.upsert(&[glucose(0, 100.0)]).unwrap();
+
+        store.begin_bulk_write().unwrap();
+        assert!(matches!(ParquetStore::open(&path), Err(StoreError::Locked(_))));
+        assert_eq!(store.upsert(&[glucose(5, 105.0), glucose(10, 110.0)]).unwrap(), 2);
+        assert_eq!(store.delete(&[glucose(0, 100.0)]).unwrap(), 1);
+        // The key index is rebuilt after the delete shifted the rows.
+        assert_eq!(store.upsert(&[glucose(10, 111.0), glucose(15, 115.0)]).unwrap(), 1);
+        store.finish_bulk_write().unwrap();
+
+        let reopened = ParquetStore::open(&path).unwrap();
+        let values: Vec<f64> = reopened
+            .query(&Query::default())
+            .unwrap()
+            .iter()
+            .map(|o| o.value)
+            .collect();
+        assert_eq!(values, [105.0, 111.0, 115.0]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "encryption")]
+    mod encrypted {
+        use super::*;
+        use crate::encryption::TEST_KDF;
+        use crate::snapshot::{self, restore};
+        use crate::store::MemoryStore;
+
+        /// A fresh directory per test: encrypted stores bring key files and
+        /// snapshots along.
+        fn store_in(name: &str) -> PathBuf {
+            let dir = std::env::temp_dir().join(format!("looplace_store_enc_{name}"));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            dir.join("looplace.parquet")
+        }
+
+        fn reading(minute: u8, value: f64) -> Observation {
+            let t = datetime!(2026-06-19 09:00:00).replace_minute(minute).unwrap();
+            Observation::new("glucose.mg_dl", t, value, "mg/dL", "dev")
+        }
+
+        fn encrypted_store(path: &Path, passphrase: &str) -> ParquetStore {
+            let mut store = ParquetStore::open(path).unwrap();
+            store.enable_encryption_with(passphrase, TEST_KDF).unwrap();
+            store
+        }
+
+        /// Every regular file under `dir` (recursively).
+        fn files_under(dir: &Path) -> Vec<PathBuf> {
+            let mut out = Vec::new();
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    out.extend(files_under(&path));
+                } else {
+                    out.push(path);
+                }
+            }
+            out
+        }
+
+        fn assert_no_plaintext(dir: &Path) {
+            for path in files_under(dir) {
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                let metadata = name.ends_with(".key.json") || name.ends_with(".lock");
+                if metadata || name == snapshot::MANIFEST_FILE {
+                    continue;
+                }
+                let bytes = std::fs::read(&path).unwrap();
+                assert!(encryption::is_sealed(&bytes), "{} is not sealed", path.display());
+                assert!(!name.ends_with(".tmp"), "temp file left behind: {name}");
+            }
+        }
+
+        #[test]
+        fn encrypted_store_round_trips_and_stays_sealed() {
+            let path = store_in("roundtrip");
+            let mut store = encrypted_store(&path, "correct horse");
+            assert_eq!(store.encryption_state(), EncryptionState::Unlocked);
+            store.upsert(&[reading(0, 100.0), reading(5, 104.0)]).unwrap();
+            store.upsert_sessions(&[session("s-1", datetime!(2026-06-19 08:00:00))]).unwrap();
+            assert_no_plaintext(path.parent().unwrap());
+
+            assert_eq!(ParquetStore::encryption_state_at(&path), EncryptionState::Locked);
+            assert!(matches!(ParquetStore::open(&path), Err(StoreError::Encrypted)));
+            assert!(matches!(
+                ParquetStore::open_encrypted(&path, "battery staple"),
+                Err(StoreError::WrongPassphrase)
+            ));
+
+            let reopened = ParquetStore::open_encrypted(&path, "correct horse").unwrap();
+            let rows = reopened.query(&Query::default()).unwrap();
+            assert_eq!(rows, store.query(&Query::default()).unwrap());
+            assert_eq!(reopened.sessions().unwrap().len(), 1);
+        }
+
+        #[test]
+        fn enabling_encryption_seals_existing_data_and_snapshots() {
+            let path = store_in("enable");
+            let mut store = ParquetStore::open(&path).unwrap();
+            store.upsert(&[reading(0, 100.0)]).unwrap();
+            let dir = path.with_file_name(SNAPSHOT_DIR);
+            let none = Attachments::default();
+            let archive = snapshot_rotating(&store, &dir, "manual", &none, None).unwrap();
+            assert_eq!(ParquetStore::encryption_state_at(&path), EncryptionState::Plaintext);
+
+            store.enable_encryption_with("pw", TEST_KDF).unwrap();
+            assert_no_plaintext(path.parent().unwrap());
+
+            // The snapshot now needs the store's key to restore.
+            let mut target = MemoryStore::new();
+            let unsealed = restore(&archive, &mut target, None, None);
+            assert!(matches!(unsealed, Err(StoreError::Encrypted)));
+            restore(&archive, &mut target, None, store.sealer()).unwrap();
+            assert_eq!(target.len(), 1);
+        }
+
+        #[test]
+        fn interrupted_encryption_is_finished_on_open() {
+            let path = store_in("interrupted");
+            ParquetStore::open(&path).unwrap().upsert(&[reading(0, 100.0)]).unwrap();
+            // Crash after the key file was written, before any table was resealed.
+            encryption::create_with(&key_path_for(&path), "pw", TEST_KDF).unwrap();
+
+            let store = ParquetStore::open_encrypted(&path, "pw").unwrap();
+            assert_eq!(store.len(), 1);
+            assert_no_plaintext(path.parent().unwrap());
+        }
+
+        #[test]
+        fn rotation_reseals_everything_under_a_new_key() {
+            let path = store_in("rotate");
+            let mut store = encrypted_store(&path, "pw");
+            store.upsert(&[reading(0, 100.0)]).unwrap();
+            let dir = path.with_file_name(SNAPSHOT_DIR);
+            let none = Attachments::default();
+            let archive = snapshot_rotating(&store, &dir, "manual", &none, store.sealer()).unwrap();
+            let before = std::fs::read(&path).unwrap();
+
+            store.rotate_key("pw").unwrap();
+            assert!(!store.cipher.as_ref().unwrap().is_rotating());
+            assert_ne!(std::fs::read(&path).unwrap()[..12], before[..12]); // new key id
+            assert_no_plaintext(path.parent().unwrap());
+
+            // Only the new key survives, and it reads the tables and the snapshot.
+            let reopened = ParquetStore::open_encrypted(&path, "pw").unwrap();
+            assert_eq!(reopened.len(), 1);
+            let mut target = MemoryStore::new();
+            restore(&archive, &mut target, None, reopened.sealer()).unwrap();
+            assert_eq!(target.len(), 1);
+        }
+
+        #[test]
+        fn passphrase_change_leaves_data_in_place() {
+            let path = store_in("passphrase");
+            let mut store = encrypted_store(&path, "old");
+            store.upsert(&[reading(0, 100.0)]).unwrap();
+            let sealed = std::fs::read(&path).unwrap();
+
+            store.change_passphrase("old", "new").unwrap();
+            assert_eq!(std::fs::read(&path).unwrap(), sealed);
+            let stale = ParquetStore::open_encrypted(&path, "old");
+            assert!(matches!(stale, Err(StoreError::WrongPassphrase)));
+            assert_eq!(ParquetStore::open_encrypted(&path, "new").unwrap().len(), 1);
+        }
+    }
 }
@@ -0,0 +1,315 @@
+//! Provenance: which sync or import wrote each observation, and undoing one.
+//!
+//! Every reader sync and file import is an [`IngestBatch`] — when it ran, what
+//! kind of ingest it was, the app version, and the device firmware or the
+//! imported file's SHA-256 — recorded in the store's batches table. The rows
+//! it writes carry its id in [`Observation::batch_id`]. Re-writing a row
+//! unchanged keeps its old batch, so a sync that re-reads the reader's whole
+//! history only claims the readings it actually added or changed.
+//!
+//! [`rollback`] deletes a bad batch's rows and marks the batch rolled back.
+//! Rows the batch *overwrote* are gone with it — restore a snapshot to get
+//! those back.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use sha2::{Digest, Sha256};
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::error::Result;
+use crate::observation::{Observation, Query};
+use crate::store::Store;
+
+/// A reader sync over USB.
+pub const READER_SYNC: &str = "reader_sync";
+/// A LibreView CSV export.
+pub const LIBREVIEW_CSV: &str = "libreview_csv";
+/// A Dexcom Clarity CSV export.
+pub const DEXCOM_CSV: &str = "dexcom_csv";
+/// A Nightscout `entries.json` export.
+pub const NIGHTSCOUT_JSON: &str = "nightscout_json";
+/// An Apple Health `export.xml`.
+pub const APPLE_HEALTH: &str = "apple_health";
+/// A CSV/JSON file read through an import mapping.
+pub const MAPPED_FILE: &str = "mapped_file";
+
+/// One sync or import run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestBatch {
+    pub id: String,
+    /// When the batch ran (UTC).
+    pub created_at: PrimitiveDateTime,
+    /// What produced it ([`READER_SYNC`], [`APPLE_HEALTH`], …).
+    pub kind: String,
+    /// Name and version of the app that wrote it.
+    pub app_version: String,
+    /// The reader's firmware, for syncs.
+    pub device_firmware: Option<String>,
+    /// SHA-256 of the imported file, for file imports.
+    pub file_hash: Option<String>,
+    /// A human hint: the file name, or the reader's serial.
+    pub detail: Option<String>,
+    /// When [`rollback`] removed its rows (UTC).
+    pub rolled_back_at: Option<PrimitiveDateTime>,
+}
+
+impl IngestBatch {
+    /// A batch starting now, with an id unique to its kind and microsecond.
+    pub fn new(kind: &str, app_version: impl Into<String>) -> Self {
+        let now = now_utc();
+        let stamp = now
+            .format(format_description!(
+                "[year][month][day]T[hour][minute][second].[subsecond digits:6]Z"
+            ))
+            .unwrap_or_else(|_| "19700101T000000.000000Z".into());
+        Self {
+            id: format!("{kind}-{stamp}"),
+            created_at: now,
+            kind: kind.to_string(),
+            app_version: app_version.into(),
+            device_firmware: None,
+            file_hash: None,
+            detail: None,
+            rolled_back_at: None,
+        }
+    }
+
+    /// Record `bytes` as the imported file's hash.
+    pub fn with_file(mut self, name: impl Into<String>, bytes: &[u8]) -> Self {
+        self.file_hash = Some(sha256_hex(bytes));
+        self.detail = Some(name.into());
+        self
+    }
+
+    /// Tag `observations` as written by this batch.
+    pub fn stamp(&self, observations: &mut [Observation]) {
+        for o in observations {
+            o.batch_id = Some(self.id.clone());
+        }
+    }
+}
+
+/// A recorded batch and the rows that still carry its id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchSummary {
+    pub batch: IngestBatch,
+    pub rows: usize,
+}
+
+/// Write `observations` as one batch: stamp them, upsert, then record the
+/// batch — unless every row was already stored unchanged, so an idle re-sync
+/// leaves no trace. Returns the number of *new* rows, as [`Store::upsert`].
+pub fn ingest(
+    store: &mut dyn Store,
+    batch: &IngestBatch,
+    mut observations: Vec<Observation>,
+) -> Result<usize> {
+    batch.stamp(&mut observations);
+    let added = store.upsert(&observations)?;
+    let claimed = match span(&observations) {
+        Some(query) => store
+            .query(&query)?
+            .iter()
+            .any(|o| o.batch_id.as_deref() == Some(batch.id.as_str())),
+        None => false,
+    };
+    if claimed {
+        store.upsert_batches(std::slice::from_ref(batch))?;
+    }
+    Ok(added)
+}
+
+/// Re-record `batch` — say, with a file hash known only once the whole file
+/// has been read — if [`ingest`] recorded it.
+pub fn amend(store: &mut dyn Store, batch: &IngestBatch) -> Result<()> {
+    if store.batches()?.iter().any(|b| b.id == batch.id) {
+        store.upsert_batches(std::slice::from_ref(batch))?;
+    }
+    Ok(())
+}
+
+/// Every recorded batch with its live row count, newest first.
+pub fn history(store: &dyn Store) -> Result<Vec<BatchSummary>> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for o in store.query(&Query::default())? {
+        if let Some(id) = o.batch_id {
+            *counts.entry(id).or_default() += 1;
+        }
+    }
+    let mut out: Vec<BatchSummary> = store
+        .batches()?
+        .into_iter()
+        .map(|batch| BatchSummary {
+            rows: counts.get(&batch.id).copied().unwrap_or(0),
+            batch,
+        })
+        .collect();
+    out.reverse();
+    Ok(out)
+}
+
+/// Delete every row batch `id` wrote, and mark the batch rolled back.
+/// Returns how many rows were removed.
+pub fn rollback(store: &mut dyn Store, id: &str) -> Result<usize> {
+    let rows = store.query(&Query::batch(id))?;
+    let removed = store.delete(&rows)?;
+    if let Some(mut batch) = store.batches()?.into_iter().find(|b| b.id == id) {
+        batch.rolled_back_at = Some(now_utc());
+        store.upsert_batches(&[batch])?;
+    }
+    Ok(removed)
+}
+
+/// Lowercase hex SHA-256, as recorded in [`IngestBatch::file_hash`].
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+/// Hashes what's read through it, so a file too large to hold in memory can
+/// still be recorded in [`IngestBatch::file_hash`].
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R> HashingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Lowercase hex SHA-256 of everything read so far.
+    pub fn finish(self) -> String {
+        hex(&self.hasher.finalize())
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// The current UTC time at the microsecond precision every store keeps.
+fn now_utc() -> PrimitiveDateTime {
+    let now = OffsetDateTime::now_utc();
+    let now = now
+        .replace_nanosecond(now.microsecond() * 1_000)
+        .unwrap_or(now);
+    PrimitiveDateTime::new(now.date(), now.time())
+}
+
+/// The query covering every row of `observations`.
+fn span(observations: &[Observation]) -> Option<Query> {
+    let first = observations.first()?.timestamp;
+    let (since, until) = observations.iter().fold((first, first), |(lo, hi), o| {
+        (lo.min(o.timestamp), hi.max(o.timestamp))
+    });
+    Some(Query {
+        stream: None,
+        since: Some(since),
+        until: Some(until),
+        batch_id: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+    use time::macros::datetime;
+
+    fn reading(t: PrimitiveDateTime, value: f64) -> Observation {
+        Observation::new("glucose.mg_dl", t, value, "mg/dL", "dev")
+    }
+
+    #[test]
+    fn rollback_removes_only_what_the_batch_changed() {
+        let mut store = MemoryStore::new();
+        let a = reading(datetime!(2026-06-19 08:00), 100.0);
+        let b = reading(datetime!(2026-06-19 08:15), 110.0);
+        let first = IngestBatch::new(READER_SYNC, "test");
+        assert_eq!(
+            ingest(&mut store, &first, vec![a.clone(), b.clone()]).unwrap(),
+            2
+        );
+
+        // A re-sync re-reads `a` unchanged and adds `c`; a bad import then
+        // overwrites `b`.
+        let c = reading(datetime!(2026-06-19 08:30), 120.0);
+        let second = IngestBatch {
+            id: "sync-2".into(),
+            ..IngestBatch::new(READER_SYNC, "test")
+        };
+        assert_eq!(ingest(&mut store, &second, vec![a, c]).unwrap(), 1);
+        let bad = IngestBatch::new(MAPPED_FILE, "test").with_file("bad.csv", b"oops");
+        let mut wrong = b;
+        wrong.value = 400.0;
+        ingest(&mut store, &bad, vec![wrong]).unwrap();
+
+        let history = history(&store).unwrap();
+        let rows: Vec<(&str, usize)> = history
+            .iter()
+            .map(|s| (s.batch.kind.as_str(), s.rows))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![(MAPPED_FILE, 1), (READER_SYNC, 1), (READER_SYNC, 1)]
+        );
+        assert_eq!(
+            history[0].batch.file_hash.as_deref(),
+            Some(sha256_hex(b"oops").as_str())
+        );
+
+        assert_eq!(rollback(&mut store, &bad.id).unwrap(), 1);
+        let values: Vec<f64> = store
+            .query(&Query::default())
+            .unwrap()
+            .iter()
+            .map(|o| o.value)
+            .collect();
+        assert_eq!(values, vec![100.0, 120.0]);
+        let undone = store
+            .batches()
+            .unwrap()
+            .into_iter()
+            .find(|b| b.id == bad.id)
+            .unwrap();
+        assert!(undone.rolled_back_at.is_some());
+    }
+
+    #[test]
+    fn hashing_reader_matches_the_whole_file_hash() {
+        let mut reader = HashingReader::new(&b"<HealthData/>"[..]);
+        std::io::copy(&mut reader, &mut std::io::sink()).unwrap();
+        assert_eq!(reader.finish(), sha256_hex(b"<HealthData/>"));
+    }
+
+    #[test]
+    fn an_idle_resync_records_no_batch() {
+        let mut store = MemoryStore::new();
+        let rows = vec![reading(datetime!(2026-06-19 08:00), 100.0)];
+        ingest(
+            &mut store,
+            &IngestBatch::new(READER_SYNC, "test"),
+            rows.clone(),
+        )
+        .unwrap();
+        let again = IngestBatch {
+            id: "sync-2".into(),
+            ..IngestBatch::new(READER_SYNC, "test")
+        };
+        assert_eq!(ingest(&mut store, &again, rows).unwrap(), 0);
+        assert_eq!(store.batches().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,273 @@
+//! Parquet schema versioning and forward migrations (behind `parquet-store`).
+//!
+//! Every file the [`ParquetStore`](crate::ParquetStore) writes carries its schema
+//! version in the Parquet key-value metadata under [`VERSION_KEY`]. On open, a
+//! file at an older version is decoded into Arrow batches, stepped forward
+//! through [`MIGRATIONS`] one version at a time, and rewritten at
+//! [`SCHEMA_VERSION`]. A file from a *newer* build is refused rather than
+//! mis-decoded.
+//!
+//! Files written before versioning existed carry no stamp; they use the v1
+//! layout, so they read as [`UNVERSIONED`].
+//!
+//! History: v2 added the observations' `batch_id` column and the batches
+//! table (see [`crate::provenance`]). The trials table (see [`crate::trial`])
+//! arrived later at v2 without a bump: a store without its file reads as
+//! empty, and an older build simply never opens it. v3 added the sessions'
+//! pause columns (`qc_pause_events`, `qc_paused_ms`).
+
+use std::sync::Arc;
+
+use arrow_array::{Array, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::file::metadata::KeyValue;
+
+use crate::error::{Result, StoreError};
+
+/// Parquet key-value metadata key holding the schema version.
+pub const VERSION_KEY: &str = "looplace.schema_version";
+
+/// The schema version this build reads and writes.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Version assumed for files without a [`VERSION_KEY`] stamp (pre-versioning).
+pub const UNVERSIONED: u32 = 1;
+
+/// The tables a store persists; each has its own file and its own migrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Table {
+    Observations,
+    Sessions,
+    /// Ingest batches; new in v2, so never migrated from v1.
+    Batches,
+    /// Per-trial cognition data; new at v2, so never migrated from v1.
+    Trials,
+}
+
+/// One forward step: upgrades `table` batches written at `from` to `from + 1`.
+///
+/// Steps must be total over well-formed input (e.g. add a column with a
+/// default, rename, re-encode) — they run unattended on a user's whole history.
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaMigration {
+    pub from: u32,
+    pub table: Table,
+    pub description: &'static str,
+    pub apply: fn(RecordBatch) -> Result<RecordBatch>,
+}
+
+/// The registry of store-level migrations, in ascending `from` order. A version
+/// bump that leaves a table's layout unchanged simply has no entry for it.
+pub const MIGRATIONS: &[SchemaMigration] = &[
+    SchemaMigration {
+        from: 1,
+        table: Table::Observations,
+        description: "add batch_id (provenance)",
+        apply: add_batch_id,
+    },
+    SchemaMigration {
+        from: 2,
+        table: Table::Sessions,
+        description: "add qc_pause_events and qc_paused_ms",
+        apply: add_pause_columns,
+    },
+];
+
+/// v1 → v2: rows written before provenance have no batch.
+fn add_batch_id(batch: RecordBatch) -> Result<RecordBatch> {
+    add_null_column(batch, "batch_id")
+}
+
+/// v2 → v3: runs recorded before pausing existed were never paused.
+fn add_pause_columns(batch: RecordBatch) -> Result<RecordBatch> {
+    let batch = add_zero_column(batch, "qc_pause_events")?;
+    add_zero_column(batch, "qc_paused_ms")
+}
+
+/// Append a non-null integer column of zeros.
+fn add_zero_column(batch: RecordBatch, name: &str) -> Result<RecordBatch> {
+    let mut fields: Vec<Field> = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.as_ref().clone())
+        .collect();
+    fields.push(Field::new(name, DataType::Int64, false));
+    let mut columns = batch.columns().to_vec();
+    columns.push(Arc::new(Int64Array::from(vec![0i64; batch.num_rows()])) as Arc<dyn Array>);
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+        .map_err(|e| StoreError::Backend(e.to_string()))
+}
+
+/// Append a nullable string column of nulls.
+fn add_null_column(batch: RecordBatch, name: &str) -> Result<RecordBatch> {
+    let mut fields: Vec<Field> = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.as_ref().clone())
+        .collect();
+    fields.push(Field::new(name, DataType::Utf8, true));
+    let mut columns = batch.columns().to_vec();
+    columns.push(Arc::new(StringArray::new_null(batch.num_rows())) as Arc<dyn Array>);
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+        .map_err(|e| StoreError::Backend(e.to_string()))
+}
+
+/// The key-value metadata stamped into every written file.
+pub fn version_metadata() -> Vec<KeyValue> {
+    vec![KeyValue::new(
+        VERSION_KEY.to_string(),
+        SCHEMA_VERSION.to_string(),
+    )]
+}
+
+/// The schema version recorded in a file's key-value metadata
+/// ([`UNVERSIONED`] if absent).
+pub fn file_version(metadata: Option<&Vec<KeyValue>>) -> Result<u32> {
+    let Some(entry) = metadata.and_then(|kv| kv.iter().find(|kv| kv.key == VERSION_KEY)) else {
+        return Ok(UNVERSIONED);
+    };
+    let raw = entry.value.as_deref().unwrap_or_default();
+    let version = raw
+        .trim()
+        .parse()
+        .map_err(|_| StoreError::Parse(format!("invalid {VERSION_KEY}: {raw:?}")))?;
+    if version > SCHEMA_VERSION {
+        return Err(StoreError::UnsupportedSchema {
+            found: version,
+            supported: SCHEMA_VERSION,
+        });
+    }
+    Ok(version)
+}
+
+/// Step a batch written at `from` up to [`SCHEMA_VERSION`] via [`MIGRATIONS`].
+pub fn upgrade(table: Table, from: u32, batch: RecordBatch) -> Result<RecordBatch> {
+    upgrade_with(MIGRATIONS, table, from, SCHEMA_VERSION, batch)
+}
+
+/// Registry-agnostic core of [`upgrade`], so the stepping logic is testable
+/// against synthetic registries.
+fn upgrade_with(
+    migrations: &[SchemaMigration],
+    table: Table,
+    from: u32,
+    to: u32,
+    mut batch: RecordBatch,
+) -> Result<RecordBatch> {
+    for version in from..to {
+        for step in migrations
+            .iter()
+            .filter(|m| m.table == table && m.from == version)
+        {
+            batch = (step.apply)(batch)?;
+        }
+    }
+    Ok(batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_array::Float64Array;
+
+    fn v1_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "value",
+            DataType::Float64,
+            false,
+        )]));
+        RecordBatch::try_new(schema, vec![Arc::new(Float64Array::from(vec![1.0, 2.0]))]).unwrap()
+    }
+
+    /// A v1 → v2 step that adds a nullable `quality` column.
+    fn add_quality(batch: RecordBatch) -> Result<RecordBatch> {
+        add_null_column(batch, "quality")
+    }
+
+    const REGISTRY: &[SchemaMigration] = &[SchemaMigration {
+        from: 1,
+        table: Table::Observations,
+        description: "add quality flags",
+        apply: add_quality,
+    }];
+
+    #[test]
+    fn missing_stamp_reads_as_unversioned() {
+        assert_eq!(file_version(None).unwrap(), UNVERSIONED);
+        let other = vec![KeyValue::new("ARROW:schema".to_string(), "…".to_string())];
+        assert_eq!(file_version(Some(&other)).unwrap(), UNVERSIONED);
+    }
+
+    #[test]
+    fn stamp_round_trips() {
+        assert_eq!(
+            file_version(Some(&version_metadata())).unwrap(),
+            SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn newer_or_garbled_stamp_is_refused() {
+        let newer = vec![KeyValue::new(
+            VERSION_KEY.to_string(),
+            (SCHEMA_VERSION + 1).to_string(),
+        )];
+        assert!(matches!(
+            file_version(Some(&newer)),
+            Err(StoreError::UnsupportedSchema { .. })
+        ));
+        let garbled = vec![KeyValue::new(VERSION_KEY.to_string(), "two".to_string())];
+        assert!(matches!(
+            file_version(Some(&garbled)),
+            Err(StoreError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn upgrade_applies_matching_steps_only() {
+        let upgraded = upgrade_with(REGISTRY, Table::Observations, 1, 2, v1_batch()).unwrap();
+        let quality = upgraded.column_by_name("quality").expect("column added");
+        assert_eq!(quality.null_count(), 2);
+
+        // Other tables, and files already at the target, are left untouched.
+        let sessions = upgrade_with(REGISTRY, Table::Sessions, 1, 2, v1_batch()).unwrap();
+        assert!(sessions.column_by_name("quality").is_none());
+        let current = upgrade_with(REGISTRY, Table::Observations, 2, 2, v1_batch()).unwrap();
+        assert!(current.column_by_name("quality").is_none());
+    }
+
+    #[test]
+    fn v2_sessions_gain_zeroed_pause_columns() {
+        let upgraded = upgrade(Table::Sessions, 2, v1_batch()).unwrap();
+        for name in ["qc_pause_events", "qc_paused_ms"] {
+            let column = upgraded
+                .column_by_name(name)
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .expect("column added");
+            assert_eq!(column.values().to_vec(), vec![0, 0]);
+        }
+        let rows = upgrade(Table::Observations, 2, v1_batch()).unwrap();
+        assert!(rows.column_by_name("qc_pause_events").is_none());
+    }
+
+    #[test]
+    fn registry_is_ordered_and_within_range() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(
+                pair[0].from <= pair[1].from,
+                "{} out of order",
+                pair[1].description
+            );
+        }
+        for step in MIGRATIONS {
+            assert!(
+                (UNVERSIONED..SCHEMA_VERSION).contains(&step.from),
+                "{}",
+                step.description
+            );
+        }
+    }
+}
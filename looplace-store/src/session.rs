@@ -7,6 +7,10 @@
 use serde_json::Value;
 use time::PrimitiveDateTime;
 
+use crate::error::Result;
+use crate::observation::Query;
+use crate::store::Store;
+
 /// One cognition test session, preserved in full.
 #[derive(Debug, Clone, PartialEq)]
 pub struct SessionRecord {
@@ -22,5 +26,54 @@ pub struct SessionRecord {
     pub qc_min_trials_met: bool,
     pub qc_device_platform: String,
     pub qc_device_user_agent: Option<String>,
+    /// Times the run was paused, and how long it stayed paused in total.
+    pub qc_pause_events: i64,
+    pub qc_paused_ms: i64,
     pub notes: Option<String>,
 }
+
+/// Remove a session completely: its record, its trials, and the observations
+/// flattened from it (matched by `session_id`). Returns whether there was a
+/// record.
+pub fn delete(store: &mut dyn Store, id: &str) -> Result<bool> {
+    let rows: Vec<_> = store
+        .query(&Query::default())?
+        .into_iter()
+        .filter(|o| o.session_id.as_deref() == Some(id))
+        .collect();
+    store.delete(&rows)?;
+    store.delete_trials(&[id.to_string()])?;
+    Ok(store.delete_sessions(&[id.to_string()])? > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::{summaries_from_json, summary_to_observations, summary_to_session};
+    use crate::store::MemoryStore;
+
+    #[test]
+    fn delete_removes_the_record_and_its_metrics() {
+        let json = r#"[
+            {"id": "a", "task": "pvt", "created_at": "2026-06-19T08:00:00Z",
+             "metrics": {"median_rt_ms": 300.0}},
+            {"id": "b", "task": "pvt", "created_at": "2026-06-19T09:00:00Z",
+             "metrics": {"median_rt_ms": 320.0}}
+        ]"#;
+        let parsed = summaries_from_json(json).unwrap();
+        let mut store = MemoryStore::new();
+        for summary in &parsed.summaries {
+            store.upsert_sessions(&[summary_to_session(summary).unwrap()]).unwrap();
+            store.upsert(&summary_to_observations(summary)).unwrap();
+        }
+
+        assert!(delete(&mut store, "a").unwrap());
+        assert!(!delete(&mut store, "a").unwrap());
+        let sessions = store.sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, "b");
+        let rows = store.query(&Query::default()).unwrap();
+        assert!(rows.iter().all(|o| o.session_id.as_deref() == Some("b")));
+        assert_eq!(rows.len(), 1);
+    }
+}
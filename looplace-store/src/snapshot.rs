@@ -0,0 +1,944 @@
+//! Store-level snapshots: a consistent, self-describing, checksummed copy of
+//! every table plus the app's small preference files, and a validated restore.
+//!
+//! A snapshot is a plain directory, readable without Looplace:
+//!
+//! ```text
+//! 20261018T120000.000000Z-pre-migrate/
+//!   manifest.json        format, reason, row counts, per-file size + SHA-256
+//!   observations.jsonl   one Observation per line
+//!   sessions.jsonl       one SessionRecord per line
+//!   batches.jsonl        one IngestBatch per line
+//!   trials.jsonl         one TrialRecord per line
+//!   files/…              prefs (restorable) and raw store files (for manual recovery)
+//! ```
+//!
+//! Tables are written as JSON Lines so a snapshot restores into *any*
+//! backend. Timestamps keep microsecond precision — the same as the file
+//! backends. [`snapshot_rotating`] is what migrations call first, keeping the
+//! newest [`SNAPSHOT_KEEP`] snapshots per directory.
+//!
+//! Snapshots of an encrypted store are taken with its [`Sealer`]: every file
+//! under the snapshot is then sealed (the manifest stays readable, so
+//! [`verify`] works without the passphrase) and [`restore`] needs the same
+//! sealer back.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use crate::error::{Result, StoreError};
+use crate::observation::{Observation, Query};
+use crate::provenance::IngestBatch;
+use crate::session::SessionRecord;
+use crate::store::Store;
+use crate::trial::TrialRecord;
+
+/// Snapshot layout version; [`verify`] refuses anything newer. Format 2 added
+/// the ingest batches table, format 3 the trials table, format 4 the sessions'
+/// pause counts.
+pub const SNAPSHOT_FORMAT: u32 = 4;
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// Conventional snapshot directory name, beside the store in the data dir.
+pub const SNAPSHOT_DIR: &str = "snapshots";
+/// Snapshots kept per directory by [`snapshot_rotating`].
+pub const SNAPSHOT_KEEP: usize = 5;
+
+const OBSERVATIONS_FILE: &str = "observations.jsonl";
+const SESSIONS_FILE: &str = "sessions.jsonl";
+const BATCHES_FILE: &str = "batches.jsonl";
+const TRIALS_FILE: &str = "trials.jsonl";
+const FILES_DIR: &str = "files";
+
+const TIMESTAMP_FORMAT: &[time::format_description::FormatItem<'static>] =
+    format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:6]");
+
+/// The self-description written alongside every snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    /// When the snapshot was taken (RFC3339, UTC).
+    pub created_at: String,
+    /// Why it was taken (`manual`, `pre-migrate`, `pre-schema-upgrade`, …).
+    pub reason: String,
+    /// Crate name and version that wrote it.
+    pub generator: String,
+    pub observations: usize,
+    pub sessions: usize,
+    /// Ingest batches; absent from format 1 snapshots.
+    #[serde(default)]
+    pub batches: usize,
+    /// Per-trial rows; absent before format 3.
+    #[serde(default)]
+    pub trials: usize,
+    /// Every listed file is sealed; restoring needs the store's [`Sealer`].
+    #[serde(default)]
+    pub sealed: bool,
+    pub files: Vec<ManifestFile>,
+}
+
+/// One file in a snapshot, with the checksum [`verify`] holds it to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// Path relative to the snapshot directory.
+    pub path: String,
+    pub kind: FileKind,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileKind {
+    Observations,
+    Sessions,
+    Batches,
+    Trials,
+    /// A small app preference file; [`restore`] copies it back.
+    Pref,
+    /// A raw store file captured before an in-place rewrite. Kept for manual
+    /// recovery only — the tables above already hold its data.
+    Raw,
+}
+
+/// Extra files to capture alongside the tables.
+#[derive(Debug, Clone, Default)]
+pub struct Attachments {
+    pub prefs: Vec<PathBuf>,
+    pub raw: Vec<PathBuf>,
+}
+
+/// Authenticated encryption for snapshot files, so an encrypted store never
+/// leaves plaintext copies behind. `role` names the file being sealed and must
+/// match on open.
+pub trait Sealer {
+    fn seal(&self, role: &str, plaintext: &[u8]) -> Result<Vec<u8>>;
+    fn open(&self, role: &str, sealed: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// What [`restore`] put back.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreReport {
+    pub observations_inserted: usize,
+    pub sessions_inserted: usize,
+    pub batches_inserted: usize,
+    pub trials_inserted: usize,
+    pub prefs_restored: usize,
+}
+
+/// Write a snapshot of `store` (plus any `attachments` that exist) to `dest`,
+/// which must not already exist. Written to a sibling `.partial` directory and
+/// renamed into place, so `dest` is either complete or absent. With a
+/// `sealer`, every file is written sealed.
+pub fn snapshot(
+    store: &dyn Store,
+    dest: &Path,
+    reason: &str,
+    attachments: &Attachments,
+    sealer: Option<&dyn Sealer>,
+) -> Result<Manifest> {
+    if dest.exists() {
+        return Err(StoreError::Io(format!("snapshot already exists: {}", dest.display())));
+    }
+    let partial = partial_path(dest);
+    if partial.exists() {
+        fs::remove_dir_all(&partial)?;
+    }
+    fs::create_dir_all(partial.join(FILES_DIR))?;
+
+    // One read of each table: the in-memory mirror is a consistent point in time.
+    let observations = store.query(&Query::default())?;
+    let sessions = store.sessions()?;
+    let batches = store.batches()?;
+    let trials = store.trials()?;
+
+    let mut files = vec![
+        write_jsonl(
+            &partial,
+            OBSERVATIONS_FILE,
+            FileKind::Observations,
+            observations.iter().map(ObservationRow::from),
+            sealer,
+        )?,
+        write_jsonl(
+            &partial,
+            SESSIONS_FILE,
+            FileKind::Sessions,
+            sessions.iter().map(SessionRow::from),
+            sealer,
+        )?,
+        write_jsonl(
+            &partial,
+            BATCHES_FILE,
+            FileKind::Batches,
+            batches.iter().map(BatchRow::from),
+            sealer,
+        )?,
+        write_jsonl(
+            &partial,
+            TRIALS_FILE,
+            FileKind::Trials,
+            trials.iter().map(TrialRow::from),
+            sealer,
+        )?,
+    ];
+    let attached = attachments
+        .prefs
+        .iter()
+        .map(|p| (p, FileKind::Pref))
+        .chain(attachments.raw.iter().map(|p| (p, FileKind::Raw)));
+    for (source, kind) in attached {
+        if !source.exists() {
+            continue;
+        }
+        let name = source
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| StoreError::Io(format!("unnamed attachment: {}", source.display())))?;
+        let rel = format!("{FILES_DIR}/{name}");
+        match sealer {
+            Some(sealer) => fs::write(partial.join(&rel), sealer.seal(&rel, &fs::read(source)?)?)?,
+            None => {
+                fs::copy(source, partial.join(&rel))?;
+            }
+        }
+        files.push(describe(&partial, rel, kind)?);
+    }
+
+    let manifest = Manifest {
+        format: SNAPSHOT_FORMAT,
+        created_at: OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "1970-01-01T00:00:00Z".into()),
+        reason: reason.to_string(),
+        generator: concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")).to_string(),
+        observations: observations.len(),
+        sessions: sessions.len(),
+        batches: batches.len(),
+        trials: trials.len(),
+        sealed: sealer.is_some(),
+        files,
+    };
+    fs::write(partial.join(MANIFEST_FILE), serde_json::to_vec_pretty(&manifest)?)?;
+    fs::rename(&partial, dest)?;
+    Ok(manifest)
+}
+
+/// Take a snapshot into a new timestamped subdirectory of `dir`, then prune
+/// all but the newest [`SNAPSHOT_KEEP`]. Returns the new snapshot's path.
+pub fn snapshot_rotating(
+    store: &dyn Store,
+    dir: &Path,
+    reason: &str,
+    attachments: &Attachments,
+    sealer: Option<&dyn Sealer>,
+) -> Result<PathBuf> {
+    // Microsecond stamps keep names unique and sorting in creation order.
+    let stamp = OffsetDateTime::now_utc()
+        .format(format_description!(
+            "[year][month][day]T[hour][minute][second].[subsecond digits:6]Z"
+        ))
+        .unwrap_or_else(|_| "19700101T000000.000000Z".into());
+    let slug: String = reason
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let dest = dir.join(format!("{stamp}-{slug}"));
+    snapshot(store, &dest, reason, attachments, sealer)?;
+    prune(dir, SNAPSHOT_KEEP)?;
+    Ok(dest)
+}
+
+/// Every complete snapshot in `dir`, oldest first. A [`reseal`] a crash
+/// interrupted is settled first, so its archive is never missing.
+pub fn list(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut out = Vec::new();
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        let path = recover_reseal(path)?;
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let settled = !name.ends_with(".partial") && !name.ends_with(".old");
+        if settled && path.join(MANIFEST_FILE).is_file() {
+            out.push(path);
+        }
+    }
+    // Names lead with a sortable UTC stamp.
+    out.sort();
+    Ok(out)
+}
+
+/// If `path` is the `.old` copy [`reseal`] sets aside, settle it and return
+/// where the archive now is. With the resealed archive in place it is left
+/// over; without one (a crash between the two renames) it is the original,
+/// still complete, and goes back.
+fn recover_reseal(path: PathBuf) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let Some(archive) = name.strip_suffix(".old") else {
+        return Ok(path);
+    };
+    let archive = path.with_file_name(archive);
+    if archive.join(MANIFEST_FILE).is_file() {
+        fs::remove_dir_all(&path)?;
+        return Ok(path);
+    }
+    fs::rename(&path, &archive)?;
+    Ok(archive)
+}
+
+fn prune(dir: &Path, keep: usize) -> Result<()> {
+    let all = list(dir)?;
+    let excess = all.len().saturating_sub(keep);
+    for old in &all[..excess] {
+        fs::remove_dir_all(old)?;
+    }
+    Ok(())
+}
+
+/// Read a snapshot's manifest without checking the files it lists.
+pub fn manifest(archive: &Path) -> Result<Manifest> {
+    let raw = fs::read(archive.join(MANIFEST_FILE))?;
+    serde_json::from_slice(&raw)
+        .map_err(|e| StoreError::Parse(format!("snapshot manifest unreadable: {e}")))
+}
+
+/// Check a snapshot is complete and intact: a supported format, no paths
+/// escaping the directory, every file present with its recorded size and
+/// checksum, and table row counts matching the manifest.
+pub fn verify(archive: &Path) -> Result<Manifest> {
+    let manifest = manifest(archive)?;
+    if manifest.format > SNAPSHOT_FORMAT {
+        return Err(StoreError::UnsupportedSchema {
+            found: manifest.format,
+            supported: SNAPSHOT_FORMAT,
+        });
+    }
+    for kind in [FileKind::Observations, FileKind::Sessions] {
+        if !manifest.files.iter().any(|f| f.kind == kind) {
+            return Err(StoreError::Parse(format!("snapshot is missing its {kind:?} table")));
+        }
+    }
+    for file in &manifest.files {
+        let rel = Path::new(&file.path);
+        if rel.is_absolute() || rel.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+            return Err(StoreError::Parse(format!("snapshot path escapes archive: {}", file.path)));
+        }
+        let actual = describe(archive, file.path.clone(), file.kind)?;
+        if actual.bytes != file.bytes || actual.sha256 != file.sha256 {
+            return Err(StoreError::Parse(format!("snapshot checksum mismatch: {}", file.path)));
+        }
+    }
+    Ok(manifest)
+}
+
+/// Restore a snapshot into `store` (upserting, so restore into an empty store
+/// for an exact copy), and copy its prefs into `prefs_dir` if given. The whole
+/// archive is [`verify`]-ed and parsed (and, if sealed, authenticated) before
+/// anything is written.
+pub fn restore(
+    archive: &Path,
+    store: &mut dyn Store,
+    prefs_dir: Option<&Path>,
+    sealer: Option<&dyn Sealer>,
+) -> Result<RestoreReport> {
+    let manifest = verify(archive)?;
+    let sealer = match (manifest.sealed, sealer) {
+        (true, None) => return Err(StoreError::Encrypted),
+        (true, sealer) => sealer,
+        (false, _) => None,
+    };
+    let read = |file: &ManifestFile| -> Result<Vec<u8>> {
+        let bytes = fs::read(archive.join(&file.path))?;
+        match sealer {
+            Some(sealer) => sealer.open(&file.path, &bytes),
+            None => Ok(bytes),
+        }
+    };
+    let mut observations = Vec::new();
+    let mut sessions = Vec::new();
+    let mut batches = Vec::new();
+    let mut trials = Vec::new();
+    let mut prefs = Vec::new();
+    for file in &manifest.files {
+        match file.kind {
+            FileKind::Observations => {
+                for row in parse_jsonl::<ObservationRow>(&read(file)?)? {
+                    observations.push(row.into_observation()?);
+                }
+            }
+            FileKind::Sessions => {
+                for row in parse_jsonl::<SessionRow>(&read(file)?)? {
+                    sessions.push(row.into_session()?);
+                }
+            }
+            FileKind::Batches => {
+                for row in parse_jsonl::<BatchRow>(&read(file)?)? {
+                    batches.push(row.into_batch()?);
+                }
+            }
+            FileKind::Trials => {
+                for row in parse_jsonl::<TrialRow>(&read(file)?)? {
+                    trials.push(row.into());
+                }
+            }
+            FileKind::Pref if prefs_dir.is_some() => prefs.push((file, read(file)?)),
+            FileKind::Pref | FileKind::Raw => {}
+        }
+    }
+    if observations.len() != manifest.observations
+        || sessions.len() != manifest.sessions
+        || batches.len() != manifest.batches
+        || trials.len() != manifest.trials
+    {
+        return Err(StoreError::Parse(format!(
+            "snapshot row counts disagree with manifest \
+             ({} observations, {} sessions, {} batches, {} trials)",
+            observations.len(),
+            sessions.len(),
+            batches.len(),
+            trials.len()
+        )));
+    }
+
+    let mut report = RestoreReport {
+        sessions_inserted: store.upsert_sessions(&sessions)?,
+        batches_inserted: store.upsert_batches(&batches)?,
+        trials_inserted: store.upsert_trials(&trials)?,
+        observations_inserted: store.upsert(&observations)?,
+        prefs_restored: 0,
+    };
+    if let Some(prefs_dir) = prefs_dir {
+        fs::create_dir_all(prefs_dir)?;
+        for (file, bytes) in prefs {
+            if let Some(name) = Path::new(&file.path).file_name() {
+                fs::write(prefs_dir.join(name), bytes)?;
+                report.prefs_restored += 1;
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Re-seal every file of a snapshot with `to`, opening already-sealed files
+/// with `from` — used when a store turns encryption on (`from` = `None`) and
+/// when it rotates its data key (one cipher holding both keys is both). The
+/// archive is verified first, and the resealed copy is built beside it and
+/// swapped in, so a crash leaves one complete version or the other — which
+/// [`list`] finds, moving a set-aside original back if need be.
+pub fn reseal(archive: &Path, from: Option<&dyn Sealer>, to: &dyn Sealer) -> Result<Manifest> {
+    let mut manifest = verify(archive)?;
+    if manifest.sealed && from.is_none() {
+        return Err(StoreError::Encrypted);
+    }
+    let staging = partial_path(archive);
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(staging.join(FILES_DIR))?;
+
+    let mut files = Vec::with_capacity(manifest.files.len());
+    for file in &manifest.files {
+        let mut bytes = fs::read(archive.join(&file.path))?;
+        if let (true, Some(from)) = (manifest.sealed, from) {
+            bytes = from.open(&file.path, &bytes)?;
+        }
+        fs::write(staging.join(&file.path), to.seal(&file.path, &bytes)?)?;
+        files.push(describe(&staging, file.path.clone(), file.kind)?);
+    }
+    manifest.sealed = true;
+    manifest.files = files;
+    fs::write(staging.join(MANIFEST_FILE), serde_json::to_vec_pretty(&manifest)?)?;
+
+    let old = suffixed(archive, "old");
+    fs::rename(archive, &old)?;
+    fs::rename(&staging, archive)?;
+    fs::remove_dir_all(&old)?;
+    Ok(manifest)
+}
+
+fn partial_path(dest: &Path) -> PathBuf {
+    suffixed(dest, "partial")
+}
+
+fn suffixed(dest: &Path, suffix: &str) -> PathBuf {
+    let name = dest
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("snapshot");
+    dest.with_file_name(format!("{name}.{suffix}"))
+}
+
+fn write_jsonl<T: Serialize>(
+    dir: &Path,
+    rel: &str,
+    kind: FileKind,
+    rows: impl Iterator<Item = T>,
+    sealer: Option<&dyn Sealer>,
+) -> Result<ManifestFile> {
+    let mut out = Vec::new();
+    for row in rows {
+        serde_json::to_writer(&mut out, &row)?;
+        out.write_all(b"\n")?;
+    }
+    if let Some(sealer) = sealer {
+        out = sealer.seal(rel, &out)?;
+    }
+    fs::write(dir.join(rel), out)?;
+    describe(dir, rel.to_string(), kind)
+}
+
+fn parse_jsonl<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<Vec<T>> {
+    let mut out = Vec::new();
+    for line in bytes.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            out.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(out)
+}
+
+fn describe(dir: &Path, rel: String, kind: FileKind) -> Result<ManifestFile> {
+    let bytes = fs::read(dir.join(&rel))?;
+    let digest = Sha256::digest(&bytes);
+    Ok(ManifestFile {
+        path: rel,
+        kind,
+        bytes: bytes.len() as u64,
+        sha256: digest.iter().map(|b| format!("{b:02x}")).collect(),
+    })
+}
+
+pub(crate) fn format_ts(t: PrimitiveDateTime) -> String {
+    t.format(TIMESTAMP_FORMAT).unwrap_or_default()
+}
+
+fn parse_ts(s: &str) -> Result<PrimitiveDateTime> {
+    PrimitiveDateTime::parse(s, TIMESTAMP_FORMAT)
+        .map_err(|e| StoreError::Parse(format!("snapshot timestamp {s:?}: {e}")))
+}
+
+#[derive(Serialize, Deserialize)]
+struct ObservationRow {
+    stream: String,
+    timestamp: String,
+    value: f64,
+    unit: String,
+    source: String,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+    #[serde(default)]
+    batch_id: Option<String>,
+}
+
+impl From<&Observation> for ObservationRow {
+    fn from(o: &Observation) -> Self {
+        Self {
+            stream: o.stream.clone(),
+            timestamp: format_ts(o.timestamp),
+            value: o.value,
+            unit: o.unit.clone(),
+            source: o.source.clone(),
+            session_id: o.session_id.clone(),
+            tags: o.tags.clone(),
+            batch_id: o.batch_id.clone(),
+        }
+    }
+}
+
+impl ObservationRow {
+    fn into_observation(self) -> Result<Observation> {
+        Ok(Observation {
+            timestamp: parse_ts(&self.timestamp)?,
+            stream: self.stream,
+            value: self.value,
+            unit: self.unit,
+            source: self.source,
+            session_id: self.session_id,
+            tags: self.tags,
+            batch_id: self.batch_id,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SessionRow {
+    id: String,
+    task: String,
+    created_at: String,
+    client_platform: String,
+    client_tz: String,
+    metrics: serde_json::Value,
+    qc_visibility_blur_events: i64,
+    qc_focus_lost_events: i64,
+    qc_min_trials_met: bool,
+    qc_device_platform: String,
+    #[serde(default)]
+    qc_device_user_agent: Option<String>,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    qc_pause_events: i64,
+    #[serde(default)]
+    qc_paused_ms: i64,
+}
+
+impl From<&SessionRecord> for SessionRow {
+    fn from(s: &SessionRecord) -> Self {
+        Self {
+            id: s.id.clone(),
+            task: s.task.clone(),
+            created_at: format_ts(s.created_at),
+            client_platform: s.client_platform.clone(),
+            client_tz: s.client_tz.clone(),
+            metrics: s.metrics.clone(),
+            qc_visibility_blur_events: s.qc_visibility_blur_events,
+            qc_focus_lost_events: s.qc_focus_lost_events,
+            qc_min_trials_met: s.qc_min_trials_met,
+            qc_device_platform: s.qc_device_platform.clone(),
+            qc_device_user_agent: s.qc_device_user_agent.clone(),
+            notes: s.notes.clone(),
+            qc_pause_events: s.qc_pause_events,
+            qc_paused_ms: s.qc_paused_ms,
+        }
+    }
+}
+
+impl SessionRow {
+    fn into_session(self) -> Result<SessionRecord> {
+        Ok(SessionRecord {
+            created_at: parse_ts(&self.created_at)?,
+            id: self.id,
+            task: self.task,
+            client_platform: self.client_platform,
+            client_tz: self.client_tz,
+            metrics: self.metrics,
+            qc_visibility_blur_events: self.qc_visibility_blur_events,
+            qc_focus_lost_events: self.qc_focus_lost_events,
+            qc_min_trials_met: self.qc_min_trials_met,
+            qc_device_platform: self.qc_device_platform,
+            qc_device_user_agent: self.qc_device_user_agent,
+            qc_pause_events: self.qc_pause_events,
+            qc_paused_ms: self.qc_paused_ms,
+            notes: self.notes,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BatchRow {
+    id: String,
+    created_at: String,
+    kind: String,
+    app_version: String,
+    #[serde(default)]
+    device_firmware: Option<String>,
+    #[serde(default)]
+    file_hash: Option<String>,
+    #[serde(default)]
+    detail: Option<String>,
+    #[serde(default)]
+    rolled_back_at: Option<String>,
+}
+
+impl From<&IngestBatch> for BatchRow {
+    fn from(b: &IngestBatch) -> Self {
+        Self {
+            id: b.id.clone(),
+            created_at: format_ts(b.created_at),
+            kind: b.kind.clone(),
+            app_version: b.app_version.clone(),
+            device_firmware: b.device_firmware.clone(),
+            file_hash: b.file_hash.clone(),
+            detail: b.detail.clone(),
+            rolled_back_at: b.rolled_back_at.map(format_ts),
+        }
+    }
+}
+
+impl BatchRow {
+    fn into_batch(self) -> Result<IngestBatch> {
+        Ok(IngestBatch {
+            created_at: parse_ts(&self.created_at)?,
+            rolled_back_at: self.rolled_back_at.as_deref().map(parse_ts).transpose()?,
+            id: self.id,
+            kind: self.kind,
+            app_version: self.app_version,
+            device_firmware: self.device_firmware,
+            file_hash: self.file_hash,
+            detail: self.detail,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TrialRow {
+    session_id: String,
+    index: i64,
+    #[serde(default)]
+    iti_ms: Option<f64>,
+    #[serde(default)]
+    onset_ms: Option<f64>,
+    #[serde(default)]
+    response_ms: Option<f64>,
+    #[serde(default)]
+    rt_ms: Option<f64>,
+    outcome: String,
+    #[serde(default)]
+    stimulus: Option<String>,
+    #[serde(default)]
+    target: Option<bool>,
+    #[serde(default)]
+    lure: Option<bool>,
+}
+
+impl From<&TrialRecord> for TrialRow {
+    fn from(t: &TrialRecord) -> Self {
+        Self {
+            session_id: t.session_id.clone(),
+            index: t.index,
+            iti_ms: t.iti_ms,
+            onset_ms: t.onset_ms,
+            response_ms: t.response_ms,
+            rt_ms: t.rt_ms,
+            outcome: t.outcome.clone(),
+            stimulus: t.stimulus.clone(),
+            target: t.target,
+            lure: t.lure,
+        }
+    }
+}
+
+impl From<TrialRow> for TrialRecord {
+    fn from(row: TrialRow) -> Self {
+        Self {
+            session_id: row.session_id,
+            index: row.index,
+            iti_ms: row.iti_ms,
+            onset_ms: row.onset_ms,
+            response_ms: row.response_ms,
+            rt_ms: row.rt_ms,
+            outcome: row.outcome,
+            stimulus: row.stimulus,
+            target: row.target,
+            lure: row.lure,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+    use time::macros::datetime;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("looplace_snapshot_{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn populated() -> MemoryStore {
+        let mut store = MemoryStore::new();
+        let t = datetime!(2025-09-21 16:21:54.093347);
+        let mut reading = Observation::new("glucose.mg_dl", t, 94.0, "mg/dL", "dev");
+        reading.tags.insert("kind".into(), "scan".into());
+        let t = datetime!(2026-06-19 08:00:00);
+        let mut metric = Observation::new("pvt.median_rt_ms", t, 312.5, "ms", "looplace");
+        metric.session_id = Some("pvt-1".into());
+        let sync = IngestBatch {
+            device_firmware: Some("2.4.3".into()),
+            ..IngestBatch::new(crate::provenance::READER_SYNC, "test")
+        };
+        sync.stamp(std::slice::from_mut(&mut reading));
+        store.upsert(&[reading, metric]).unwrap();
+        store.upsert_batches(&[sync]).unwrap();
+        store
+            .upsert_trials(&[TrialRecord {
+                session_id: "pvt-1".into(),
+                index: 0,
+                iti_ms: Some(3_250.0),
+                onset_ms: Some(3_250.0),
+                response_ms: Some(3_562.5),
+                rt_ms: Some(312.5),
+                outcome: "reaction".into(),
+                stimulus: None,
+                target: None,
+                lure: None,
+            }])
+            .unwrap();
+        store
+            .upsert_sessions(&[SessionRecord {
+                id: "pvt-1".into(),
+                task: "pvt".into(),
+                created_at: datetime!(2026-06-19 08:00:00),
+                client_platform: "desktop".into(),
+                client_tz: "UTC".into(),
+                metrics: serde_json::json!({"median_rt_ms": 312.5}),
+                qc_visibility_blur_events: 0,
+                qc_focus_lost_events: 1,
+                qc_min_trials_met: true,
+                qc_device_platform: "desktop".into(),
+                qc_device_user_agent: None,
+                qc_pause_events: 0,
+                qc_paused_ms: 0,
+                notes: Some("felt sharp".into()),
+            }])
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn snapshot_then_restore_is_lossless() {
+        let dir = temp_dir("roundtrip");
+        let prefs = dir.join("glucose_prefs.json");
+        fs::write(&prefs, r#"{"low":70,"high":180}"#).unwrap();
+        let store = populated();
+
+        let dest = dir.join("snap");
+        let manifest = snapshot(
+            &store,
+            &dest,
+            "manual",
+            &Attachments {
+                prefs: vec![prefs, dir.join("absent.json")],
+                raw: Vec::new(),
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            (manifest.observations, manifest.sessions, manifest.batches, manifest.trials),
+            (2, 1, 1, 1)
+        );
+        assert_eq!(manifest.files.len(), 5); // four tables + the one pref that exists
+        assert_eq!(verify(&dest).unwrap(), manifest);
+
+        let mut restored = MemoryStore::new();
+        let restored_prefs = dir.join("restored");
+        let report = restore(&dest, &mut restored, Some(&restored_prefs), None).unwrap();
+        assert_eq!(
+            report,
+            RestoreReport {
+                observations_inserted: 2,
+                sessions_inserted: 1,
+                batches_inserted: 1,
+                trials_inserted: 1,
+                prefs_restored: 1,
+            }
+        );
+        assert_eq!(
+            restored.query(&Query::default()).unwrap(),
+            store.query(&Query::default()).unwrap()
+        );
+        assert_eq!(restored.sessions().unwrap(), store.sessions().unwrap());
+        assert_eq!(restored.batches().unwrap(), store.batches().unwrap());
+        assert_eq!(restored.trials().unwrap(), store.trials().unwrap());
+        assert!(restored_prefs.join("glucose_prefs.json").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn tampered_snapshot_is_rejected_before_any_write() {
+        let dir = temp_dir("tampered");
+        let dest = dir.join("snap");
+        snapshot(&populated(), &dest, "manual", &Attachments::default(), None).unwrap();
+        let table = dest.join(OBSERVATIONS_FILE);
+        let edited = fs::read_to_string(&table).unwrap().replace("94.0", "194.0");
+        fs::write(&table, edited).unwrap();
+
+        let mut store = MemoryStore::new();
+        assert!(matches!(restore(&dest, &mut store, None, None), Err(StoreError::Parse(_))));
+        assert!(store.is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn manifest_paths_cannot_escape_the_archive() {
+        let dir = temp_dir("escape");
+        let dest = dir.join("snap");
+        let none = Attachments::default();
+        let mut manifest = snapshot(&populated(), &dest, "manual", &none, None).unwrap();
+        manifest.files[0].path = "../outside.jsonl".into();
+        fs::write(dest.join(MANIFEST_FILE), serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+        assert!(matches!(verify(&dest), Err(StoreError::Parse(_))));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_reseal_cut_short_between_renames_keeps_its_archive() {
+        let dir = temp_dir("reseal_crash");
+        let none = Attachments::default();
+        let archive = dir.join("20260619T080000Z-manual");
+        snapshot(&populated(), &archive, "manual", &none, None).unwrap();
+        let other = dir.join("20260620T080000Z-manual");
+        snapshot(&populated(), &other, "manual", &none, None).unwrap();
+
+        // `archive` was set aside and its resealed copy not yet moved in;
+        // `other` was swapped but its old copy not yet removed.
+        fs::rename(&archive, suffixed(&archive, "old")).unwrap();
+        fs::create_dir_all(partial_path(&archive)).unwrap();
+        copy_dir(&other, &suffixed(&other, "old"));
+
+        assert_eq!(list(&dir).unwrap(), vec![archive.clone(), other.clone()]);
+        verify(&archive).unwrap();
+        assert!(!suffixed(&archive, "old").exists());
+        assert!(!suffixed(&other, "old").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn copy_dir(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let path = entry.unwrap().path();
+            let dest = to.join(path.file_name().unwrap());
+            if path.is_dir() {
+                copy_dir(&path, &dest);
+            } else {
+                fs::copy(&path, &dest).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_keeps_the_newest_snapshots() {
+        let dir = temp_dir("rotate");
+        let store = populated();
+        let mut taken = Vec::new();
+        for _ in 0..SNAPSHOT_KEEP + 2 {
+            let none = Attachments::default();
+            let path = snapshot_rotating(&store, &dir, "pre-migrate", &none, None);
+            taken.push(path.unwrap());
+        }
+
+        let kept = list(&dir).unwrap();
+        assert_eq!(kept.len(), SNAPSHOT_KEEP);
+        assert_eq!(kept, taken[2..].to_vec());
+        assert!(!taken[0].exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
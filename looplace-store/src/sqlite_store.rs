@@ -0,0 +1,704 @@
+//! SQLite backend for [`Store`] (behind the `sqlite-store` feature).
+//!
+//! Where [`ParquetStore`](crate::ParquetStore) rewrites a whole file per upsert,
+//! this backend updates rows in place inside a transaction, with the
+//! observation key and session timestamps indexed — the better fit for
+//! frequent small writes. Parquet stays the interchange format; move data
+//! between the two with [`copy_store`](crate::copy_store).
+//!
+//! Timestamps are stored as naive-wall-clock microseconds (the same encoding
+//! as the Parquet backend); tags and metrics as JSON text.
+
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::{params, Connection, OptionalExtension, ToSql};
+
+use crate::error::{Result, StoreError};
+use crate::observation::{Observation, Query};
+use crate::provenance::IngestBatch;
+use crate::session::SessionRecord;
+use crate::streams;
+use crate::store::{micros_to_pdt, pdt_to_micros, Store};
+use crate::trial::TrialRecord;
+
+/// Layout version recorded in SQLite's `user_version` pragma. v2 added
+/// `observations.batch_id` and the `batches` table, v3 the sessions' pause
+/// columns.
+const SQLITE_SCHEMA_VERSION: i64 = 3;
+
+/// How long a writer waits on another connection's write lock.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(1);
+
+const CREATE_TABLES: &str = "
+    CREATE TABLE IF NOT EXISTS observations (
+        stream     TEXT    NOT NULL,
+        timestamp  INTEGER NOT NULL,
+        source     TEXT    NOT NULL,
+        -- The `kind` tag, duplicated out of tags_json so it can be part of the
+        -- key; '' when absent.
+        kind       TEXT    NOT NULL,
+        value      REAL    NOT NULL,
+        unit       TEXT    NOT NULL,
+        session_id TEXT,
+        tags_json  TEXT    NOT NULL,
+        batch_id   TEXT,
+        PRIMARY KEY (stream, timestamp, source, kind)
+    );
+    CREATE INDEX IF NOT EXISTS observations_by_time ON observations (timestamp);
+    CREATE TABLE IF NOT EXISTS sessions (
+        id                        TEXT    PRIMARY KEY NOT NULL,
+        task                      TEXT    NOT NULL,
+        created_at                INTEGER NOT NULL,
+        client_platform           TEXT    NOT NULL,
+        client_tz                 TEXT    NOT NULL,
+        metrics_json              TEXT    NOT NULL,
+        qc_visibility_blur_events INTEGER NOT NULL,
+        qc_focus_lost_events      INTEGER NOT NULL,
+        qc_min_trials_met         INTEGER NOT NULL,
+        qc_device_platform        TEXT    NOT NULL,
+        qc_device_user_agent      TEXT,
+        notes                     TEXT,
+        qc_pause_events           INTEGER NOT NULL DEFAULT 0,
+        qc_paused_ms              INTEGER NOT NULL DEFAULT 0
+    );
+    CREATE INDEX IF NOT EXISTS sessions_by_time ON sessions (created_at);
+    CREATE TABLE IF NOT EXISTS batches (
+        id              TEXT    PRIMARY KEY NOT NULL,
+        created_at      INTEGER NOT NULL,
+        kind            TEXT    NOT NULL,
+        app_version     TEXT    NOT NULL,
+        device_firmware TEXT,
+        file_hash       TEXT,
+        detail          TEXT,
+        rolled_back_at  INTEGER
+    );
+    -- Added without a version bump: older readers just never see it.
+    CREATE TABLE IF NOT EXISTS trials (
+        session_id  TEXT    NOT NULL,
+        idx         INTEGER NOT NULL,
+        iti_ms      REAL,
+        onset_ms    REAL,
+        response_ms REAL,
+        rt_ms       REAL,
+        outcome     TEXT    NOT NULL,
+        stimulus    TEXT,
+        target      INTEGER,
+        lure        INTEGER,
+        PRIMARY KEY (session_id, idx)
+    );
+";
+
+/// A [`Store`] in one SQLite database file.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path`, creating tables as needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        Self::init(Connection::open(path).map_err(backend)?)
+    }
+
+    /// A private, non-persistent database — handy for tests and scratch copies.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory().map_err(backend)?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(backend)?;
+        if version > SQLITE_SCHEMA_VERSION {
+            return Err(StoreError::UnsupportedSchema {
+                found: version as u32,
+                supported: SQLITE_SCHEMA_VERSION as u32,
+            });
+        }
+        // WAL lets readers in other processes proceed while we write; the busy
+        // timeout makes a concurrent writer wait briefly instead of failing.
+        conn.pragma_update(None, "journal_mode", "WAL").map_err(backend)?;
+        conn.busy_timeout(BUSY_TIMEOUT).map_err(backend)?;
+        if version == 1 {
+            conn.execute_batch("ALTER TABLE observations ADD COLUMN batch_id TEXT")
+                .map_err(backend)?;
+        }
+        if (1..3).contains(&version) && has_table(&conn, "sessions")? {
+            conn.execute_batch(
+                "ALTER TABLE sessions ADD COLUMN qc_pause_events INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE sessions ADD COLUMN qc_paused_ms INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(backend)?;
+        }
+        conn.execute_batch(CREATE_TABLES).map_err(backend)?;
+        conn.pragma_update(None, "user_version", SQLITE_SCHEMA_VERSION)
+            .map_err(backend)?;
+        Ok(Self { conn })
+    }
+
+    /// Number of stored observations.
+    pub fn len(&self) -> Result<usize> {
+        let n: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM observations", [], |row| row.get(0))
+            .map_err(backend)?;
+        Ok(n as usize)
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+}
+
+impl Store for SqliteStore {
+    fn upsert(&mut self, observations: &[Observation]) -> Result<usize> {
+        streams::validate_all(observations)?;
+        let tx = self.conn.transaction().map_err(backend)?;
+        let mut inserted = 0;
+        {
+            let mut exists = tx
+                .prepare_cached(
+                    "SELECT 1 FROM observations
+                     WHERE stream = ?1 AND timestamp = ?2 AND source = ?3 AND kind = ?4",
+                )
+                .map_err(backend)?;
+            let mut write = tx
+                .prepare_cached(
+                    "INSERT INTO observations
+                       (stream, timestamp, source, kind, value, unit, session_id, tags_json,
+                        batch_id)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                     ON CONFLICT (stream, timestamp, source, kind) DO UPDATE SET
+                       value = excluded.value,
+                       unit = excluded.unit,
+                       session_id = excluded.session_id,
+                       tags_json = excluded.tags_json,
+                       -- An unchanged row keeps the batch that last changed it.
+                       batch_id = CASE
+                         WHEN value = excluded.value AND unit = excluded.unit
+                          AND session_id IS excluded.session_id
+                          AND tags_json = excluded.tags_json
+                         THEN batch_id ELSE excluded.batch_id END",
+                )
+                .map_err(backend)?;
+            for obs in observations {
+                let timestamp = pdt_to_micros(obs.timestamp);
+                let kind = obs.tags.get("kind").map(String::as_str).unwrap_or("");
+                let found = exists
+                    .query_row(params![obs.stream, timestamp, obs.source, kind], |_| Ok(()))
+                    .optional()
+                    .map_err(backend)?;
+                if found.is_none() {
+                    inserted += 1;
+                }
+                write
+                    .execute(params![
+                        obs.stream,
+                        timestamp,
+                        obs.source,
+                        kind,
+                        obs.value,
+                        obs.unit,
+                        obs.session_id,
+                        serde_json::to_string(&obs.tags)?,
+                        obs.batch_id,
+                    ])
+                    .map_err(backend)?;
+            }
+        }
+        tx.commit().map_err(backend)?;
+        Ok(inserted)
+    }
+
+    fn delete(&mut self, observations: &[Observation]) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(backend)?;
+        let mut removed = 0;
+        {
+            let mut delete = tx
+                .prepare_cached(
+                    "DELETE FROM observations
+                     WHERE stream = ?1 AND timestamp = ?2 AND source = ?3 AND kind = ?4",
+                )
+                .map_err(backend)?;
+            for obs in observations {
+                let kind = obs.tags.get("kind").map(String::as_str).unwrap_or("");
+                removed += delete
+                    .execute(params![obs.stream, pdt_to_micros(obs.timestamp), obs.source, kind])
+                    .map_err(backend)?;
+            }
+        }
+        tx.commit().map_err(backend)?;
+        Ok(removed)
+    }
+
+    fn query(&self, query: &Query) -> Result<Vec<Observation>> {
+        let mut sql = String::from(
+            "SELECT stream, timestamp, value, unit, source, session_id, tags_json, batch_id
+             FROM observations WHERE 1 = 1",
+        );
+        let mut args: Vec<Box<dyn ToSql>> = Vec::new();
+        if let Some(stream) = &query.stream {
+            sql.push_str(" AND stream = ?");
+            args.push(Box::new(stream.clone()));
+        }
+        if let Some(since) = query.since {
+            sql.push_str(" AND timestamp >= ?");
+            args.push(Box::new(pdt_to_micros(since)));
+        }
+        if let Some(until) = query.until {
+            sql.push_str(" AND timestamp <= ?");
+            args.push(Box::new(pdt_to_micros(until)));
+        }
+        if let Some(id) = &query.batch_id {
+            sql.push_str(" AND batch_id = ?");
+            args.push(Box::new(id.clone()));
+        }
+        // rowid breaks timestamp ties in insertion order, matching MemoryStore.
+        sql.push_str(" ORDER BY timestamp, rowid");
+
+        let mut stmt = self.conn.prepare(&sql).map_err(backend)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(args.iter()), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, Option<String>>(7)?,
+                ))
+            })
+            .map_err(backend)?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            let (stream, timestamp, value, unit, source, session_id, tags, batch_id) =
+                row.map_err(backend)?;
+            out.push(Observation {
+                stream,
+                timestamp: micros_to_pdt(timestamp),
+                value,
+                unit,
+                source,
+                session_id,
+                tags: serde_json::from_str(&tags).unwrap_or_default(),
+                batch_id,
+            });
+        }
+        Ok(out)
+    }
+
+    fn upsert_sessions(&mut self, sessions: &[SessionRecord]) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(backend)?;
+        let mut inserted = 0;
+        {
+            let mut exists = tx
+                .prepare_cached("SELECT 1 FROM sessions WHERE id = ?1")
+                .map_err(backend)?;
+            let mut write = tx
+                .prepare_cached(
+                    "INSERT INTO sessions
+                       (id, task, created_at, client_platform, client_tz, metrics_json,
+                        qc_visibility_blur_events, qc_focus_lost_events, qc_min_trials_met,
+                        qc_device_platform, qc_device_user_agent, notes,
+                        qc_pause_events, qc_paused_ms)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                     ON CONFLICT (id) DO UPDATE SET
+                       task = excluded.task,
+                       created_at = excluded.created_at,
+                       client_platform = excluded.client_platform,
+                       client_tz = excluded.client_tz,
+                       metrics_json = excluded.metrics_json,
+                       qc_visibility_blur_events = excluded.qc_visibility_blur_events,
+                       qc_focus_lost_events = excluded.qc_focus_lost_events,
+                       qc_min_trials_met = excluded.qc_min_trials_met,
+                       qc_device_platform = excluded.qc_device_platform,
+                       qc_device_user_agent = excluded.qc_device_user_agent,
+                       notes = excluded.notes,
+                       qc_pause_events = excluded.qc_pause_events,
+                       qc_paused_ms = excluded.qc_paused_ms",
+                )
+                .map_err(backend)?;
+            for session in sessions {
+                let found = exists
+                    .query_row(params![session.id], |_| Ok(()))
+                    .optional()
+                    .map_err(backend)?;
+                if found.is_none() {
+                    inserted += 1;
+                }
+                write
+                    .execute(params![
+                        session.id,
+                        session.task,
+                        pdt_to_micros(session.created_at),
+                        session.client_platform,
+                        session.client_tz,
+                        serde_json::to_string(&session.metrics)?,
+                        session.qc_visibility_blur_events,
+                        session.qc_focus_lost_events,
+                        session.qc_min_trials_met,
+                        session.qc_device_platform,
+                        session.qc_device_user_agent,
+                        session.notes,
+                        session.qc_pause_events,
+                        session.qc_paused_ms,
+                    ])
+                    .map_err(backend)?;
+            }
+        }
+        tx.commit().map_err(backend)?;
+        Ok(inserted)
+    }
+
+    fn delete_sessions(&mut self, ids: &[String]) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(backend)?;
+        let mut removed = 0;
+        {
+            let mut delete = tx
+                .prepare_cached("DELETE FROM sessions WHERE id = ?1")
+                .map_err(backend)?;
+            for id in ids {
+                removed += delete.execute(params![id]).map_err(backend)?;
+            }
+        }
+        tx.commit().map_err(backend)?;
+        Ok(removed)
+    }
+
+    fn sessions(&self) -> Result<Vec<SessionRecord>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, task, created_at, client_platform, client_tz, metrics_json,
+                        qc_visibility_blur_events, qc_focus_lost_events, qc_min_trials_met,
+                        qc_device_platform, qc_device_user_agent, notes,
+                        qc_pause_events, qc_paused_ms
+                 FROM sessions ORDER BY created_at, rowid",
+            )
+            .map_err(backend)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(SessionRecord {
+                    id: row.get(0)?,
+                    task: row.get(1)?,
+                    created_at: micros_to_pdt(row.get(2)?),
+                    client_platform: row.get(3)?,
+                    client_tz: row.get(4)?,
+                    metrics: serde_json::from_str(&row.get::<_, String>(5)?)
+                        .unwrap_or(serde_json::Value::Null),
+                    qc_visibility_blur_events: row.get(6)?,
+                    qc_focus_lost_events: row.get(7)?,
+                    qc_min_trials_met: row.get(8)?,
+                    qc_device_platform: row.get(9)?,
+                    qc_device_user_agent: row.get(10)?,
+                    notes: row.get(11)?,
+                    qc_pause_events: row.get(12)?,
+                    qc_paused_ms: row.get(13)?,
+                })
+            })
+            .map_err(backend)?;
+        rows.collect::<std::result::Result<_, _>>().map_err(backend)
+    }
+
+    fn upsert_batches(&mut self, batches: &[IngestBatch]) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(backend)?;
+        let mut inserted = 0;
+        {
+            let mut exists = tx
+                .prepare_cached("SELECT 1 FROM batches WHERE id = ?1")
+                .map_err(backend)?;
+            let mut write = tx
+                .prepare_cached(
+                    "INSERT INTO batches
+                       (id, created_at, kind, app_version, device_firmware, file_hash, detail,
+                        rolled_back_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                     ON CONFLICT (id) DO UPDATE SET
+                       created_at = excluded.created_at,
+                       kind = excluded.kind,
+                       app_version = excluded.app_version,
+                       device_firmware = excluded.device_firmware,
+                       file_hash = excluded.file_hash,
+                       detail = excluded.detail,
+                       rolled_back_at = excluded.rolled_back_at",
+                )
+                .map_err(backend)?;
+            for batch in batches {
+                let found = exists
+                    .query_row(params![batch.id], |_| Ok(()))
+                    .optional()
+                    .map_err(backend)?;
+                if found.is_none() {
+                    inserted += 1;
+                }
+                write
+                    .execute(params![
+                        batch.id,
+                        pdt_to_micros(batch.created_at),
+                        batch.kind,
+                        batch.app_version,
+                        batch.device_firmware,
+                        batch.file_hash,
+                        batch.detail,
+                        batch.rolled_back_at.map(pdt_to_micros),
+                    ])
+                    .map_err(backend)?;
+            }
+        }
+        tx.commit().map_err(backend)?;
+        Ok(inserted)
+    }
+
+    fn batches(&self) -> Result<Vec<IngestBatch>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT id, created_at, kind, app_version, device_firmware, file_hash, detail,
+                        rolled_back_at
+                 FROM batches ORDER BY created_at, rowid",
+            )
+            .map_err(backend)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(IngestBatch {
+                    id: row.get(0)?,
+                    created_at: micros_to_pdt(row.get(1)?),
+                    kind: row.get(2)?,
+                    app_version: row.get(3)?,
+                    device_firmware: row.get(4)?,
+                    file_hash: row.get(5)?,
+                    detail: row.get(6)?,
+                    rolled_back_at: row.get::<_, Option<i64>>(7)?.map(micros_to_pdt),
+                })
+            })
+            .map_err(backend)?;
+        rows.collect::<std::result::Result<_, _>>().map_err(backend)
+    }
+
+    fn upsert_trials(&mut self, trials: &[TrialRecord]) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(backend)?;
+        let mut inserted = 0;
+        {
+            let mut exists = tx
+                .prepare_cached("SELECT 1 FROM trials WHERE session_id = ?1 AND idx = ?2")
+                .map_err(backend)?;
+            let mut write = tx
+                .prepare_cached(
+                    "INSERT INTO trials
+                       (session_id, idx, iti_ms, onset_ms, response_ms, rt_ms, outcome, stimulus,
+                        target, lure)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                     ON CONFLICT (session_id, idx) DO UPDATE SET
+                       iti_ms = excluded.iti_ms,
+                       onset_ms = excluded.onset_ms,
+                       response_ms = excluded.response_ms,
+                       rt_ms = excluded.rt_ms,
+                       outcome = excluded.outcome,
+                       stimulus = excluded.stimulus,
+                       target = excluded.target,
+                       lure = excluded.lure",
+                )
+                .map_err(backend)?;
+            for trial in trials {
+                let found = exists
+                    .query_row(params![trial.session_id, trial.index], |_| Ok(()))
+                    .optional()
+                    .map_err(backend)?;
+                if found.is_none() {
+                    inserted += 1;
+                }
+                write
+                    .execute(params![
+                        trial.session_id,
+                        trial.index,
+                        trial.iti_ms,
+                        trial.onset_ms,
+                        trial.response_ms,
+                        trial.rt_ms,
+                        trial.outcome,
+                        trial.stimulus,
+                        trial.target,
+                        trial.lure,
+                    ])
+                    .map_err(backend)?;
+            }
+        }
+        tx.commit().map_err(backend)?;
+        Ok(inserted)
+    }
+
+    fn delete_trials(&mut self, session_ids: &[String]) -> Result<usize> {
+        let tx = self.conn.transaction().map_err(backend)?;
+        let mut removed = 0;
+        {
+            let mut delete = tx
+                .prepare_cached("DELETE FROM trials WHERE session_id = ?1")
+                .map_err(backend)?;
+            for id in session_ids {
+                removed += delete.execute(params![id]).map_err(backend)?;
+            }
+        }
+        tx.commit().map_err(backend)?;
+        Ok(removed)
+    }
+
+    fn trials(&self) -> Result<Vec<TrialRecord>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT session_id, idx, iti_ms, onset_ms, response_ms, rt_ms, outcome, stimulus,
+                        target, lure
+                 FROM trials ORDER BY session_id, idx",
+            )
+            .map_err(backend)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(TrialRecord {
+                    session_id: row.get(0)?,
+                    index: row.get(1)?,
+                    iti_ms: row.get(2)?,
+                    onset_ms: row.get(3)?,
+                    response_ms: row.get(4)?,
+                    rt_ms: row.get(5)?,
+                    outcome: row.get(6)?,
+                    stimulus: row.get(7)?,
+                    target: row.get(8)?,
+                    lure: row.get(9)?,
+                })
+            })
+            .map_err(backend)?;
+        rows.collect::<std::result::Result<_, _>>().map_err(backend)
+    }
+}
+
+fn backend(e: rusqlite::Error) -> StoreError {
+    StoreError::Backend(e.to_string())
+}
+
+/// Whether the database already has table `name` (older layouts may not).
+fn has_table(conn: &Connection, name: &str) -> Result<bool> {
+    conn.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        params![name],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|found| found.is_some())
+    .map_err(backend)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conformance;
+    use time::macros::datetime;
+
+    #[test]
+    fn conforms_to_the_store_contract() {
+        conformance::run(|_| SqliteStore::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn persists_across_reopen() {
+        let dir = std::env::temp_dir().join("looplace_store_sqlite");
+        let path = dir.join("reopen.sqlite");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut reading =
+            Observation::new("glucose.mg_dl", datetime!(2026-06-19 09:00:00), 100.0, "mg/dL", "dev");
+        reading.tags.insert("kind".into(), "scan".into());
+        {
+            let mut store = SqliteStore::open(&path).unwrap();
+            assert_eq!(store.upsert(std::slice::from_ref(&reading)).unwrap(), 1);
+        }
+
+        let store = SqliteStore::open(&path).unwrap();
+        assert_eq!(store.len().unwrap(), 1);
+        assert_eq!(store.query(&Query::default()).unwrap(), vec![reading]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn upgrades_a_v1_database_in_place() {
+        let dir = std::env::temp_dir().join("looplace_store_sqlite_v1");
+        let path = dir.join("v1.sqlite");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE observations (
+                   stream TEXT NOT NULL, timestamp INTEGER NOT NULL, source TEXT NOT NULL,
+                   kind TEXT NOT NULL, value REAL NOT NULL, unit TEXT NOT NULL,
+                   session_id TEXT, tags_json TEXT NOT NULL,
+                   PRIMARY KEY (stream, timestamp, source, kind));
+                 INSERT INTO observations VALUES
+                   ('glucose.mg_dl', 0, 'dev', '', 100.0, 'mg/dL', NULL, '{}');
+                 PRAGMA user_version = 1;",
+            )
+            .unwrap();
+        }
+
+        let mut store = SqliteStore::open(&path).unwrap();
+        let old = store.query(&Query::default()).unwrap();
+        assert_eq!(old[0].batch_id, None);
+        let mut next = old[0].clone();
+        next.timestamp = datetime!(2026-06-19 09:00:00);
+        next.batch_id = Some("sync-1".into());
+        store.upsert(&[next]).unwrap();
+        assert_eq!(
+            store.query(&Query::default()).unwrap()[1]
+                .batch_id
+                .as_deref(),
+            Some("sync-1")
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn upgrades_v2_sessions_with_unpaused_runs() {
+        let dir = std::env::temp_dir().join("looplace_store_sqlite_v2");
+        let path = dir.join("v2.sqlite");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE sessions (
+                   id TEXT PRIMARY KEY NOT NULL, task TEXT NOT NULL, created_at INTEGER NOT NULL,
+                   client_platform TEXT NOT NULL, client_tz TEXT NOT NULL,
+                   metrics_json TEXT NOT NULL, qc_visibility_blur_events INTEGER NOT NULL,
+                   qc_focus_lost_events INTEGER NOT NULL, qc_min_trials_met INTEGER NOT NULL,
+                   qc_device_platform TEXT NOT NULL, qc_device_user_agent TEXT, notes TEXT);
+                 INSERT INTO sessions VALUES
+                   ('pvt-1', 'pvt', 0, 'desktop', 'UTC', '{}', 0, 0, 1, 'desktop', NULL, NULL);
+                 PRAGMA user_version = 2;",
+            )
+            .unwrap();
+        }
+
+        let mut store = SqliteStore::open(&path).unwrap();
+        let mut session = store.sessions().unwrap().remove(0);
+        assert_eq!((session.qc_pause_events, session.qc_paused_ms), (0, 0));
+        session.qc_pause_events = 1;
+        session.qc_paused_ms = 12_000;
+        store.upsert_sessions(std::slice::from_ref(&session)).unwrap();
+        drop(store);
+
+        let store = SqliteStore::open(&path).unwrap();
+        assert_eq!(store.sessions().unwrap(), vec![session]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
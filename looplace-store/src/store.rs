@@ -1,28 +1,70 @@
 //! The storage protocol and an in-memory backend.
 
+use std::collections::hash_map::{Entry, HashMap};
+use std::collections::HashSet;
+
 use crate::error::Result;
 use crate::observation::{Observation, Query};
+use crate::provenance::IngestBatch;
 use crate::session::SessionRecord;
+use crate::streams;
+use crate::trial::TrialRecord;
+
+#[cfg(any(feature = "parquet-store", feature = "sqlite-store"))]
+use time::PrimitiveDateTime;
 
 /// The storage protocol. Backends (in-memory, Parquet, later Lance) implement
 /// this; callers depend only on the trait.
 ///
-/// Two tables: tidy [`Observation`]s (for correlation across streams) and full
-/// [`SessionRecord`]s (lossless cognition sessions, for the Results UI).
+/// Four tables: tidy [`Observation`]s (for correlation across streams), full
+/// [`SessionRecord`]s (lossless cognition sessions, for the Results UI), the
+/// [`IngestBatch`]es that wrote them (see [`crate::provenance`]), and the
+/// opt-in per-trial [`TrialRecord`]s behind sessions (see [`crate::trial`]).
 pub trait Store {
     /// Idempotently write observations, overwriting any with the same
     /// [`Observation::key`]. Returns the number of *new* rows added.
+    ///
+    /// A row re-written with nothing changed but its `batch_id` keeps the
+    /// stored one, so provenance names the batch that last changed it.
+    ///
+    /// Every observation is first checked against the [`crate::streams`]
+    /// registry; an unknown stream or out-of-range value refuses the batch.
     fn upsert(&mut self, observations: &[Observation]) -> Result<usize>;
 
     /// Return observations matching `query`, ordered by timestamp ascending.
     fn query(&self, query: &Query) -> Result<Vec<Observation>>;
 
+    /// Remove the stored rows sharing an [`Observation::key`] with any of
+    /// `observations` (other fields are ignored). Returns how many were removed.
+    fn delete(&mut self, observations: &[Observation]) -> Result<usize>;
+
     /// Idempotently write session records, overwriting any with the same `id`.
     /// Returns the number of *new* rows added.
     fn upsert_sessions(&mut self, sessions: &[SessionRecord]) -> Result<usize>;
 
+    /// Remove the session records with the given ids (their observations are
+    /// untouched — see [`crate::session::delete`]). Returns how many were removed.
+    fn delete_sessions(&mut self, ids: &[String]) -> Result<usize>;
+
     /// Return all session records, ordered by `created_at` ascending.
     fn sessions(&self) -> Result<Vec<SessionRecord>>;
+
+    /// Idempotently record ingest batches, overwriting any with the same `id`.
+    /// Returns the number of *new* batches.
+    fn upsert_batches(&mut self, batches: &[IngestBatch]) -> Result<usize>;
+
+    /// Return every recorded batch, ordered by `created_at` ascending.
+    fn batches(&self) -> Result<Vec<IngestBatch>>;
+
+    /// Idempotently write trials, overwriting any with the same
+    /// `(session_id, index)`. Returns the number of *new* rows added.
+    fn upsert_trials(&mut self, trials: &[TrialRecord]) -> Result<usize>;
+
+    /// Remove every trial of the given sessions. Returns how many were removed.
+    fn delete_trials(&mut self, session_ids: &[String]) -> Result<usize>;
+
+    /// Return all trials, ordered by `session_id` then `index`.
+    fn trials(&self) -> Result<Vec<TrialRecord>>;
 }
 
 /// In-memory backend — always available, used for tests and as the reference
@@ -31,6 +73,8 @@ pub trait Store {
 pub struct MemoryStore {
     rows: Vec<Observation>,
     session_rows: Vec<SessionRecord>,
+    batch_rows: Vec<IngestBatch>,
+    trial_rows: Vec<TrialRecord>,
 }
 
 impl MemoryStore {
@@ -49,6 +93,7 @@ impl MemoryStore {
 
 impl Store for MemoryStore {
     fn upsert(&mut self, observations: &[Observation]) -> Result<usize> {
+        streams::validate_all(observations)?;
         Ok(upsert_into(&mut self.rows, observations))
     }
 
@@ -56,30 +101,107 @@ impl Store for MemoryStore {
         Ok(query_rows(&self.rows, query))
     }
 
+    fn delete(&mut self, observations: &[Observation]) -> Result<usize> {
+        Ok(delete_from(&mut self.rows, observations))
+    }
+
     fn upsert_sessions(&mut self, sessions: &[SessionRecord]) -> Result<usize> {
         Ok(upsert_sessions_into(&mut self.session_rows, sessions))
     }
 
+    fn delete_sessions(&mut self, ids: &[String]) -> Result<usize> {
+        Ok(delete_sessions_from(&mut self.session_rows, ids))
+    }
+
     fn sessions(&self) -> Result<Vec<SessionRecord>> {
         Ok(sorted_sessions(&self.session_rows))
     }
+
+    fn upsert_batches(&mut self, batches: &[IngestBatch]) -> Result<usize> {
+        Ok(upsert_batches_into(&mut self.batch_rows, batches))
+    }
+
+    fn batches(&self) -> Result<Vec<IngestBatch>> {
+        Ok(sorted_batches(&self.batch_rows))
+    }
+
+    fn upsert_trials(&mut self, trials: &[TrialRecord]) -> Result<usize> {
+        Ok(upsert_trials_into(&mut self.trial_rows, trials))
+    }
+
+    fn delete_trials(&mut self, session_ids: &[String]) -> Result<usize> {
+        Ok(delete_trials_from(&mut self.trial_rows, session_ids))
+    }
+
+    fn trials(&self) -> Result<Vec<TrialRecord>> {
+        Ok(sorted_trials(&self.trial_rows))
+    }
 }
 
-/// Shared upsert semantics over a row vector (overwrite by [`Observation::key`]).
-/// Returns the number of *new* rows added. Reused by every in-memory-backed store.
+/// Shared upsert semantics over a row vector (overwrite by [`Observation::key`],
+/// keeping the stored row when only `batch_id` differs). Returns the number of
+/// *new* rows added. Reused by every in-memory-backed store. Rows are looked
+/// up through a key index, so large imports stay linear.
 pub(crate) fn upsert_into(rows: &mut Vec<Observation>, observations: &[Observation]) -> usize {
+    let mut index = key_index(rows);
+    upsert_indexed(rows, &mut index, observations)
+}
+
+/// Each row's position by [`Observation::key`], for [`upsert_indexed`].
+pub(crate) type KeyIndex = HashMap<OwnedKey, usize>;
+
+pub(crate) fn key_index(rows: &[Observation]) -> KeyIndex {
+    rows.iter().enumerate().map(|(i, r)| (owned_key(r), i)).collect()
+}
+
+/// [`upsert_into`] through an `index` the caller keeps over `rows` from one
+/// upsert to the next, so a chunked import doesn't rebuild it per chunk.
+pub(crate) fn upsert_indexed(
+    rows: &mut Vec<Observation>,
+    index: &mut KeyIndex,
+    observations: &[Observation],
+) -> usize {
     let mut inserted = 0;
     for obs in observations {
-        if let Some(existing) = rows.iter_mut().find(|r| r.key() == obs.key()) {
-            *existing = obs.clone();
-        } else {
-            rows.push(obs.clone());
-            inserted += 1;
+        match index.entry(owned_key(obs)) {
+            Entry::Occupied(at) => {
+                let row = &mut rows[*at.get()];
+                if !same_reading(row, obs) {
+                    *row = obs.clone();
+                }
+            }
+            Entry::Vacant(slot) => {
+                slot.insert(rows.len());
+                rows.push(obs.clone());
+                inserted += 1;
+            }
         }
     }
     inserted
 }
 
+/// Whether two rows with the same key agree on everything but provenance.
+fn same_reading(a: &Observation, b: &Observation) -> bool {
+    a.value == b.value && a.unit == b.unit && a.session_id == b.session_id && a.tags == b.tags
+}
+
+pub(crate) type OwnedKey = (String, time::PrimitiveDateTime, String, Option<String>);
+
+fn owned_key(o: &Observation) -> OwnedKey {
+    let (stream, timestamp, source, kind) = o.key();
+    (stream.into(), timestamp, source.into(), kind.map(Into::into))
+}
+
+/// Shared delete semantics: drop rows keyed like any of `observations`.
+/// Returns the number removed. Keys are looked up through a set, so rolling
+/// back a large import stays linear.
+pub(crate) fn delete_from(rows: &mut Vec<Observation>, observations: &[Observation]) -> usize {
+    let keys: HashSet<_> = observations.iter().map(Observation::key).collect();
+    let before = rows.len();
+    rows.retain(|r| !keys.contains(&r.key()));
+    before - rows.len()
+}
+
 /// Shared query semantics: filter then sort by timestamp ascending.
 pub(crate) fn query_rows(rows: &[Observation], query: &Query) -> Vec<Observation> {
     let mut out: Vec<Observation> = rows.iter().filter(|o| query.matches(o)).cloned().collect();
@@ -104,6 +226,13 @@ pub(crate) fn upsert_sessions_into(
     inserted
 }
 
+/// Shared session delete (by `id`). Returns rows removed.
+pub(crate) fn delete_sessions_from(rows: &mut Vec<SessionRecord>, ids: &[String]) -> usize {
+    let before = rows.len();
+    rows.retain(|r| !ids.contains(&r.id));
+    before - rows.len()
+}
+
 /// Sessions sorted by `created_at` ascending.
 pub(crate) fn sorted_sessions(rows: &[SessionRecord]) -> Vec<SessionRecord> {
     let mut out = rows.to_vec();
@@ -111,11 +240,123 @@ pub(crate) fn sorted_sessions(rows: &[SessionRecord]) -> Vec<SessionRecord> {
     out
 }
 
+/// Shared upsert for ingest batches (overwrite by `id`). Returns new rows added.
+pub(crate) fn upsert_batches_into(rows: &mut Vec<IngestBatch>, batches: &[IngestBatch]) -> usize {
+    let mut inserted = 0;
+    for batch in batches {
+        if let Some(existing) = rows.iter_mut().find(|r| r.id == batch.id) {
+            *existing = batch.clone();
+        } else {
+            rows.push(batch.clone());
+            inserted += 1;
+        }
+    }
+    inserted
+}
+
+/// Batches sorted by `created_at` ascending.
+pub(crate) fn sorted_batches(rows: &[IngestBatch]) -> Vec<IngestBatch> {
+    let mut out = rows.to_vec();
+    out.sort_by_key(|batch| batch.created_at);
+    out
+}
+
+/// Shared upsert for trials (overwrite by `(session_id, index)`), indexed like
+/// [`upsert_into`] since a run adds many at once. Returns new rows added.
+pub(crate) fn upsert_trials_into(rows: &mut Vec<TrialRecord>, trials: &[TrialRecord]) -> usize {
+    let mut index: HashMap<(String, i64), usize> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, r)| ((r.session_id.clone(), r.index), i))
+        .collect();
+    let mut inserted = 0;
+    for trial in trials {
+        match index.entry((trial.session_id.clone(), trial.index)) {
+            Entry::Occupied(at) => rows[*at.get()] = trial.clone(),
+            Entry::Vacant(slot) => {
+                slot.insert(rows.len());
+                rows.push(trial.clone());
+                inserted += 1;
+            }
+        }
+    }
+    inserted
+}
+
+/// Shared trial delete (by `session_id`). Returns rows removed.
+pub(crate) fn delete_trials_from(rows: &mut Vec<TrialRecord>, session_ids: &[String]) -> usize {
+    let before = rows.len();
+    rows.retain(|r| !session_ids.contains(&r.session_id));
+    before - rows.len()
+}
+
+/// Trials sorted by `session_id`, then `index`.
+pub(crate) fn sorted_trials(rows: &[TrialRecord]) -> Vec<TrialRecord> {
+    let mut out = rows.to_vec();
+    out.sort_by(|a, b| (&a.session_id, a.index).cmp(&(&b.session_id, b.index)));
+    out
+}
+
+/// Copy every observation, session, ingest batch and trial from one store
+/// into another, losslessly. Idempotent (it's an upsert), so it doubles as a
+/// resumable backend migration.
+pub fn copy_store(from: &dyn Store, to: &mut dyn Store) -> Result<CopyReport> {
+    let observations = from.query(&Query::default())?;
+    let sessions = from.sessions()?;
+    let batches = from.batches()?;
+    let trials = from.trials()?;
+    Ok(CopyReport {
+        batches_inserted: to.upsert_batches(&batches)?,
+        sessions_inserted: to.upsert_sessions(&sessions)?,
+        trials_inserted: to.upsert_trials(&trials)?,
+        observations_inserted: to.upsert(&observations)?,
+        batches: batches.len(),
+        trials: trials.len(),
+        sessions: sessions.len(),
+        observations: observations.len(),
+    })
+}
+
+/// What [`copy_store`] moved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyReport {
+    /// Rows read from the source.
+    pub observations: usize,
+    pub sessions: usize,
+    pub batches: usize,
+    pub trials: usize,
+    /// Rows that were new to the destination.
+    pub observations_inserted: usize,
+    pub sessions_inserted: usize,
+    pub batches_inserted: usize,
+    pub trials_inserted: usize,
+}
+
+/// Timestamp encoding shared by the file backends: microseconds since the Unix
+/// epoch of the *naive* wall-clock (no offset applied — see the crate docs).
+#[cfg(any(feature = "parquet-store", feature = "sqlite-store"))]
+pub(crate) fn pdt_to_micros(t: PrimitiveDateTime) -> i64 {
+    (t.assume_utc().unix_timestamp_nanos() / 1_000) as i64
+}
+
+#[cfg(any(feature = "parquet-store", feature = "sqlite-store"))]
+pub(crate) fn micros_to_pdt(micros: i64) -> PrimitiveDateTime {
+    let odt = time::OffsetDateTime::from_unix_timestamp_nanos(micros as i128 * 1_000)
+        .unwrap_or(time::OffsetDateTime::UNIX_EPOCH);
+    PrimitiveDateTime::new(odt.date(), odt.time())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::conformance;
     use time::macros::datetime;
 
+    #[test]
+    fn conforms_to_the_store_contract() {
+        conformance::run(|_| MemoryStore::new());
+    }
+
     fn obs(stream: &str, t: time::PrimitiveDateTime, v: f64) -> Observation {
         Observation::new(stream, t, v, "", "dev")
     }
@@ -0,0 +1,304 @@
+//! The stream registry: every known observation stream, with its canonical
+//! unit, valid value range, display precision, direction, and localized label.
+//!
+//! Stream names are dotted (`<domain>.<metric>`); cognition metrics are
+//! `<task>.<metric key>` as written by the task's metrics struct. Backends
+//! [`validate`] every observation at upsert, so a typo'd or unregistered stream,
+//! or a value outside its range, is refused rather than silently stored.
+//! Streams under [`CUSTOM_PREFIX`] are the escape hatch for user-defined data:
+//! always accepted, with any unit.
+//!
+//! Adding a stream: add a [`StreamSpec`] to [`STREAMS`] and its `label_key`
+//! to every `ui/i18n/*/looplace-ui.ftl`.
+
+use crate::error::{Result, StoreError};
+use crate::observation::Observation;
+
+/// Glucose in mg/dL (the Libre's device-internal unit).
+pub const GLUCOSE: &str = "glucose.mg_dl";
+/// Blood ketones in mmol/L.
+pub const KETONE: &str = "ketone.mmol_l";
+/// Prefix for user-defined streams, which skip registry checks.
+pub const CUSTOM_PREFIX: &str = "custom.";
+
+/// Everything the store and UI know about one stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamSpec {
+    pub name: &'static str,
+    /// Canonical unit (`""` for unitless scores).
+    pub unit: &'static str,
+    /// Inclusive bounds of a plausible value; anything outside is refused.
+    pub min: f64,
+    pub max: f64,
+    /// Decimal places to display.
+    pub precision: u8,
+    /// `Some(true)` if larger values are better, `Some(false)` if smaller are,
+    /// `None` if neither (counts, or glucose, where in-range is what's good).
+    pub higher_is_better: Option<bool>,
+    /// Fluent message id of the stream's display label.
+    pub label_key: &'static str,
+}
+
+impl StreamSpec {
+    /// Whether `value` is finite and within this stream's range.
+    pub fn accepts(&self, value: f64) -> bool {
+        value.is_finite() && (self.min..=self.max).contains(&value)
+    }
+
+    /// `value` rounded to the stream's precision, without the unit.
+    pub fn format(&self, value: f64) -> String {
+        format!("{value:.prec$}", prec = usize::from(self.precision))
+    }
+}
+
+const fn spec(
+    name: &'static str,
+    unit: &'static str,
+    (min, max): (f64, f64),
+    precision: u8,
+    higher_is_better: Option<bool>,
+    label_key: &'static str,
+) -> StreamSpec {
+    StreamSpec {
+        name,
+        unit,
+        min,
+        max,
+        precision,
+        higher_is_better,
+        label_key,
+    }
+}
+
+/// Reaction times, including timeouts.
+const RT_MS: (f64, f64) = (0.0, 60_000.0);
+/// Differences between two reaction times (interference effects).
+const RT_DIFF_MS: (f64, f64) = (-60_000.0, 60_000.0);
+const COUNT: (f64, f64) = (0.0, 100_000.0);
+const RATIO: (f64, f64) = (0.0, 1.0);
+/// Signal-detection scores; corrected rates keep them well inside this.
+const SDT: (f64, f64) = (-10.0, 10.0);
+const LOWER: Option<bool> = Some(false);
+const HIGHER: Option<bool> = Some(true);
+
+/// Every registered stream.
+pub const STREAMS: &[StreamSpec] = &[
+    spec(GLUCOSE, "mg/dL", (10.0, 1000.0), 0, None, "stream-glucose-mg-dl"),
+    spec(KETONE, "mmol/L", (0.0, 10.0), 1, None, "stream-ketone-mmol-l"),
+    // PVT (`PvtMetrics`).
+    spec("pvt.total_trials", "count", COUNT, 0, None, "stream-pvt-total-trials"),
+    spec("pvt.reacted_trials", "count", COUNT, 0, None, "stream-pvt-reacted-trials"),
+    spec("pvt.median_rt_ms", "ms", RT_MS, 0, LOWER, "stream-pvt-median-rt-ms"),
+    spec("pvt.mean_rt_ms", "ms", RT_MS, 0, LOWER, "stream-pvt-mean-rt-ms"),
+    spec("pvt.sd_rt_ms", "ms", RT_MS, 0, LOWER, "stream-pvt-sd-rt-ms"),
+    spec("pvt.p10_rt_ms", "ms", RT_MS, 0, LOWER, "stream-pvt-p10-rt-ms"),
+    spec("pvt.p90_rt_ms", "ms", RT_MS, 0, LOWER, "stream-pvt-p90-rt-ms"),
+    spec("pvt.lapses_ge_500ms", "count", COUNT, 0, LOWER, "stream-pvt-lapses-ge-500ms"),
+    spec("pvt.minor_lapses_355_499ms", "count", COUNT, 0, LOWER, "stream-pvt-minor-lapses-355-499ms"),
+    spec("pvt.false_starts", "count", COUNT, 0, LOWER, "stream-pvt-false-starts"),
+    spec("pvt.time_on_task_slope_ms_per_min", "ms/min", (-10_000.0, 10_000.0), 2, LOWER, "stream-pvt-time-on-task-slope-ms-per-min"),
+    spec("pvt.mean_reciprocal_rt_per_s", "1/s", (0.0, 20.0), 2, HIGHER, "stream-pvt-mean-reciprocal-rt-per-s"),
+    spec("pvt.fastest_10pct_rt_ms", "ms", RT_MS, 0, LOWER, "stream-pvt-fastest-10pct-rt-ms"),
+    spec("pvt.slowest_10pct_rt_ms", "ms", RT_MS, 0, LOWER, "stream-pvt-slowest-10pct-rt-ms"),
+    spec("pvt.lapse_probability", "ratio", RATIO, 3, LOWER, "stream-pvt-lapse-probability"),
+    spec("pvt.performance_score", "ratio", RATIO, 3, HIGHER, "stream-pvt-performance-score"),
+    // 2-back (`NBackMetrics`).
+    spec("nback2.total_trials", "count", COUNT, 0, None, "stream-nback2-total-trials"),
+    spec("nback2.target_trials", "count", COUNT, 0, None, "stream-nback2-target-trials"),
+    spec("nback2.non_target_trials", "count", COUNT, 0, None, "stream-nback2-non-target-trials"),
+    spec("nback2.hits", "count", COUNT, 0, None, "stream-nback2-hits"),
+    spec("nback2.misses", "count", COUNT, 0, None, "stream-nback2-misses"),
+    spec("nback2.false_alarms", "count", COUNT, 0, None, "stream-nback2-false-alarms"),
+    spec("nback2.correct_rejections", "count", COUNT, 0, None, "stream-nback2-correct-rejections"),
+    spec("nback2.response_count", "count", COUNT, 0, None, "stream-nback2-response-count"),
+    spec("nback2.hit_rate", "ratio", RATIO, 3, HIGHER, "stream-nback2-hit-rate"),
+    spec("nback2.false_alarm_rate", "ratio", RATIO, 3, LOWER, "stream-nback2-false-alarm-rate"),
+    spec("nback2.accuracy", "ratio", RATIO, 3, HIGHER, "stream-nback2-accuracy"),
+    spec("nback2.d_prime", "", SDT, 2, HIGHER, "stream-nback2-d-prime"),
+    spec("nback2.criterion", "", SDT, 2, None, "stream-nback2-criterion"),
+    spec("nback2.mean_hit_rt_ms", "ms", RT_MS, 0, LOWER, "stream-nback2-mean-hit-rt-ms"),
+    spec("nback2.median_hit_rt_ms", "ms", RT_MS, 0, LOWER, "stream-nback2-median-hit-rt-ms"),
+    spec("nback2.sd_hit_rt_ms", "ms", RT_MS, 0, LOWER, "stream-nback2-sd-hit-rt-ms"),
+    spec("nback2.p10_hit_rt_ms", "ms", RT_MS, 0, LOWER, "stream-nback2-p10-hit-rt-ms"),
+    spec("nback2.p90_hit_rt_ms", "ms", RT_MS, 0, LOWER, "stream-nback2-p90-hit-rt-ms"),
+    spec("nback2.n", "", (1.0, 4.0), 0, None, "stream-nback2-n"),
+    spec("nback2.lure_trials", "count", COUNT, 0, None, "stream-nback2-lure-trials"),
+    spec("nback2.lure_false_alarms", "count", COUNT, 0, LOWER, "stream-nback2-lure-false-alarms"),
+    spec("nback2.non_lure_false_alarms", "count", COUNT, 0, LOWER, "stream-nback2-non-lure-false-alarms"),
+    spec("nback2.lure_false_alarm_rate", "ratio", RATIO, 3, LOWER, "stream-nback2-lure-false-alarm-rate"),
+    spec("nback2.non_lure_false_alarm_rate", "ratio", RATIO, 3, LOWER, "stream-nback2-non-lure-false-alarm-rate"),
+    spec("nback2.lure_d_prime", "", SDT, 2, HIGHER, "stream-nback2-lure-d-prime"),
+    // Flanker (`FlankerMetrics`).
+    spec("flanker.total_trials", "count", COUNT, 0, None, "stream-flanker-total-trials"),
+    spec("flanker.congruent_trials", "count", COUNT, 0, None, "stream-flanker-congruent-trials"),
+    spec("flanker.incongruent_trials", "count", COUNT, 0, None, "stream-flanker-incongruent-trials"),
+    spec("flanker.correct", "count", COUNT, 0, None, "stream-flanker-correct"),
+    spec("flanker.errors", "count", COUNT, 0, LOWER, "stream-flanker-errors"),
+    spec("flanker.misses", "count", COUNT, 0, LOWER, "stream-flanker-misses"),
+    spec("flanker.congruent_correct", "count", COUNT, 0, None, "stream-flanker-congruent-correct"),
+    spec("flanker.incongruent_correct", "count", COUNT, 0, None, "stream-flanker-incongruent-correct"),
+    spec("flanker.accuracy", "ratio", RATIO, 3, HIGHER, "stream-flanker-accuracy"),
+    spec("flanker.congruent_accuracy", "ratio", RATIO, 3, HIGHER, "stream-flanker-congruent-accuracy"),
+    spec("flanker.incongruent_accuracy", "ratio", RATIO, 3, HIGHER, "stream-flanker-incongruent-accuracy"),
+    spec("flanker.congruent_mean_rt_ms", "ms", RT_MS, 0, LOWER, "stream-flanker-congruent-mean-rt-ms"),
+    spec("flanker.incongruent_mean_rt_ms", "ms", RT_MS, 0, LOWER, "stream-flanker-incongruent-mean-rt-ms"),
+    spec("flanker.congruent_median_rt_ms", "ms", RT_MS, 0, LOWER, "stream-flanker-congruent-median-rt-ms"),
+    spec("flanker.incongruent_median_rt_ms", "ms", RT_MS, 0, LOWER, "stream-flanker-incongruent-median-rt-ms"),
+    spec("flanker.conflict_effect_ms", "ms", RT_DIFF_MS, 0, LOWER, "stream-flanker-conflict-effect-ms"),
+    spec("flanker.conflict_effect_accuracy", "ratio", (-1.0, 1.0), 3, LOWER, "stream-flanker-conflict-effect-accuracy"),
+    spec("flanker.response_count", "count", COUNT, 0, None, "stream-flanker-response-count"),
+    // DSST (`DsstMetrics`). `symbol_set` is a tag, not a stream.
+    spec("dsst.duration_ms", "ms", (0.0, 3_600_000.0), 0, None, "stream-dsst-duration-ms"),
+    spec("dsst.responses", "count", COUNT, 0, None, "stream-dsst-responses"),
+    spec("dsst.correct", "count", COUNT, 0, HIGHER, "stream-dsst-correct"),
+    spec("dsst.errors", "count", COUNT, 0, LOWER, "stream-dsst-errors"),
+    spec("dsst.correct_per_minute", "1/min", (0.0, 1_000.0), 1, HIGHER, "stream-dsst-correct-per-minute"),
+    spec("dsst.accuracy", "ratio", RATIO, 3, HIGHER, "stream-dsst-accuracy"),
+    spec("dsst.mean_rt_ms", "ms", RT_MS, 0, LOWER, "stream-dsst-mean-rt-ms"),
+    spec("dsst.median_rt_ms", "ms", RT_MS, 0, LOWER, "stream-dsst-median-rt-ms"),
+    // Word recall (`WordRecallMetrics`). `form_id` and `response_mode` are
+    // tags; delayed streams are absent for runs that skipped delayed recall.
+    spec("word_recall.list_length", "count", COUNT, 0, None, "stream-word-recall-list-length"),
+    spec("word_recall.immediate_correct", "count", COUNT, 0, HIGHER, "stream-word-recall-immediate-correct"),
+    spec("word_recall.immediate_intrusions", "count", COUNT, 0, LOWER, "stream-word-recall-immediate-intrusions"),
+    spec("word_recall.immediate_repetitions", "count", COUNT, 0, LOWER, "stream-word-recall-immediate-repetitions"),
+    spec("word_recall.delayed_correct", "count", COUNT, 0, HIGHER, "stream-word-recall-delayed-correct"),
+    spec("word_recall.delayed_intrusions", "count", COUNT, 0, LOWER, "stream-word-recall-delayed-intrusions"),
+    spec("word_recall.delayed_repetitions", "count", COUNT, 0, LOWER, "stream-word-recall-delayed-repetitions"),
+    spec("word_recall.delay_ms", "ms", (0.0, 86_400_000.0), 0, None, "stream-word-recall-delay-ms"),
+    // Delayed over immediate correct; above 1 when more come back later.
+    spec("word_recall.retention", "ratio", (0.0, 100.0), 2, HIGHER, "stream-word-recall-retention"),
+    // Task switching (`TaskSwitchMetrics`).
+    spec("task_switch.total_trials", "count", COUNT, 0, None, "stream-task-switch-total-trials"),
+    spec("task_switch.correct", "count", COUNT, 0, None, "stream-task-switch-correct"),
+    spec("task_switch.errors", "count", COUNT, 0, LOWER, "stream-task-switch-errors"),
+    spec("task_switch.misses", "count", COUNT, 0, LOWER, "stream-task-switch-misses"),
+    spec("task_switch.accuracy", "ratio", RATIO, 3, HIGHER, "stream-task-switch-accuracy"),
+    spec("task_switch.pure_trials", "count", COUNT, 0, None, "stream-task-switch-pure-trials"),
+    spec("task_switch.repeat_trials", "count", COUNT, 0, None, "stream-task-switch-repeat-trials"),
+    spec("task_switch.switch_trials", "count", COUNT, 0, None, "stream-task-switch-switch-trials"),
+    spec("task_switch.pure_median_rt_ms", "ms", RT_MS, 0, LOWER, "stream-task-switch-pure-median-rt-ms"),
+    spec("task_switch.repeat_median_rt_ms", "ms", RT_MS, 0, LOWER, "stream-task-switch-repeat-median-rt-ms"),
+    spec("task_switch.switch_median_rt_ms", "ms", RT_MS, 0, LOWER, "stream-task-switch-switch-median-rt-ms"),
+    spec("task_switch.pure_error_rate", "ratio", RATIO, 3, LOWER, "stream-task-switch-pure-error-rate"),
+    spec("task_switch.repeat_error_rate", "ratio", RATIO, 3, LOWER, "stream-task-switch-repeat-error-rate"),
+    spec("task_switch.switch_error_rate", "ratio", RATIO, 3, LOWER, "stream-task-switch-switch-error-rate"),
+    spec("task_switch.switch_cost_ms", "ms", RT_DIFF_MS, 0, LOWER, "stream-task-switch-switch-cost-ms"),
+    spec("task_switch.mixing_cost_ms", "ms", RT_DIFF_MS, 0, LOWER, "stream-task-switch-mixing-cost-ms"),
+    spec("task_switch.switch_cost_error_rate", "ratio", (-1.0, 1.0), 3, LOWER, "stream-task-switch-switch-cost-error-rate"),
+    spec("task_switch.mixing_cost_error_rate", "ratio", (-1.0, 1.0), 3, LOWER, "stream-task-switch-mixing-cost-error-rate"),
+    spec("task_switch.response_count", "count", COUNT, 0, None, "stream-task-switch-response-count"),
+    // Journal entries (`journal.rs`); the value is the entry's optional dose.
+    spec("journal.meal", "g", (0.0, 2000.0), 0, None, "stream-journal-meal"),
+    spec("journal.medication", "units", (0.0, 10_000.0), 1, None, "stream-journal-medication"),
+    spec("journal.exercise", "min", (0.0, 1440.0), 0, None, "stream-journal-exercise"),
+    spec("journal.sleep", "h", (0.0, 24.0), 1, None, "stream-journal-sleep"),
+    spec("journal.note", "", (0.0, 1_000_000.0), 2, None, "stream-journal-note"),
+    // Apple Health imports (`convert/health.rs`); sleep and workouts are
+    // durations of the recorded interval, with the stage/activity in `kind`.
+    spec("health.heart_rate_bpm", "bpm", (20.0, 300.0), 0, None, "stream-health-heart-rate-bpm"),
+    spec("health.hrv_sdnn_ms", "ms", (0.0, 1000.0), 0, HIGHER, "stream-health-hrv-sdnn-ms"),
+    spec("health.steps", "count", COUNT, 0, None, "stream-health-steps"),
+    spec("health.active_energy_kcal", "kcal", (0.0, 20_000.0), 1, None, "stream-health-active-energy-kcal"),
+    spec("health.sleep_h", "h", (0.0, 24.0), 2, None, "stream-health-sleep-h"),
+    spec("health.workout_min", "min", (0.0, 1440.0), 0, None, "stream-health-workout-min"),
+];
+
+/// The registered spec for `stream`, if any.
+pub fn lookup(stream: &str) -> Option<&'static StreamSpec> {
+    STREAMS.iter().find(|s| s.name == stream)
+}
+
+/// The stream a cognition task's metric is stored under.
+pub fn metric_stream(task: &str, key: &str) -> String {
+    format!("{task}.{key}")
+}
+
+/// Whether `stream` is a user-defined [`CUSTOM_PREFIX`] stream.
+pub fn is_custom(stream: &str) -> bool {
+    stream.starts_with(CUSTOM_PREFIX) && stream.len() > CUSTOM_PREFIX.len()
+}
+
+/// Check one observation against the registry: the stream must be registered
+/// (or custom) and the value finite and in range.
+pub fn validate(o: &Observation) -> Result<()> {
+    if is_custom(&o.stream) {
+        return if o.value.is_finite() {
+            Ok(())
+        } else {
+            Err(invalid(o))
+        };
+    }
+    let spec = lookup(&o.stream).ok_or_else(|| StoreError::UnknownStream(o.stream.clone()))?;
+    if spec.accepts(o.value) {
+        Ok(())
+    } else {
+        Err(invalid(o))
+    }
+}
+
+/// [`validate`] a whole batch; the first failure refuses all of it.
+pub fn validate_all(observations: &[Observation]) -> Result<()> {
+    observations.iter().try_for_each(validate)
+}
+
+fn invalid(o: &Observation) -> StoreError {
+    StoreError::InvalidValue {
+        stream: o.stream.clone(),
+        value: o.value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use time::macros::datetime;
+
+    fn obs(stream: &str, value: f64) -> Observation {
+        Observation::new(stream, datetime!(2026-06-19 08:00:00), value, "", "test")
+    }
+
+    #[test]
+    fn registry_is_consistent() {
+        let mut names = HashSet::new();
+        let mut labels = HashSet::new();
+        for s in STREAMS {
+            assert!(names.insert(s.name), "duplicate stream {}", s.name);
+            assert!(labels.insert(s.label_key), "duplicate label key {}", s.label_key);
+            assert!(s.min < s.max, "{} has an empty range", s.name);
+            assert!(s.name.contains('.') && !is_custom(s.name), "{} is not a dotted name", s.name);
+            // Fluent ids: `stream-` + the name with separators hyphenated.
+            let expected = format!("stream-{}", s.name.replace(['.', '_'], "-"));
+            assert_eq!(s.label_key, expected);
+        }
+    }
+
+    #[test]
+    fn known_streams_in_range_are_accepted() {
+        assert!(validate(&obs(GLUCOSE, 94.0)).is_ok());
+        assert!(validate(&obs("pvt.median_rt_ms", 312.5)).is_ok());
+        assert!(validate(&obs("pvt.mean_reciprocal_rt_per_s", 3.4)).is_ok());
+        assert!(validate(&obs("pvt.performance_score", 0.95)).is_ok());
+        assert!(validate(&obs("nback2.d_prime", -0.4)).is_ok());
+        assert!(validate(&obs("nback2.lure_false_alarm_rate", 0.25)).is_ok());
+        assert!(validate(&obs("nback2.lure_d_prime", 1.2)).is_ok());
+        assert!(validate(&obs("flanker.conflict_effect_ms", -12.0)).is_ok());
+        assert!(validate(&obs("dsst.correct_per_minute", 42.7)).is_ok());
+        assert!(validate(&obs("custom.sleep_hours", 7.5)).is_ok());
+        assert_eq!(lookup("nback2.accuracy").unwrap().format(0.8333), "0.833");
+    }
+
+    #[test]
+    fn unknown_streams_and_bad_values_are_flagged() {
+        assert!(matches!(validate(&obs("pvt.median_rt", 300.0)), Err(StoreError::UnknownStream(s)) if s == "pvt.median_rt"));
+        assert!(matches!(validate(&obs("custom.", 1.0)), Err(StoreError::UnknownStream(_))));
+        assert!(matches!(validate(&obs(GLUCOSE, 0.0)), Err(StoreError::InvalidValue { .. })));
+        assert!(matches!(validate(&obs("nback2.accuracy", 83.0)), Err(StoreError::InvalidValue { .. })));
+        assert!(matches!(validate(&obs("pvt.lapse_probability", 1.5)), Err(StoreError::InvalidValue { .. })));
+        assert!(matches!(validate(&obs("custom.x", f64::NAN)), Err(StoreError::InvalidValue { .. })));
+
+        let batch = [obs(GLUCOSE, 100.0), obs("glucose.mmol_l", 5.5)];
+        assert!(matches!(validate_all(&batch), Err(StoreError::UnknownStream(_))));
+    }
+}
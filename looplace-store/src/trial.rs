@@ -0,0 +1,104 @@
+//! Per-trial raw data behind a cognition session, kept only when the user opts
+//! in.
+//!
+//! A [`crate::session::SessionRecord`] keeps a run's aggregate metrics; the
+//! trials table keeps every stimulus and response behind them, keyed by
+//! `(session_id, index)`. That's enough to recompute metrics under new
+//! definitions (reciprocal RT, another lapse threshold) and to plot a run
+//! trial by trial. Times are milliseconds, measured by the task's monotonic
+//! clock; nothing here is a wall-clock timestamp.
+
+use crate::error::Result;
+use crate::store::Store;
+
+/// One trial of a cognition session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrialRecord {
+    pub session_id: String,
+    /// Position in the run, from 0.
+    pub index: i64,
+    /// Scheduled wait before the stimulus (PVT's inter-trial interval, the
+    /// 2-back's interstimulus interval).
+    pub iti_ms: Option<f64>,
+    /// Stimulus onset since the run started (PVT) or since its first stimulus
+    /// (2-back). `None` if the stimulus never appeared.
+    pub onset_ms: Option<f64>,
+    /// The response, on the same clock as `onset_ms`. `None` without one.
+    pub response_ms: Option<f64>,
+    /// Response time after onset, as the task scored it.
+    pub rt_ms: Option<f64>,
+    /// The task's verdict: `reaction`, `lapse` or `false_start` for the PVT;
+    /// `hit`, `miss`, `false_alarm` or `correct_rejection` for the 2-back;
+    /// `correct`, `error` or `miss` for the flanker; `correct` or `error` for
+    /// the DSST; `recalled`, `intrusion` or `repetition` (each also with a
+    /// `delayed_` prefix) for word recall, whose "trials" are its answers;
+    /// `correct`, `error` or `miss` for task switching.
+    pub outcome: String,
+    /// What was shown (the 2-back's letter, the flanker's row of arrows, the
+    /// DSST's symbol, task switching's `rule:digit:kind`, e.g.
+    /// `parity:7:switch`), or the word recall answer as given.
+    pub stimulus: Option<String>,
+    /// N-back: the stimulus matched the one n back.
+    pub target: Option<bool>,
+    /// N-back: a lure (matched the stimulus n − 1 or n + 1 back).
+    pub lure: Option<bool>,
+}
+
+/// The trials of `session_id`, in order; empty if none were kept.
+pub fn for_session(store: &dyn Store, session_id: &str) -> Result<Vec<TrialRecord>> {
+    Ok(store
+        .trials()?
+        .into_iter()
+        .filter(|t| t.session_id == session_id)
+        .collect())
+}
+
+/// Reaction times of the scored responses (`reaction` and `hit`), in order —
+/// the input to any RT metric.
+pub fn reaction_times(trials: &[TrialRecord]) -> Vec<f64> {
+    trials
+        .iter()
+        .filter(|t| matches!(t.outcome.as_str(), "reaction" | "hit"))
+        .filter_map(|t| t.rt_ms)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+
+    fn trial(session_id: &str, index: i64, outcome: &str, rt_ms: Option<f64>) -> TrialRecord {
+        TrialRecord {
+            session_id: session_id.into(),
+            index,
+            iti_ms: Some(4_000.0),
+            onset_ms: Some(4_000.0 * (index + 1) as f64),
+            response_ms: None,
+            rt_ms,
+            outcome: outcome.into(),
+            stimulus: None,
+            target: None,
+            lure: None,
+        }
+    }
+
+    #[test]
+    fn a_session_reads_back_its_own_trials_in_order() {
+        let mut store = MemoryStore::new();
+        store
+            .upsert_trials(&[
+                trial("pvt-1", 1, "lapse", None),
+                trial("pvt-2", 0, "reaction", Some(280.0)),
+                trial("pvt-1", 0, "reaction", Some(301.5)),
+                trial("pvt-1", 2, "false_start", Some(40.0)),
+            ])
+            .unwrap();
+
+        let trials = for_session(&store, "pvt-1").unwrap();
+        let indices: Vec<i64> = trials.iter().map(|t| t.index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+        // Lapses and false starts aren't reactions, whatever their RT.
+        assert_eq!(reaction_times(&trials), vec![301.5]);
+    }
+}
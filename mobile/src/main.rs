@@ -1,7 +1,9 @@
 use dioxus::prelude::*;
 
 use ui::components::Navbar;
-use ui::views::{Glucose, Home, NBack2, Pvt, Results};
+use ui::views::{
+    Dsst, Flanker, Glucose, Home, Import, Journal, NBack2, Pvt, Results, TaskSwitch, WordRecall,
+};
 
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
@@ -13,10 +15,22 @@ enum Route {
     Pvt {},
     #[route("/test/nback")]
     NBack2 {},
+    #[route("/test/flanker")]
+    Flanker {},
+    #[route("/test/dsst")]
+    Dsst {},
+    #[route("/test/word-recall")]
+    WordRecall {},
+    #[route("/test/task-switch")]
+    TaskSwitch {},
     #[route("/results")]
     Results {},
     #[route("/glucose")]
     Glucose {},
+    #[route("/journal")]
+    Journal {},
+    #[route("/import")]
+    Import {},
 }
 
 const MAIN_CSS: Asset = asset!("/assets/main.css");
@@ -28,6 +42,7 @@ fn main() {
 #[component]
 fn App() -> Element {
     // Build cool things ✌️
+    ui::core::store::use_store_provider();
 
     rsx! {
         // Global app resources
@@ -70,6 +85,26 @@ fn MobileNavbar() -> Element {
                     to: Route::NBack2 {},
                     "2-back"
                 }
+                Link {
+                    class: "navbar__link",
+                    to: Route::Flanker {},
+                    "Flanker"
+                }
+                Link {
+                    class: "navbar__link",
+                    to: Route::Dsst {},
+                    "DSST"
+                }
+                Link {
+                    class: "navbar__link",
+                    to: Route::WordRecall {},
+                    "Recall"
+                }
+                Link {
+                    class: "navbar__link",
+                    to: Route::TaskSwitch {},
+                    "Switch"
+                }
                 Link {
                     class: "navbar__link",
                     to: Route::Results {},
@@ -80,6 +115,16 @@ fn MobileNavbar() -> Element {
                     to: Route::Glucose {},
                     "Glucose"
                 }
+                Link {
+                    class: "navbar__link",
+                    to: Route::Journal {},
+                    "Journal"
+                }
+                Link {
+                    class: "navbar__link",
+                    to: Route::Import {},
+                    "Import"
+                }
             }
         }
 
@@ -8,9 +8,15 @@
 ## Navigation
 nav-home = Home
 nav-pvt = PVT
-nav-nback = 2-back
+nav-nback = N-back
+nav-flanker = Flanker
+nav-dsst = DSST
+nav-word-recall = Recall
+nav-task-switch = Switch
 nav-results = Results
 nav-glucose = Glucose
+nav-journal = Journal
+nav-import = Import
 nav-language-label = Language
 
 ## Brand & general
@@ -75,9 +81,17 @@ value-missing = —
 pvt-how-summary = How the task works
 pvt-how-step-wait = Wait for the milliseconds counter to appear in the centre.
 pvt-how-step-respond = Tap or press space as soon as you see it—speed and consistency both matter.
-pvt-how-step-jitter = Runs use 2–10 s jitter; false starts add delay, lapses ≥500 ms are flagged.
+# $min, $max – integer seconds bounding the wait before each stimulus.
+pvt-how-step-jitter = Runs use { $min }–{ $max } s jitter; false starts add delay, lapses ≥500 ms are flagged.
 # $trials – integer number of target valid reactions in a session.
 pvt-how-step-target = Each session targets { $trials } valid reactions.
+# $minutes – integer length of a time-based session.
+pvt-how-step-duration = Each session runs for { $minutes } minutes; a reaction slower than 30 s counts as a lapse.
+pvt-preset-label = Protocol
+pvt-preset-quick = Quick (20 trials)
+pvt-preset-pvt-b-3min = PVT-B (3 min)
+pvt-preset-5min = 5-minute PVT
+pvt-preset-10min = 10-minute PVT (standard)
 pvt-start = Start
 pvt-progress-label = Progress
 pvt-last-session = Last session
@@ -99,11 +113,21 @@ pvt-error-generic = ⚠️ { $message }
 ## N-Back (2-back) task (instructions & UI)
 nback-how-summary = How the task works
 nback-how-step-timing = Each letter displays for 0.5 s, followed by 2.5 s of blank interval.
-nback-how-step-rule = Press space (or tap the pad) whenever the letter matches the one from two trials ago.
+# $n – the N of the N-back (integer 1–4).
+nback-how-step-rule = Press space (or tap the pad) whenever the letter matches { $n ->
+        [one] the one just before it
+       *[other] the one from { $n } trials ago
+    }.
 nback-how-step-practice = Practice block lasts ~35 seconds; main run is about 3 minutes.
 nback-how-step-strategy = Focus on accuracy first, then speed. False alarms tax d′ just like misses.
 nback-start-practice = Start practice
 nback-start-main = Start main session
+nback-level-label = Level
+# $n – the N of the N-back (integer 1–4).
+nback-level-option = { $n }-back
+nback-adaptive-label = Adaptive: step the level up or down after each main session, by accuracy
+nback-metric-level = Level
+nback-next-level = Next session: { $n }-back
 nback-practice-recap = Practice recap
 # $hits – number of hits; $targets – number of target trials; $false_alarms – number of false alarms; $accuracy – rounded percentage (no % sign inside value)
 nback-practice-metrics = Hits { $hits } / { $targets } • False alarms { $false_alarms } • Accuracy { $accuracy }%
@@ -118,6 +142,9 @@ nback-metric-correct-rejections = Correct rejections
 nback-metric-accuracy = Accuracy
 nback-metric-dprime = d′
 nback-metric-criterion = Criterion
+nback-metric-lure-false-alarms = Lure false alarms
+nback-metric-non-lure-false-alarms = Other false alarms
+nback-metric-lure-dprime = Lure d′
 nback-metric-median-hit-rt = Median hit RT
 nback-metric-mean-hit-rt = Mean hit RT
 nback-metric-hit-rt-p10p90 = Hit RT p10/p90
@@ -126,6 +153,10 @@ nback-error-generic = ⚠️ { $message }
 ## Common (shared UI labels)
 common-cancel = Cancel
 common-progress = Progress
+common-pause = Pause
+common-resume = Resume
+# Shown over the task canvas while a run is paused
+task-paused-note = Paused. The interrupted trial is discarded; resume when you are ready.
 
 ## PVT guidance (dynamic status text)
 pvt-guidance-idle = Press start, then wait for the milliseconds counter to appear.
@@ -133,6 +164,7 @@ pvt-guidance-waiting = Hold steady… the counter will appear soon.
 pvt-guidance-active = Tap or press space the moment the counter appears.
 pvt-guidance-completed = Session complete. Start again when ready.
 pvt-guidance-aborted = Run cancelled. Start to retry.
+pvt-guidance-paused = Paused. Resume when ready.
 
 ## N-Back additional labels
 nback-get-ready = Get ready
@@ -141,12 +173,157 @@ nback-mode-main = Main session
 # Accessibility label for reaction target / hitbox
 nback-aria-respond = Respond to current letter
 
+## Flanker task (instructions & UI)
+flanker-how-summary = How the task works
+flanker-how-step-rule = A row of five arrows appears; answer with the direction of the middle one and ignore the others.
+flanker-how-step-keys = Press ← or F for left, → or J for right, or tap the arrow buttons.
+flanker-how-step-timing = Each row stays up for 1.5 s or until you answer; a cross marks the gap between rows.
+flanker-how-step-practice = Practice is 8 rows with feedback; the main run is 48 rows, about 2 minutes, without feedback.
+flanker-start-practice = Start practice
+flanker-start-main = Start main session
+flanker-get-ready = Get ready
+flanker-mode-practice = Practice
+flanker-mode-main = Main session
+flanker-practice-recap = Practice recap
+flanker-last-session = Last main session
+flanker-metrics-placeholder = Metrics will appear after the first completed session.
+flanker-metric-accuracy = Accuracy
+flanker-metric-congruent-accuracy = Congruent accuracy
+flanker-metric-incongruent-accuracy = Incongruent accuracy
+flanker-metric-congruent-rt = Congruent median RT
+flanker-metric-incongruent-rt = Incongruent median RT
+flanker-metric-conflict-effect = Conflict effect
+flanker-metric-errors = Errors
+flanker-metric-misses = Misses
+flanker-feedback-correct = Correct
+flanker-feedback-error = Wrong direction
+flanker-feedback-miss = Too slow
+# Accessibility labels for the response buttons
+flanker-aria-left = Middle arrow points left
+flanker-aria-right = Middle arrow points right
+# $message – error text
+flanker-error-generic = ⚠️ { $message }
+
+## DSST task (instructions & UI)
+dsst-how-summary = How the task works
+dsst-how-step-rule = A key pairs nine symbols with the digits 1 to 9. Answer each symbol shown with its digit.
+dsst-how-step-keys = Press the number keys 1–9, or tap the digit buttons.
+dsst-how-step-timing = A new symbol appears as soon as you answer; the session lasts 90 seconds.
+dsst-how-step-practice = Practice runs 20 seconds with feedback. The symbols change between sessions, so there is no key to memorise.
+dsst-start-practice = Start practice
+dsst-start-main = Start main session
+dsst-get-ready = Get ready
+dsst-mode-practice = Practice
+dsst-mode-main = Main session
+dsst-progress-correct = Correct
+dsst-practice-recap = Practice recap
+dsst-last-session = Last main session
+dsst-metrics-placeholder = Metrics will appear after the first completed session.
+dsst-metric-per-minute = Correct per minute
+dsst-metric-correct = Correct
+dsst-metric-errors = Errors
+dsst-metric-accuracy = Accuracy
+dsst-metric-median-rt = Median RT
+dsst-feedback-correct = Correct
+dsst-feedback-error = Check the key
+# Accessibility label for the symbol–digit key
+dsst-aria-key = Symbol key
+# $message – error text
+dsst-error-generic = ⚠️ { $message }
+
+## Word recall task (instructions & UI)
+word-recall-how-summary = How the task works
+word-recall-how-step-study = Twelve words appear one at a time, two seconds each. Try to remember them all.
+word-recall-how-step-recall = Then give as many as you remember, in any order, by typing them or picking them from a word bank.
+word-recall-how-step-delayed = After a main session, come back about ten minutes later to recall the same words again, without seeing them.
+word-recall-how-step-forms = Each session uses a different list, so repeat sessions stay fair. Practice uses a short list with feedback.
+word-recall-response-mode = Answer by
+word-recall-mode-typed = Typing the words
+word-recall-mode-selected = Picking from a word bank
+word-recall-start-practice = Start practice
+word-recall-start-main = Start main session
+word-recall-get-ready = Get ready
+word-recall-mode-practice = Practice
+word-recall-mode-main = Main session
+word-recall-mode-delayed = Delayed recall
+word-recall-prompt = Which words were on the list?
+word-recall-add = Add
+word-recall-done = I'm done
+word-recall-progress-given = Answers
+word-recall-delayed-title = Delayed recall pending
+# $minutes – minutes since immediate recall; $suggested – recommended delay in minutes
+word-recall-delayed-waiting = Your immediate recall was saved { $minutes } min ago. For the delayed recall, wait about { $suggested } min — another task in the meantime is ideal.
+word-recall-start-delayed = Start delayed recall
+word-recall-skip-delayed = Skip delayed recall
+word-recall-practice-recap = Practice recap
+word-recall-last-session = Last main session
+word-recall-metrics-placeholder = Metrics will appear after the first completed session.
+word-recall-metric-correct = Recalled
+word-recall-metric-immediate = Immediate recall
+word-recall-metric-delayed = Delayed recall
+word-recall-metric-intrusions = Intrusions
+word-recall-metric-repetitions = Repetitions
+# Accessibility label for the typed-answer field
+word-recall-aria-entry = Word you remember
+# $message – error text
+word-recall-error-generic = ⚠️ { $message }
+# Word pools: four equivalent forms of twelve words, plus practice. Never edit
+# a versioned pool; add word-recall-v2-* instead (see tasks/word_recall/pool.rs).
+word-recall-v1-form-a = apple, river, candle, horse, window, garden, ticket, button, mountain, pencil, blanket, lemon
+word-recall-v1-form-b = bread, forest, mirror, tiger, basket, ocean, hammer, jacket, bottle, castle, flower, wagon
+word-recall-v1-form-c = chair, island, kettle, rabbit, ladder, desert, wallet, carpet, onion, bridge, violin, pillow
+word-recall-v1-form-d = cloud, tractor, spoon, monkey, lantern, valley, helmet, cherry, rocket, sugar, engine, towel
+word-recall-v1-practice = lamp, coin, boat, shoe
+word-recall-v1-practice-foils = cup, door, bell, rope
+
+## Task-switching task (instructions & UI)
+task-switch-how-summary = How the task works
+task-switch-how-step-rules = A digit appears with a cue above it. ODD / EVEN: is the digit odd or even? LOW / HIGH: is it below or above 5?
+task-switch-how-step-keys = Press ← or F for odd or low, → or J for even or high, or tap the buttons.
+task-switch-how-step-blocks = The main run has a block of each rule on its own, then a longer block where the cue changes from trial to trial — about 3 minutes in all.
+task-switch-how-step-practice = Practice is 12 mixed trials with feedback; the main run has no feedback.
+task-switch-start-practice = Start practice
+task-switch-start-main = Start main session
+task-switch-get-ready = Get ready
+task-switch-mode-practice = Practice
+task-switch-mode-main = Main session
+task-switch-cue-parity = ODD / EVEN
+task-switch-cue-magnitude = LOW / HIGH
+task-switch-block-parity = Next block: odd or even only
+task-switch-block-magnitude = Next block: lower or higher than 5 only
+task-switch-block-mixed = Next block: both rules — follow the cue
+task-switch-response-left = Odd · Low
+task-switch-response-right = Even · High
+task-switch-practice-recap = Practice recap
+task-switch-last-session = Last main session
+task-switch-metrics-placeholder = Metrics will appear after the first completed session.
+task-switch-metric-accuracy = Accuracy
+task-switch-metric-pure-rt = Single-rule median RT
+task-switch-metric-repeat-rt = Repeat median RT
+task-switch-metric-switch-rt = Switch median RT
+task-switch-metric-switch-cost = Switch cost
+task-switch-metric-mixing-cost = Mixing cost
+task-switch-metric-errors = Errors
+task-switch-metric-misses = Misses
+task-switch-feedback-correct = Correct
+task-switch-feedback-error = Wrong key for this cue
+task-switch-feedback-miss = Too slow
+# Accessibility labels for the response buttons
+task-switch-aria-left = Odd, or lower than 5
+task-switch-aria-right = Even, or higher than 5
+# $message – error text
+task-switch-error-generic = ⚠️ { $message }
+
 ## Home page
 home-title = Looplace
 home-tagline-short = Small loops • clear minds.
 home-intro-1 = Measure your focus and working memory, track your glucose, and explore how your body and mind move together — all stored locally on this device.
 home-feature-pvt = Precise PVT timing with local metrics
-home-feature-nback = 2-back working memory sessions
+home-feature-nback = N-back working memory sessions (1- to 4-back)
+home-feature-flanker = Flanker sessions for inhibitory control (conflict effect)
+home-feature-dsst = Digit symbol substitution for processing speed
+home-feature-word-recall = Word-list recall, immediate and delayed, for episodic memory
+home-feature-task-switch = Cued task switching for mental flexibility (switch and mixing costs)
 home-feature-glucose = Blood glucose from your FreeStyle Libre 2
 home-feature-local = Everything stored locally, yours to export
 home-cta = Choose a task — or sync your reader — to get started.
@@ -157,14 +334,32 @@ page-pvt-title = Psychomotor Vigilance Task
 page-pvt-intro = Run a short vigilance block to capture reaction time metrics and lapse counts.
 
 ## N-Back page
-page-nback-title = 2-back Working Memory
-page-nback-intro = Start with a short guided warm-up, then follow the 2-back stream—respond whenever the letter matches the one from two trials ago to capture sensitivity (d′), response bias, and reaction-time trends.
+page-nback-title = N-back Working Memory
+page-nback-intro = Start with a short guided warm-up, then follow the letter stream—respond whenever the letter matches the one N trials back to capture sensitivity (d′), response bias, and reaction-time trends.
+
+## Flanker page
+page-flanker-title = Flanker Inhibitory Control
+page-flanker-intro = Report the direction of the middle arrow while ignoring its neighbours. Comparing rows where they agree with rows where they conflict measures how well you filter out distraction.
+
+## DSST page
+page-dsst-title = Digit Symbol Substitution
+page-dsst-intro = Match each symbol to its digit using the key, as fast and accurately as you can. How many you code correctly in 90 seconds tracks your processing speed.
+
+## Word recall page
+page-word-recall-title = Word-List Recall
+page-word-recall-intro = Study a short list of words, then recall as many as you can — now, and again after a delay. How much stays with you tracks episodic memory.
+
+## Task switching page
+page-task-switch-title = Task Switching
+page-task-switch-intro = Sort digits by one rule or another as a cue tells you. The extra time a change of rule costs you measures how flexibly you shift between tasks.
 
 ## Results page (extra)
 results-page-intro = Review summaries from recent runs, inspect quality checks, and export data for deeper analysis.
 results-refresh = Refresh
 results-empty = No runs recorded yet. Completed sessions will appear after you finish a task.
 results-error-prefix = ⚠️
+results-keep-trials = Keep per-trial data for new cognition runs
+results-keep-trials-hint = Stores every stimulus and response locally, so metrics can be recomputed later and a run can be plotted trial by trial.
 
 ## Glucose / health page
 glucose-title = Glucose
@@ -173,3 +368,200 @@ glucose-sync = Sync from reader
 glucose-syncing = Syncing…
 glucose-empty = No glucose readings yet. Connect your FreeStyle Libre 2 and choose “Sync from reader”.
 glucose-desktop-only = Glucose sync runs in the Looplace desktop app, where it can reach your reader over USB.
+glucose-locked = This health store is encrypted. Enter your passphrase to view and sync readings.
+glucose-passphrase = Passphrase
+glucose-unlock = Unlock
+glucose-lock = Lock
+glucose-unlocked = Encrypted · unlocked for this session
+glucose-encrypt = Encrypt with passphrase
+glucose-encrypt-hint = Stored readings become unreadable without this passphrase. It can’t be recovered if you forget it.
+
+## Journal
+journal-title = Journal
+journal-intro = Log meals, medication, exercise, sleep and anything else worth noting. Entries appear as markers on your glucose chart and cognition trends.
+journal-desktop-only = The journal is kept in the Looplace desktop app’s local health store.
+journal-locked = Your health store is encrypted. Unlock it on the Glucose page to view and add entries.
+journal-empty = No entries yet. Add your first one above.
+journal-category = Type
+journal-start = Start
+journal-end = End (optional)
+journal-amount = Amount (optional)
+journal-notes = Notes
+journal-add = Add entry
+journal-update = Save changes
+journal-cancel = Cancel
+journal-edit = Edit
+journal-delete = Delete
+
+## Import
+import-title = Import data
+import-intro = Bring your history from other apps into the local health store. Files are read on this computer; nothing is uploaded.
+import-desktop-only = Imports go into the Looplace desktop app’s local health store.
+import-locked = Your health store is encrypted. Unlock it on the Glucose page before importing.
+import-apple-health = Apple Health
+import-apple-health-hint = On your iPhone, open Health, tap your profile, choose “Export All Health Data”, unzip the export and pick export.xml. Heart rate, HRV, steps, active energy, sleep and workouts are imported.
+import-running = Importing… { $percent }% ({ $imported } records)
+import-done = Imported { $imported } records ({ $skipped } skipped).
+import-mapped = Spreadsheet (CSV or JSON)
+import-mapped-hint = For things you track yourself, such as weight, blood pressure or mood. Choose a file, say which columns hold the time and the value and where they belong, then preview before importing. Save the mapping to reuse it next time.
+import-mapped-saved = Saved mapping
+import-mapped-none = — New mapping —
+import-mapped-name = Mapping name
+import-mapped-time-column = Time column
+import-mapped-time-format = Time format
+import-mapped-format-pattern = Pattern
+import-mapped-format-rfc3339 = ISO 8601 with offset
+import-mapped-format-unix-s = Unix seconds
+import-mapped-format-unix-ms = Unix milliseconds
+import-mapped-pattern-hint = For example [day]/[month]/[year] [hour]:[minute]
+import-mapped-value-column = Value column
+import-mapped-choose-column = — Choose a column —
+import-mapped-stream = Stream
+import-mapped-stream-hint = A built-in stream, or custom.your_name
+import-mapped-unit = Unit
+import-mapped-tz = Time zone
+import-mapped-preview = Preview
+import-mapped-import = Import
+import-mapped-save = Save mapping
+import-mapped-delete = Delete mapping
+import-mapped-ready = { $ready } rows ready to import, { $skipped } skipped.
+import-bundle = Move or share your data
+import-bundle-hint = Export a zip of Parquet and CSV files with a manifest describing them, to open in other tools, hand to your clinician, or import on another computer. A full export includes your settings; importing one restores them.
+import-bundle-scope = What to export
+import-bundle-scope-all = Everything
+import-bundle-scope-glucose = Glucose and ketones
+import-bundle-scope-health = Apple Health data
+import-bundle-scope-journal = Journal
+import-bundle-scope-cognition = Cognitive tests
+import-bundle-since = From (optional)
+import-bundle-export = Export bundle
+import-bundle-import = Import a bundle
+import-bundle-exported = Bundle saved to { $path }
+import-bundle-imported = Imported { $observations } new measurements and { $sessions } new sessions.
+import-history = Sync and import history
+import-history-hint = Every reader sync and file import, newest first, with the measurements it still holds. Rolling one back deletes those measurements; anything it overwrote comes back only from a snapshot.
+import-history-empty = Nothing synced or imported yet.
+import-history-when = When
+import-history-kind = Kind
+import-history-source = Source
+import-history-rows = Measurements
+import-history-rollback = Roll back
+import-history-confirm = Really roll back?
+import-history-rolled-back = Rolled back
+import-history-removed = Removed { $removed } measurements.
+import-history-kind-reader = Reader sync
+import-history-kind-libreview = LibreView export
+import-history-kind-dexcom = Dexcom Clarity export
+import-history-kind-nightscout = Nightscout export
+import-history-kind-apple-health = Apple Health export
+import-history-kind-mapped = Spreadsheet
+
+## Stream labels (looplace-store's stream registry)
+stream-glucose-mg-dl = Glucose
+stream-ketone-mmol-l = Ketones
+stream-pvt-total-trials = PVT trials
+stream-pvt-reacted-trials = PVT responses
+stream-pvt-median-rt-ms = PVT median RT
+stream-pvt-mean-rt-ms = PVT mean RT
+stream-pvt-sd-rt-ms = PVT RT variability (SD)
+stream-pvt-p10-rt-ms = PVT fastest RT (P10)
+stream-pvt-p90-rt-ms = PVT slowest RT (P90)
+stream-pvt-lapses-ge-500ms = PVT lapses ≥500 ms
+stream-pvt-minor-lapses-355-499ms = PVT minor lapses 355–499 ms
+stream-pvt-false-starts = PVT false starts
+stream-pvt-time-on-task-slope-ms-per-min = PVT time-on-task slope
+stream-pvt-mean-reciprocal-rt-per-s = PVT response speed (mean 1/RT)
+stream-pvt-fastest-10pct-rt-ms = PVT fastest 10% RT
+stream-pvt-slowest-10pct-rt-ms = PVT slowest 10% RT
+stream-pvt-lapse-probability = PVT lapse probability
+stream-pvt-performance-score = PVT performance score
+stream-nback2-total-trials = N-back trials
+stream-nback2-target-trials = N-back target trials
+stream-nback2-non-target-trials = N-back non-target trials
+stream-nback2-hits = N-back hits
+stream-nback2-misses = N-back misses
+stream-nback2-false-alarms = N-back false alarms
+stream-nback2-correct-rejections = N-back correct rejections
+stream-nback2-response-count = N-back responses
+stream-nback2-hit-rate = N-back hit rate
+stream-nback2-false-alarm-rate = N-back false alarm rate
+stream-nback2-accuracy = N-back accuracy
+stream-nback2-d-prime = N-back d′
+stream-nback2-criterion = N-back criterion
+stream-nback2-mean-hit-rt-ms = N-back mean hit RT
+stream-nback2-median-hit-rt-ms = N-back median hit RT
+stream-nback2-sd-hit-rt-ms = N-back hit RT variability (SD)
+stream-nback2-p10-hit-rt-ms = N-back fastest hit RT (P10)
+stream-nback2-p90-hit-rt-ms = N-back slowest hit RT (P90)
+stream-nback2-n = N-back level
+stream-nback2-lure-trials = N-back lure trials
+stream-nback2-lure-false-alarms = N-back lure false alarms
+stream-nback2-non-lure-false-alarms = N-back non-lure false alarms
+stream-nback2-lure-false-alarm-rate = N-back lure false alarm rate
+stream-nback2-non-lure-false-alarm-rate = N-back non-lure false alarm rate
+stream-nback2-lure-d-prime = N-back lure d′
+stream-flanker-total-trials = Flanker trials
+stream-flanker-congruent-trials = Flanker congruent trials
+stream-flanker-incongruent-trials = Flanker incongruent trials
+stream-flanker-correct = Flanker correct responses
+stream-flanker-errors = Flanker errors
+stream-flanker-misses = Flanker misses
+stream-flanker-congruent-correct = Flanker congruent correct
+stream-flanker-incongruent-correct = Flanker incongruent correct
+stream-flanker-accuracy = Flanker accuracy
+stream-flanker-congruent-accuracy = Flanker congruent accuracy
+stream-flanker-incongruent-accuracy = Flanker incongruent accuracy
+stream-flanker-congruent-mean-rt-ms = Flanker congruent mean RT
+stream-flanker-incongruent-mean-rt-ms = Flanker incongruent mean RT
+stream-flanker-congruent-median-rt-ms = Flanker congruent median RT
+stream-flanker-incongruent-median-rt-ms = Flanker incongruent median RT
+stream-flanker-conflict-effect-ms = Flanker conflict effect (RT)
+stream-flanker-conflict-effect-accuracy = Flanker conflict effect (accuracy)
+stream-flanker-response-count = Flanker responses
+stream-dsst-duration-ms = DSST duration
+stream-dsst-responses = DSST responses
+stream-dsst-correct = DSST correct
+stream-dsst-errors = DSST errors
+stream-dsst-correct-per-minute = DSST correct per minute
+stream-dsst-accuracy = DSST accuracy
+stream-dsst-mean-rt-ms = DSST mean RT
+stream-dsst-median-rt-ms = DSST median RT
+stream-word-recall-list-length = Word recall list length
+stream-word-recall-immediate-correct = Word recall immediate correct
+stream-word-recall-immediate-intrusions = Word recall immediate intrusions
+stream-word-recall-immediate-repetitions = Word recall immediate repetitions
+stream-word-recall-delayed-correct = Word recall delayed correct
+stream-word-recall-delayed-intrusions = Word recall delayed intrusions
+stream-word-recall-delayed-repetitions = Word recall delayed repetitions
+stream-word-recall-delay-ms = Word recall delay
+stream-word-recall-retention = Word recall retention
+stream-task-switch-total-trials = Task switching trials
+stream-task-switch-correct = Task switching correct
+stream-task-switch-errors = Task switching errors
+stream-task-switch-misses = Task switching misses
+stream-task-switch-accuracy = Task switching accuracy
+stream-task-switch-pure-trials = Task switching single-rule trials
+stream-task-switch-repeat-trials = Task switching repeat trials
+stream-task-switch-switch-trials = Task switching switch trials
+stream-task-switch-pure-median-rt-ms = Task switching single-rule median RT
+stream-task-switch-repeat-median-rt-ms = Task switching repeat median RT
+stream-task-switch-switch-median-rt-ms = Task switching switch median RT
+stream-task-switch-pure-error-rate = Task switching single-rule error rate
+stream-task-switch-repeat-error-rate = Task switching repeat error rate
+stream-task-switch-switch-error-rate = Task switching switch error rate
+stream-task-switch-switch-cost-ms = Switch cost (RT)
+stream-task-switch-mixing-cost-ms = Mixing cost (RT)
+stream-task-switch-switch-cost-error-rate = Switch cost (errors)
+stream-task-switch-mixing-cost-error-rate = Mixing cost (errors)
+stream-task-switch-response-count = Task switching responses
+stream-journal-meal = Meal
+stream-journal-medication = Medication
+stream-journal-exercise = Exercise
+stream-journal-sleep = Sleep
+stream-journal-note = Note
+stream-health-heart-rate-bpm = Heart rate
+stream-health-hrv-sdnn-ms = Heart rate variability (SDNN)
+stream-health-steps = Steps
+stream-health-active-energy-kcal = Active energy
+stream-health-sleep-h = Sleep
+stream-health-workout-min = Workout
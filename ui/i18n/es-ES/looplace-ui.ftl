@@ -6,9 +6,15 @@
 ## Navegación
 nav-home = Inicio
 nav-pvt = PVT
-nav-nback = 2-back
+nav-nback = N-back
+nav-flanker = Flanker
+nav-dsst = DSST
+nav-word-recall = Recuerdo
+nav-task-switch = Alternancia
 nav-results = Resultados
 nav-glucose = Glucosa
+nav-journal = Diario
+nav-import = Importar
 nav-language-label = Idioma
 
 
@@ -78,9 +84,17 @@ value-missing = —
 pvt-how-summary = Cómo funciona la tarea
 pvt-how-step-wait = Espera a que aparezca el contador de milisegundos en el centro.
 pvt-how-step-respond = Toca o pulsa espacio en cuanto lo veas—importan la velocidad y la consistencia.
-pvt-how-step-jitter = La tarea usa un intervalo variable de 2–10 s; los falsos inicios añaden retraso y los lapsos ≥500 ms se marcan.
+# $min, $max – segundos enteros que acotan la espera antes de cada estímulo.
+pvt-how-step-jitter = La tarea usa un intervalo variable de { $min }–{ $max } s; los falsos inicios añaden retraso y los lapsos ≥500 ms se marcan.
 # $trials – número entero de reacciones válidas objetivo.
 pvt-how-step-target = Cada sesión busca { $trials } reacciones válidas.
+# $minutes – duración entera de una sesión por tiempo.
+pvt-how-step-duration = Cada sesión dura { $minutes } minutos; una reacción de más de 30 s cuenta como lapso.
+pvt-preset-label = Protocolo
+pvt-preset-quick = Rápido (20 ensayos)
+pvt-preset-pvt-b-3min = PVT-B (3 min)
+pvt-preset-5min = PVT de 5 minutos
+pvt-preset-10min = PVT de 10 minutos (estándar)
 pvt-start = Comenzar
 pvt-progress-label = Progreso
 pvt-last-session = Última sesión
@@ -103,11 +117,21 @@ pvt-error-generic = ⚠️ { $message }
 ## Tarea 2-back (instrucciones y UI)
 nback-how-summary = Cómo funciona la tarea
 nback-how-step-timing = Cada letra se muestra 0,5 s y luego 2,5 s de intervalo en blanco.
-nback-how-step-rule = Pulsa espacio (o toca el panel) cuando la letra coincida con la de dos ensayos atrás.
+# $n – la N del N-back (entero de 1 a 4).
+nback-how-step-rule = Pulsa espacio (o toca el panel) cuando la letra coincida con { $n ->
+        [one] la inmediatamente anterior
+       *[other] la de { $n } ensayos atrás
+    }.
 nback-how-step-practice = El bloque de práctica dura ~35 segundos; la sesión principal unos 3 minutos.
 nback-how-step-strategy = Prioriza la precisión antes que la velocidad. Las falsas alarmas afectan a d′ igual que los fallos.
 nback-start-practice = Iniciar práctica
 nback-start-main = Iniciar sesión principal
+nback-level-label = Nivel
+# $n – la N del N-back (entero de 1 a 4).
+nback-level-option = { $n }-back
+nback-adaptive-label = Adaptativo: sube o baja el nivel tras cada sesión principal, según la precisión
+nback-metric-level = Nivel
+nback-next-level = Próxima sesión: { $n }-back
 nback-practice-recap = Resumen de práctica
 # $hits – aciertos; $targets – objetivos; $false_alarms – falsas alarmas; $accuracy – porcentaje redondeado
 nback-practice-metrics = Aciertos { $hits } / { $targets } • Falsas alarmas { $false_alarms } • Precisión { $accuracy }%
@@ -122,6 +146,9 @@ nback-metric-correct-rejections = Rechazos correctos
 nback-metric-accuracy = Precisión
 nback-metric-dprime = d′
 nback-metric-criterion = Criterio
+nback-metric-lure-false-alarms = Falsas alarmas en señuelos
+nback-metric-non-lure-false-alarms = Otras falsas alarmas
+nback-metric-lure-dprime = d′ frente a señuelos
 nback-metric-median-hit-rt = RT mediana aciertos
 nback-metric-mean-hit-rt = RT media aciertos
 nback-metric-hit-rt-p10p90 = RT aciertos p10/p90
@@ -131,6 +158,10 @@ nback-error-generic = ⚠️ { $message }
 ## Comunes (etiquetas compartidas)
 common-cancel = Cancelar
 common-progress = Progreso
+common-pause = Pausa
+common-resume = Reanudar
+# Shown over the task canvas while a run is paused
+task-paused-note = En pausa. El ensayo interrumpido se descarta; reanuda cuando estés listo.
 
 ## Guía PVT (textos dinámicos de estado)
 pvt-guidance-idle = Pulsa comenzar y espera a que aparezca el contador de milisegundos.
@@ -138,6 +169,7 @@ pvt-guidance-waiting = Mantente preparado… el contador aparecerá pronto.
 pvt-guidance-active = Toca o pulsa espacio en cuanto aparezca el contador.
 pvt-guidance-completed = Sesión completa. Comienza de nuevo cuando estés listo.
 pvt-guidance-aborted = Sesión cancelada. Pulsa comenzar para reintentar.
+pvt-guidance-paused = En pausa. Reanuda cuando estés listo.
 
 ## N-Back etiquetas adicionales
 nback-get-ready = Prepárate
@@ -146,12 +178,157 @@ nback-mode-main = Sesión principal
 # Etiqueta accesible para el área de respuesta
 nback-aria-respond = Responder a la letra actual
 
+## Tarea Flanker (instrucciones y UI)
+flanker-how-summary = Cómo funciona la tarea
+flanker-how-step-rule = Aparece una fila de cinco flechas; responde con la dirección de la central e ignora las demás.
+flanker-how-step-keys = Pulsa ← o F para izquierda, → o J para derecha, o toca los botones de flecha.
+flanker-how-step-timing = Cada fila se muestra 1,5 s o hasta que respondas; una cruz marca la pausa entre filas.
+flanker-how-step-practice = La práctica tiene 8 filas con retroalimentación; la sesión principal, 48 filas (unos 2 minutos) sin ella.
+flanker-start-practice = Iniciar práctica
+flanker-start-main = Iniciar sesión principal
+flanker-get-ready = Prepárate
+flanker-mode-practice = Práctica
+flanker-mode-main = Sesión principal
+flanker-practice-recap = Resumen de práctica
+flanker-last-session = Última sesión principal
+flanker-metrics-placeholder = Las métricas aparecerán tras la primera sesión completada.
+flanker-metric-accuracy = Precisión
+flanker-metric-congruent-accuracy = Precisión congruente
+flanker-metric-incongruent-accuracy = Precisión incongruente
+flanker-metric-congruent-rt = RT mediana congruente
+flanker-metric-incongruent-rt = RT mediana incongruente
+flanker-metric-conflict-effect = Efecto de conflicto
+flanker-metric-errors = Errores
+flanker-metric-misses = Omisiones
+flanker-feedback-correct = Correcto
+flanker-feedback-error = Dirección equivocada
+flanker-feedback-miss = Demasiado lento
+# Etiquetas accesibles de los botones de respuesta
+flanker-aria-left = La flecha central apunta a la izquierda
+flanker-aria-right = La flecha central apunta a la derecha
+# $message – texto de error
+flanker-error-generic = ⚠️ { $message }
+
+## Tarea DSST (instrucciones y UI)
+dsst-how-summary = Cómo funciona la tarea
+dsst-how-step-rule = Una clave empareja nueve símbolos con los dígitos del 1 al 9. Responde a cada símbolo con su dígito.
+dsst-how-step-keys = Pulsa las teclas numéricas 1–9 o toca los botones de dígitos.
+dsst-how-step-timing = Aparece un símbolo nuevo en cuanto respondes; la sesión dura 90 segundos.
+dsst-how-step-practice = La práctica dura 20 segundos con retroalimentación. Los símbolos cambian entre sesiones, así que no hay clave que memorizar.
+dsst-start-practice = Iniciar práctica
+dsst-start-main = Iniciar sesión principal
+dsst-get-ready = Prepárate
+dsst-mode-practice = Práctica
+dsst-mode-main = Sesión principal
+dsst-progress-correct = Correctas
+dsst-practice-recap = Resumen de práctica
+dsst-last-session = Última sesión principal
+dsst-metrics-placeholder = Las métricas aparecerán tras la primera sesión completada.
+dsst-metric-per-minute = Correctas por minuto
+dsst-metric-correct = Correctas
+dsst-metric-errors = Errores
+dsst-metric-accuracy = Precisión
+dsst-metric-median-rt = RT mediana
+dsst-feedback-correct = Correcto
+dsst-feedback-error = Revisa la clave
+# Etiqueta accesible de la clave símbolo–dígito
+dsst-aria-key = Clave de símbolos
+# $message – texto de error
+dsst-error-generic = ⚠️ { $message }
+
+## Tarea de recuerdo de palabras (instrucciones e interfaz)
+word-recall-how-summary = Cómo funciona la tarea
+word-recall-how-step-study = Aparecen doce palabras una a una, dos segundos cada una. Intenta recordarlas todas.
+word-recall-how-step-recall = Después escribe todas las que recuerdes, en cualquier orden, o elígelas de un banco de palabras.
+word-recall-how-step-delayed = Tras una sesión principal, vuelve unos diez minutos después para recordar las mismas palabras sin verlas.
+word-recall-how-step-forms = Cada sesión usa una lista distinta, así las repeticiones siguen siendo válidas. La práctica usa una lista corta con retroalimentación.
+word-recall-response-mode = Responder
+word-recall-mode-typed = Escribiendo las palabras
+word-recall-mode-selected = Eligiendo de un banco de palabras
+word-recall-start-practice = Iniciar práctica
+word-recall-start-main = Iniciar sesión principal
+word-recall-get-ready = Prepárate
+word-recall-mode-practice = Práctica
+word-recall-mode-main = Sesión principal
+word-recall-mode-delayed = Recuerdo diferido
+word-recall-prompt = ¿Qué palabras estaban en la lista?
+word-recall-add = Añadir
+word-recall-done = He terminado
+word-recall-progress-given = Respuestas
+word-recall-delayed-title = Recuerdo diferido pendiente
+# $minutes – minutos desde el recuerdo inmediato; $suggested – espera recomendada en minutos
+word-recall-delayed-waiting = Tu recuerdo inmediato se guardó hace { $minutes } min. Para el recuerdo diferido, espera unos { $suggested } min; lo ideal es hacer otra tarea mientras tanto.
+word-recall-start-delayed = Iniciar recuerdo diferido
+word-recall-skip-delayed = Omitir recuerdo diferido
+word-recall-practice-recap = Resumen de práctica
+word-recall-last-session = Última sesión principal
+word-recall-metrics-placeholder = Las métricas aparecerán tras la primera sesión completada.
+word-recall-metric-correct = Recordadas
+word-recall-metric-immediate = Recuerdo inmediato
+word-recall-metric-delayed = Recuerdo diferido
+word-recall-metric-intrusions = Intrusiones
+word-recall-metric-repetitions = Repeticiones
+# Etiqueta de accesibilidad del campo de respuesta
+word-recall-aria-entry = Palabra que recuerdas
+# $message – texto del error
+word-recall-error-generic = ⚠️ { $message }
+# Conjuntos de palabras: cuatro formas equivalentes de doce palabras y la práctica.
+# No edites un conjunto versionado; añade word-recall-v2-* (ver tasks/word_recall/pool.rs).
+word-recall-v1-form-a = manzana, río, vela, caballo, ventana, jardín, billete, botón, montaña, lápiz, manta, limón
+word-recall-v1-form-b = pan, bosque, espejo, tigre, cesta, océano, martillo, chaqueta, botella, castillo, flor, carro
+word-recall-v1-form-c = silla, isla, tetera, conejo, escalera, desierto, cartera, alfombra, cebolla, puente, violín, almohada
+word-recall-v1-form-d = nube, tractor, cuchara, mono, farol, valle, casco, cereza, cohete, azúcar, motor, toalla
+word-recall-v1-practice = lámpara, moneda, barco, zapato
+word-recall-v1-practice-foils = taza, puerta, campana, cuerda
+
+## Tarea de alternancia (instrucciones e interfaz)
+task-switch-how-summary = Cómo funciona la tarea
+task-switch-how-step-rules = Aparece un dígito con una señal encima. IMPAR / PAR: ¿el dígito es impar o par? MENOR / MAYOR: ¿es menor o mayor que 5?
+task-switch-how-step-keys = Pulsa ← o F para impar o menor, → o J para par o mayor, o toca los botones.
+task-switch-how-step-blocks = La sesión principal tiene un bloque de cada regla por separado y luego un bloque más largo en el que la señal cambia de un ensayo a otro; unos 3 minutos en total.
+task-switch-how-step-practice = La práctica tiene 12 ensayos mixtos con retroalimentación; la sesión principal no la tiene.
+task-switch-start-practice = Iniciar práctica
+task-switch-start-main = Iniciar sesión principal
+task-switch-get-ready = Prepárate
+task-switch-mode-practice = Práctica
+task-switch-mode-main = Sesión principal
+task-switch-cue-parity = IMPAR / PAR
+task-switch-cue-magnitude = MENOR / MAYOR
+task-switch-block-parity = Siguiente bloque: solo impar o par
+task-switch-block-magnitude = Siguiente bloque: solo menor o mayor que 5
+task-switch-block-mixed = Siguiente bloque: ambas reglas; sigue la señal
+task-switch-response-left = Impar · Menor
+task-switch-response-right = Par · Mayor
+task-switch-practice-recap = Resumen de práctica
+task-switch-last-session = Última sesión principal
+task-switch-metrics-placeholder = Las métricas aparecerán tras la primera sesión completada.
+task-switch-metric-accuracy = Precisión
+task-switch-metric-pure-rt = RT mediana con una regla
+task-switch-metric-repeat-rt = RT mediana en repetición
+task-switch-metric-switch-rt = RT mediana en cambio
+task-switch-metric-switch-cost = Coste de cambio
+task-switch-metric-mixing-cost = Coste de mezcla
+task-switch-metric-errors = Errores
+task-switch-metric-misses = Omisiones
+task-switch-feedback-correct = Correcto
+task-switch-feedback-error = Tecla equivocada para esta señal
+task-switch-feedback-miss = Demasiado lento
+# Etiquetas de accesibilidad de los botones de respuesta
+task-switch-aria-left = Impar, o menor que 5
+task-switch-aria-right = Par, o mayor que 5
+# $message – texto del error
+task-switch-error-generic = ⚠️ { $message }
+
 ## Página de inicio
 home-title = Looplace
 home-tagline-short = Bucles pequeños • mentes claras.
 home-intro-1 = Mide tu atención y memoria de trabajo, controla tu glucosa y explora cómo se mueven juntos tu cuerpo y tu mente, todo guardado localmente en este dispositivo.
 home-feature-pvt = Temporización PVT precisa con métricas locales
-home-feature-nback = Sesiones de memoria de trabajo 2-back
+home-feature-nback = Sesiones de memoria de trabajo N-back (de 1 a 4 atrás)
+home-feature-flanker = Sesiones flanker de control inhibitorio (efecto de conflicto)
+home-feature-dsst = Sustitución de dígitos y símbolos para la velocidad de procesamiento
+home-feature-word-recall = Recuerdo de listas de palabras, inmediato y diferido, para la memoria episódica
+home-feature-task-switch = Alternancia de tareas con señal para la flexibilidad mental (costes de cambio y de mezcla)
 home-feature-glucose = Glucosa en sangre desde tu FreeStyle Libre 2
 home-feature-local = Todo guardado localmente, tuyo para exportar
 home-cta = Elige una tarea —o sincroniza tu lector— para comenzar.
@@ -162,14 +339,32 @@ page-pvt-title = Tarea de Vigilancia Psicomotora
 page-pvt-intro = Ejecuta un bloque corto de vigilancia para capturar métricas de tiempo de reacción y lapsos.
 
 ## Página N-Back
-page-nback-title = Memoria de trabajo 2-back
-page-nback-intro = Comienza con un breve calentamiento guiado y luego sigue la secuencia 2-back — responde cuando la letra coincida con la de dos ensayos atrás para capturar sensibilidad (d′), sesgo de respuesta y tendencias de tiempo de reacción.
+page-nback-title = Memoria de trabajo N-back
+page-nback-intro = Comienza con un breve calentamiento guiado y luego sigue la secuencia de letras — responde cuando la letra coincida con la de N ensayos atrás para capturar sensibilidad (d′), sesgo de respuesta y tendencias de tiempo de reacción.
+
+## Página Flanker
+page-flanker-title = Control inhibitorio Flanker
+page-flanker-intro = Indica la dirección de la flecha central ignorando a sus vecinas. Comparar las filas en las que coinciden con las que se contradicen mide lo bien que filtras las distracciones.
+
+## Página DSST
+page-dsst-title = Sustitución de dígitos y símbolos
+page-dsst-intro = Empareja cada símbolo con su dígito usando la clave, lo más rápido y preciso que puedas. Cuántos codificas bien en 90 segundos refleja tu velocidad de procesamiento.
+
+## Página de recuerdo de palabras
+page-word-recall-title = Recuerdo de listas de palabras
+page-word-recall-intro = Estudia una lista corta de palabras y recuerda todas las que puedas: ahora y de nuevo tras una espera. Lo que retienes refleja tu memoria episódica.
+
+## Página de alternancia de tareas
+page-task-switch-title = Alternancia de tareas
+page-task-switch-intro = Clasifica dígitos con una regla u otra según indique la señal. El tiempo extra que te cuesta cambiar de regla mide con qué flexibilidad pasas de una tarea a otra.
 
 ## Página de resultados (extra)
 results-page-intro = Revisa resúmenes de sesiones recientes, inspecciona controles de calidad y exporta datos para análisis más profundo.
 results-refresh = Actualizar
 results-empty = Aún no hay sesiones registradas. Las sesiones completadas aparecerán después de terminar una tarea.
 results-error-prefix = ⚠️
+results-keep-trials = Guardar los datos de cada ensayo en las nuevas sesiones cognitivas
+results-keep-trials-hint = Guarda localmente cada estímulo y respuesta, para poder recalcular las métricas más adelante y ver una sesión ensayo a ensayo.
 
 ## Glucosa / página de salud
 glucose-title = Glucosa
@@ -178,3 +373,200 @@ glucose-sync = Sincronizar desde el lector
 glucose-syncing = Sincronizando…
 glucose-empty = Aún no hay lecturas de glucosa. Conecta tu FreeStyle Libre 2 y elige «Sincronizar desde el lector».
 glucose-desktop-only = La sincronización de glucosa se ejecuta en la app de escritorio de Looplace, donde puede acceder a tu lector por USB.
+glucose-locked = Este almacén de salud está cifrado. Introduce tu frase de contraseña para ver y sincronizar lecturas.
+glucose-passphrase = Frase de contraseña
+glucose-unlock = Desbloquear
+glucose-lock = Bloquear
+glucose-unlocked = Cifrado · desbloqueado en esta sesión
+glucose-encrypt = Cifrar con frase de contraseña
+glucose-encrypt-hint = Las lecturas guardadas no se podrán leer sin esta frase de contraseña. Si la olvidas, no se puede recuperar.
+
+## Diario
+journal-title = Diario
+journal-intro = Registra comidas, medicación, ejercicio, sueño y cualquier otra cosa que valga la pena anotar. Las entradas aparecen como marcadores en tu gráfico de glucosa y en tus tendencias cognitivas.
+journal-desktop-only = El diario se guarda en el almacén de salud local de la aplicación de escritorio de Looplace.
+journal-locked = Tu almacén de salud está cifrado. Desbloquéalo en la página de Glucosa para ver y añadir entradas.
+journal-empty = Aún no hay entradas. Añade la primera arriba.
+journal-category = Tipo
+journal-start = Inicio
+journal-end = Fin (opcional)
+journal-amount = Cantidad (opcional)
+journal-notes = Notas
+journal-add = Añadir entrada
+journal-update = Guardar cambios
+journal-cancel = Cancelar
+journal-edit = Editar
+journal-delete = Eliminar
+
+## Importar
+import-title = Importar datos
+import-intro = Trae tu historial de otras aplicaciones al almacén de salud local. Los archivos se leen en este ordenador; no se sube nada.
+import-desktop-only = Las importaciones se guardan en el almacén de salud local de la aplicación de escritorio de Looplace.
+import-locked = Tu almacén de salud está cifrado. Desbloquéalo en la página de Glucosa antes de importar.
+import-apple-health = Apple Health
+import-apple-health-hint = En tu iPhone, abre Salud, toca tu perfil, elige «Exportar todos los datos de salud», descomprime la exportación y selecciona export.xml. Se importan frecuencia cardiaca, VFC, pasos, energía activa, sueño y entrenamientos.
+import-running = Importando… { $percent } % ({ $imported } registros)
+import-done = Se importaron { $imported } registros ({ $skipped } omitidos).
+import-mapped = Hoja de cálculo (CSV o JSON)
+import-mapped-hint = Para lo que registras tú mismo, como peso, tensión arterial o estado de ánimo. Elige un archivo, indica qué columnas contienen la hora y el valor y a dónde pertenecen, y revisa la vista previa antes de importar. Guarda la asignación para reutilizarla la próxima vez.
+import-mapped-saved = Asignación guardada
+import-mapped-none = — Nueva asignación —
+import-mapped-name = Nombre de la asignación
+import-mapped-time-column = Columna de hora
+import-mapped-time-format = Formato de hora
+import-mapped-format-pattern = Patrón
+import-mapped-format-rfc3339 = ISO 8601 con desfase
+import-mapped-format-unix-s = Segundos Unix
+import-mapped-format-unix-ms = Milisegundos Unix
+import-mapped-pattern-hint = Por ejemplo [day]/[month]/[year] [hour]:[minute]
+import-mapped-value-column = Columna de valor
+import-mapped-choose-column = — Elige una columna —
+import-mapped-stream = Flujo
+import-mapped-stream-hint = Un flujo integrado, o custom.tu_nombre
+import-mapped-unit = Unidad
+import-mapped-tz = Zona horaria
+import-mapped-preview = Vista previa
+import-mapped-import = Importar
+import-mapped-save = Guardar asignación
+import-mapped-delete = Eliminar asignación
+import-mapped-ready = { $ready } filas listas para importar, { $skipped } omitidas.
+import-bundle = Mover o compartir tus datos
+import-bundle-hint = Exporta un zip con archivos Parquet y CSV y un manifiesto que los describe, para abrirlo con otras herramientas, entregarlo a tu médico o importarlo en otro ordenador. Una exportación completa incluye tus ajustes; al importarla se restauran.
+import-bundle-scope = Qué exportar
+import-bundle-scope-all = Todo
+import-bundle-scope-glucose = Glucosa y cetonas
+import-bundle-scope-health = Datos de Apple Health
+import-bundle-scope-journal = Diario
+import-bundle-scope-cognition = Pruebas cognitivas
+import-bundle-since = Desde (opcional)
+import-bundle-export = Exportar paquete
+import-bundle-import = Importar un paquete
+import-bundle-exported = Paquete guardado en { $path }
+import-bundle-imported = Se importaron { $observations } mediciones nuevas y { $sessions } sesiones nuevas.
+import-history = Historial de sincronizaciones e importaciones
+import-history-hint = Cada sincronización del lector e importación de archivos, de la más reciente a la más antigua, con las mediciones que aún conserva. Deshacer una borra esas mediciones; lo que sobrescribió solo se recupera desde una instantánea.
+import-history-empty = Aún no se ha sincronizado ni importado nada.
+import-history-when = Cuándo
+import-history-kind = Tipo
+import-history-source = Origen
+import-history-rows = Mediciones
+import-history-rollback = Deshacer
+import-history-confirm = ¿Seguro que quieres deshacerla?
+import-history-rolled-back = Deshecha
+import-history-removed = Se eliminaron { $removed } mediciones.
+import-history-kind-reader = Sincronización del lector
+import-history-kind-libreview = Exportación de LibreView
+import-history-kind-dexcom = Exportación de Dexcom Clarity
+import-history-kind-nightscout = Exportación de Nightscout
+import-history-kind-apple-health = Exportación de Apple Health
+import-history-kind-mapped = Hoja de cálculo
+
+## Etiquetas de flujos (registro de flujos de looplace-store)
+stream-glucose-mg-dl = Glucosa
+stream-ketone-mmol-l = Cetonas
+stream-pvt-total-trials = Ensayos PVT
+stream-pvt-reacted-trials = Respuestas PVT
+stream-pvt-median-rt-ms = TR mediano PVT
+stream-pvt-mean-rt-ms = TR medio PVT
+stream-pvt-sd-rt-ms = Variabilidad del TR PVT (DE)
+stream-pvt-p10-rt-ms = TR más rápido PVT (P10)
+stream-pvt-p90-rt-ms = TR más lento PVT (P90)
+stream-pvt-lapses-ge-500ms = Lapsos PVT ≥500 ms
+stream-pvt-minor-lapses-355-499ms = Lapsos menores PVT 355–499 ms
+stream-pvt-false-starts = Salidas en falso PVT
+stream-pvt-time-on-task-slope-ms-per-min = Pendiente de tiempo en tarea PVT
+stream-pvt-mean-reciprocal-rt-per-s = Velocidad de respuesta PVT (media 1/TR)
+stream-pvt-fastest-10pct-rt-ms = TR del 10 % más rápido PVT
+stream-pvt-slowest-10pct-rt-ms = TR del 10 % más lento PVT
+stream-pvt-lapse-probability = Probabilidad de lapso PVT
+stream-pvt-performance-score = Puntuación de rendimiento PVT
+stream-nback2-total-trials = Ensayos N-back
+stream-nback2-target-trials = Ensayos objetivo N-back
+stream-nback2-non-target-trials = Ensayos no objetivo N-back
+stream-nback2-hits = Aciertos N-back
+stream-nback2-misses = Omisiones N-back
+stream-nback2-false-alarms = Falsas alarmas N-back
+stream-nback2-correct-rejections = Rechazos correctos N-back
+stream-nback2-response-count = Respuestas N-back
+stream-nback2-hit-rate = Tasa de aciertos N-back
+stream-nback2-false-alarm-rate = Tasa de falsas alarmas N-back
+stream-nback2-accuracy = Precisión N-back
+stream-nback2-d-prime = d′ N-back
+stream-nback2-criterion = Criterio N-back
+stream-nback2-mean-hit-rt-ms = TR medio de aciertos N-back
+stream-nback2-median-hit-rt-ms = TR mediano de aciertos N-back
+stream-nback2-sd-hit-rt-ms = Variabilidad del TR de aciertos N-back (DE)
+stream-nback2-p10-hit-rt-ms = TR de acierto más rápido N-back (P10)
+stream-nback2-p90-hit-rt-ms = TR de acierto más lento N-back (P90)
+stream-nback2-n = Nivel N-back
+stream-nback2-lure-trials = Ensayos señuelo N-back
+stream-nback2-lure-false-alarms = Falsas alarmas en señuelos N-back
+stream-nback2-non-lure-false-alarms = Falsas alarmas fuera de señuelos N-back
+stream-nback2-lure-false-alarm-rate = Tasa de falsas alarmas en señuelos N-back
+stream-nback2-non-lure-false-alarm-rate = Tasa de falsas alarmas fuera de señuelos N-back
+stream-nback2-lure-d-prime = d′ frente a señuelos N-back
+stream-flanker-total-trials = Ensayos flanker
+stream-flanker-congruent-trials = Ensayos congruentes flanker
+stream-flanker-incongruent-trials = Ensayos incongruentes flanker
+stream-flanker-correct = Respuestas correctas flanker
+stream-flanker-errors = Errores flanker
+stream-flanker-misses = Omisiones flanker
+stream-flanker-congruent-correct = Correctas congruentes flanker
+stream-flanker-incongruent-correct = Correctas incongruentes flanker
+stream-flanker-accuracy = Precisión flanker
+stream-flanker-congruent-accuracy = Precisión congruente flanker
+stream-flanker-incongruent-accuracy = Precisión incongruente flanker
+stream-flanker-congruent-mean-rt-ms = TR medio congruente flanker
+stream-flanker-incongruent-mean-rt-ms = TR medio incongruente flanker
+stream-flanker-congruent-median-rt-ms = TR mediano congruente flanker
+stream-flanker-incongruent-median-rt-ms = TR mediano incongruente flanker
+stream-flanker-conflict-effect-ms = Efecto de conflicto flanker (TR)
+stream-flanker-conflict-effect-accuracy = Efecto de conflicto flanker (precisión)
+stream-flanker-response-count = Respuestas flanker
+stream-dsst-duration-ms = Duración DSST
+stream-dsst-responses = Respuestas DSST
+stream-dsst-correct = Correctas DSST
+stream-dsst-errors = Errores DSST
+stream-dsst-correct-per-minute = Correctas por minuto DSST
+stream-dsst-accuracy = Precisión DSST
+stream-dsst-mean-rt-ms = TR medio DSST
+stream-dsst-median-rt-ms = TR mediano DSST
+stream-word-recall-list-length = Longitud de lista (recuerdo)
+stream-word-recall-immediate-correct = Recuerdo inmediato: correctas
+stream-word-recall-immediate-intrusions = Recuerdo inmediato: intrusiones
+stream-word-recall-immediate-repetitions = Recuerdo inmediato: repeticiones
+stream-word-recall-delayed-correct = Recuerdo diferido: correctas
+stream-word-recall-delayed-intrusions = Recuerdo diferido: intrusiones
+stream-word-recall-delayed-repetitions = Recuerdo diferido: repeticiones
+stream-word-recall-delay-ms = Espera del recuerdo diferido
+stream-word-recall-retention = Retención (recuerdo de palabras)
+stream-task-switch-total-trials = Ensayos de alternancia
+stream-task-switch-correct = Aciertos de alternancia
+stream-task-switch-errors = Errores de alternancia
+stream-task-switch-misses = Omisiones de alternancia
+stream-task-switch-accuracy = Precisión de alternancia
+stream-task-switch-pure-trials = Ensayos con una regla (alternancia)
+stream-task-switch-repeat-trials = Ensayos de repetición (alternancia)
+stream-task-switch-switch-trials = Ensayos de cambio (alternancia)
+stream-task-switch-pure-median-rt-ms = RT mediana con una regla (alternancia)
+stream-task-switch-repeat-median-rt-ms = RT mediana en repetición (alternancia)
+stream-task-switch-switch-median-rt-ms = RT mediana en cambio (alternancia)
+stream-task-switch-pure-error-rate = Tasa de error con una regla (alternancia)
+stream-task-switch-repeat-error-rate = Tasa de error en repetición (alternancia)
+stream-task-switch-switch-error-rate = Tasa de error en cambio (alternancia)
+stream-task-switch-switch-cost-ms = Coste de cambio (RT)
+stream-task-switch-mixing-cost-ms = Coste de mezcla (RT)
+stream-task-switch-switch-cost-error-rate = Coste de cambio (errores)
+stream-task-switch-mixing-cost-error-rate = Coste de mezcla (errores)
+stream-task-switch-response-count = Respuestas de alternancia
+stream-journal-meal = Comida
+stream-journal-medication = Medicación
+stream-journal-exercise = Ejercicio
+stream-journal-sleep = Sueño
+stream-journal-note = Nota
+stream-health-heart-rate-bpm = Frecuencia cardiaca
+stream-health-hrv-sdnn-ms = Variabilidad de la frecuencia cardiaca (SDNN)
+stream-health-steps = Pasos
+stream-health-active-energy-kcal = Energía activa
+stream-health-sleep-h = Sueño
+stream-health-workout-min = Entrenamiento
@@ -9,9 +9,15 @@
 ## Navigation
 nav-home = Accueil
 nav-pvt = PVT
-nav-nback = 2-back
+nav-nback = N-back
+nav-flanker = Flanker
+nav-dsst = DSST
+nav-word-recall = Rappel
+nav-task-switch = Alternance
 nav-results = Résultats
 nav-glucose = Glucose
+nav-journal = Journal
+nav-import = Importer
 nav-language-label = Langue
 
 ## Brand & general
@@ -77,9 +83,17 @@ value-missing = —
 pvt-how-summary = Comment fonctionne la tâche
 pvt-how-step-wait = Attendez que le compteur de millisecondes apparaisse au centre.
 pvt-how-step-respond = Touchez ou appuyez sur espace dès qu’il apparaît — la vitesse et la constance comptent toutes deux.
-pvt-how-step-jitter = La tâche utilise un intervalle variable de 2–10 s ; les faux départs ajoutent un délai, les lapses ≥500 ms sont signalés.
+# $min, $max – secondes entières encadrant l’attente avant chaque stimulus.
+pvt-how-step-jitter = La tâche utilise un intervalle variable de { $min }–{ $max } s ; les faux départs ajoutent un délai, les lapses ≥500 ms sont signalés.
 # $trials – nombre entier de réactions valides cible.
 pvt-how-step-target = Chaque session vise { $trials } réactions valides.
+# $minutes – durée entière d’une session chronométrée.
+pvt-how-step-duration = Chaque session dure { $minutes } minutes ; une réaction de plus de 30 s compte comme une lapse.
+pvt-preset-label = Protocole
+pvt-preset-quick = Rapide (20 essais)
+pvt-preset-pvt-b-3min = PVT-B (3 min)
+pvt-preset-5min = PVT de 5 minutes
+pvt-preset-10min = PVT de 10 minutes (standard)
 pvt-start = Démarrer
 pvt-progress-label = Progression
 pvt-last-session = Dernière session
@@ -102,11 +116,21 @@ pvt-error-generic = ⚠️ { $message }
 ## Tâche 2-back (instructions & interface)
 nback-how-summary = Comment fonctionne la tâche
 nback-how-step-timing = Chaque lettre s’affiche 0,5 s puis 2,5 s d’intervalle vide.
-nback-how-step-rule = Appuyez sur espace (ou touchez la zone) quand la lettre correspond à celle d’il y a deux essais.
+# $n – le N du N-back (entier de 1 à 4).
+nback-how-step-rule = Appuyez sur espace (ou touchez la zone) quand la lettre correspond à { $n ->
+        [one] la précédente
+       *[other] celle d’il y a { $n } essais
+    }.
 nback-how-step-practice = Le bloc d’entraînement dure ~35 secondes ; la session principale environ 3 minutes.
 nback-how-step-strategy = Priorisez la précision avant la vitesse. Les fausses alertes pénalisent d′ autant que les omissions.
 nback-start-practice = Démarrer entraînement
 nback-start-main = Démarrer session principale
+nback-level-label = Niveau
+# $n – le N du N-back (entier de 1 à 4).
+nback-level-option = { $n }-back
+nback-adaptive-label = Adaptatif : monte ou descend le niveau après chaque session principale, selon la précision
+nback-metric-level = Niveau
+nback-next-level = Prochaine session : { $n }-back
 nback-practice-recap = Récap entraînement
 # $hits – succès; $targets – cibles; $false_alarms – fausses alertes; $accuracy – pourcentage arrondi
 nback-practice-metrics = Succès { $hits } / { $targets } • Fausses alertes { $false_alarms } • Précision { $accuracy }%
@@ -121,6 +145,9 @@ nback-metric-correct-rejections = Rejets corrects
 nback-metric-accuracy = Précision
 nback-metric-dprime = d′
 nback-metric-criterion = Critère
+nback-metric-lure-false-alarms = Fausses alertes sur leurres
+nback-metric-non-lure-false-alarms = Autres fausses alertes
+nback-metric-lure-dprime = d′ face aux leurres
 nback-metric-median-hit-rt = RT médiane succès
 nback-metric-mean-hit-rt = RT moyenne succès
 nback-metric-hit-rt-p10p90 = RT succès p10/p90
@@ -130,6 +157,10 @@ nback-error-generic = ⚠️ { $message }
 ## Commun (étiquettes partagées)
 common-cancel = Annuler
 common-progress = Progression
+common-pause = Pause
+common-resume = Reprendre
+# Shown over the task canvas while a run is paused
+task-paused-note = En pause. L’essai interrompu est ignoré ; reprenez quand vous êtes prêt.
 
 ## Guidance PVT (textes d’état dynamiques)
 pvt-guidance-idle = Appuyez sur démarrer puis attendez que le compteur de millisecondes apparaisse.
@@ -137,6 +168,7 @@ pvt-guidance-waiting = Restez prêt… le compteur apparaîtra bientôt.
 pvt-guidance-active = Touchez ou appuyez sur espace dès que le compteur apparaît.
 pvt-guidance-completed = Session terminée. Redémarrez quand vous voulez.
 pvt-guidance-aborted = Session annulée. Relancez pour réessayer.
+pvt-guidance-paused = En pause. Reprenez quand vous êtes prêt.
 
 ## N-Back libellés supplémentaires
 nback-get-ready = Préparez-vous
@@ -145,12 +177,157 @@ nback-mode-main = Session principale
 # Libellé accessibilité pour la zone de réponse
 nback-aria-respond = Répondre à la lettre actuelle
 
+## Tâche Flanker (instructions & interface)
+flanker-how-summary = Fonctionnement de la tâche
+flanker-how-step-rule = Une rangée de cinq flèches apparaît ; répondez selon la direction de la flèche centrale et ignorez les autres.
+flanker-how-step-keys = Appuyez sur ← ou F pour gauche, → ou J pour droite, ou touchez les boutons fléchés.
+flanker-how-step-timing = Chaque rangée reste affichée 1,5 s ou jusqu’à votre réponse ; une croix marque la pause entre les rangées.
+flanker-how-step-practice = L’entraînement compte 8 rangées avec retour ; la session principale 48 rangées (environ 2 minutes) sans retour.
+flanker-start-practice = Démarrer l’entraînement
+flanker-start-main = Démarrer la session principale
+flanker-get-ready = Préparez-vous
+flanker-mode-practice = Entraînement
+flanker-mode-main = Session principale
+flanker-practice-recap = Récapitulatif d’entraînement
+flanker-last-session = Dernière session principale
+flanker-metrics-placeholder = Les métriques apparaîtront après la première session complétée.
+flanker-metric-accuracy = Précision
+flanker-metric-congruent-accuracy = Précision congruente
+flanker-metric-incongruent-accuracy = Précision incongruente
+flanker-metric-congruent-rt = RT médiane congruente
+flanker-metric-incongruent-rt = RT médiane incongruente
+flanker-metric-conflict-effect = Effet de conflit
+flanker-metric-errors = Erreurs
+flanker-metric-misses = Omissions
+flanker-feedback-correct = Correct
+flanker-feedback-error = Mauvaise direction
+flanker-feedback-miss = Trop lent
+# Libellés accessibilité des boutons de réponse
+flanker-aria-left = La flèche centrale pointe à gauche
+flanker-aria-right = La flèche centrale pointe à droite
+# $message – texte d'erreur
+flanker-error-generic = ⚠️ { $message }
+
+## Tâche DSST (instructions & interface)
+dsst-how-summary = Fonctionnement de la tâche
+dsst-how-step-rule = Une clé associe neuf symboles aux chiffres de 1 à 9. Répondez à chaque symbole affiché par son chiffre.
+dsst-how-step-keys = Appuyez sur les touches 1–9 ou touchez les boutons chiffrés.
+dsst-how-step-timing = Un nouveau symbole apparaît dès votre réponse ; la session dure 90 secondes.
+dsst-how-step-practice = L’entraînement dure 20 secondes avec retour. Les symboles changent d’une session à l’autre : inutile de mémoriser la clé.
+dsst-start-practice = Démarrer l’entraînement
+dsst-start-main = Démarrer la session principale
+dsst-get-ready = Préparez-vous
+dsst-mode-practice = Entraînement
+dsst-mode-main = Session principale
+dsst-progress-correct = Correctes
+dsst-practice-recap = Récapitulatif d’entraînement
+dsst-last-session = Dernière session principale
+dsst-metrics-placeholder = Les métriques apparaîtront après la première session complétée.
+dsst-metric-per-minute = Correctes par minute
+dsst-metric-correct = Correctes
+dsst-metric-errors = Erreurs
+dsst-metric-accuracy = Précision
+dsst-metric-median-rt = RT médiane
+dsst-feedback-correct = Correct
+dsst-feedback-error = Vérifiez la clé
+# Libellé accessibilité de la clé symboles–chiffres
+dsst-aria-key = Clé des symboles
+# $message – texte d'erreur
+dsst-error-generic = ⚠️ { $message }
+
+## Tâche de rappel de mots (instructions et interface)
+word-recall-how-summary = Fonctionnement de la tâche
+word-recall-how-step-study = Douze mots apparaissent un par un, deux secondes chacun. Essayez de tous les retenir.
+word-recall-how-step-recall = Donnez ensuite tous ceux dont vous vous souvenez, dans n’importe quel ordre, en les tapant ou en les choisissant dans une banque de mots.
+word-recall-how-step-delayed = Après une session principale, revenez une dizaine de minutes plus tard pour rappeler les mêmes mots sans les revoir.
+word-recall-how-step-forms = Chaque session utilise une liste différente, pour que les sessions répétées restent valables. L’entraînement utilise une courte liste avec retour.
+word-recall-response-mode = Répondre
+word-recall-mode-typed = En tapant les mots
+word-recall-mode-selected = En choisissant dans une banque de mots
+word-recall-start-practice = Démarrer l’entraînement
+word-recall-start-main = Démarrer la session principale
+word-recall-get-ready = Préparez-vous
+word-recall-mode-practice = Entraînement
+word-recall-mode-main = Session principale
+word-recall-mode-delayed = Rappel différé
+word-recall-prompt = Quels mots figuraient dans la liste ?
+word-recall-add = Ajouter
+word-recall-done = J’ai terminé
+word-recall-progress-given = Réponses
+word-recall-delayed-title = Rappel différé en attente
+# $minutes – minutes depuis le rappel immédiat ; $suggested – délai conseillé en minutes
+word-recall-delayed-waiting = Votre rappel immédiat a été enregistré il y a { $minutes } min. Pour le rappel différé, attendez environ { $suggested } min ; une autre tâche entre-temps est idéale.
+word-recall-start-delayed = Démarrer le rappel différé
+word-recall-skip-delayed = Passer le rappel différé
+word-recall-practice-recap = Récapitulatif d’entraînement
+word-recall-last-session = Dernière session principale
+word-recall-metrics-placeholder = Les métriques apparaîtront après la première session complétée.
+word-recall-metric-correct = Rappelés
+word-recall-metric-immediate = Rappel immédiat
+word-recall-metric-delayed = Rappel différé
+word-recall-metric-intrusions = Intrusions
+word-recall-metric-repetitions = Répétitions
+# Libellé d’accessibilité du champ de réponse
+word-recall-aria-entry = Mot dont vous vous souvenez
+# $message – texte de l’erreur
+word-recall-error-generic = ⚠️ { $message }
+# Réserves de mots : quatre formes équivalentes de douze mots, plus l’entraînement.
+# Ne modifiez jamais une réserve versionnée ; ajoutez word-recall-v2-* (voir tasks/word_recall/pool.rs).
+word-recall-v1-form-a = pomme, rivière, bougie, cheval, fenêtre, jardin, billet, bouton, montagne, crayon, couverture, citron
+word-recall-v1-form-b = pain, forêt, miroir, tigre, panier, océan, marteau, veste, bouteille, château, fleur, chariot
+word-recall-v1-form-c = chaise, île, bouilloire, lapin, échelle, désert, portefeuille, tapis, oignon, pont, violon, oreiller
+word-recall-v1-form-d = nuage, tracteur, cuillère, singe, lanterne, vallée, casque, cerise, fusée, sucre, moteur, serviette
+word-recall-v1-practice = lampe, pièce, bateau, chaussure
+word-recall-v1-practice-foils = tasse, porte, cloche, corde
+
+## Tâche d’alternance (instructions et interface)
+task-switch-how-summary = Fonctionnement de la tâche
+task-switch-how-step-rules = Un chiffre apparaît avec un indice au-dessus. IMPAIR / PAIR : le chiffre est-il impair ou pair ? PETIT / GRAND : est-il inférieur ou supérieur à 5 ?
+task-switch-how-step-keys = Appuyez sur ← ou F pour impair ou petit, → ou J pour pair ou grand, ou touchez les boutons.
+task-switch-how-step-blocks = La session principale comprend un bloc de chaque règle seule, puis un bloc plus long où l’indice change d’un essai à l’autre — environ 3 minutes en tout.
+task-switch-how-step-practice = L’entraînement compte 12 essais mixtes avec retour ; la session principale n’en donne pas.
+task-switch-start-practice = Démarrer l’entraînement
+task-switch-start-main = Démarrer la session principale
+task-switch-get-ready = Préparez-vous
+task-switch-mode-practice = Entraînement
+task-switch-mode-main = Session principale
+task-switch-cue-parity = IMPAIR / PAIR
+task-switch-cue-magnitude = PETIT / GRAND
+task-switch-block-parity = Bloc suivant : impair ou pair uniquement
+task-switch-block-magnitude = Bloc suivant : inférieur ou supérieur à 5 uniquement
+task-switch-block-mixed = Bloc suivant : les deux règles — suivez l’indice
+task-switch-response-left = Impair · Petit
+task-switch-response-right = Pair · Grand
+task-switch-practice-recap = Récapitulatif d’entraînement
+task-switch-last-session = Dernière session principale
+task-switch-metrics-placeholder = Les métriques apparaîtront après la première session complétée.
+task-switch-metric-accuracy = Précision
+task-switch-metric-pure-rt = RT médiane à règle unique
+task-switch-metric-repeat-rt = RT médiane en répétition
+task-switch-metric-switch-rt = RT médiane en alternance
+task-switch-metric-switch-cost = Coût d’alternance
+task-switch-metric-mixing-cost = Coût de mélange
+task-switch-metric-errors = Erreurs
+task-switch-metric-misses = Omissions
+task-switch-feedback-correct = Correct
+task-switch-feedback-error = Mauvaise touche pour cet indice
+task-switch-feedback-miss = Trop lent
+# Libellés d’accessibilité des boutons de réponse
+task-switch-aria-left = Impair, ou inférieur à 5
+task-switch-aria-right = Pair, ou supérieur à 5
+# $message – texte de l’erreur
+task-switch-error-generic = ⚠️ { $message }
+
 ## Page d’accueil
 home-title = Looplace
 home-tagline-short = Petites boucles • esprit clair.
 home-intro-1 = Mesurez votre attention et votre mémoire de travail, suivez votre glycémie, et explorez comment votre corps et votre esprit évoluent ensemble — le tout stocké localement sur cet appareil.
 home-feature-pvt = Chronométrage PVT précis avec métriques locales
-home-feature-nback = Sessions de mémoire de travail 2-back
+home-feature-nback = Sessions de mémoire de travail N-back (de 1 à 4 en arrière)
+home-feature-flanker = Sessions flanker de contrôle inhibiteur (effet de conflit)
+home-feature-dsst = Substitution chiffres-symboles pour la vitesse de traitement
+home-feature-word-recall = Rappel de listes de mots, immédiat et différé, pour la mémoire épisodique
+home-feature-task-switch = Alternance de tâches indicée pour la flexibilité mentale (coûts d’alternance et de mélange)
 home-feature-glucose = Glycémie depuis votre FreeStyle Libre 2
 home-feature-local = Tout est stocké localement, à vous de l’exporter
 home-cta = Choisissez une tâche — ou synchronisez votre lecteur — pour commencer.
@@ -161,14 +338,32 @@ page-pvt-title = Tâche de Vigilance Psychomotrice
 page-pvt-intro = Lancez un court bloc de vigilance pour capturer temps de réaction et lapses.
 
 ## Page N-Back
-page-nback-title = Mémoire de travail 2-back
-page-nback-intro = Commencez par un court échauffement guidé puis suivez le flux 2-back — répondez quand la lettre correspond à celle d’il y a deux essais pour capturer sensibilité (d′), biais de réponse et tendances de temps de réaction.
+page-nback-title = Mémoire de travail N-back
+page-nback-intro = Commencez par un court échauffement guidé puis suivez le flux de lettres — répondez quand la lettre correspond à celle d’il y a N essais pour capturer sensibilité (d′), biais de réponse et tendances de temps de réaction.
+
+## Page Flanker
+page-flanker-title = Contrôle inhibiteur Flanker
+page-flanker-intro = Indiquez la direction de la flèche centrale en ignorant ses voisines. Comparer les rangées où elles concordent à celles où elles s’opposent mesure votre capacité à filtrer les distractions.
+
+## Page DSST
+page-dsst-title = Substitution chiffres-symboles
+page-dsst-intro = Associez chaque symbole à son chiffre grâce à la clé, aussi vite et justement que possible. Le nombre de bonnes réponses en 90 secondes reflète votre vitesse de traitement.
+
+## Page de rappel de mots
+page-word-recall-title = Rappel de listes de mots
+page-word-recall-intro = Étudiez une courte liste de mots, puis rappelez-en le plus possible : tout de suite, puis après un délai. Ce que vous retenez reflète votre mémoire épisodique.
+
+## Page d’alternance de tâches
+page-task-switch-title = Alternance de tâches
+page-task-switch-intro = Classez des chiffres selon une règle ou l’autre, comme l’indique l’indice. Le temps supplémentaire que vous coûte un changement de règle mesure la souplesse avec laquelle vous passez d’une tâche à l’autre.
 
 ## Page Résultats (supplément)
 results-page-intro = Consultez les résumés des sessions récentes, inspectez les contrôles de qualité et exportez les données pour une analyse approfondie.
 results-refresh = Actualiser
 results-empty = Aucune session enregistrée. Les sessions complétées apparaîtront après avoir terminé une tâche.
 results-error-prefix = ⚠️
+results-keep-trials = Conserver les données de chaque essai des nouvelles sessions cognitives
+results-keep-trials-hint = Enregistre localement chaque stimulus et chaque réponse, pour recalculer les métriques plus tard et afficher une session essai par essai.
 
 ## Glucose / page santé
 glucose-title = Glucose
@@ -177,3 +372,200 @@ glucose-sync = Synchroniser depuis le lecteur
 glucose-syncing = Synchronisation…
 glucose-empty = Aucun relevé de glycémie pour l’instant. Connectez votre FreeStyle Libre 2 et choisissez « Synchroniser depuis le lecteur ».
 glucose-desktop-only = La synchronisation de la glycémie s’exécute dans l’application de bureau Looplace, où elle peut accéder à votre lecteur via USB.
+glucose-locked = Ce stockage de santé est chiffré. Saisissez votre phrase secrète pour afficher et synchroniser les relevés.
+glucose-passphrase = Phrase secrète
+glucose-unlock = Déverrouiller
+glucose-lock = Verrouiller
+glucose-unlocked = Chiffré · déverrouillé pour cette session
+glucose-encrypt = Chiffrer avec une phrase secrète
+glucose-encrypt-hint = Les relevés enregistrés deviennent illisibles sans cette phrase secrète. Elle ne peut pas être récupérée en cas d’oubli.
+
+## Journal
+journal-title = Journal
+journal-intro = Notez repas, médicaments, exercice, sommeil et tout ce qui mérite d’être consigné. Les entrées apparaissent comme repères sur votre courbe de glycémie et vos tendances cognitives.
+journal-desktop-only = Le journal est conservé dans le stockage santé local de l’application de bureau Looplace.
+journal-locked = Votre stockage santé est chiffré. Déverrouillez-le sur la page Glycémie pour voir et ajouter des entrées.
+journal-empty = Aucune entrée pour l’instant. Ajoutez la première ci-dessus.
+journal-category = Type
+journal-start = Début
+journal-end = Fin (facultatif)
+journal-amount = Quantité (facultatif)
+journal-notes = Notes
+journal-add = Ajouter l’entrée
+journal-update = Enregistrer
+journal-cancel = Annuler
+journal-edit = Modifier
+journal-delete = Supprimer
+
+## Import
+import-title = Importer des données
+import-intro = Rapatriez votre historique d’autres applications dans le stockage santé local. Les fichiers sont lus sur cet ordinateur ; rien n’est envoyé.
+import-desktop-only = Les imports sont enregistrés dans le stockage santé local de l’application de bureau Looplace.
+import-locked = Votre stockage santé est chiffré. Déverrouillez-le sur la page Glycémie avant d’importer.
+import-apple-health = Apple Santé
+import-apple-health-hint = Sur votre iPhone, ouvrez Santé, touchez votre profil, choisissez « Exporter toutes les données de santé », décompressez l’export et sélectionnez export.xml. Fréquence cardiaque, VFC, pas, énergie active, sommeil et entraînements sont importés.
+import-running = Import en cours… { $percent } % ({ $imported } enregistrements)
+import-done = { $imported } enregistrements importés ({ $skipped } ignorés).
+import-mapped = Tableur (CSV ou JSON)
+import-mapped-hint = Pour ce que vous suivez vous-même, comme le poids, la tension ou l’humeur. Choisissez un fichier, indiquez les colonnes de l’heure et de la valeur et leur destination, puis vérifiez l’aperçu avant d’importer. Enregistrez la correspondance pour la réutiliser la prochaine fois.
+import-mapped-saved = Correspondance enregistrée
+import-mapped-none = — Nouvelle correspondance —
+import-mapped-name = Nom de la correspondance
+import-mapped-time-column = Colonne de l’heure
+import-mapped-time-format = Format de l’heure
+import-mapped-format-pattern = Motif
+import-mapped-format-rfc3339 = ISO 8601 avec décalage
+import-mapped-format-unix-s = Secondes Unix
+import-mapped-format-unix-ms = Millisecondes Unix
+import-mapped-pattern-hint = Par exemple [day]/[month]/[year] [hour]:[minute]
+import-mapped-value-column = Colonne de la valeur
+import-mapped-choose-column = — Choisir une colonne —
+import-mapped-stream = Flux
+import-mapped-stream-hint = Un flux intégré, ou custom.votre_nom
+import-mapped-unit = Unité
+import-mapped-tz = Fuseau horaire
+import-mapped-preview = Aperçu
+import-mapped-import = Importer
+import-mapped-save = Enregistrer la correspondance
+import-mapped-delete = Supprimer la correspondance
+import-mapped-ready = { $ready } lignes prêtes à importer, { $skipped } ignorées.
+import-bundle = Déplacer ou partager vos données
+import-bundle-hint = Exportez un zip de fichiers Parquet et CSV accompagnés d'un manifeste qui les décrit, à ouvrir dans d'autres outils, à remettre à votre médecin ou à importer sur un autre ordinateur. Un export complet inclut vos réglages ; l'importer les restaure.
+import-bundle-scope = Quoi exporter
+import-bundle-scope-all = Tout
+import-bundle-scope-glucose = Glycémie et cétones
+import-bundle-scope-health = Données Apple Santé
+import-bundle-scope-journal = Journal
+import-bundle-scope-cognition = Tests cognitifs
+import-bundle-since = À partir du (facultatif)
+import-bundle-export = Exporter le paquet
+import-bundle-import = Importer un paquet
+import-bundle-exported = Paquet enregistré dans { $path }
+import-bundle-imported = { $observations } nouvelles mesures et { $sessions } nouvelles séances importées.
+import-history = Historique des synchronisations et imports
+import-history-hint = Chaque synchronisation du lecteur et import de fichier, du plus récent au plus ancien, avec les mesures qu'il contient encore. Annuler l'un d'eux supprime ces mesures ; ce qu'il a écrasé ne revient qu'à partir d'un instantané.
+import-history-empty = Rien n'a encore été synchronisé ni importé.
+import-history-when = Quand
+import-history-kind = Type
+import-history-source = Source
+import-history-rows = Mesures
+import-history-rollback = Annuler
+import-history-confirm = Vraiment annuler ?
+import-history-rolled-back = Annulé
+import-history-removed = { $removed } mesures supprimées.
+import-history-kind-reader = Synchronisation du lecteur
+import-history-kind-libreview = Export LibreView
+import-history-kind-dexcom = Export Dexcom Clarity
+import-history-kind-nightscout = Export Nightscout
+import-history-kind-apple-health = Export Apple Health
+import-history-kind-mapped = Tableur
+
+## Libellés des flux (registre des flux de looplace-store)
+stream-glucose-mg-dl = Glycémie
+stream-ketone-mmol-l = Cétones
+stream-pvt-total-trials = Essais PVT
+stream-pvt-reacted-trials = Réponses PVT
+stream-pvt-median-rt-ms = TR médian PVT
+stream-pvt-mean-rt-ms = TR moyen PVT
+stream-pvt-sd-rt-ms = Variabilité du TR PVT (ET)
+stream-pvt-p10-rt-ms = TR le plus rapide PVT (P10)
+stream-pvt-p90-rt-ms = TR le plus lent PVT (P90)
+stream-pvt-lapses-ge-500ms = Lapsus PVT ≥500 ms
+stream-pvt-minor-lapses-355-499ms = Lapsus mineurs PVT 355–499 ms
+stream-pvt-false-starts = Faux départs PVT
+stream-pvt-time-on-task-slope-ms-per-min = Pente temps-sur-tâche PVT
+stream-pvt-mean-reciprocal-rt-per-s = Vitesse de réponse PVT (moyenne 1/TR)
+stream-pvt-fastest-10pct-rt-ms = TR des 10 % les plus rapides PVT
+stream-pvt-slowest-10pct-rt-ms = TR des 10 % les plus lents PVT
+stream-pvt-lapse-probability = Probabilité de lapsus PVT
+stream-pvt-performance-score = Score de performance PVT
+stream-nback2-total-trials = Essais N-back
+stream-nback2-target-trials = Essais cibles N-back
+stream-nback2-non-target-trials = Essais non cibles N-back
+stream-nback2-hits = Réussites N-back
+stream-nback2-misses = Omissions N-back
+stream-nback2-false-alarms = Fausses alarmes N-back
+stream-nback2-correct-rejections = Rejets corrects N-back
+stream-nback2-response-count = Réponses N-back
+stream-nback2-hit-rate = Taux de réussite N-back
+stream-nback2-false-alarm-rate = Taux de fausses alarmes N-back
+stream-nback2-accuracy = Précision N-back
+stream-nback2-d-prime = d′ N-back
+stream-nback2-criterion = Critère N-back
+stream-nback2-mean-hit-rt-ms = TR moyen des réussites N-back
+stream-nback2-median-hit-rt-ms = TR médian des réussites N-back
+stream-nback2-sd-hit-rt-ms = Variabilité du TR des réussites N-back (ET)
+stream-nback2-p10-hit-rt-ms = TR de réussite le plus rapide N-back (P10)
+stream-nback2-p90-hit-rt-ms = TR de réussite le plus lent N-back (P90)
+stream-nback2-n = Niveau N-back
+stream-nback2-lure-trials = Essais leurres N-back
+stream-nback2-lure-false-alarms = Fausses alarmes sur leurres N-back
+stream-nback2-non-lure-false-alarms = Fausses alarmes hors leurres N-back
+stream-nback2-lure-false-alarm-rate = Taux de fausses alarmes sur leurres N-back
+stream-nback2-non-lure-false-alarm-rate = Taux de fausses alarmes hors leurres N-back
+stream-nback2-lure-d-prime = d′ face aux leurres N-back
+stream-flanker-total-trials = Essais flanker
+stream-flanker-congruent-trials = Essais congruents flanker
+stream-flanker-incongruent-trials = Essais incongruents flanker
+stream-flanker-correct = Réponses correctes flanker
+stream-flanker-errors = Erreurs flanker
+stream-flanker-misses = Omissions flanker
+stream-flanker-congruent-correct = Correctes congruentes flanker
+stream-flanker-incongruent-correct = Correctes incongruentes flanker
+stream-flanker-accuracy = Précision flanker
+stream-flanker-congruent-accuracy = Précision congruente flanker
+stream-flanker-incongruent-accuracy = Précision incongruente flanker
+stream-flanker-congruent-mean-rt-ms = TR moyen congruent flanker
+stream-flanker-incongruent-mean-rt-ms = TR moyen incongruent flanker
+stream-flanker-congruent-median-rt-ms = TR médian congruent flanker
+stream-flanker-incongruent-median-rt-ms = TR médian incongruent flanker
+stream-flanker-conflict-effect-ms = Effet de conflit flanker (TR)
+stream-flanker-conflict-effect-accuracy = Effet de conflit flanker (précision)
+stream-flanker-response-count = Réponses flanker
+stream-dsst-duration-ms = Durée DSST
+stream-dsst-responses = Réponses DSST
+stream-dsst-correct = Correctes DSST
+stream-dsst-errors = Erreurs DSST
+stream-dsst-correct-per-minute = Correctes par minute DSST
+stream-dsst-accuracy = Précision DSST
+stream-dsst-mean-rt-ms = TR moyen DSST
+stream-dsst-median-rt-ms = TR médian DSST
+stream-word-recall-list-length = Longueur de liste (rappel)
+stream-word-recall-immediate-correct = Rappel immédiat : corrects
+stream-word-recall-immediate-intrusions = Rappel immédiat : intrusions
+stream-word-recall-immediate-repetitions = Rappel immédiat : répétitions
+stream-word-recall-delayed-correct = Rappel différé : corrects
+stream-word-recall-delayed-intrusions = Rappel différé : intrusions
+stream-word-recall-delayed-repetitions = Rappel différé : répétitions
+stream-word-recall-delay-ms = Délai du rappel différé
+stream-word-recall-retention = Rétention (rappel de mots)
+stream-task-switch-total-trials = Essais d’alternance
+stream-task-switch-correct = Réponses correctes (alternance)
+stream-task-switch-errors = Erreurs d’alternance
+stream-task-switch-misses = Omissions d’alternance
+stream-task-switch-accuracy = Précision (alternance)
+stream-task-switch-pure-trials = Essais à règle unique (alternance)
+stream-task-switch-repeat-trials = Essais de répétition (alternance)
+stream-task-switch-switch-trials = Essais d’alternance de règle
+stream-task-switch-pure-median-rt-ms = RT médiane à règle unique (alternance)
+stream-task-switch-repeat-median-rt-ms = RT médiane en répétition (alternance)
+stream-task-switch-switch-median-rt-ms = RT médiane en alternance de règle
+stream-task-switch-pure-error-rate = Taux d’erreur à règle unique (alternance)
+stream-task-switch-repeat-error-rate = Taux d’erreur en répétition (alternance)
+stream-task-switch-switch-error-rate = Taux d’erreur en alternance de règle
+stream-task-switch-switch-cost-ms = Coût d’alternance (RT)
+stream-task-switch-mixing-cost-ms = Coût de mélange (RT)
+stream-task-switch-switch-cost-error-rate = Coût d’alternance (erreurs)
+stream-task-switch-mixing-cost-error-rate = Coût de mélange (erreurs)
+stream-task-switch-response-count = Réponses (alternance)
+stream-journal-meal = Repas
+stream-journal-medication = Médicament
+stream-journal-exercise = Exercice
+stream-journal-sleep = Sommeil
+stream-journal-note = Note
+stream-health-heart-rate-bpm = Fréquence cardiaque
+stream-health-hrv-sdnn-ms = Variabilité de la fréquence cardiaque (SDNN)
+stream-health-steps = Pas
+stream-health-active-energy-kcal = Énergie active
+stream-health-sleep-h = Sommeil
+stream-health-workout-min = Entraînement
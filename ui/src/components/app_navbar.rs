@@ -34,6 +34,10 @@ const NAVBAR_CSS_INLINE: &str = include_str!(concat!(
 ///         home: || rsx!( Link { class: "navbar__link", to: Route::Home {} } ),
 ///         pvt: || rsx!( Link { class: "navbar__link", to: Route::Pvt {} } ),
 ///         nback: || rsx!( Link { class: "navbar__link", to: Route::NBack2 {} } ),
+///         flanker: || rsx!( Link { class: "navbar__link", to: Route::Flanker {} } ),
+///         dsst: || rsx!( Link { class: "navbar__link", to: Route::Dsst {} } ),
+///         word_recall: || rsx!( Link { class: "navbar__link", to: Route::WordRecall {} } ),
+///         task_switch: || rsx!( Link { class: "navbar__link", to: Route::TaskSwitch {} } ),
 ///         results: || rsx!( Link { class: "navbar__link", to: Route::Results {} } ),
 ///     });
 /// }
@@ -50,8 +54,14 @@ pub struct NavBuilder {
     pub home: fn(label: &str) -> Element,
     pub pvt: fn(label: &str) -> Element,
     pub nback: fn(label: &str) -> Element,
+    pub flanker: fn(label: &str) -> Element,
+    pub dsst: fn(label: &str) -> Element,
+    pub word_recall: fn(label: &str) -> Element,
+    pub task_switch: fn(label: &str) -> Element,
     pub results: fn(label: &str) -> Element,
     pub glucose: fn(label: &str) -> Element,
+    pub journal: fn(label: &str) -> Element,
+    pub import: fn(label: &str) -> Element,
 }
 
 static NAV_BUILDER: OnceCell<NavBuilder> = OnceCell::new();
@@ -100,16 +110,28 @@ pub fn AppNavbar(children: Element) -> Element {
         let home = (b.home)(&t!("nav-home"));
         let pvt = (b.pvt)(&t!("nav-pvt"));
         let nback = (b.nback)(&t!("nav-nback"));
+        let flanker = (b.flanker)(&t!("nav-flanker"));
+        let dsst = (b.dsst)(&t!("nav-dsst"));
+        let word_recall = (b.word_recall)(&t!("nav-word-recall"));
+        let task_switch = (b.task_switch)(&t!("nav-task-switch"));
         let results = (b.results)(&t!("nav-results"));
         let glucose = (b.glucose)(&t!("nav-glucose"));
+        let journal = (b.journal)(&t!("nav-journal"));
+        let import = (b.import)(&t!("nav-import"));
 
         rsx! {
             nav { class: "navbar__links",
                 {home}
                 {pvt}
                 {nback}
+                {flanker}
+                {dsst}
+                {word_recall}
+                {task_switch}
                 {results}
                 {glucose}
+                {journal}
+                {import}
             }
         }
         .expect("AppNavbar: rsx render failed")
@@ -5,6 +5,15 @@
 //! (web) and mobile (iOS/Android) builds free of Parquet/hidapi *and* free of the
 //! Libre 2 device keys; on those targets [`load`] returns an `unsupported`
 //! snapshot and the view shows a desktop-only note.
+//!
+//! Everything goes through the app's [`SharedStore`] handle: [`load`] reads a
+//! snapshot once, and [`refresh`] folds later store changes into it by
+//! re-querying only the changed span. An encrypted store reads as
+//! [`StoreEncryption::Locked`] until unlocked, and the view asks for the
+//! passphrase instead of data.
+
+pub use crate::core::store::StoreEncryption;
+use crate::core::store::{SharedStore, StoreChange};
 
 /// One glucose reading, flattened for display.
 #[derive(Debug, Clone, PartialEq)]
@@ -27,9 +36,12 @@ pub struct GlucosePoint {
 pub struct GlucoseData {
     pub points: Vec<GlucosePoint>,
     pub unit: String,
+    /// Localized stream label, from the store's stream registry.
+    pub label: String,
     pub error: Option<String>,
     /// False on web/mobile (no local store or reader) → the view shows a note.
     pub supported: bool,
+    pub encryption: StoreEncryption,
 }
 
 impl GlucoseData {
@@ -38,8 +50,10 @@ impl GlucoseData {
         Self {
             points: Vec::new(),
             unit: "mg/dL".into(),
+            label: String::new(),
             error: None,
             supported: false,
+            encryption: StoreEncryption::Plaintext,
         }
     }
 
@@ -47,9 +61,23 @@ impl GlucoseData {
     fn error(msg: String) -> Self {
         Self {
             points: Vec::new(),
-            unit: "mg/dL".into(),
+            unit: glucose_spec().unit.into(),
+            label: crate::i18n::tr(glucose_spec().label_key),
             error: Some(msg),
             supported: true,
+            encryption: StoreEncryption::Plaintext,
+        }
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    fn locked() -> Self {
+        Self {
+            points: Vec::new(),
+            unit: glucose_spec().unit.into(),
+            label: crate::i18n::tr(glucose_spec().label_key),
+            error: None,
+            supported: true,
+            encryption: StoreEncryption::Locked,
         }
     }
 }
@@ -80,38 +108,103 @@ impl Default for GlucoseSettings {
     }
 }
 
+/// The chart's trace: 15-minute means of `points` as `(ts_unix, value)`, each
+/// at its bucket's middle (kept within the readings' span). The store's
+/// [`aggregate`](looplace_store::aggregate) does the bucketing.
+pub fn trace(points: &[GlucosePoint]) -> Vec<(i64, f64)> {
+    use looplace_store::aggregate::{aggregate, Aggregation, Interval, Stat};
+    use looplace_store::streams::GLUCOSE;
+    use looplace_store::Observation;
+
+    let rows: Vec<Observation> = points
+        .iter()
+        .filter_map(|p| {
+            let t = time::OffsetDateTime::from_unix_timestamp(p.ts_unix).ok()?;
+            let at = time::PrimitiveDateTime::new(t.date(), t.time());
+            Some(Observation::new(GLUCOSE, at, p.value, "mg/dL", "chart"))
+        })
+        .collect();
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let half = 15 * 60 / 2;
+    let quarter_hours = Aggregation::resample(Interval::FIFTEEN_MINUTES, &[Stat::Mean]);
+    aggregate(&rows, &quarter_hours)
+        .iter()
+        .map(|o| {
+            let middle = o.timestamp.assume_utc().unix_timestamp() + half;
+            (middle.clamp(first.ts_unix, last.ts_unix), o.value)
+        })
+        .collect()
+}
+
 // ---- Desktop backend ------------------------------------------------------
 
+/// The glucose stream's registry entry (unit, label).
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
-const GLUCOSE_STREAM: &str = "glucose.mg_dl";
+fn glucose_spec() -> &'static looplace_store::streams::StreamSpec {
+    looplace_store::streams::lookup(looplace_store::streams::GLUCOSE)
+        .expect("glucose stream is registered")
+}
 
 /// Read all glucose observations from the local store.
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
-pub fn load() -> GlucoseData {
-    use looplace_store::{ParquetStore, Query, Store};
+pub fn load(store: &SharedStore) -> GlucoseData {
+    use crate::core::store::Unavailable;
+    use looplace_store::{Query, Store};
 
-    let path = match crate::core::storage::data_dir() {
-        Ok(dir) => dir.join("looplace.parquet"),
-        Err(e) => return GlucoseData::error(format!("data dir unavailable: {e}")),
-    };
-    // `open` treats a missing file as an empty store, so first-run is not an error.
-    let store = match ParquetStore::open(&path) {
-        Ok(s) => s,
-        Err(e) => return GlucoseData::error(format!("couldn't open store: {e}")),
-    };
-    let rows = match store.query(&Query::stream(GLUCOSE_STREAM)) {
-        Ok(r) => r,
-        Err(e) => return GlucoseData::error(format!("couldn't read glucose: {e}")),
+    let rows = match store.read(|s| s.query(&Query::stream(glucose_spec().name))) {
+        Ok(Ok(r)) => r,
+        Ok(Err(e)) => return GlucoseData::error(format!("couldn't read glucose: {e}")),
+        Err(Unavailable::Locked) => return GlucoseData::locked(),
+        Err(e) => return GlucoseData::error(e.to_string()),
     };
-    let points = rows.iter().map(point_from_obs).collect();
     GlucoseData {
-        points,
-        unit: "mg/dL".into(),
+        points: rows.iter().map(point_from_obs).collect(),
+        unit: glucose_spec().unit.into(),
+        label: crate::i18n::tr(glucose_spec().label_key),
         error: None,
         supported: true,
+        encryption: store.encryption(),
     }
 }
 
+/// Fold store `changes` into `data`: re-read just the span each change touched
+/// on the glucose stream, or everything after a reset (unlock, lock, encrypt).
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn refresh(store: &SharedStore, data: &mut GlucoseData, changes: &[StoreChange]) {
+    use looplace_store::Store;
+
+    for change in changes {
+        let Some(query) = change.requery(glucose_spec().name) else {
+            continue;
+        };
+        if *change == StoreChange::Reset || !data.supported {
+            *data = load(store);
+            continue;
+        }
+        match store.read(|s| s.query(&query)) {
+            Ok(Ok(rows)) => splice(&mut data.points, &query, &rows),
+            _ => *data = load(store),
+        }
+    }
+}
+
+/// Replace the points within `query`'s time bounds with `rows`.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn splice(
+    points: &mut Vec<GlucosePoint>,
+    query: &looplace_store::Query,
+    rows: &[looplace_store::Observation],
+) {
+    let unix = |t: time::PrimitiveDateTime| t.assume_utc().unix_timestamp();
+    let since = query.since.map_or(i64::MIN, unix);
+    let until = query.until.map_or(i64::MAX, unix);
+    points.retain(|p| !(since..=until).contains(&p.ts_unix));
+    points.extend(rows.iter().map(point_from_obs));
+    points.sort_by_key(|p| p.ts_unix);
+}
+
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 fn point_from_obs(o: &looplace_store::Observation) -> GlucosePoint {
     let is = |k: &str| o.tags.get(k).map(|v| v == "true").unwrap_or(false);
@@ -167,18 +260,19 @@ pub fn save_settings(settings: &GlucoseSettings) {
 /// read-only against the device. **Private on purpose:** it must only ever run on
 /// the [`device_thread`] — see that function for why.
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
-fn sync_from_reader() -> std::result::Result<SyncReport, String> {
+fn sync_from_reader(store: &SharedStore) -> std::result::Result<SyncReport, String> {
     use looplace_libre::LibreDevice;
+    use looplace_store::convert::cgm::skip_known_readings;
     use looplace_store::convert::reading_to_observation;
-    use looplace_store::{ParquetStore, Store};
+    use looplace_store::provenance::{self, IngestBatch, READER_SYNC};
 
-    let dir = crate::core::storage::data_dir().map_err(|e| format!("data dir unavailable: {e}"))?;
-    let mut store = ParquetStore::open(dir.join("looplace.parquet"))
-        .map_err(|e| format!("couldn't open store: {e}"))?;
+    // Fail fast on a locked store, before touching the reader.
+    store.read(|_| ()).map_err(|e| e.to_string())?;
 
     let mut device = LibreDevice::open_libre2().map_err(|e| format!("reader not found: {e}"))?;
     device.connect().map_err(|e| format!("handshake failed: {e}"))?;
     let serial = device.serial_number().unwrap_or_else(|_| "unknown".into());
+    let firmware = device.software_version().ok();
     let readings = device.read_all().map_err(|e| format!("read failed: {e}"))?;
 
     // Host IANA timezone (e.g. "America/Denver"), recorded with each reading so its
@@ -189,8 +283,17 @@ fn sync_from_reader() -> std::result::Result<SyncReport, String> {
         .filter_map(|r| reading_to_observation(r, &serial, &tz))
         .collect();
     let total = observations.len();
+    let mut batch = IngestBatch::new(READER_SYNC, crate::core::import::APP_VERSION);
+    batch.device_firmware = firmware;
+    batch.detail = Some(serial.clone());
+    // Readings already imported from a LibreView export sit at the same minute
+    // without seconds; skip those rather than storing each reading twice.
     let added = store
-        .upsert(&observations)
+        .write(|s| {
+            skip_known_readings(s, observations)
+                .and_then(|fresh| provenance::ingest(s, &batch, fresh))
+        })
+        .map_err(|e| e.to_string())?
         .map_err(|e| format!("store write failed: {e}"))?;
     Ok(SyncReport {
         serial,
@@ -204,7 +307,7 @@ type SyncReply = futures_channel::oneshot::Sender<std::result::Result<SyncReport
 
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 enum DeviceCmd {
-    Sync(SyncReply),
+    Sync(SharedStore, SyncReply),
 }
 
 /// A single long-lived thread that owns **all** hidapi/IOKit interaction.
@@ -225,8 +328,8 @@ fn device_thread() -> &'static std::sync::Mutex<std::sync::mpsc::Sender<DeviceCm
             .spawn(move || {
                 while let Ok(cmd) = rx.recv() {
                     match cmd {
-                        DeviceCmd::Sync(reply) => {
-                            let _ = reply.send(sync_from_reader());
+                        DeviceCmd::Sync(store, reply) => {
+                            let _ = reply.send(sync_from_reader(&store));
                         }
                     }
                 }
@@ -236,14 +339,17 @@ fn device_thread() -> &'static std::sync::Mutex<std::sync::mpsc::Sender<DeviceCm
     })
 }
 
-/// Enqueue a reader sync on the [`device_thread`]; `await` the returned receiver
-/// on the UI task. Resolves to canceled if the device thread can't be reached.
+/// Enqueue a reader sync into `store` on the [`device_thread`]; `await` the
+/// returned receiver on the UI task. Resolves to canceled if the device thread
+/// can't be reached. The new readings reach the views through the store's
+/// change feed.
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
-pub fn request_sync() -> futures_channel::oneshot::Receiver<std::result::Result<SyncReport, String>>
-{
+pub fn request_sync(
+    store: &SharedStore,
+) -> futures_channel::oneshot::Receiver<std::result::Result<SyncReport, String>> {
     let (tx, rx) = futures_channel::oneshot::channel();
     if let Ok(sender) = device_thread().lock() {
-        let _ = sender.send(DeviceCmd::Sync(tx));
+        let _ = sender.send(DeviceCmd::Sync(store.clone(), tx));
     }
     rx
 }
@@ -251,10 +357,13 @@ pub fn request_sync() -> futures_channel::oneshot::Receiver<std::result::Result<
 // ---- Non-desktop stub (web / mobile) --------------------------------------
 
 #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-pub fn load() -> GlucoseData {
+pub fn load(_store: &SharedStore) -> GlucoseData {
     GlucoseData::unsupported()
 }
 
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn refresh(_store: &SharedStore, _data: &mut GlucoseData, _changes: &[StoreChange]) {}
+
 #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 pub fn load_settings() -> GlucoseSettings {
     GlucoseSettings::default()
@@ -262,3 +371,59 @@ pub fn load_settings() -> GlucoseSettings {
 
 #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
 pub fn save_settings(_settings: &GlucoseSettings) {}
+
+#[cfg(all(test, any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+mod tests {
+    use super::*;
+    use looplace_store::{Observation, Query};
+    use time::macros::datetime;
+
+    fn reading(t: time::PrimitiveDateTime, value: f64) -> Observation {
+        Observation::new(glucose_spec().name, t, value, "mg/dL", "dev")
+    }
+
+    #[test]
+    fn splice_replaces_only_the_changed_span() {
+        let rows = [
+            reading(datetime!(2026-06-19 08:00:00), 90.0),
+            reading(datetime!(2026-06-19 08:15:00), 100.0),
+            reading(datetime!(2026-06-19 08:30:00), 110.0),
+        ];
+        let mut points: Vec<_> = rows.iter().map(point_from_obs).collect();
+
+        // 08:15 was rewritten and 08:20 added; 08:00 and 08:30 are untouched.
+        let query = Query {
+            stream: Some(glucose_spec().name.into()),
+            since: Some(datetime!(2026-06-19 08:15:00)),
+            until: Some(datetime!(2026-06-19 08:20:00)),
+            batch_id: None,
+        };
+        let fresh = [
+            reading(datetime!(2026-06-19 08:15:00), 105.0),
+            reading(datetime!(2026-06-19 08:20:00), 107.0),
+        ];
+        splice(&mut points, &query, &fresh);
+        let values: Vec<_> = points.iter().map(|p| p.value).collect();
+        assert_eq!(values, vec![90.0, 105.0, 107.0, 110.0]);
+    }
+
+    #[test]
+    fn trace_follows_quarter_hour_means_within_the_readings() {
+        let rows = [
+            reading(datetime!(2026-06-19 08:01:00), 100.0),
+            reading(datetime!(2026-06-19 08:14:00), 110.0),
+            reading(datetime!(2026-06-19 08:20:00), 120.0),
+        ];
+        let points: Vec<_> = rows.iter().map(point_from_obs).collect();
+        let unix = |t: time::PrimitiveDateTime| t.assume_utc().unix_timestamp();
+        assert_eq!(
+            trace(&points),
+            vec![
+                (unix(datetime!(2026-06-19 08:07:30)), 105.0),
+                // The last bucket's middle, 08:22:30, is past the last reading.
+                (unix(datetime!(2026-06-19 08:20:00)), 120.0),
+            ]
+        );
+        assert!(trace(&[]).is_empty());
+    }
+}
@@ -0,0 +1,380 @@
+//! Imports of other apps' exports into the local store, for the Import view.
+//!
+//! An Apple Health import runs on its own thread and reports an
+//! [`ImportStatus`] back to a signal as it goes. Spreadsheets are small, so a
+//! mapped import is previewed and written in place; its mappings are saved
+//! by name in `import_mappings.json` beside the glucose prefs. Either way the
+//! rows reach the other views through the store's change feed like any other
+//! write. Each import (and each reader sync) is recorded as a provenance batch
+//! (see `looplace_store::provenance`), listed by [`history`] and undone with
+//! [`rollback`]. A whole-store bundle (see `looplace_store::bundle`) is
+//! written to the exports folder and can be imported back here on another
+//! machine. Like the store itself, imports are **desktop-only**: on
+//! web/mobile [`supported`] is false and the view shows a note instead.
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+use dioxus::prelude::*;
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+use crate::core::store::SharedStore;
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub use looplace_store::convert::cgm::ParsedReadings;
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub use looplace_store::convert::mapped::{ImportMapping, MappedFormat, TimestampFormat};
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub use looplace_store::provenance::BatchSummary;
+
+/// Recorded in every batch this app writes.
+pub const APP_VERSION: &str = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"));
+
+/// Where an import stands.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ImportStatus {
+    #[default]
+    Idle,
+    Running {
+        /// Bytes of the file read so far, of `total`.
+        read: u64,
+        total: u64,
+        imported: usize,
+    },
+    Done {
+        imported: usize,
+        skipped: usize,
+    },
+    Failed(String),
+}
+
+impl ImportStatus {
+    pub fn is_running(&self) -> bool {
+        matches!(self, ImportStatus::Running { .. })
+    }
+
+    /// Share of the file read, in `0.0..=1.0` (zero until the size is known).
+    pub fn fraction(&self) -> f64 {
+        match *self {
+            ImportStatus::Running { read, total, .. } if total > 0 => {
+                (read as f64 / total as f64).min(1.0)
+            }
+            ImportStatus::Done { .. } => 1.0,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Whether this platform can import (it has a local store).
+pub fn supported() -> bool {
+    cfg!(any(
+        target_os = "macos",
+        target_os = "windows",
+        target_os = "linux"
+    ))
+}
+
+/// Import an Apple Health `export.xml` at `path` in the background, writing
+/// batch by batch inside one bulk write (so the store's files are rewritten
+/// once, at the end) and reporting progress to `status`.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn apple_health(
+    store: &SharedStore,
+    path: std::path::PathBuf,
+    mut status: Signal<ImportStatus>,
+) {
+    use futures_util::StreamExt;
+
+    let (tx, mut rx) = futures_channel::mpsc::unbounded::<ImportStatus>();
+    let store = store.clone();
+    status.set(ImportStatus::Running {
+        read: 0,
+        total: 0,
+        imported: 0,
+    });
+    let worker = std::thread::Builder::new()
+        .name("looplace-import".into())
+        .spawn(move || {
+            let result = import_apple_health(&store, &path, |s| {
+                let _ = tx.unbounded_send(s);
+            });
+            let _ = tx.unbounded_send(result.unwrap_or_else(ImportStatus::Failed));
+        });
+    if let Err(e) = worker {
+        status.set(ImportStatus::Failed(format!("couldn't start import: {e}")));
+        return;
+    }
+    spawn(async move {
+        while let Some(next) = rx.next().await {
+            status.set(next);
+        }
+    });
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn import_apple_health(
+    store: &SharedStore,
+    path: &std::path::Path,
+    mut report: impl FnMut(ImportStatus),
+) -> Result<ImportStatus, String> {
+    use looplace_store::convert::health::apple_health_xml;
+    use looplace_store::provenance::{self, HashingReader, IngestBatch, APPLE_HEALTH};
+    use looplace_store::StoreError;
+
+    let file =
+        std::fs::File::open(path).map_err(|e| format!("couldn't open {}: {e}", path.display()))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let tz = iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string());
+    let mut ingest = IngestBatch::new(APPLE_HEALTH, APP_VERSION);
+    ingest.detail = path.file_name().map(|n| n.to_string_lossy().into_owned());
+    // Hashed as it streams: the export can be gigabytes.
+    let mut input = std::io::BufReader::with_capacity(1 << 20, HashingReader::new(file));
+    store
+        .write(|s| s.get_mut().begin_bulk_write())
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("store write failed: {e}"))?;
+    let done = apple_health_xml(&mut input, &tz, |batch, progress| {
+        store
+            .write(|s| provenance::ingest(s, &ingest, batch))
+            .map_err(|e| StoreError::Backend(e.to_string()))??;
+        report(ImportStatus::Running {
+            read: progress.bytes,
+            total,
+            imported: progress.imported,
+        });
+        Ok(())
+    });
+    if done.is_ok() {
+        ingest.file_hash = Some(input.into_inner().finish());
+    }
+    // Finished even when the import stopped part-way, so the batches already
+    // written are kept, as with any other interrupted import.
+    store
+        .write(|s| {
+            if done.is_ok() {
+                provenance::amend(s, &ingest)?;
+            }
+            s.get_mut().finish_bulk_write()
+        })
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("store write failed: {e}"))?;
+    let done = done.map_err(|e| e.to_string())?;
+    Ok(ImportStatus::Done {
+        imported: done.imported,
+        skipped: done.skipped,
+    })
+}
+
+/// A spreadsheet chosen for a mapped import.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappedFile {
+    pub name: String,
+    pub format: MappedFormat,
+    pub text: String,
+    pub columns: Vec<String>,
+}
+
+/// Read a CSV/JSON file and list its columns.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn read_mapped_file(path: &std::path::Path) -> Result<MappedFile, String> {
+    use looplace_store::convert::mapped::columns;
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let text = std::fs::read_to_string(path).map_err(|e| format!("couldn't read {name}: {e}"))?;
+    let format = MappedFormat::for_file_name(&name);
+    let columns = columns(&text, format).map_err(|e| e.to_string())?;
+    Ok(MappedFile {
+        name,
+        format,
+        text,
+        columns,
+    })
+}
+
+/// A blank mapping to start from, in the host's timezone.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn new_mapping() -> ImportMapping {
+    ImportMapping {
+        name: String::new(),
+        timestamp_column: String::new(),
+        timestamp_format: TimestampFormat::Pattern("[year]-[month]-[day] [hour]:[minute]".into()),
+        value_column: String::new(),
+        stream: looplace_store::streams::CUSTOM_PREFIX.into(),
+        unit: String::new(),
+        tz: iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string()),
+    }
+}
+
+/// The dry run: every row mapped, nothing written.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn preview(file: &MappedFile, mapping: &ImportMapping) -> Result<ParsedReadings, String> {
+    looplace_store::convert::mapped::parse(&file.text, file.format, mapping)
+        .map_err(|e| e.to_string())
+}
+
+/// Write a previewed import of `file`. Returns the number of new rows.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn write_mapped(
+    store: &SharedStore,
+    file: &MappedFile,
+    parsed: &ParsedReadings,
+) -> Result<usize, String> {
+    use looplace_store::provenance::{self, IngestBatch, MAPPED_FILE};
+
+    let batch = IngestBatch::new(MAPPED_FILE, APP_VERSION).with_file(&file.name, file.text.as_bytes());
+    store
+        .write(|s| provenance::ingest(s, &batch, parsed.observations.clone()))
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("store write failed: {e}"))
+}
+
+/// Every sync and import on record, newest first, with the rows each still
+/// holds.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn history(store: &SharedStore) -> Result<Vec<BatchSummary>, String> {
+    store
+        .read(|s| looplace_store::provenance::history(s))
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Delete the rows batch `id` wrote. Returns how many were removed.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn rollback(store: &SharedStore, id: &str) -> Result<usize, String> {
+    store
+        .write(|s| looplace_store::provenance::rollback(s, id))
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("rollback failed: {e}"))
+}
+
+/// Where saved mappings live (sibling to the store and the glucose prefs).
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn mappings_path() -> Option<std::path::PathBuf> {
+    crate::core::storage::data_dir()
+        .ok()
+        .map(|d| d.join("import_mappings.json"))
+}
+
+/// Saved mappings, by name. A missing or unreadable file is an empty list.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn load_mappings() -> Vec<ImportMapping> {
+    mappings_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Save `mapping`, replacing any with the same name. Returns the new list.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn save_mapping(mapping: &ImportMapping) -> Result<Vec<ImportMapping>, String> {
+    mapping.check().map_err(|e| e.to_string())?;
+    let mut all = load_mappings();
+    all.retain(|m| m.name != mapping.name);
+    all.push(mapping.clone());
+    all.sort_by(|a, b| a.name.cmp(&b.name));
+    write_mappings(&all)?;
+    Ok(all)
+}
+
+/// Forget the mapping called `name`. Returns the new list.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn delete_mapping(name: &str) -> Result<Vec<ImportMapping>, String> {
+    let mut all = load_mappings();
+    all.retain(|m| m.name != name);
+    write_mappings(&all)?;
+    Ok(all)
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn write_mappings(all: &[ImportMapping]) -> Result<(), String> {
+    let path = mappings_path().ok_or("no app data directory")?;
+    let json = serde_json::to_string_pretty(all).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| format!("couldn't save mappings: {e}"))
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub use looplace_store::snapshot::RestoreReport;
+
+/// Export a bundle named `scope` to the exports folder: everything (prefs
+/// included) if `domains` is empty, else just the streams under those domains
+/// (`glucose`, `health`, …), from `since` on — say, for a clinician. Returns
+/// the bundle's path.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn export_bundle(
+    store: &SharedStore,
+    scope: &str,
+    domains: &[&str],
+    since: Option<time::Date>,
+) -> Result<std::path::PathBuf, String> {
+    use looplace_store::bundle::{export, BundleOptions, BUNDLE_EXTENSION};
+    use looplace_store::migrate::PREFS_FILES;
+    use looplace_store::streams::STREAMS;
+
+    let data_dir = crate::core::storage::data_dir().map_err(|e| e.to_string())?;
+    let options = BundleOptions {
+        streams: STREAMS
+            .iter()
+            .filter(|s| domains.iter().any(|d| s.name.split('.').next() == Some(*d)))
+            .map(|s| s.name.to_string())
+            .collect(),
+        since: since.map(|d| d.midnight()),
+        prefs: if domains.is_empty() {
+            PREFS_FILES.iter().map(|name| data_dir.join(name)).collect()
+        } else {
+            Vec::new()
+        },
+        ..Default::default()
+    };
+    let dir = data_dir.join("exports");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let stamp = time::OffsetDateTime::now_utc()
+        .format(time::macros::format_description!(
+            "[year][month][day]_[hour][minute][second]"
+        ))
+        .unwrap_or_else(|_| "export".into());
+    let path = dir.join(format!("looplace-{scope}-{stamp}.{BUNDLE_EXTENSION}"));
+    store
+        .read(|s| export(s, &path, &options))
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("export failed: {e}"))?;
+    Ok(path)
+}
+
+/// Import a bundle into the store, and its prefs beside it. Everything is
+/// checked before anything is written.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn import_bundle(store: &SharedStore, path: &std::path::Path) -> Result<RestoreReport, String> {
+    let data_dir = crate::core::storage::data_dir().map_err(|e| e.to_string())?;
+    store
+        .write(|s| looplace_store::bundle::import(path, s, Some(&data_dir)))
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("import failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_fraction_is_clamped_and_complete_when_done() {
+        let running = |read, total| ImportStatus::Running {
+            read,
+            total,
+            imported: 0,
+        };
+        assert_eq!(running(50, 200).fraction(), 0.25);
+        assert_eq!(running(300, 200).fraction(), 1.0);
+        assert_eq!(running(10, 0).fraction(), 0.0);
+        assert_eq!(
+            ImportStatus::Done {
+                imported: 1,
+                skipped: 0
+            }
+            .fraction(),
+            1.0
+        );
+        assert!(!ImportStatus::Idle.is_running());
+    }
+}
@@ -0,0 +1,411 @@
+//! Journal data access for the Journal view and the chart markers.
+//!
+//! Entries live in the local store as `journal.*` observations (see
+//! `looplace_store::journal`), so like glucose the backend is **desktop-only**;
+//! on web/mobile [`load`] returns an `unsupported` snapshot and the view shows
+//! a desktop-only note. Times are local wall-clock, entered and shown as-is.
+
+use time::macros::format_description;
+use time::PrimitiveDateTime;
+
+use crate::core::store::{SharedStore, StoreChange};
+
+/// What kind of event an entry records (mirrors the store's categories).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JournalCategory {
+    #[default]
+    Meal,
+    Medication,
+    Exercise,
+    Sleep,
+    Note,
+}
+
+impl JournalCategory {
+    pub const ALL: [JournalCategory; 5] = [
+        JournalCategory::Meal,
+        JournalCategory::Medication,
+        JournalCategory::Exercise,
+        JournalCategory::Sleep,
+        JournalCategory::Note,
+    ];
+
+    /// Stable id, used as the form's `<option>` value.
+    pub fn key(self) -> &'static str {
+        match self {
+            JournalCategory::Meal => "meal",
+            JournalCategory::Medication => "medication",
+            JournalCategory::Exercise => "exercise",
+            JournalCategory::Sleep => "sleep",
+            JournalCategory::Note => "note",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.key() == key)
+    }
+
+    /// Marker glyph on the charts and in the list.
+    pub fn glyph(self) -> &'static str {
+        match self {
+            JournalCategory::Meal => "🍽",
+            JournalCategory::Medication => "💊",
+            JournalCategory::Exercise => "🏃",
+            JournalCategory::Sleep => "🛌",
+            JournalCategory::Note => "📝",
+        }
+    }
+
+    /// Localized name (the store registry's label for the category's stream).
+    pub fn label(self) -> String {
+        match self {
+            JournalCategory::Meal => crate::t!("stream-journal-meal"),
+            JournalCategory::Medication => crate::t!("stream-journal-medication"),
+            JournalCategory::Exercise => crate::t!("stream-journal-exercise"),
+            JournalCategory::Sleep => crate::t!("stream-journal-sleep"),
+            JournalCategory::Note => crate::t!("stream-journal-note"),
+        }
+    }
+}
+
+/// One stored entry, flattened for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalItem {
+    pub id: String,
+    pub category: JournalCategory,
+    /// Unix seconds, treating the local wall-clock start as UTC — the same
+    /// basis as `GlucosePoint::ts_unix`, so markers line up with readings.
+    pub start_unix: i64,
+    /// Start as `YYYY-MM-DDTHH:MM` (the `datetime-local` input format).
+    pub start: String,
+    pub end: Option<String>,
+    pub dose: Option<f64>,
+    pub notes: String,
+}
+
+impl JournalItem {
+    /// Human label, e.g. `2026-06-19 12:30` or `2026-06-19 18:00 – 18:40`.
+    pub fn when_label(&self) -> String {
+        let start = self.start.replace('T', " ");
+        match &self.end {
+            Some(end) if end.get(..10) == self.start.get(..10) => {
+                format!("{start} – {}", end.get(11..).unwrap_or(end))
+            }
+            Some(end) => format!("{start} – {}", end.replace('T', " ")),
+            None => start,
+        }
+    }
+}
+
+/// A snapshot of the journal for the view.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JournalData {
+    /// Sorted by start, oldest first.
+    pub items: Vec<JournalItem>,
+    pub error: Option<String>,
+    /// False on web/mobile (no local store) → the view shows a note.
+    pub supported: bool,
+    /// The store is encrypted and not yet unlocked (on the Glucose page).
+    pub locked: bool,
+}
+
+/// The entry form's fields, as typed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JournalDraft {
+    /// `None` for a new entry.
+    pub id: Option<String>,
+    pub category: JournalCategory,
+    pub start: String,
+    pub end: String,
+    pub dose: String,
+    pub notes: String,
+}
+
+impl JournalDraft {
+    /// A blank entry starting now.
+    pub fn starting_now() -> Self {
+        let now =
+            time::OffsetDateTime::now_local().unwrap_or_else(|_| time::OffsetDateTime::now_utc());
+        Self {
+            start: format_input(PrimitiveDateTime::new(now.date(), now.time())),
+            ..Self::default()
+        }
+    }
+
+    pub fn from_item(item: &JournalItem) -> Self {
+        Self {
+            id: Some(item.id.clone()),
+            category: item.category,
+            start: item.start.clone(),
+            end: item.end.clone().unwrap_or_default(),
+            dose: item.dose.map(|d| d.to_string()).unwrap_or_default(),
+            notes: item.notes.clone(),
+        }
+    }
+}
+
+/// A draft's fields, parsed.
+#[derive(Debug, Clone, PartialEq)]
+struct ParsedDraft {
+    start: PrimitiveDateTime,
+    end: Option<PrimitiveDateTime>,
+    dose: Option<f64>,
+    notes: Option<String>,
+}
+
+/// Parse the draft, or say which field is wrong.
+fn parse_draft(draft: &JournalDraft) -> std::result::Result<ParsedDraft, String> {
+    let start = parse_input(&draft.start).ok_or("start time is missing or invalid")?;
+    let end = match draft.end.trim() {
+        "" => None,
+        s => Some(parse_input(s).ok_or("end time is invalid")?),
+    };
+    if end.is_some_and(|end| end < start) {
+        return Err("end time is before the start".into());
+    }
+    let dose = match draft.dose.trim() {
+        "" => None,
+        s => Some(
+            s.replace(',', ".")
+                .parse::<f64>()
+                .ok()
+                .filter(|d| d.is_finite())
+                .ok_or("amount is not a number")?,
+        ),
+    };
+    let notes = Some(draft.notes.trim().to_string()).filter(|n| !n.is_empty());
+    Ok(ParsedDraft {
+        start,
+        end,
+        dose,
+        notes,
+    })
+}
+
+/// Parse a `datetime-local` value (`YYYY-MM-DDTHH:MM`, seconds optional).
+fn parse_input(s: &str) -> Option<PrimitiveDateTime> {
+    let s = s.trim();
+    PrimitiveDateTime::parse(
+        s,
+        format_description!("[year]-[month]-[day]T[hour]:[minute]"),
+    )
+    .or_else(|_| {
+        PrimitiveDateTime::parse(
+            s,
+            format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]"),
+        )
+    })
+    .ok()
+}
+
+fn format_input(t: PrimitiveDateTime) -> String {
+    t.format(format_description!("[year]-[month]-[day]T[hour]:[minute]"))
+        .unwrap_or_default()
+}
+
+// ---- Desktop backend ------------------------------------------------------
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn to_store(category: JournalCategory) -> looplace_store::journal::Category {
+    use looplace_store::journal::Category;
+    match category {
+        JournalCategory::Meal => Category::Meal,
+        JournalCategory::Medication => Category::Medication,
+        JournalCategory::Exercise => Category::Exercise,
+        JournalCategory::Sleep => Category::Sleep,
+        JournalCategory::Note => Category::Note,
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn from_store(category: looplace_store::journal::Category) -> JournalCategory {
+    use looplace_store::journal::Category;
+    match category {
+        Category::Meal => JournalCategory::Meal,
+        Category::Medication => JournalCategory::Medication,
+        Category::Exercise => JournalCategory::Exercise,
+        Category::Sleep => JournalCategory::Sleep,
+        Category::Note => JournalCategory::Note,
+    }
+}
+
+/// The amount's unit for `category` (from the store's stream registry).
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn dose_unit(category: JournalCategory) -> String {
+    to_store(category).spec().unit.to_string()
+}
+
+/// Read every journal entry from the local store.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn load(store: &SharedStore) -> JournalData {
+    use crate::core::store::Unavailable;
+
+    match store.read(|s| looplace_store::journal::entries(s, None, None)) {
+        Err(Unavailable::Locked) => JournalData {
+            supported: true,
+            locked: true,
+            ..JournalData::default()
+        },
+        Err(e) => JournalData {
+            supported: true,
+            error: Some(e.to_string()),
+            ..JournalData::default()
+        },
+        Ok(Ok(entries)) => JournalData {
+            items: entries.iter().map(item_from_entry).collect(),
+            supported: true,
+            ..JournalData::default()
+        },
+        Ok(Err(e)) => JournalData {
+            supported: true,
+            error: Some(format!("couldn't read journal: {e}")),
+            ..JournalData::default()
+        },
+    }
+}
+
+/// Whether any of `changes` may have touched the journal.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn affected(changes: &[StoreChange]) -> bool {
+    use looplace_store::journal::Category;
+    changes
+        .iter()
+        .any(|c| Category::ALL.iter().any(|cat| c.touches(cat.stream())))
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn item_from_entry(e: &looplace_store::journal::JournalEntry) -> JournalItem {
+    JournalItem {
+        id: e.id.clone(),
+        category: from_store(e.category),
+        start_unix: e.start.assume_utc().unix_timestamp(),
+        start: format_input(e.start),
+        end: e.end.map(format_input),
+        dose: e.dose,
+        notes: e.notes.clone().unwrap_or_default(),
+    }
+}
+
+/// Add the draft as a new entry, or replace the entry it was opened from.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn save(store: &SharedStore, draft: &JournalDraft) -> std::result::Result<(), String> {
+    use looplace_store::journal::{self, JournalEntry};
+    use looplace_store::StoreError;
+
+    let parsed = parse_draft(draft)?;
+    let id = draft
+        .id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let mut entry = JournalEntry::new(id, to_store(draft.category), parsed.start);
+    entry.end = parsed.end;
+    entry.dose = parsed.dose;
+    entry.notes = parsed.notes;
+
+    let saved = store
+        .write(|s| journal::save(s, &entry))
+        .map_err(|e| e.to_string())?;
+    match saved {
+        Ok(()) => Ok(()),
+        Err(StoreError::InvalidValue { .. }) => Err("amount is out of range".into()),
+        Err(e) => Err(format!("couldn't save entry: {e}")),
+    }
+}
+
+/// Delete the entry with `id`.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn delete(store: &SharedStore, id: &str) -> std::result::Result<(), String> {
+    store
+        .write(|s| looplace_store::journal::delete(s, id))
+        .map_err(|e| e.to_string())?
+        .map(|_| ())
+        .map_err(|e| format!("couldn't delete entry: {e}"))
+}
+
+// ---- Non-desktop stub (web / mobile) --------------------------------------
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn dose_unit(_category: JournalCategory) -> String {
+    String::new()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn load(_store: &SharedStore) -> JournalData {
+    JournalData::default()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn affected(changes: &[StoreChange]) -> bool {
+    !changes.is_empty()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn save(_store: &SharedStore, draft: &JournalDraft) -> std::result::Result<(), String> {
+    parse_draft(draft)?;
+    Err("no local store on this platform".into())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn delete(_store: &SharedStore, _id: &str) -> std::result::Result<(), String> {
+    Err("no local store on this platform".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn draft(start: &str, end: &str, dose: &str) -> JournalDraft {
+        JournalDraft {
+            start: start.into(),
+            end: end.into(),
+            dose: dose.into(),
+            notes: "  ".into(),
+            ..JournalDraft::default()
+        }
+    }
+
+    #[test]
+    fn drafts_parse_from_form_values() {
+        let parsed = parse_draft(&draft("2026-06-19T18:00", "2026-06-19T18:40:00", "2,5")).unwrap();
+        assert_eq!(parsed.start, datetime!(2026-06-19 18:00:00));
+        assert_eq!(parsed.end, Some(datetime!(2026-06-19 18:40:00)));
+        assert_eq!(parsed.dose, Some(2.5));
+        assert_eq!(parsed.notes, None);
+        assert_eq!(
+            parse_draft(&draft("2026-06-19T18:00", "", ""))
+                .unwrap()
+                .dose,
+            None
+        );
+    }
+
+    #[test]
+    fn bad_drafts_name_the_field() {
+        assert!(parse_draft(&draft("", "", ""))
+            .unwrap_err()
+            .contains("start"));
+        assert!(
+            parse_draft(&draft("2026-06-19T18:00", "2026-06-19T17:00", ""))
+                .unwrap_err()
+                .contains("before")
+        );
+        assert!(parse_draft(&draft("2026-06-19T18:00", "", "lots"))
+            .unwrap_err()
+            .contains("amount"));
+    }
+
+    #[test]
+    fn same_day_ranges_shorten_the_end() {
+        let item = JournalItem {
+            id: "a".into(),
+            category: JournalCategory::Exercise,
+            start_unix: 0,
+            start: "2026-06-19T18:00".into(),
+            end: Some("2026-06-19T18:40".into()),
+            dose: None,
+            notes: String::new(),
+        };
+        assert_eq!(item.when_label(), "2026-06-19 18:00 – 18:40");
+    }
+}
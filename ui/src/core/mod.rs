@@ -1,7 +1,10 @@
 pub mod format;
 pub mod glucose;
+pub mod import;
+pub mod journal;
 pub mod platform;
 pub mod qc;
 pub mod readiness;
 pub mod storage;
+pub mod store;
 pub mod timing;
@@ -10,6 +10,11 @@ pub struct QualityFlags {
     pub focus_lost_events: u32,
     pub min_trials_met: bool,
     pub device: DeviceSnapshot,
+    /// Times the run was paused, and the total time it spent paused.
+    #[serde(default)]
+    pub pause_events: u32,
+    #[serde(default)]
+    pub paused_ms: u64,
 }
 
 impl QualityFlags {
@@ -19,6 +24,8 @@ impl QualityFlags {
             focus_lost_events: 0,
             min_trials_met: true,
             device: DeviceSnapshot::capture(),
+            pause_events: 0,
+            paused_ms: 0,
         }
     }
 
@@ -33,6 +40,12 @@ impl QualityFlags {
     pub fn mark_min_trials(&mut self, met: bool) {
         self.min_trials_met = met;
     }
+
+    /// Copy the engine's pause tally into the run's flags.
+    pub fn record_pauses(&mut self, count: u32, paused_ms: f64) {
+        self.pause_events = count;
+        self.paused_ms = paused_ms.max(0.0).round() as u64;
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
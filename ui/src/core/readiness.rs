@@ -11,6 +11,11 @@
 //! ----------------
 //! - 2‑back (`"nback2"`): recommend ≥ 72 h (3 days) between full (main) runs.
 //! - PVT (`"pvt"`): recommend ≥ 4 h between runs (multiple daily samples ok).
+//! - Flanker (`"flanker"`): recommend ≥ 24 h between main runs.
+//! - DSST (`"dsst"`): recommend ≥ 24 h between main runs.
+//! - Word recall (`"word_recall"`): recommend ≥ 72 h between main runs; the
+//!   lists rotate, but recall of any list improves with practice.
+//! - Task switching (`"task_switch"`): recommend ≥ 24 h between main runs.
 //!
 //! The UI should always allow the user to start a task even if still in a
 //! cooldown window; we only surface an advisory indicator.
@@ -170,9 +175,13 @@ pub fn evaluate(task: &str, last: Option<&SummaryRecord>) -> Readiness {
 /// Policy mapping (hard‑coded initial version).
 fn policy_min_interval_hours(task: &str) -> f64 {
     match task {
-        "nback2" => 72.0, // 3 days
-        "pvt" => 4.0,     // 4 hours
-        _ => 0.0,         // Unknown task: no restriction
+        "nback2" => 72.0,      // 3 days
+        "pvt" => 4.0,          // 4 hours
+        "flanker" => 24.0,     // 1 day
+        "dsst" => 24.0,        // 1 day
+        "word_recall" => 72.0, // 3 days
+        "task_switch" => 24.0, // 1 day
+        _ => 0.0,              // Unknown task: no restriction
     }
 }
 
@@ -271,6 +280,40 @@ mod tests {
         assert!(r.wait_remaining_hours.unwrap() > 0.0);
     }
 
+    #[test]
+    fn early_for_recent_flanker() {
+        let now = OffsetDateTime::now_utc();
+        let last = record("flanker", now - Duration::hours(6));
+        assert!(!evaluate("flanker", Some(&last)).ready);
+        let last = record("flanker", now - Duration::hours(25));
+        assert!(evaluate("flanker", Some(&last)).ready);
+    }
+
+    #[test]
+    fn early_for_recent_dsst() {
+        let now = OffsetDateTime::now_utc();
+        let last = record("dsst", now - Duration::hours(12));
+        assert!(!evaluate("dsst", Some(&last)).ready);
+    }
+
+    #[test]
+    fn word_recall_waits_three_days() {
+        let now = OffsetDateTime::now_utc();
+        let last = record("word_recall", now - Duration::hours(48));
+        assert!(!evaluate("word_recall", Some(&last)).ready);
+        let last = record("word_recall", now - Duration::hours(73));
+        assert!(evaluate("word_recall", Some(&last)).ready);
+    }
+
+    #[test]
+    fn task_switch_waits_a_day() {
+        let now = OffsetDateTime::now_utc();
+        let last = record("task_switch", now - Duration::hours(12));
+        assert!(!evaluate("task_switch", Some(&last)).ready);
+        let last = record("task_switch", now - Duration::hours(25));
+        assert!(evaluate("task_switch", Some(&last)).ready);
+    }
+
     #[test]
     fn ready_after_interval() {
         let now = OffsetDateTime::now_utc();
@@ -1,4 +1,13 @@
 //! Local persistence helpers for summaries and settings.
+//!
+//! Where finished runs live depends on the platform. On desktop they go to the
+//! local store, through the app's [`SharedStore`] handle, as a session record
+//! plus one observation per registered metric; `summaries.json` is then only
+//! read by the one-time startup migration, and JSON is just an export format.
+//! Web keeps runs in localStorage, and mobile in `summaries.json`.
+//!
+//! Desktop can also keep each run's raw trials (see [`TrialSample`]), when the
+//! user opts in with [`set_keep_trials`]; other platforms never keep them.
 
 use std::{fmt, fs, io};
 
@@ -7,6 +16,7 @@ use uuid::Uuid;
 
 use super::platform::{platform_string, timezone_string};
 use super::qc::QualityFlags;
+use super::store::SharedStore;
 
 #[cfg(target_arch = "wasm32")]
 const STORAGE_KEY: &str = "looplace_summaries";
@@ -44,6 +54,25 @@ impl SummaryRecord {
     }
 }
 
+/// One trial of a finished run, as kept when [`keep_trials`] is on. Times are
+/// milliseconds on the task's own clock (see `looplace_store::trial`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrialSample {
+    pub index: usize,
+    pub iti_ms: Option<f64>,
+    pub onset_ms: Option<f64>,
+    pub response_ms: Option<f64>,
+    pub rt_ms: Option<f64>,
+    /// `reaction`/`lapse`/`false_start` (PVT), `hit`/`miss`/`false_alarm`/
+    /// `correct_rejection` (N-back), `correct`/`error`/`miss` (flanker),
+    /// `correct`/`error` (DSST), `recalled`/`intrusion`/`repetition` and their
+    /// `delayed_*` forms (word recall), `correct`/`error`/`miss` (task switching).
+    pub outcome: String,
+    pub stimulus: Option<String>,
+    pub target: Option<bool>,
+    pub lure: Option<bool>,
+}
+
 impl ClientInfo {
     pub fn current() -> Self {
         Self {
@@ -59,6 +88,8 @@ pub enum StorageError {
     LocalUnavailable,
     WriteFailed,
     ReadFailed,
+    /// The local store refused the read or write (locked, unreadable, …).
+    Store(String),
 }
 
 impl fmt::Display for StorageError {
@@ -69,7 +100,22 @@ impl fmt::Display for StorageError {
 
 impl std::error::Error for StorageError {}
 
-pub fn load_summaries() -> Result<Vec<SummaryRecord>, StorageError> {
+/// Every stored run, in storage order.
+pub fn load_summaries(store: &SharedStore) -> Result<Vec<SummaryRecord>, StorageError> {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        store_runs::load(store)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = store;
+        load_legacy()
+    }
+}
+
+/// The runs in the legacy list (localStorage on web, `summaries.json` natively).
+pub fn load_legacy() -> Result<Vec<SummaryRecord>, StorageError> {
     #[cfg(target_arch = "wasm32")]
     {
         let storage = local_storage().ok_or(StorageError::LocalUnavailable)?;
@@ -103,28 +149,209 @@ pub fn load_summaries() -> Result<Vec<SummaryRecord>, StorageError> {
     }
 }
 
-pub fn append_summary(summary: &SummaryRecord) -> Result<(), StorageError> {
-    let mut records = load_summaries()?;
-    records.push(summary.clone());
-    save_all(&records)
+/// Persist a finished run, with its `trials` if any (desktop only; pass them
+/// only when [`keep_trials`] is on). A run already stored under the same id is
+/// replaced.
+pub fn append_summary(
+    store: &SharedStore,
+    summary: &SummaryRecord,
+    trials: &[TrialSample],
+) -> Result<(), StorageError> {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        store_runs::append(store, summary, trials)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = (store, trials);
+        let mut records = load_legacy()?;
+        records.retain(|r| r.id != summary.id);
+        records.push(summary.clone());
+        save_all(&records)
+    }
+}
+
+/// The kept trials of run `id`, in order; empty if none were kept.
+pub fn load_trials(store: &SharedStore, id: &str) -> Result<Vec<TrialSample>, StorageError> {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        store_runs::load_trials(store, id)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = (store, id);
+        Ok(Vec::new())
+    }
+}
+
+/// Whether finished runs keep their raw trials. Off unless the user opted in;
+/// always off off-desktop.
+pub fn keep_trials() -> bool {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        load_cognition_prefs().keep_trials
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        false
+    }
+}
+
+/// Turn [`keep_trials`] on or off (best-effort; a no-op off-desktop). Trials
+/// already kept stay until their run is deleted.
+pub fn set_keep_trials(keep: bool) {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        let mut prefs = load_cognition_prefs();
+        prefs.keep_trials = keep;
+        save_cognition_prefs(&prefs);
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = keep;
+    }
+}
+
+/// Id of the PVT preset picked last time, if one was saved (desktop only).
+pub fn pvt_preset() -> Option<String> {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        load_cognition_prefs().pvt_preset
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Remember the PVT preset for the next visit (best-effort; a no-op
+/// off-desktop).
+pub fn set_pvt_preset(id: &str) {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        let mut prefs = load_cognition_prefs();
+        prefs.pvt_preset = Some(id.to_string());
+        save_cognition_prefs(&prefs);
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = id;
+    }
+}
+
+/// The N-back level to start at next, if one was saved (desktop only). In
+/// adaptive mode this is where the last main block stepped to.
+pub fn nback_level() -> Option<u8> {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        load_cognition_prefs().nback_level
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// Whether N-back sessions step their level between blocks (desktop only).
+pub fn nback_adaptive() -> bool {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        load_cognition_prefs().nback_adaptive
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        false
+    }
 }
 
-/// Delete a single summary by its id.
+/// Remember the N-back level and adaptive mode for the next visit
+/// (best-effort; a no-op off-desktop).
+pub fn set_nback_level(n: u8, adaptive: bool) {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        let mut prefs = load_cognition_prefs();
+        prefs.nback_level = Some(n);
+        prefs.nback_adaptive = adaptive;
+        save_cognition_prefs(&prefs);
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = (n, adaptive);
+    }
+}
+
+/// Cognition-task preferences, in `cognition_prefs.json` beside the store.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CognitionPrefs {
+    #[serde(default)]
+    keep_trials: bool,
+    #[serde(default)]
+    pvt_preset: Option<String>,
+    #[serde(default)]
+    nback_level: Option<u8>,
+    #[serde(default)]
+    nback_adaptive: bool,
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn cognition_prefs_path() -> Option<std::path::PathBuf> {
+    data_dir().ok().map(|d| d.join("cognition_prefs.json"))
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn load_cognition_prefs() -> CognitionPrefs {
+    cognition_prefs_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn save_cognition_prefs(prefs: &CognitionPrefs) {
+    if let Some(path) = cognition_prefs_path() {
+        if let Ok(json) = serde_json::to_string_pretty(prefs) {
+            let _ = fs::write(path, json);
+        }
+    }
+}
+
+/// Delete a single summary by its id (on desktop, with its metric observations
+/// and kept trials).
 ///
 /// Returns:
 /// - Ok(true)  if a record with the given id was found and removed (and persistence updated)
 /// - Ok(false) if no record matched (no write performed)
-pub fn delete_summary(id: &str) -> Result<bool, StorageError> {
-    let mut records = load_summaries()?;
-    let before = records.len();
-    records.retain(|r| r.id != id);
-    let deleted = records.len() != before;
-    if deleted {
-        save_all(&records)?;
+pub fn delete_summary(store: &SharedStore, id: &str) -> Result<bool, StorageError> {
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        store_runs::delete(store, id)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        let _ = store;
+        let mut records = load_legacy()?;
+        let before = records.len();
+        records.retain(|r| r.id != id);
+        let deleted = records.len() != before;
+        if deleted {
+            save_all(&records)?;
+        }
+        Ok(deleted)
     }
-    Ok(deleted)
 }
 
+/// Overwrite the legacy list (see [`load_legacy`]).
 pub fn save_all(records: &[SummaryRecord]) -> Result<(), StorageError> {
     #[cfg(target_arch = "wasm32")]
     {
@@ -163,6 +390,7 @@ fn local_storage() -> Option<web_sys::Storage> {
         .flatten()
 }
 
+/// Empty the legacy list (see [`load_legacy`]).
 pub fn clear_all() -> Result<(), StorageError> {
     #[cfg(target_arch = "wasm32")]
     {
@@ -201,3 +429,172 @@ pub fn data_dir() -> Result<std::path::PathBuf, StorageError> {
 fn summaries_file_path() -> Result<std::path::PathBuf, StorageError> {
     Ok(data_dir()?.join(SUMMARY_FILE_NAME))
 }
+
+/// Runs as store rows: the session table keeps the record losslessly, and its
+/// registered metrics are flattened into observations for the timeline.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+mod store_runs {
+    use looplace_store::convert::{summary_to_observations, summary_to_session, CognitionSummary};
+    use looplace_store::{session, trial, Observation, SessionRecord, Store, TrialRecord};
+    use time::format_description::well_known::Rfc3339;
+
+    use super::{ClientInfo, SharedStore, StorageError, SummaryRecord, TrialSample};
+    use crate::core::qc::{DeviceSnapshot, QualityFlags};
+
+    pub(super) fn load(store: &SharedStore) -> Result<Vec<SummaryRecord>, StorageError> {
+        let sessions = store
+            .read(|s| s.sessions())
+            .map_err(|e| StorageError::Store(e.to_string()))?
+            .map_err(|e| StorageError::Store(e.to_string()))?;
+        Ok(sessions.iter().map(from_session).collect())
+    }
+
+    pub(super) fn append(
+        store: &SharedStore,
+        summary: &SummaryRecord,
+        trials: &[TrialSample],
+    ) -> Result<(), StorageError> {
+        let (session, observations) = to_rows(summary)?;
+        let trials: Vec<TrialRecord> = trials.iter().map(|t| to_trial(&summary.id, t)).collect();
+        store
+            .write(|s| {
+                s.upsert_sessions(&[session])?;
+                if !trials.is_empty() {
+                    s.upsert_trials(&trials)?;
+                }
+                s.upsert(&observations)
+            })
+            .map_err(|e| StorageError::Store(e.to_string()))?
+            .map_err(|e| StorageError::Store(e.to_string()))?;
+        Ok(())
+    }
+
+    pub(super) fn load_trials(
+        store: &SharedStore,
+        id: &str,
+    ) -> Result<Vec<TrialSample>, StorageError> {
+        let trials = store
+            .read(|s| trial::for_session(s, id))
+            .map_err(|e| StorageError::Store(e.to_string()))?
+            .map_err(|e| StorageError::Store(e.to_string()))?;
+        Ok(trials.into_iter().map(from_trial).collect())
+    }
+
+    pub(super) fn delete(store: &SharedStore, id: &str) -> Result<bool, StorageError> {
+        store
+            .write(|s| session::delete(s, id))
+            .map_err(|e| StorageError::Store(e.to_string()))?
+            .map_err(|e| StorageError::Store(e.to_string()))
+    }
+
+    /// Convert through the store's legacy-summary mirror, so a run written
+    /// now is stored exactly like one the migration imported.
+    pub(super) fn to_rows(
+        summary: &SummaryRecord,
+    ) -> Result<(SessionRecord, Vec<Observation>), StorageError> {
+        let value = serde_json::to_value(summary).map_err(StorageError::Serialization)?;
+        let legacy: CognitionSummary =
+            serde_json::from_value(value).map_err(StorageError::Serialization)?;
+        let session = summary_to_session(&legacy)
+            .ok_or_else(|| StorageError::Store(format!("bad timestamp {}", summary.created_at)))?;
+        Ok((session, summary_to_observations(&legacy)))
+    }
+
+    pub(super) fn to_trial(session_id: &str, t: &TrialSample) -> TrialRecord {
+        TrialRecord {
+            session_id: session_id.to_string(),
+            index: t.index as i64,
+            iti_ms: t.iti_ms,
+            onset_ms: t.onset_ms,
+            response_ms: t.response_ms,
+            rt_ms: t.rt_ms,
+            outcome: t.outcome.clone(),
+            stimulus: t.stimulus.clone(),
+            target: t.target,
+            lure: t.lure,
+        }
+    }
+
+    pub(super) fn from_trial(t: TrialRecord) -> TrialSample {
+        TrialSample {
+            index: usize::try_from(t.index.max(0)).unwrap_or_default(),
+            iti_ms: t.iti_ms,
+            onset_ms: t.onset_ms,
+            response_ms: t.response_ms,
+            rt_ms: t.rt_ms,
+            outcome: t.outcome,
+            stimulus: t.stimulus,
+            target: t.target,
+            lure: t.lure,
+        }
+    }
+
+    pub(super) fn from_session(s: &SessionRecord) -> SummaryRecord {
+        let count = |n: i64| u32::try_from(n.max(0)).unwrap_or(u32::MAX);
+        SummaryRecord {
+            id: s.id.clone(),
+            task: s.task.clone(),
+            created_at: s.created_at.assume_utc().format(&Rfc3339).unwrap_or_default(),
+            client: ClientInfo {
+                platform: s.client_platform.clone(),
+                tz: s.client_tz.clone(),
+            },
+            metrics: s.metrics.clone(),
+            qc: QualityFlags {
+                visibility_blur_events: count(s.qc_visibility_blur_events),
+                focus_lost_events: count(s.qc_focus_lost_events),
+                min_trials_met: s.qc_min_trials_met,
+                device: DeviceSnapshot {
+                    platform: s.qc_device_platform.clone(),
+                    user_agent: s.qc_device_user_agent.clone(),
+                },
+                pause_events: count(s.qc_pause_events),
+                paused_ms: u64::try_from(s.qc_paused_ms.max(0)).unwrap_or_default(),
+            },
+            notes: s.notes.clone(),
+        }
+    }
+}
+
+#[cfg(all(test, any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_round_trip_through_store_rows() {
+        let mut qc = QualityFlags::pristine();
+        qc.log_focus_loss();
+        qc.record_pauses(2, 41_500.4);
+        let mut record = SummaryRecord::new(
+            "pvt",
+            serde_json::json!({"median_rt_ms": 301.5, "lapses_ge_500ms": 1, "label": "x"}),
+            qc,
+        );
+        record.notes = Some("after coffee".into());
+
+        let (session, observations) = store_runs::to_rows(&record).unwrap();
+        assert_eq!(store_runs::from_session(&session), record);
+        // Only registered numeric metrics become observations.
+        let streams: Vec<_> = observations.iter().map(|o| o.stream.as_str()).collect();
+        assert_eq!(streams, vec!["pvt.lapses_ge_500ms", "pvt.median_rt_ms"]);
+        assert!(observations.iter().all(|o| o.session_id.as_deref() == Some(record.id.as_str())));
+    }
+
+    #[test]
+    fn trials_round_trip_through_store_rows() {
+        let sample = TrialSample {
+            index: 3,
+            iti_ms: Some(2_500.0),
+            onset_ms: Some(10_750.0),
+            response_ms: Some(11_262.5),
+            rt_ms: Some(512.5),
+            outcome: "hit".into(),
+            stimulus: Some("K".into()),
+            target: Some(true),
+            lure: Some(false),
+        };
+        let row = store_runs::to_trial("nback2-1", &sample);
+        assert_eq!(row.session_id, "nback2-1");
+        assert_eq!(store_runs::from_trial(row), sample);
+    }
+}
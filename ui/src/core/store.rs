@@ -0,0 +1,379 @@
+//! The app's one long-lived handle on the local store, shared through Dioxus
+//! context.
+//!
+//! Each platform's `App` calls [`use_store_provider`] once; views take the
+//! handle with [`use_shared_store`] and refresh with [`use_store_changes`],
+//! which hands them the [`StoreChange`]s since their last refresh. Every write
+//! through the handle (a reader sync, a journal edit) is reported there, so
+//! views re-query just what changed instead of reopening the store.
+//!
+//! Like the rest of the health backend, the store itself is **desktop-only**;
+//! on web/mobile the handle is empty and no changes are ever reported.
+
+use std::collections::VecDeque;
+
+use dioxus::prelude::*;
+
+/// What a write changed (the store's [`looplace_store::feed::Change`]).
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub use looplace_store::feed::Change as StoreChange;
+
+/// Stand-in for the store's change type on web/mobile, where there is no
+/// store; only the catch-all reset exists.
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoreChange {
+    Reset,
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+impl StoreChange {
+    pub fn touches(&self, _stream: &str) -> bool {
+        true
+    }
+
+    pub fn touches_sessions(&self) -> bool {
+        true
+    }
+}
+
+/// Whether the local store is encrypted, and if so whether this session has
+/// unlocked it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StoreEncryption {
+    #[default]
+    Plaintext,
+    /// Encrypted and waiting for a passphrase: no data can be read or synced.
+    Locked,
+    Unlocked,
+}
+
+/// Recent changes, numbered, as the views see them. Only the last
+/// [`StoreFeed::KEEP`] are kept; a view that falls further behind gets a
+/// [`StoreChange::Reset`] instead.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StoreFeed {
+    revision: u64,
+    log: VecDeque<StoreChange>,
+}
+
+impl StoreFeed {
+    const KEEP: usize = 64;
+
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    fn push(&mut self, change: StoreChange) {
+        self.revision += 1;
+        self.log.push_back(change);
+        if self.log.len() > Self::KEEP {
+            self.log.pop_front();
+        }
+    }
+
+    /// The changes after revision `seen`, oldest first.
+    pub fn since(&self, seen: u64) -> Vec<StoreChange> {
+        let missed = self.revision.saturating_sub(seen);
+        match usize::try_from(missed) {
+            Ok(n) if n <= self.log.len() => {
+                self.log.iter().skip(self.log.len() - n).cloned().collect()
+            }
+            _ => vec![StoreChange::Reset],
+        }
+    }
+}
+
+/// Open the store and provide it (and its change feed) to every view below.
+/// Call once, in the platform `App`.
+pub fn use_store_provider() -> SharedStore {
+    let store = use_context_provider(SharedStore::open);
+    let feed = use_context_provider(|| Signal::new(StoreFeed::default()));
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    {
+        let store = store.clone();
+        use_hook(move || forward_changes(&store, feed));
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let _ = feed;
+    store
+}
+
+/// Relay the store's changes (reported on whichever thread wrote) into the
+/// feed signal, on the UI task.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn forward_changes(store: &SharedStore, mut feed: Signal<StoreFeed>) {
+    use futures_util::StreamExt;
+
+    let (tx, mut rx) = futures_channel::mpsc::unbounded::<StoreChange>();
+    let subscription = store.feed.subscribe(move |change| {
+        let _ = tx.unbounded_send(change.clone());
+    });
+    spawn(async move {
+        let _subscription = subscription;
+        while let Some(change) = rx.next().await {
+            feed.write().push(change);
+        }
+    });
+}
+
+/// The handle provided by [`use_store_provider`].
+pub fn use_shared_store() -> SharedStore {
+    use_context::<SharedStore>()
+}
+
+/// Call `on_change` with the store changes since the last call (never with
+/// none). Runs after render, so it may write signals — but it must not read
+/// the ones it writes.
+pub fn use_store_changes(mut on_change: impl FnMut(&[StoreChange]) + 'static) {
+    let feed = use_context::<Signal<StoreFeed>>();
+    let mut seen = use_signal(|| feed.peek().revision());
+    use_effect(move || {
+        let feed = feed.read();
+        let last = *seen.peek();
+        if feed.revision() != last {
+            seen.set(feed.revision());
+            on_change(&feed.since(last));
+        }
+    });
+}
+
+// ---- Desktop backend ------------------------------------------------------
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+type Backend = looplace_store::feed::WatchedStore<looplace_store::ParquetStore>;
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+enum Slot {
+    Open(Box<Backend>),
+    /// Encrypted, and no passphrase given yet this session.
+    Locked,
+    Failed(String),
+}
+
+/// Why the store can't be used right now.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Unavailable {
+    Locked,
+    Failed(String),
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+impl std::fmt::Display for Unavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unavailable::Locked => f.write_str("store is locked"),
+            Unavailable::Failed(e) => write!(f, "couldn't open store: {e}"),
+        }
+    }
+}
+
+/// The local store, opened once and shared (cheap to clone). The passphrase
+/// of an unlocked store lives only inside the open store, for the session.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+#[derive(Clone)]
+pub struct SharedStore {
+    slot: std::sync::Arc<std::sync::Mutex<Slot>>,
+    /// Queues writers (see [`SharedStore::write`]).
+    writer: std::sync::Arc<std::sync::Mutex<()>>,
+    /// What the open store reports its writes to; each write's changes are
+    /// relayed to `feed` once it has let go of `slot`.
+    writes: looplace_store::feed::Feed,
+    feed: looplace_store::feed::Feed,
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+impl SharedStore {
+    /// Open the local store. `open` treats a missing file as an empty store,
+    /// so first-run is not an error; an encrypted one opens locked.
+    pub fn open() -> Self {
+        let store = Self {
+            slot: std::sync::Arc::new(std::sync::Mutex::new(Slot::Locked)),
+            writer: std::sync::Arc::default(),
+            writes: looplace_store::feed::Feed::new(),
+            feed: looplace_store::feed::Feed::new(),
+        };
+        let slot = match store_path() {
+            Ok(path) => store.slot_for(looplace_store::ParquetStore::open(&path)),
+            Err(e) => Slot::Failed(e),
+        };
+        *store.lock_slot() = slot;
+        store
+    }
+
+    fn slot_for(&self, opened: looplace_store::Result<looplace_store::ParquetStore>) -> Slot {
+        use looplace_store::feed::WatchedStore;
+        use looplace_store::StoreError;
+
+        match opened {
+            Ok(s) => Slot::Open(Box::new(WatchedStore::with_feed(s, self.writes.clone()))),
+            Err(StoreError::Encrypted) => Slot::Locked,
+            Err(e) => Slot::Failed(e.to_string()),
+        }
+    }
+
+    fn lock_slot(&self) -> std::sync::MutexGuard<'_, Slot> {
+        self.slot
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Run `f` against the open store.
+    pub fn read<R>(&self, f: impl FnOnce(&Backend) -> R) -> Result<R, Unavailable> {
+        match &*self.lock_slot() {
+            Slot::Open(store) => Ok(f(store)),
+            Slot::Locked => Err(Unavailable::Locked),
+            Slot::Failed(e) => Err(Unavailable::Failed(e.clone())),
+        }
+    }
+
+    /// Run `f` against the open store, for writing. Writes take turns, and
+    /// are reported to the views' change feed once `f` has returned, so a
+    /// view re-querying on a change doesn't wait on the write. Keep `f`
+    /// short: reads wait for it.
+    pub fn write<R>(&self, f: impl FnOnce(&mut Backend) -> R) -> Result<R, Unavailable> {
+        let _turn = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (tx, rx) = std::sync::mpsc::channel::<StoreChange>();
+        let _held = self.writes.subscribe(move |change| {
+            let _ = tx.send(change.clone());
+        });
+        let out = match &mut *self.lock_slot() {
+            Slot::Open(store) => f(store),
+            Slot::Locked => return Err(Unavailable::Locked),
+            Slot::Failed(e) => return Err(Unavailable::Failed(e.clone())),
+        };
+        for change in rx.try_iter() {
+            self.feed.emit(&change);
+        }
+        Ok(out)
+    }
+
+    pub fn encryption(&self) -> StoreEncryption {
+        use looplace_store::encryption::EncryptionState;
+
+        match &*self.lock_slot() {
+            Slot::Open(store) => match store.get_ref().encryption_state() {
+                EncryptionState::Unlocked => StoreEncryption::Unlocked,
+                EncryptionState::Locked => StoreEncryption::Locked,
+                EncryptionState::Plaintext => StoreEncryption::Plaintext,
+            },
+            Slot::Locked => StoreEncryption::Locked,
+            Slot::Failed(_) => StoreEncryption::Plaintext,
+        }
+    }
+
+    /// Unlock the encrypted store for this session. Errors (wrong passphrase
+    /// included) come back as a display string.
+    pub fn unlock(&self, pass: &str) -> std::result::Result<(), String> {
+        use looplace_store::encryption::EncryptionState;
+        use looplace_store::{ParquetStore, StoreError};
+
+        let path = store_path()?;
+        // Only unlock a store that is actually encrypted: `open_encrypted`
+        // would otherwise encrypt a plaintext one.
+        if ParquetStore::encryption_state_at(&path) != EncryptionState::Locked {
+            return Err("store is not encrypted".into());
+        }
+        match ParquetStore::open_encrypted(&path, pass) {
+            Ok(s) => {
+                *self.lock_slot() = self.slot_for(Ok(s));
+                self.feed.emit(&StoreChange::Reset);
+                Ok(())
+            }
+            Err(StoreError::WrongPassphrase) => Err("wrong passphrase".into()),
+            Err(e) => Err(format!("couldn't unlock store: {e}")),
+        }
+    }
+
+    /// Close an unlocked store, forgetting its passphrase; it reads as locked
+    /// again. A plaintext store stays open.
+    pub fn lock(&self) {
+        use looplace_store::encryption::EncryptionState;
+
+        {
+            let mut slot = self.lock_slot();
+            let unlocked = matches!(&*slot, Slot::Open(store)
+                if store.get_ref().encryption_state() == EncryptionState::Unlocked);
+            if !unlocked {
+                return;
+            }
+            *slot = Slot::Locked;
+        }
+        self.feed.emit(&StoreChange::Reset);
+    }
+
+    /// Encrypt the (plaintext) store under `pass`; it stays unlocked for the
+    /// session.
+    pub fn encrypt(&self, pass: &str) -> std::result::Result<(), String> {
+        if pass.is_empty() {
+            return Err("passphrase is empty".into());
+        }
+        self.write(|store| store.get_mut().enable_encryption(pass))
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("couldn't encrypt store: {e}"))?;
+        self.feed.emit(&StoreChange::Reset);
+        Ok(())
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn store_path() -> std::result::Result<std::path::PathBuf, String> {
+    crate::core::storage::data_dir()
+        .map(|dir| dir.join("looplace.parquet"))
+        .map_err(|e| format!("data dir unavailable: {e}"))
+}
+
+// ---- Non-desktop stub (web / mobile) --------------------------------------
+
+/// No local store on this platform.
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+#[derive(Clone, Default)]
+pub struct SharedStore;
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+impl SharedStore {
+    pub fn open() -> Self {
+        Self
+    }
+
+    pub fn encryption(&self) -> StoreEncryption {
+        StoreEncryption::Plaintext
+    }
+
+    pub fn unlock(&self, _pass: &str) -> std::result::Result<(), String> {
+        Err("no local store on this platform".into())
+    }
+
+    pub fn lock(&self) {}
+
+    pub fn encrypt(&self, _pass: &str) -> std::result::Result<(), String> {
+        Err("no local store on this platform".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feed_replays_missed_changes_or_resets() {
+        let mut feed = StoreFeed::default();
+        assert!(feed.since(0).is_empty());
+        for _ in 0..3 {
+            feed.push(StoreChange::Reset);
+        }
+        assert_eq!(feed.since(1).len(), 2);
+        assert!(feed.since(3).is_empty());
+
+        for _ in 0..StoreFeed::KEEP {
+            feed.push(StoreChange::Reset);
+        }
+        // Revision 1's successors have aged out: one reset stands in for them.
+        assert_eq!(feed.since(1), vec![StoreChange::Reset]);
+        assert_eq!(feed.since(feed.revision() - 1).len(), 1);
+    }
+}
@@ -37,6 +37,8 @@
 //! - `init()` – load localization bundles (safe to call multiple times).
 //! - `set_language(tag: &str)` – switch language at runtime.
 //! - `available_languages()` – discover embedded language tags (for a picker).
+//! - `current_language()` – the language currently selected.
+//! - `tr(id)` – runtime-keyed lookup (e.g. a stream's registry `label_key`).
 //! - Helper fns: `tr_nav_*`, `tr_tagline()` etc. (ergonomic lookup wrappers).
 //! - `fl` macro re-export (for direct keyed access when needed).
 //! - `LOADER` – global `FluentLanguageLoader` consumed by helpers & `fl!` macro.
@@ -115,6 +117,24 @@ pub fn available_languages() -> Vec<String> {
     langs
 }
 
+/// The language currently selected, e.g. `en-US` (the fallback before
+/// `init()`).
+pub fn current_language() -> String {
+    use i18n_embed::LanguageLoader;
+    LOADER.current_language().to_string()
+}
+
+/// Look up a message whose id is only known at runtime, such as a stream's
+/// `label_key` from the store's registry. Not compile-checked like `t!`, so an
+/// unknown id falls back to the id itself rather than a lookup-failure string.
+pub fn tr(id: &str) -> String {
+    if LOADER.has(id) {
+        LOADER.get(id)
+    } else {
+        id.to_string()
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 fn requested_languages() -> Vec<LanguageIdentifier> {
     i18n_embed::WebLanguageRequester::requested_languages()
@@ -150,4 +170,20 @@ mod tests {
         let after = fl!(&*LOADER, "nav-home");
         assert_eq!(before, after);
     }
+
+    #[test]
+    fn unknown_runtime_ids_fall_back_to_the_id() {
+        init();
+        assert_eq!(tr("nav-home"), "Home");
+        assert_eq!(tr("no-such-message"), "no-such-message");
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+    #[test]
+    fn every_registered_stream_has_a_label() {
+        init();
+        for spec in looplace_store::streams::STREAMS {
+            assert!(LOADER.has(spec.label_key), "missing label {}", spec.label_key);
+        }
+    }
 }
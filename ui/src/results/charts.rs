@@ -1,24 +1,34 @@
 use std::fmt::Write;
 
 use dioxus::prelude::*;
+use looplace_store::aggregate::{aggregate, Aggregation, Stat};
+use looplace_store::Observation;
 
 use crate::{
-    core::{format, storage::SummaryRecord},
+    core::{format, journal::JournalItem, storage::SummaryRecord},
     results::{
-        format_date_badge, format_time_badge, format_timestamp, parse_nback_metrics,
-        parse_pvt_metrics, parse_timestamp, record_is_clean,
+        format_date_badge, format_time_badge, format_timestamp, latest_nback_level,
+        latest_pvt_preset, parse_dsst_metrics, parse_nback_metrics, parse_pvt_metrics,
+        parse_timestamp, record_is_clean,
     },
 };
 
+/// `journal` entries are marked on the reaction-time and DSST trends where
+/// they fall between runs.
 #[component]
-pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
+pub fn ResultsSparklines(
+    records: Vec<SummaryRecord>,
+    #[props(default)] journal: Vec<JournalItem>,
+) -> Element {
     let total_runs = records.len();
     let latest_stamp = records.first().map(format_timestamp);
     let latest_meta = latest_stamp.unwrap_or_default();
+    let pvt_preset = latest_pvt_preset(&records).unwrap_or_default();
+    let nback_level = latest_nback_level(&records).unwrap_or(2);
 
-    let mut pvt_points = Vec::new();
-    let mut nback_accuracy = Vec::new();
-    let mut nback_dprime = Vec::new();
+    // Each clean run's headline metrics, averaged below by the store.
+    let mut run_metrics = Vec::new();
+    let mut dsst_points = Vec::new();
 
     let mut trend_points = Vec::new();
     let mut bar_points = Vec::new();
@@ -26,6 +36,7 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
     let mut clean_total = 0usize;
     let mut clean_pvt = 0usize;
     let mut clean_nback = 0usize;
+    let mut clean_dsst = 0usize;
 
     for record in records.iter().rev() {
         if !record_is_clean(record) {
@@ -37,12 +48,15 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
         if let Some(ts) = parse_timestamp(record) {
             match record.task.as_str() {
                 "pvt" => {
-                    if let Some(metrics) = parse_pvt_metrics(record) {
+                    if let Some(metrics) = parse_pvt_metrics(record)
+                        .filter(|metrics| metrics.preset() == pvt_preset)
+                    {
                         if metrics.median_rt_ms.is_finite() {
-                            pvt_points.push(metrics.median_rt_ms);
+                            run_metrics.push(run_metric(PVT_MEDIAN, ts, metrics.median_rt_ms));
                             trend_points.push(SparkPoint {
                                 value: metrics.median_rt_ms,
                                 badge: format_date_badge(ts),
+                                wall_clock: local_wall_clock(ts),
                             });
                         }
 
@@ -55,38 +69,54 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
                     }
                 }
                 "nback2" => {
-                    if let Some(metrics) = parse_nback_metrics(record) {
+                    if let Some(metrics) =
+                        parse_nback_metrics(record).filter(|metrics| metrics.n == nback_level)
+                    {
                         if metrics.accuracy.is_finite() {
-                            nback_accuracy.push(metrics.accuracy);
+                            run_metrics.push(run_metric(NBACK_ACCURACY, ts, metrics.accuracy));
                         }
                         if metrics.d_prime.is_finite() {
-                            nback_dprime.push(metrics.d_prime);
+                            run_metrics.push(run_metric(NBACK_DPRIME, ts, metrics.d_prime));
                         }
                         clean_nback += 1;
                     }
                 }
+                "dsst" => {
+                    if let Some(metrics) = parse_dsst_metrics(record) {
+                        if metrics.correct_per_minute.is_finite() {
+                            run_metrics.push(run_metric(DSST_RATE, ts, metrics.correct_per_minute));
+                            dsst_points.push(SparkPoint {
+                                value: metrics.correct_per_minute,
+                                badge: format_date_badge(ts),
+                                wall_clock: local_wall_clock(ts),
+                            });
+                        }
+                        clean_dsst += 1;
+                    }
+                }
                 _ => {}
             }
         }
     }
 
-    let avg_pvt_median = average(&pvt_points);
-    let avg_nback_accuracy = average(&nback_accuracy);
-    let avg_nback_dprime = average(&nback_dprime);
-
-    let pvt_runs = pvt_points.len();
-    let nback_runs = nback_accuracy.len();
+    let summary = summarize(&run_metrics);
+    let (avg_pvt_median, pvt_runs) = summary(PVT_MEDIAN);
+    let (avg_nback_accuracy, nback_runs) = summary(NBACK_ACCURACY);
+    let (avg_nback_dprime, _) = summary(NBACK_DPRIME);
+    let (avg_dsst, dsst_runs) = summary(DSST_RATE);
 
+    let pvt_label = pvt_preset.label();
     let pvt_meta_text = if pvt_runs > 0 {
-        "Average of recent PVT runs"
+        format!("Average of recent {pvt_label} runs")
     } else {
-        "Run a PVT to populate"
+        "Run a PVT to populate".to_string()
     };
 
+    let nback_label = format!("{nback_level}-back");
     let nback_accuracy_meta = if nback_runs > 0 {
-        "Mean accuracy across runs"
+        format!("Mean accuracy across {nback_label} runs")
     } else {
-        "Complete a 2-back session"
+        "Complete an N-back session".to_string()
     };
 
     let dprime_meta = if nback_runs > 0 {
@@ -95,7 +125,14 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
         "Data pending"
     };
 
-    let sparkline = build_sparkline(&trend_points);
+    let dsst_meta_text = if dsst_runs == 0 {
+        "Complete a DSST session"
+    } else {
+        "Correct per minute"
+    };
+
+    let sparkline = build_sparkline(&trend_points, &journal);
+    let dsst_sparkline = build_sparkline(&dsst_points, &journal);
     let bar_chart = build_dual_bars(&bar_points);
 
     rsx! {
@@ -122,7 +159,7 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
                         span { class: "results-highlight__meta", "{pvt_meta_text} ({clean_pvt} clean)" }
                     }
                     div { class: "results-highlight",
-                        span { class: "results-highlight__label", "2-back accuracy" }
+                        span { class: "results-highlight__label", "{nback_label} accuracy" }
                         strong { class: "results-highlight__value", "{format::format_percent(avg_nback_accuracy)}" }
                         span { class: "results-highlight__meta", "{nback_accuracy_meta} ({clean_nback} clean)" }
                     }
@@ -131,13 +168,18 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
                         strong { class: "results-highlight__value", "{format::format_number(avg_nback_dprime, 2)}" }
                         span { class: "results-highlight__meta", "{dprime_meta}" }
                     }
+                    div { class: "results-highlight",
+                        span { class: "results-highlight__label", "DSST speed" }
+                        strong { class: "results-highlight__value", "{format::format_number(avg_dsst, 1)}" }
+                        span { class: "results-highlight__meta", "{dsst_meta_text} ({clean_dsst} clean)" }
+                    }
                 }
 
                 div { class: "results-charts__grid",
                     div { class: "results-chart results-chart--sparkline",
                         div { class: "results-chart__header",
                             span { class: "results-chart__title", "Median reaction time" }
-                            span { class: "results-chart__meta", "PVT trend" }
+                            span { class: "results-chart__meta", "{pvt_label}" }
                         }
                         if let Some(chart) = sparkline {
                             svg {
@@ -151,6 +193,16 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
                                     }
                                 }
                                 path { d: "{chart.fill_path}", fill: "url(#sparkline-fill)" }
+                                for (i , marker) in chart.markers.iter().enumerate() {
+                                    g { key: "j{i}",
+                                        title { "{marker.title}" }
+                                        line {
+                                            x1: "{marker.x}", y1: "0", x2: "{marker.x}", y2: "110",
+                                            stroke: "#a78bfa", stroke_width: "1", stroke_dasharray: "3 3",
+                                        }
+                                        text { x: "{marker.x}", y: "10", text_anchor: "middle", font_size: "10", "{marker.glyph}" }
+                                    }
+                                }
                                 path { d: "{chart.path}", fill: "none", stroke: "#f05a7e", stroke_width: "3", stroke_linecap: "round" }
                                 if let Some((start, end)) = chart.labels {
                                     text { x: "0", y: "118", class: "results-chart__axis", "{start}" }
@@ -169,7 +221,7 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
                     div { class: "results-chart results-chart--bars",
                         div { class: "results-chart__header",
                             span { class: "results-chart__title", "Lapses & false starts" }
-                            span { class: "results-chart__meta", "Last 8 PVT sessions" }
+                            span { class: "results-chart__meta", "Last 8 · {pvt_label}" }
                         }
                         if let Some(chart) = bar_chart {
                             svg {
@@ -210,23 +262,99 @@ pub fn ResultsSparklines(records: Vec<SummaryRecord>) -> Element {
                             p { class: "results-card__placeholder", "Run a few PVT sessions to populate lapse totals." }
                         }
                     }
+
+                    div { class: "results-chart results-chart--sparkline",
+                        div { class: "results-chart__header",
+                            span { class: "results-chart__title", "Processing speed" }
+                            span { class: "results-chart__meta", "DSST correct per minute" }
+                        }
+                        if let Some(chart) = dsst_sparkline {
+                            svg {
+                                class: "results-chart__svg",
+                                view_box: "0 0 360 120",
+                                preserve_aspect_ratio: "none",
+                                defs {
+                                    linearGradient { id: "sparkline-fill-dsst", x1: "0", x2: "0", y1: "0", y2: "1",
+                                        stop { offset: "0%", stop_color: "rgba(167,139,250,0.45)" }
+                                        stop { offset: "100%", stop_color: "rgba(167,139,250,0.0)" }
+                                    }
+                                }
+                                path { d: "{chart.fill_path}", fill: "url(#sparkline-fill-dsst)" }
+                                for (i , marker) in chart.markers.iter().enumerate() {
+                                    g { key: "j{i}",
+                                        title { "{marker.title}" }
+                                        line {
+                                            x1: "{marker.x}", y1: "0", x2: "{marker.x}", y2: "110",
+                                            stroke: "#f05a7e", stroke_width: "1", stroke_dasharray: "3 3",
+                                        }
+                                        text { x: "{marker.x}", y: "10", text_anchor: "middle", font_size: "10", "{marker.glyph}" }
+                                    }
+                                }
+                                path { d: "{chart.path}", fill: "none", stroke: "#a78bfa", stroke_width: "3", stroke_linecap: "round" }
+                                if let Some((start, end)) = chart.labels {
+                                    text { x: "0", y: "118", class: "results-chart__axis", "{start}" }
+                                    text { x: "360", y: "118", class: "results-chart__axis", text_anchor: "end", "{end}" }
+                                }
+                            }
+                            div { class: "results-chart__footer",
+                                span { "Min {format::format_number(chart.min, 1)}" }
+                                span { "Max {format::format_number(chart.max, 1)}" }
+                            }
+                        } else {
+                            p { class: "results-card__placeholder", "Complete more DSST runs to unlock the trend." }
+                        }
+                    }
                 }
             }
         }
     }
 }
 
-fn average(values: &[f64]) -> f64 {
-    if values.is_empty() {
-        f64::NAN
-    } else {
-        values.iter().copied().sum::<f64>() / values.len() as f64
+const PVT_MEDIAN: &str = "pvt.median_rt_ms";
+const NBACK_ACCURACY: &str = "nback2.accuracy";
+const NBACK_DPRIME: &str = "nback2.d_prime";
+const DSST_RATE: &str = "dsst.correct_per_minute";
+
+fn run_metric(stream: &str, ts: time::OffsetDateTime, value: f64) -> Observation {
+    let utc = ts.to_offset(time::UtcOffset::UTC);
+    let at = time::PrimitiveDateTime::new(utc.date(), utc.time());
+    Observation::new(stream, at, value, "", "looplace.results")
+}
+
+/// Each metric's mean over `rows` and its run count, as the store's
+/// [`aggregate`] computes them; `(NaN, 0)` for a metric without runs.
+fn summarize(rows: &[Observation]) -> impl Fn(&str) -> (f64, usize) {
+    let overall = Aggregation::overall(&[Stat::Mean]);
+    let means = aggregate(rows, &overall);
+    move |stream| {
+        let name = overall.series_name(stream, Stat::Mean);
+        means
+            .iter()
+            .find(|o| o.stream == name)
+            .map_or((f64::NAN, 0), |o| {
+                let runs = o.tags.get("n").and_then(|n| n.parse().ok()).unwrap_or(0);
+                (o.value, runs)
+            })
     }
 }
 
+/// A run's instant as local wall-clock seconds — the basis journal entries
+/// use (`JournalItem::start_unix`), so the two can be compared.
+fn local_wall_clock(ts: time::OffsetDateTime) -> i64 {
+    let offset = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
+    ts.to_offset(offset).replace_offset(time::UtcOffset::UTC).unix_timestamp()
+}
+
 struct SparkPoint {
     value: f64,
     badge: String,
+    wall_clock: i64,
+}
+
+struct SparkMarker {
+    x: f64,
+    glyph: &'static str,
+    title: String,
 }
 
 struct SparklineChart {
@@ -235,9 +363,27 @@ struct SparklineChart {
     min: f64,
     max: f64,
     labels: Option<(String, String)>,
+    markers: Vec<SparkMarker>,
 }
 
-fn build_sparkline(points: &[SparkPoint]) -> Option<SparklineChart> {
+/// The x of `wall_clock` on an index-spaced sparkline: interpolated between the
+/// runs either side of it, or `None` outside the plotted runs.
+fn marker_x(points: &[SparkPoint], step: f64, wall_clock: i64) -> Option<f64> {
+    points.windows(2).enumerate().find_map(|(index, pair)| {
+        let (a, b) = (pair[0].wall_clock, pair[1].wall_clock);
+        if wall_clock < a || wall_clock > b {
+            return None;
+        }
+        let frac = if b > a {
+            (wall_clock - a) as f64 / (b - a) as f64
+        } else {
+            0.0
+        };
+        Some(step * (index as f64 + frac))
+    })
+}
+
+fn build_sparkline(points: &[SparkPoint], journal: &[JournalItem]) -> Option<SparklineChart> {
     if points.len() < 2 {
         return None;
     }
@@ -301,12 +447,24 @@ fn build_sparkline(points: &[SparkPoint]) -> Option<SparklineChart> {
         .zip(points.last())
         .map(|(start, end)| (start.badge.clone(), end.badge.clone()));
 
+    let markers = journal
+        .iter()
+        .filter_map(|entry| {
+            Some(SparkMarker {
+                x: marker_x(points, step, entry.start_unix)?,
+                glyph: entry.category.glyph(),
+                title: format!("{} · {}", entry.category.label(), entry.when_label()),
+            })
+        })
+        .collect();
+
     Some(SparklineChart {
         path,
         fill_path,
         min,
         max,
         labels,
+        markers,
     })
 }
 
@@ -1,15 +1,29 @@
+use std::fmt::Write;
+
 use dioxus::prelude::*;
 
 use crate::{
-    core::{format, storage::SummaryRecord},
+    core::{
+        format,
+        storage::{self, SummaryRecord, TrialSample},
+        store::use_shared_store,
+    },
     results::{
-        format_device, format_timestamp, parse_nback_metrics, parse_pvt_metrics, qc_summary,
-        task_label,
+        format_device, format_timestamp, parse_dsst_metrics, parse_flanker_metrics,
+        parse_nback_metrics, parse_pvt_metrics, parse_task_switch_metrics,
+        parse_word_recall_metrics, qc_summary, task_label,
     },
 };
 
 #[component]
 pub fn ResultsDetailPanel(record: Option<SummaryRecord>) -> Element {
+    let store = use_shared_store();
+    // Only runs finished with "keep trials" on have any.
+    let trials = record
+        .as_ref()
+        .and_then(|r| storage::load_trials(&store, &r.id).ok())
+        .unwrap_or_default();
+
     rsx! {
         section { class: "results-card results-detail",
             div { class: "results-card__header",
@@ -17,7 +31,7 @@ pub fn ResultsDetailPanel(record: Option<SummaryRecord>) -> Element {
             }
 
             match record {
-                Some(record) => render_record(&record),
+                Some(record) => render_record(&record, &trials),
                 None => rsx! {
                     p { class: "results-card__placeholder",
                         "Select a run to review metrics, quality checks, and device context."
@@ -28,10 +42,11 @@ pub fn ResultsDetailPanel(record: Option<SummaryRecord>) -> Element {
     }
 }
 
-fn render_record(record: &SummaryRecord) -> Element {
+fn render_record(record: &SummaryRecord, trials: &[TrialSample]) -> Element {
     let timestamp = format_timestamp(record);
     let qc = qc_summary(record);
     let device = format_device(&record.client.platform, &record.client.tz);
+    let paused_s = (record.qc.paused_ms as f64 / 1_000.0).round();
     let qc_trials_label = if record.qc.min_trials_met {
         "Yes"
     } else {
@@ -41,6 +56,10 @@ fn render_record(record: &SummaryRecord) -> Element {
     let content = match record.task.as_str() {
         "pvt" => render_pvt(record),
         "nback2" => render_nback(record),
+        "flanker" => render_flanker(record),
+        "dsst" => render_dsst(record),
+        "word_recall" => render_word_recall(record),
+        "task_switch" => render_task_switch(record),
         _ => rsx! {
             p { class: "results-card__placeholder", "Metrics for this session aren't available yet." }
         },
@@ -57,12 +76,71 @@ fn render_record(record: &SummaryRecord) -> Element {
 
         {content}
 
+        if let Some(chart) = build_trial_chart(&record.task, trials) {
+            div { class: "results-chart results-chart--trials",
+                div { class: "results-chart__header",
+                    span { class: "results-chart__title", "Reaction time by trial" }
+                    span { class: "results-chart__meta", "{trials.len()} trials" }
+                }
+                svg {
+                    class: "results-chart__svg",
+                    view_box: "0 0 360 140",
+                    preserve_aspect_ratio: "none",
+                    if let Some(y) = chart.lapse_y {
+                        line {
+                            x1: "0", y1: "{y}", x2: "360", y2: "{y}",
+                            stroke: "rgba(245,247,251,0.35)", stroke_width: "1", stroke_dasharray: "4 4",
+                        }
+                        text { x: "360", y: "{y}", dy: "-4", class: "results-chart__axis", text_anchor: "end", "500 ms" }
+                    }
+                    for dot in chart.dots.iter() {
+                        circle {
+                            key: "{dot.index}",
+                            cx: "{dot.x}",
+                            cy: "{dot.y}",
+                            r: "3.5",
+                            fill: "{dot.color}",
+                            title { "{dot.title}" }
+                        }
+                    }
+                    path { d: "{chart.misses}", stroke: "rgba(245,247,251,0.5)", stroke_width: "2" }
+                }
+                div { class: "results-chart__footer",
+                    span { "Max {format::format_ms(chart.max)}" }
+                    span { "Trial 1–{trials.len()}" }
+                }
+                div { class: "results-chart__legend",
+                    span { class: "results-chart__legend-item",
+                        span { class: "results-chart__legend-swatch results-chart__legend-swatch--response" }
+                        span { "Responses" }
+                    }
+                    span { class: "results-chart__legend-item",
+                        span { class: "results-chart__legend-swatch results-chart__legend-swatch--error" }
+                        span {
+                            match record.task.as_str() {
+                                "pvt" => "False starts",
+                                "flanker" | "dsst" | "task_switch" => "Errors",
+                                _ => "False alarms",
+                            }
+                        }
+                    }
+                    span { class: "results-chart__legend-item",
+                        span { class: "results-chart__legend-swatch results-chart__legend-swatch--none" }
+                        span { if record.task == "pvt" { "No response" } else { "Misses" } }
+                    }
+                }
+            }
+        }
+
         div { class: "results-detail__qc",
             h4 { "Quality checks" }
             ul {
                 li { "{qc}" }
                 li { "Focus lost events: {record.qc.focus_lost_events}" }
                 li { "Window blur events: {record.qc.visibility_blur_events}" }
+                if record.qc.pause_events > 0 {
+                    li { "Pauses: {record.qc.pause_events} ({paused_s} s)" }
+                }
                 li { "Minimum trials met: {qc_trials_label}" }
                 li { "Captured platform: {record.client.platform}" }
             }
@@ -80,6 +158,7 @@ fn render_pvt(record: &SummaryRecord) -> Element {
             };
             rsx! {
                 ul { class: "results-detail__grid",
+                    li { span { class: "results-detail__metric-label", "Protocol" } span { class: "results-detail__metric-value", "{metrics.preset().label()}" } }
                     li { span { class: "results-detail__metric-label", "Median RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.median_rt_ms)}" } }
                     li { span { class: "results-detail__metric-label", "Mean RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.mean_rt_ms)}" } }
                     li { span { class: "results-detail__metric-label", "SD" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.sd_rt_ms)}" } }
@@ -89,6 +168,13 @@ fn render_pvt(record: &SummaryRecord) -> Element {
                     li { span { class: "results-detail__metric-label", "Minor lapses 355–499 ms" } span { class: "results-detail__metric-value", "{metrics.minor_lapses_355_499ms}" } }
                     li { span { class: "results-detail__metric-label", "False starts" } span { class: "results-detail__metric-value", "{metrics.false_starts}" } }
                     li { span { class: "results-detail__metric-label", "Slope" } span { class: "results-detail__metric-value", "{format::format_slope(metrics.time_on_task_slope_ms_per_min)}" } }
+                    if metrics.has_outcome_measures() {
+                        li { span { class: "results-detail__metric-label", "Response speed (1/RT)" } span { class: "results-detail__metric-value", "{format::format_number(metrics.mean_reciprocal_rt_per_s, 2)} /s" } }
+                        li { span { class: "results-detail__metric-label", "Fastest 10%" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.fastest_10pct_rt_ms)}" } }
+                        li { span { class: "results-detail__metric-label", "Slowest 10%" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.slowest_10pct_rt_ms)}" } }
+                        li { span { class: "results-detail__metric-label", "Lapse probability" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.lapse_probability)}" } }
+                        li { span { class: "results-detail__metric-label", "Performance score" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.performance_score)}" } }
+                    }
                     li {
                         span { class: "results-detail__metric-label", "Minimum trials met" }
                         span { class: "results-detail__metric-value", "{min_trials_label}" }
@@ -107,6 +193,7 @@ fn render_nback(record: &SummaryRecord) -> Element {
         Some(metrics) => {
             rsx! {
                 ul { class: "results-detail__grid",
+                    li { span { class: "results-detail__metric-label", "Level" } span { class: "results-detail__metric-value", "{metrics.level_label()}" } }
                     li { span { class: "results-detail__metric-label", "Accuracy" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.accuracy)}" } }
                     li { span { class: "results-detail__metric-label", "d′" } span { class: "results-detail__metric-value", "{format::format_number(metrics.d_prime, 2)}" } }
                     li { span { class: "results-detail__metric-label", "Criterion" } span { class: "results-detail__metric-value", "{format::format_number(metrics.criterion, 2)}" } }
@@ -114,6 +201,12 @@ fn render_nback(record: &SummaryRecord) -> Element {
                     li { span { class: "results-detail__metric-label", "Misses" } span { class: "results-detail__metric-value", "{metrics.misses}" } }
                     li { span { class: "results-detail__metric-label", "False alarms" } span { class: "results-detail__metric-value", "{metrics.false_alarms}" } }
                     li { span { class: "results-detail__metric-label", "Correct rejections" } span { class: "results-detail__metric-value", "{metrics.correct_rejections}" } }
+                    if metrics.has_lure_measures() {
+                        li { span { class: "results-detail__metric-label", "Lure false alarms" } span { class: "results-detail__metric-value", "{metrics.lure_false_alarms} / {metrics.lure_trials}" } }
+                        li { span { class: "results-detail__metric-label", "Lure FA rate" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.lure_false_alarm_rate)}" } }
+                        li { span { class: "results-detail__metric-label", "Non-lure FA rate" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.non_lure_false_alarm_rate)}" } }
+                        li { span { class: "results-detail__metric-label", "Lure d′" } span { class: "results-detail__metric-value", "{format::format_number(metrics.lure_d_prime, 2)}" } }
+                    }
                     li { span { class: "results-detail__metric-label", "Median hit RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.median_hit_rt_ms)}" } }
                     li { span { class: "results-detail__metric-label", "Mean hit RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.mean_hit_rt_ms)}" } }
                     li { span { class: "results-detail__metric-label", "Responses" } span { class: "results-detail__metric-value", "{metrics.response_count}" } }
@@ -121,7 +214,216 @@ fn render_nback(record: &SummaryRecord) -> Element {
             }
         }
         None => rsx! {
-            p { class: "results-card__placeholder", "Unable to decode 2-back metrics for this run." }
+            p { class: "results-card__placeholder", "Unable to decode N-back metrics for this run." }
+        },
+    }
+}
+
+fn render_flanker(record: &SummaryRecord) -> Element {
+    match parse_flanker_metrics(record) {
+        Some(metrics) => {
+            let min_trials_label = if metrics.meets_min_trial_requirement {
+                "Yes"
+            } else {
+                "No"
+            };
+            let conflict_ms = format!("{:+.0} ms", metrics.conflict_effect_ms);
+            rsx! {
+                ul { class: "results-detail__grid",
+                    li { span { class: "results-detail__metric-label", "Accuracy" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.accuracy)}" } }
+                    li { span { class: "results-detail__metric-label", "Congruent accuracy" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.congruent_accuracy)}" } }
+                    li { span { class: "results-detail__metric-label", "Incongruent accuracy" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.incongruent_accuracy)}" } }
+                    li { span { class: "results-detail__metric-label", "Congruent median RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.congruent_median_rt_ms)}" } }
+                    li { span { class: "results-detail__metric-label", "Incongruent median RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.incongruent_median_rt_ms)}" } }
+                    li { span { class: "results-detail__metric-label", "Conflict effect (RT)" } span { class: "results-detail__metric-value", "{conflict_ms}" } }
+                    li { span { class: "results-detail__metric-label", "Conflict effect (accuracy)" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.conflict_effect_accuracy)}" } }
+                    li { span { class: "results-detail__metric-label", "Errors" } span { class: "results-detail__metric-value", "{metrics.errors}" } }
+                    li { span { class: "results-detail__metric-label", "Misses" } span { class: "results-detail__metric-value", "{metrics.misses}" } }
+                    li {
+                        span { class: "results-detail__metric-label", "Minimum trials met" }
+                        span { class: "results-detail__metric-value", "{min_trials_label}" }
+                    }
+                }
+            }
+        }
+        None => rsx! {
+            p { class: "results-card__placeholder", "Unable to decode flanker metrics for this run." }
         },
     }
 }
+
+fn render_dsst(record: &SummaryRecord) -> Element {
+    match parse_dsst_metrics(record) {
+        Some(metrics) => {
+            let min_trials_label = if metrics.meets_min_trial_requirement {
+                "Yes"
+            } else {
+                "No"
+            };
+            let symbol_set = metrics.symbol_set + 1;
+            rsx! {
+                ul { class: "results-detail__grid",
+                    li { span { class: "results-detail__metric-label", "Correct per minute" } span { class: "results-detail__metric-value", "{format::format_number(metrics.correct_per_minute, 1)}" } }
+                    li { span { class: "results-detail__metric-label", "Correct" } span { class: "results-detail__metric-value", "{metrics.correct}" } }
+                    li { span { class: "results-detail__metric-label", "Errors" } span { class: "results-detail__metric-value", "{metrics.errors}" } }
+                    li { span { class: "results-detail__metric-label", "Accuracy" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.accuracy)}" } }
+                    li { span { class: "results-detail__metric-label", "Median RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.median_rt_ms)}" } }
+                    li { span { class: "results-detail__metric-label", "Mean RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.mean_rt_ms)}" } }
+                    li { span { class: "results-detail__metric-label", "Symbol set" } span { class: "results-detail__metric-value", "{symbol_set}" } }
+                    li {
+                        span { class: "results-detail__metric-label", "Minimum trials met" }
+                        span { class: "results-detail__metric-value", "{min_trials_label}" }
+                    }
+                }
+            }
+        }
+        None => rsx! {
+            p { class: "results-card__placeholder", "Unable to decode DSST metrics for this run." }
+        },
+    }
+}
+
+fn render_word_recall(record: &SummaryRecord) -> Element {
+    match parse_word_recall_metrics(record) {
+        Some(metrics) => {
+            let list_length = metrics.list_length;
+            let delayed = metrics
+                .delayed_correct
+                .map(|correct| format!("{correct} / {list_length}"))
+                .unwrap_or_else(|| "Skipped".into());
+            let delayed_errors = match (metrics.delayed_intrusions, metrics.delayed_repetitions) {
+                (Some(intrusions), Some(repetitions)) => format!("{intrusions} / {repetitions}"),
+                _ => "—".into(),
+            };
+            let delay = metrics
+                .delay_ms
+                .map(|ms| format!("{:.0} min", ms / 60_000.0))
+                .unwrap_or_else(|| "—".into());
+            let retention = metrics
+                .retention
+                .map(format::format_percent)
+                .unwrap_or_else(|| "—".into());
+            rsx! {
+                ul { class: "results-detail__grid",
+                    li { span { class: "results-detail__metric-label", "Immediate recall" } span { class: "results-detail__metric-value", "{metrics.immediate_correct} / {list_length}" } }
+                    li { span { class: "results-detail__metric-label", "Delayed recall" } span { class: "results-detail__metric-value", "{delayed}" } }
+                    li { span { class: "results-detail__metric-label", "Retention" } span { class: "results-detail__metric-value", "{retention}" } }
+                    li { span { class: "results-detail__metric-label", "Delay" } span { class: "results-detail__metric-value", "{delay}" } }
+                    li { span { class: "results-detail__metric-label", "Immediate intrusions / repetitions" } span { class: "results-detail__metric-value", "{metrics.immediate_intrusions} / {metrics.immediate_repetitions}" } }
+                    li { span { class: "results-detail__metric-label", "Delayed intrusions / repetitions" } span { class: "results-detail__metric-value", "{delayed_errors}" } }
+                    li { span { class: "results-detail__metric-label", "List" } span { class: "results-detail__metric-value", "{metrics.form_id}" } }
+                    li { span { class: "results-detail__metric-label", "Answered by" } span { class: "results-detail__metric-value", "{metrics.response_mode}" } }
+                }
+            }
+        }
+        None => rsx! {
+            p { class: "results-card__placeholder", "Unable to decode word recall metrics for this run." }
+        },
+    }
+}
+
+fn render_task_switch(record: &SummaryRecord) -> Element {
+    match parse_task_switch_metrics(record) {
+        Some(metrics) => {
+            let min_trials_label = if metrics.meets_min_trial_requirement {
+                "Yes"
+            } else {
+                "No"
+            };
+            let switch_cost = format!("{:+.0} ms", metrics.switch_cost_ms);
+            let mixing_cost = format!("{:+.0} ms", metrics.mixing_cost_ms);
+            rsx! {
+                ul { class: "results-detail__grid",
+                    li { span { class: "results-detail__metric-label", "Accuracy" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.accuracy)}" } }
+                    li { span { class: "results-detail__metric-label", "Pure median RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.pure_median_rt_ms)}" } }
+                    li { span { class: "results-detail__metric-label", "Repeat median RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.repeat_median_rt_ms)}" } }
+                    li { span { class: "results-detail__metric-label", "Switch median RT" } span { class: "results-detail__metric-value", "{format::format_ms(metrics.switch_median_rt_ms)}" } }
+                    li { span { class: "results-detail__metric-label", "Switch cost (RT)" } span { class: "results-detail__metric-value", "{switch_cost}" } }
+                    li { span { class: "results-detail__metric-label", "Mixing cost (RT)" } span { class: "results-detail__metric-value", "{mixing_cost}" } }
+                    li { span { class: "results-detail__metric-label", "Switch cost (errors)" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.switch_cost_error_rate)}" } }
+                    li { span { class: "results-detail__metric-label", "Mixing cost (errors)" } span { class: "results-detail__metric-value", "{format::format_percent(metrics.mixing_cost_error_rate)}" } }
+                    li { span { class: "results-detail__metric-label", "Errors / misses" } span { class: "results-detail__metric-value", "{metrics.errors} / {metrics.misses}" } }
+                    li {
+                        span { class: "results-detail__metric-label", "Minimum trials met" }
+                        span { class: "results-detail__metric-value", "{min_trials_label}" }
+                    }
+                }
+            }
+        }
+        None => rsx! {
+            p { class: "results-card__placeholder", "Unable to decode task-switching metrics for this run." }
+        },
+    }
+}
+
+struct TrialDot {
+    index: usize,
+    x: f64,
+    y: f64,
+    color: &'static str,
+    title: String,
+}
+
+struct TrialChart {
+    dots: Vec<TrialDot>,
+    /// Ticks along the top for trials with no response to plot.
+    misses: String,
+    /// The PVT's 500 ms lapse threshold, when it's in range.
+    lapse_y: Option<f64>,
+    max: f64,
+}
+
+/// Each trial's RT against its position in the run: scored responses and
+/// errors as dots, unanswered trials (N-back and flanker misses, PVT timeouts)
+/// as ticks.
+/// `None` without any trial to plot.
+fn build_trial_chart(task: &str, trials: &[TrialSample]) -> Option<TrialChart> {
+    // Recall answers are self-paced words, not timed trials.
+    if trials.is_empty() || task == "word_recall" {
+        return None;
+    }
+
+    let width = 360.0;
+    let height = 130.0;
+    let top = 10.0;
+    let mut max = trials
+        .iter()
+        .filter_map(|t| t.rt_ms)
+        .filter(|rt| rt.is_finite())
+        .fold(0.0_f64, f64::max);
+    if task == "pvt" {
+        max = max.max(600.0);
+    }
+    let max = max.max(1.0);
+    let step = width / trials.len().max(2) as f64;
+    let y_of = |rt: f64| height - (rt / max).clamp(0.0, 1.0) * (height - top);
+
+    let mut dots = Vec::new();
+    let mut misses = String::new();
+    for (position, trial) in trials.iter().enumerate() {
+        let x = step * (position as f64 + 0.5);
+        match (trial.outcome.as_str(), trial.rt_ms) {
+            ("lapse" | "miss", _) => {
+                let _ = write!(misses, "M{:.2} 0 L{:.2} {:.2} ", x, x, top - 4.0);
+            }
+            (outcome, Some(rt)) if rt.is_finite() => dots.push(TrialDot {
+                index: trial.index,
+                x,
+                y: y_of(rt),
+                color: match outcome {
+                    "reaction" | "hit" | "correct" => "#f05a7e",
+                    _ => "#a78bfa",
+                },
+                title: format!("#{} · {} · {}", trial.index + 1, outcome, format::format_ms(rt)),
+            }),
+            _ => {}
+        }
+    }
+
+    Some(TrialChart {
+        dots,
+        misses,
+        lapse_y: (task == "pvt").then(|| y_of(500.0)),
+        max,
+    })
+}
@@ -6,8 +6,10 @@ use std::fmt::Write as _;
 use crate::core::platform;
 use crate::core::{format, storage::SummaryRecord};
 use crate::results::{
-    format_date_badge, format_time_badge, format_timestamp, parse_nback_metrics, parse_pvt_metrics,
-    parse_timestamp, qc_summary, record_is_clean,
+    format_date_badge, format_time_badge, format_timestamp, latest_nback_level, latest_pvt_preset,
+    parse_dsst_metrics, parse_flanker_metrics, parse_nback_metrics, parse_pvt_metrics,
+    parse_task_switch_metrics, parse_timestamp, parse_word_recall_metrics, qc_summary,
+    record_is_clean,
 };
 use time::OffsetDateTime;
 
@@ -203,6 +205,10 @@ pub fn ResultsExportPanel(records: Vec<SummaryRecord>) -> Element {
     let total_runs = records.len();
     let pvt_runs = records.iter().filter(|r| r.task == "pvt").count();
     let nback_runs = records.iter().filter(|r| r.task == "nback2").count();
+    let flanker_runs = records.iter().filter(|r| r.task == "flanker").count();
+    let dsst_runs = records.iter().filter(|r| r.task == "dsst").count();
+    let word_recall_runs = records.iter().filter(|r| r.task == "word_recall").count();
+    let task_switch_runs = records.iter().filter(|r| r.task == "task_switch").count();
 
     let status = use_signal(|| ExportStatus::Idle);
     let busy = use_signal(|| false);
@@ -370,7 +376,11 @@ pub fn ResultsExportPanel(records: Vec<SummaryRecord>) -> Element {
                 ul { class: "results-export__summary",
                     li { strong { "{total_runs}" } " total runs cached" }
                     li { strong { "{pvt_runs}" } " psychomotor vigilance" }
-                    li { strong { "{nback_runs}" } " 2-back runs" }
+                    li { strong { "{nback_runs}" } " N-back runs" }
+                    li { strong { "{flanker_runs}" } " flanker runs" }
+                    li { strong { "{dsst_runs}" } " DSST runs" }
+                    li { strong { "{word_recall_runs}" } " word recall runs" }
+                    li { strong { "{task_switch_runs}" } " task-switching runs" }
                 }
 
                 div { class: "results-export__actions",
@@ -444,19 +454,27 @@ async fn perform_png_export(records: Vec<SummaryRecord>) -> Result<String, Strin
 }
 
 fn build_csv(records: &[SummaryRecord]) -> String {
-    // Fixed schema: 4 core + 5 PVT + 7 NBack + 5 tail = 21 columns
+    // Fixed schema: 4 core + 11 PVT + 12 NBack + 6 Flanker + 5 DSST + 7 word recall
+    // + 6 task switching + 7 tail = 58 columns
     let header = [
         "task",
         "created_at",
         "platform",
         "tz",
         // PVT metrics
+        "pvt_preset",
         "median_rt_ms",
         "mean_rt_ms",
         "lapses_500ms",
         "false_starts",
         "slope_ms_per_min",
+        "mean_reciprocal_rt_per_s",
+        "fastest_10pct_rt_ms",
+        "slowest_10pct_rt_ms",
+        "lapse_probability",
+        "performance_score",
         // N-back metrics
+        "nback_n",
         "accuracy",
         "d_prime",
         "criterion",
@@ -464,12 +482,46 @@ fn build_csv(records: &[SummaryRecord]) -> String {
         "misses",
         "false_alarms",
         "correct_rejections",
+        "lure_trials",
+        "lure_false_alarm_rate",
+        "non_lure_false_alarm_rate",
+        "lure_d_prime",
+        // Flanker metrics
+        "flanker_accuracy",
+        "congruent_accuracy",
+        "incongruent_accuracy",
+        "congruent_median_rt_ms",
+        "incongruent_median_rt_ms",
+        "conflict_effect_ms",
+        // DSST metrics
+        "dsst_correct_per_minute",
+        "dsst_correct",
+        "dsst_errors",
+        "dsst_accuracy",
+        "dsst_median_rt_ms",
+        // Word recall metrics
+        "recall_form",
+        "recall_response_mode",
+        "recall_immediate_correct",
+        "recall_immediate_intrusions",
+        "recall_delayed_correct",
+        "recall_delayed_intrusions",
+        "recall_retention",
+        // Task-switching metrics
+        "switch_accuracy",
+        "repeat_trial_median_rt_ms",
+        "switch_trial_median_rt_ms",
+        "switch_cost_ms",
+        "mixing_cost_ms",
+        "switch_cost_error_rate",
         // General / QC
         "notes",
         "qc_summary",
         "qc_focus_lost",
         "qc_visibility_blur",
         "qc_min_trials_met",
+        "qc_pause_events",
+        "qc_paused_ms",
     ];
     let mut out = String::new();
     out.push_str(
@@ -491,27 +543,40 @@ fn build_csv(records: &[SummaryRecord]) -> String {
         row.push(record.client.platform.clone());
         row.push(record.client.tz.clone());
 
-        // PVT (5)
+        // PVT (11)
         if record.task == "pvt" {
             if let Some(m) = parse_pvt_metrics(record) {
                 row.extend([
+                    m.preset().id().to_string(),
                     m.median_rt_ms.to_string(),
                     m.mean_rt_ms.to_string(),
                     m.lapses_ge_500ms.to_string(),
                     m.false_starts.to_string(),
                     m.time_on_task_slope_ms_per_min.to_string(),
                 ]);
+                if m.has_outcome_measures() {
+                    row.extend([
+                        m.mean_reciprocal_rt_per_s.to_string(),
+                        m.fastest_10pct_rt_ms.to_string(),
+                        m.slowest_10pct_rt_ms.to_string(),
+                        m.lapse_probability.to_string(),
+                        m.performance_score.to_string(),
+                    ]);
+                } else {
+                    row.extend(std::iter::repeat_n(String::new(), 5));
+                }
             } else {
-                row.extend(std::iter::repeat_n(String::new(), 5));
+                row.extend(std::iter::repeat_n(String::new(), 11));
             }
         } else {
-            row.extend(std::iter::repeat_n(String::new(), 5));
+            row.extend(std::iter::repeat_n(String::new(), 11));
         }
 
-        // NBack (7)
+        // NBack (12)
         if record.task == "nback2" {
             if let Some(m) = parse_nback_metrics(record) {
                 row.extend([
+                    m.n.to_string(),
                     m.accuracy.to_string(),
                     m.d_prime.to_string(),
                     m.criterion.to_string(),
@@ -520,6 +585,71 @@ fn build_csv(records: &[SummaryRecord]) -> String {
                     m.false_alarms.to_string(),
                     m.correct_rejections.to_string(),
                 ]);
+                if m.has_lure_measures() {
+                    row.extend([
+                        m.lure_trials.to_string(),
+                        m.lure_false_alarm_rate.to_string(),
+                        m.non_lure_false_alarm_rate.to_string(),
+                        m.lure_d_prime.to_string(),
+                    ]);
+                } else {
+                    row.extend(std::iter::repeat_n(String::new(), 4));
+                }
+            } else {
+                row.extend(std::iter::repeat_n(String::new(), 12));
+            }
+        } else {
+            row.extend(std::iter::repeat_n(String::new(), 12));
+        }
+
+        // Flanker (6)
+        if record.task == "flanker" {
+            if let Some(m) = parse_flanker_metrics(record) {
+                row.extend([
+                    m.accuracy.to_string(),
+                    m.congruent_accuracy.to_string(),
+                    m.incongruent_accuracy.to_string(),
+                    m.congruent_median_rt_ms.to_string(),
+                    m.incongruent_median_rt_ms.to_string(),
+                    m.conflict_effect_ms.to_string(),
+                ]);
+            } else {
+                row.extend(std::iter::repeat_n(String::new(), 6));
+            }
+        } else {
+            row.extend(std::iter::repeat_n(String::new(), 6));
+        }
+
+        // DSST (5)
+        if record.task == "dsst" {
+            if let Some(m) = parse_dsst_metrics(record) {
+                row.extend([
+                    m.correct_per_minute.to_string(),
+                    m.correct.to_string(),
+                    m.errors.to_string(),
+                    m.accuracy.to_string(),
+                    m.median_rt_ms.to_string(),
+                ]);
+            } else {
+                row.extend(std::iter::repeat_n(String::new(), 5));
+            }
+        } else {
+            row.extend(std::iter::repeat_n(String::new(), 5));
+        }
+
+        // Word recall (7); delayed columns stay empty when it was skipped.
+        if record.task == "word_recall" {
+            if let Some(m) = parse_word_recall_metrics(record) {
+                let optional = |value: Option<String>| value.unwrap_or_default();
+                row.extend([
+                    m.form_id.clone(),
+                    m.response_mode.clone(),
+                    m.immediate_correct.to_string(),
+                    m.immediate_intrusions.to_string(),
+                    optional(m.delayed_correct.map(|v| v.to_string())),
+                    optional(m.delayed_intrusions.map(|v| v.to_string())),
+                    optional(m.retention.map(|v| v.to_string())),
+                ]);
             } else {
                 row.extend(std::iter::repeat_n(String::new(), 7));
             }
@@ -527,12 +657,32 @@ fn build_csv(records: &[SummaryRecord]) -> String {
             row.extend(std::iter::repeat_n(String::new(), 7));
         }
 
-        // Tail (5)
+        // Task switching (6)
+        if record.task == "task_switch" {
+            if let Some(m) = parse_task_switch_metrics(record) {
+                row.extend([
+                    m.accuracy.to_string(),
+                    m.repeat_median_rt_ms.to_string(),
+                    m.switch_median_rt_ms.to_string(),
+                    m.switch_cost_ms.to_string(),
+                    m.mixing_cost_ms.to_string(),
+                    m.switch_cost_error_rate.to_string(),
+                ]);
+            } else {
+                row.extend(std::iter::repeat_n(String::new(), 6));
+            }
+        } else {
+            row.extend(std::iter::repeat_n(String::new(), 6));
+        }
+
+        // Tail (7)
         row.push(record.notes.clone().unwrap_or_default());
         row.push(qc_summary(record));
         row.push(record.qc.focus_lost_events.to_string());
         row.push(record.qc.visibility_blur_events.to_string());
         row.push(record.qc.min_trials_met.to_string());
+        row.push(record.qc.pause_events.to_string());
+        row.push(record.qc.paused_ms.to_string());
 
         out.push_str(
             &row.into_iter()
@@ -932,15 +1082,15 @@ fn svg_snapshot_with_scale(records: &[SummaryRecord], scale: f64) -> String {
 
     let pvt_value = format::format_ms(overview.avg_pvt.unwrap_or(f64::NAN));
     let pvt_meta = if overview.clean_pvt > 0 {
-        format!("{} clean PVT sessions", overview.clean_pvt)
+        format!("{} clean {} sessions", overview.clean_pvt, overview.pvt_label)
     } else {
         "Run a PVT to populate".to_string()
     };
     let accuracy_value = format::format_percent(overview.avg_nback_accuracy.unwrap_or(f64::NAN));
     let accuracy_meta = if overview.clean_nback > 0 {
-        format!("{} clean 2-back sessions", overview.clean_nback)
+        format!("{} clean {} sessions", overview.clean_nback, overview.nback_label)
     } else {
-        "Complete a 2-back session".to_string()
+        "Complete an N-back session".to_string()
     };
     let dprime_value = overview
         .avg_nback_dprime
@@ -953,11 +1103,13 @@ fn svg_snapshot_with_scale(records: &[SummaryRecord], scale: f64) -> String {
         "Data pending".to_string()
     };
 
+    let accuracy_label = format!("{} accuracy", overview.nback_label);
+    let dprime_label = format!("{} d′", overview.nback_label);
     let highlight_cards = [
         ("Total runs", overview.total_runs.to_string(), total_meta),
         ("Median PVT", pvt_value, pvt_meta),
-        ("2-back accuracy", accuracy_value, accuracy_meta),
-        ("2-back d′", dprime_value, dprime_meta),
+        (accuracy_label.as_str(), accuracy_value, accuracy_meta),
+        (dprime_label.as_str(), dprime_value, dprime_meta),
     ];
 
     let mut svg = String::with_capacity(48_000);
@@ -1130,11 +1282,12 @@ fn svg_snapshot_with_scale(records: &[SummaryRecord], scale: f64) -> String {
     );
     let _ = writeln!(
         svg,
-        "    <text x='{:.2}' y='{:.2}' fill='{}' fill-opacity='{:.2}' font-family='{FONT_STACK}' font-size='16'>Median reaction time across clean runs</text>",
+        "    <text x='{:.2}' y='{:.2}' fill='{}' fill-opacity='{:.2}' font-family='{FONT_STACK}' font-size='16'>Median reaction time across clean {} runs</text>",
         CARD_PADDING_X,
         spark_subtitle_baseline,
         theme.text_base,
-        OP_TEXT_MUTED
+        OP_TEXT_MUTED,
+        overview.pvt_label
     );
 
     if let Some(chart) = spark_chart {
@@ -1253,11 +1406,12 @@ fn svg_snapshot_with_scale(records: &[SummaryRecord], scale: f64) -> String {
     );
     let _ = writeln!(
         svg,
-        "    <text x='{:.2}' y='{:.2}' fill='{}' fill-opacity='{:.2}' font-family='{FONT_STACK}' font-size='16'>Recent clean PVT sessions</text>",
+        "    <text x='{:.2}' y='{:.2}' fill='{}' fill-opacity='{:.2}' font-family='{FONT_STACK}' font-size='16'>Recent clean {} sessions</text>",
         CARD_PADDING_X,
         bars_subtitle_baseline,
         theme.text_base,
-        OP_TEXT_MUTED
+        OP_TEXT_MUTED,
+        overview.pvt_label
     );
 
     let legend_x = bars_rect.width - CARD_PADDING_X - 220.0;
@@ -1383,6 +1537,10 @@ struct SnapshotOverview {
     total_runs: usize,
     clean_runs: usize,
     clean_pvt: usize,
+    /// Name of the PVT protocol the PVT figures are limited to.
+    pvt_label: &'static str,
+    /// Level the N-back figures are limited to, e.g. `3-back`.
+    nback_label: String,
     clean_nback: usize,
     avg_pvt: Option<f64>,
     avg_nback_accuracy: Option<f64>,
@@ -1409,6 +1567,8 @@ impl SnapshotOverview {
             clean_refs = records.iter().collect();
         }
 
+        let pvt_preset = latest_pvt_preset(records).unwrap_or_default();
+        let nback_level = latest_nback_level(records).unwrap_or(2);
         let mut pvt_medians = Vec::new();
         let mut nback_accuracy = Vec::new();
         let mut nback_dprime = Vec::new();
@@ -1422,7 +1582,9 @@ impl SnapshotOverview {
             if let Some(ts) = parse_timestamp(record) {
                 match record.task.as_str() {
                     "pvt" => {
-                        if let Some(metrics) = parse_pvt_metrics(record) {
+                        if let Some(metrics) = parse_pvt_metrics(record)
+                            .filter(|metrics| metrics.preset() == pvt_preset)
+                        {
                             if metrics.median_rt_ms.is_finite() {
                                 pvt_medians.push(metrics.median_rt_ms);
                                 spark_collect.push((
@@ -1445,7 +1607,9 @@ impl SnapshotOverview {
                         }
                     }
                     "nback2" => {
-                        if let Some(metrics) = parse_nback_metrics(record) {
+                        if let Some(metrics) = parse_nback_metrics(record)
+                            .filter(|metrics| metrics.n == nback_level)
+                        {
                             if metrics.accuracy.is_finite() {
                                 nback_accuracy.push(metrics.accuracy);
                             }
@@ -1460,11 +1624,11 @@ impl SnapshotOverview {
             }
         }
 
-        spark_collect.sort_by(|a, b| a.0.cmp(&b.0));
+        spark_collect.sort_by_key(|a| a.0);
         let spark_points: Vec<SparkPoint> =
             spark_collect.into_iter().map(|(_, point)| point).collect();
 
-        bar_collect.sort_by(|a, b| a.0.cmp(&b.0));
+        bar_collect.sort_by_key(|a| a.0);
         let mut bar_samples: Vec<BarSample> =
             bar_collect.into_iter().map(|(_, sample)| sample).collect();
         if bar_samples.len() > 8 {
@@ -1480,6 +1644,8 @@ impl SnapshotOverview {
             total_runs,
             clean_runs,
             clean_pvt,
+            pvt_label: pvt_preset.label(),
+            nback_label: format!("{nback_level}-back"),
             clean_nback,
             avg_pvt,
             avg_nback_accuracy,
@@ -2,10 +2,12 @@ use crate::{
     core::{
         format,
         storage::{delete_summary, SummaryRecord},
+        store::{use_shared_store, SharedStore},
     },
     results::{
-        format_device, format_timestamp, parse_nback_metrics, parse_pvt_metrics, qc_summary,
-        task_label, ResultsState,
+        format_device, format_timestamp, parse_dsst_metrics, parse_flanker_metrics,
+        parse_nback_metrics, parse_pvt_metrics, parse_task_switch_metrics,
+        parse_word_recall_metrics, qc_summary, task_label, ResultsState,
     },
 };
 use dioxus::prelude::*;
@@ -30,6 +32,7 @@ fn confirm_delete_prompt() -> bool {
 
 #[component]
 pub fn ResultsList(results: Signal<ResultsState>, selected_id: Signal<Option<String>>) -> Element {
+    let store = use_shared_store();
     let state = results();
     let active_id = selected_id();
 
@@ -77,7 +80,7 @@ pub fn ResultsList(results: Signal<ResultsState>, selected_id: Signal<Option<Str
             } else {
                 ul { class: "results-list__items",
                     for entry in entries.into_iter() {
-                        {render_list_entry(entry, selected_id, results)}
+                        {render_list_entry(entry, selected_id, results, store.clone())}
                     }
                 }
             }
@@ -100,6 +103,7 @@ fn render_list_entry(
     entry: ListEntry,
     mut selected_id: Signal<Option<String>>,
     mut results: Signal<ResultsState>,
+    store: SharedStore,
 ) -> Element {
     let ListEntry {
         id,
@@ -154,8 +158,8 @@ fn render_list_entry(
                         if !confirm_delete_prompt() {
                             return;
                         }
-                        if delete_summary(&delete_id).unwrap_or(false) {
-                            results.set(ResultsState::load());
+                        if delete_summary(&store, &delete_id).unwrap_or(false) {
+                            results.set(ResultsState::load(&store));
                             if selected_id().as_ref().map(|x| x == &delete_id).unwrap_or(false) {
                                 let state_now = results();
                                 if let Some(first) = state_now.records.first() {
@@ -177,6 +181,7 @@ fn metric_snippets(record: &SummaryRecord) -> Vec<(String, String)> {
         "pvt" => parse_pvt_metrics(record)
             .map(|metrics| {
                 vec![
+                    ("Protocol".into(), metrics.preset().label().into()),
                     ("Median RT".into(), format::format_ms(metrics.median_rt_ms)),
                     ("Lapses".into(), metrics.lapses_ge_500ms.to_string()),
                     ("False starts".into(), metrics.false_starts.to_string()),
@@ -186,12 +191,75 @@ fn metric_snippets(record: &SummaryRecord) -> Vec<(String, String)> {
         "nback2" => parse_nback_metrics(record)
             .map(|metrics| {
                 vec![
+                    ("Level".into(), metrics.level_label()),
                     ("Accuracy".into(), format::format_percent(metrics.accuracy)),
                     ("d′".into(), format::format_number(metrics.d_prime, 2)),
                     ("Responses".into(), metrics.response_count.to_string()),
                 ]
             })
             .unwrap_or_else(|| vec![("Metrics".into(), "Unavailable".into())]),
+        "flanker" => parse_flanker_metrics(record)
+            .map(|metrics| {
+                vec![
+                    ("Accuracy".into(), format::format_percent(metrics.accuracy)),
+                    ("Conflict".into(), format!("{:+.0} ms", metrics.conflict_effect_ms)),
+                    (
+                        "Incongruent RT".into(),
+                        format::format_ms(metrics.incongruent_median_rt_ms),
+                    ),
+                    ("Errors".into(), metrics.errors.to_string()),
+                ]
+            })
+            .unwrap_or_else(|| vec![("Metrics".into(), "Unavailable".into())]),
+        "dsst" => parse_dsst_metrics(record)
+            .map(|metrics| {
+                vec![
+                    (
+                        "Correct/min".into(),
+                        format::format_number(metrics.correct_per_minute, 1),
+                    ),
+                    ("Correct".into(), metrics.correct.to_string()),
+                    ("Errors".into(), metrics.errors.to_string()),
+                    ("Median RT".into(), format::format_ms(metrics.median_rt_ms)),
+                ]
+            })
+            .unwrap_or_else(|| vec![("Metrics".into(), "Unavailable".into())]),
+        "word_recall" => parse_word_recall_metrics(record)
+            .map(|metrics| {
+                let of_list = |correct: u32| format!("{correct}/{}", metrics.list_length);
+                vec![
+                    ("List".into(), metrics.form_id.clone()),
+                    ("Immediate".into(), of_list(metrics.immediate_correct)),
+                    (
+                        "Delayed".into(),
+                        metrics
+                            .delayed_correct
+                            .map(of_list)
+                            .unwrap_or_else(|| "—".into()),
+                    ),
+                    (
+                        "Intrusions".into(),
+                        metrics.immediate_intrusions.to_string(),
+                    ),
+                ]
+            })
+            .unwrap_or_else(|| vec![("Metrics".into(), "Unavailable".into())]),
+        "task_switch" => parse_task_switch_metrics(record)
+            .map(|metrics| {
+                vec![
+                    ("Accuracy".into(), format::format_percent(metrics.accuracy)),
+                    (
+                        "Switch cost".into(),
+                        format!("{:+.0} ms", metrics.switch_cost_ms),
+                    ),
+                    (
+                        "Mixing cost".into(),
+                        format!("{:+.0} ms", metrics.mixing_cost_ms),
+                    ),
+                    ("Errors".into(), metrics.errors.to_string()),
+                ]
+            })
+            .unwrap_or_else(|| vec![("Metrics".into(), "Unavailable".into())]),
         _ => vec![("Task".into(), "Unknown".into())],
     }
 }
@@ -16,6 +16,7 @@ mod utils;
 pub(crate) use utils::*;
 
 use crate::core::storage::{self, SummaryRecord};
+use crate::core::store::SharedStore;
 
 /// Shared state for the results view aggregating stored summaries or load errors.
 #[derive(Debug, Clone, Default)]
@@ -25,8 +26,8 @@ pub struct ResultsState {
 }
 
 impl ResultsState {
-    pub fn load() -> Self {
-        match storage::load_summaries() {
+    pub fn load(store: &SharedStore) -> Self {
+        match storage::load_summaries(store) {
             Ok(mut records) => {
                 records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
                 Self {
@@ -1,6 +1,13 @@
 use crate::{
     core::storage::SummaryRecord,
-    tasks::{nback::NBackMetrics, pvt::PvtMetrics},
+    tasks::{
+        dsst::DsstMetrics,
+        flanker::FlankerMetrics,
+        nback::NBackMetrics,
+        pvt::{PvtMetrics, PvtPreset},
+        task_switch::TaskSwitchMetrics,
+        word_recall::WordRecallMetrics,
+    },
 };
 use time::{format_description::well_known::Rfc3339, macros::format_description, OffsetDateTime};
 
@@ -58,6 +65,13 @@ pub(crate) fn qc_summary(record: &SummaryRecord) -> String {
     if !qc.min_trials_met {
         parts.push("Min trials not met".to_string());
     }
+    if qc.pause_events > 0 {
+        parts.push(format!(
+            "Paused ×{} ({}s)",
+            qc.pause_events,
+            (qc.paused_ms as f64 / 1_000.0).round()
+        ));
+    }
 
     if parts.is_empty() {
         "QC: clean run".to_string()
@@ -68,13 +82,20 @@ pub(crate) fn qc_summary(record: &SummaryRecord) -> String {
 
 pub(crate) fn record_is_clean(record: &SummaryRecord) -> bool {
     let qc = &record.qc;
-    qc.min_trials_met && qc.focus_lost_events == 0 && qc.visibility_blur_events == 0
+    qc.min_trials_met
+        && qc.focus_lost_events == 0
+        && qc.visibility_blur_events == 0
+        && qc.pause_events == 0
 }
 
 pub(crate) fn task_label(task: &str) -> &'static str {
     match task {
         "pvt" => "Psychomotor Vigilance",
-        "nback2" => "2-back working memory",
+        "nback2" => "N-back working memory",
+        "flanker" => "Flanker inhibitory control",
+        "dsst" => "Digit symbol substitution",
+        "word_recall" => "Word-list recall",
+        "task_switch" => "Task switching",
         _ => "Session",
     }
 }
@@ -95,6 +116,44 @@ pub(crate) fn parse_pvt_metrics(record: &SummaryRecord) -> Option<PvtMetrics> {
     serde_json::from_value(record.metrics.clone()).ok()
 }
 
+/// The protocol of the newest PVT run. Charts plot only runs of this preset,
+/// so results from different protocols are never mixed.
+pub(crate) fn latest_pvt_preset(records: &[SummaryRecord]) -> Option<PvtPreset> {
+    records
+        .iter()
+        .filter(|record| record.task == "pvt")
+        .max_by(|a, b| a.created_at.cmp(&b.created_at))
+        .and_then(parse_pvt_metrics)
+        .map(|metrics| metrics.preset())
+}
+
+/// The level of the newest N-back run. Charts plot only runs at this level, so
+/// scores from different N are never averaged together.
+pub(crate) fn latest_nback_level(records: &[SummaryRecord]) -> Option<u8> {
+    records
+        .iter()
+        .filter(|record| record.task == "nback2")
+        .max_by(|a, b| a.created_at.cmp(&b.created_at))
+        .and_then(parse_nback_metrics)
+        .map(|metrics| metrics.n)
+}
+
 pub(crate) fn parse_nback_metrics(record: &SummaryRecord) -> Option<NBackMetrics> {
     serde_json::from_value(record.metrics.clone()).ok()
 }
+
+pub(crate) fn parse_flanker_metrics(record: &SummaryRecord) -> Option<FlankerMetrics> {
+    serde_json::from_value(record.metrics.clone()).ok()
+}
+
+pub(crate) fn parse_dsst_metrics(record: &SummaryRecord) -> Option<DsstMetrics> {
+    serde_json::from_value(record.metrics.clone()).ok()
+}
+
+pub(crate) fn parse_word_recall_metrics(record: &SummaryRecord) -> Option<WordRecallMetrics> {
+    serde_json::from_value(record.metrics.clone()).ok()
+}
+
+pub(crate) fn parse_task_switch_metrics(record: &SummaryRecord) -> Option<TaskSwitchMetrics> {
+    serde_json::from_value(record.metrics.clone()).ok()
+}
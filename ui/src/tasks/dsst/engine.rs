@@ -0,0 +1,556 @@
+//! Engine managing the Digit Symbol Substitution task (DSST).
+//!
+//! A key pairs nine symbols with the digits 1–9. One symbol is shown at a time
+//! and the participant answers with its digit; every answer, right or wrong,
+//! brings up the next symbol straight away, until the block's time runs out.
+//! The score is how many symbols were coded correctly in that time.
+//!
+//! Each session draws its symbols from one of several alternate sets, and the
+//! key's pairing is shuffled per seed, so the key can't simply be memorised
+//! from one session to the next.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::core::storage::TrialSample;
+use crate::core::timing::{self, InstantStamp};
+
+use super::metrics::DsstMetrics;
+
+/// Alternate symbol sets, rotated between sessions. Each has nine glyphs, one
+/// per digit.
+pub const SYMBOL_SETS: [[&str; 9]; 3] = [
+    ["○", "△", "□", "◇", "☆", "✕", "◐", "▽", "⬡"],
+    ["∧", "∨", "⊂", "⊃", "⊥", "∩", "∪", "≡", "÷"],
+    ["♠", "♣", "♥", "♦", "☼", "☾", "⚑", "♪", "⌂"],
+];
+
+const KEY_SEED_TAG: u64 = 0x44_5353_544b_4559_u64; // "DSSTKEY"
+
+/// Different run phases for the DSST engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunMode {
+    Practice,
+    Main,
+}
+
+impl RunMode {
+    fn seed_tag(self) -> u64 {
+        match self {
+            RunMode::Practice => 0x44_5350_5241_4354_u64, // "DSPRACT"
+            RunMode::Main => 0x44_534d_4149_4e52_u64,     // "DSMAINR"
+        }
+    }
+}
+
+/// Public configuration knobs for the task.
+#[derive(Debug, Clone)]
+pub struct DsstConfig {
+    pub duration_ms: u64,
+    pub practice_duration_ms: u64,
+    pub lead_in_ms: u64,
+    /// Index into [`SYMBOL_SETS`].
+    pub symbol_set: usize,
+    pub seed: u64,
+}
+
+impl DsstConfig {
+    /// The configuration for a user's `session`-th main run (counting from
+    /// zero): the symbol set rotates and the key is reshuffled each time.
+    pub fn for_session(session: usize) -> Self {
+        Self {
+            symbol_set: session % SYMBOL_SETS.len(),
+            seed: session as u64 + 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn block_duration_ms(&self, mode: RunMode) -> u64 {
+        match mode {
+            RunMode::Practice => self.practice_duration_ms,
+            RunMode::Main => self.duration_ms,
+        }
+    }
+}
+
+impl Default for DsstConfig {
+    fn default() -> Self {
+        Self {
+            duration_ms: 90_000,
+            practice_duration_ms: 20_000,
+            lead_in_ms: 750,
+            symbol_set: 0,
+            seed: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineState {
+    Idle,
+    /// Lead-in before the first (or, after a pause, the next) symbol.
+    Waiting {
+        mode: RunMode,
+    },
+    Active {
+        mode: RunMode,
+    },
+    Paused {
+        mode: RunMode,
+    },
+    Completed {
+        mode: RunMode,
+    },
+    Aborted,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DsstItem {
+    pub index: usize,
+    pub symbol: &'static str,
+    /// The digit the key pairs with `symbol`: the correct answer.
+    pub digit: u8,
+    pub presented_at: Option<InstantStamp>,
+    pub response: Option<ItemResponse>,
+    pub outcome: ItemOutcome,
+}
+
+impl DsstItem {
+    pub fn is_completed(&self) -> bool {
+        !matches!(self.outcome, ItemOutcome::Pending)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemResponse {
+    pub timestamp: InstantStamp,
+    pub rt_ms: f64,
+    pub digit: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemOutcome {
+    Pending,
+    Correct { rt_ms: f64 },
+    Error { rt_ms: f64 },
+}
+
+/// When to show a block's first symbol; `begin` then reports how long it runs.
+#[derive(Debug, Clone)]
+pub struct BlockSchedule {
+    pub run_id: u64,
+    pub lead_in_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub enum ResponseOutcome {
+    /// The answer was scored and the next symbol is up.
+    Recorded {
+        item_index: usize,
+        correct: bool,
+    },
+    Ignored,
+}
+
+#[derive(Debug, Clone)]
+pub struct DsstEngine {
+    pub config: DsstConfig,
+    pub state: EngineState,
+    pub run_id: u64,
+    /// `key[d - 1]` is the symbol paired with digit `d`.
+    key: [&'static str; 9],
+    items: Vec<DsstItem>,
+    rng: StdRng,
+    /// When the block last started running (first symbol or resume).
+    running_since: Option<InstantStamp>,
+    /// Time run before the current stretch, across pauses.
+    elapsed_ms: f64,
+    last_practice_metrics: Option<DsstMetrics>,
+    last_main_metrics: Option<DsstMetrics>,
+    paused_at: Option<InstantStamp>,
+    pause_count: u32,
+    paused_ms: f64,
+}
+
+impl DsstEngine {
+    pub fn new(config: DsstConfig) -> Self {
+        let key = build_key(&config);
+        Self {
+            config,
+            state: EngineState::Idle,
+            run_id: 0,
+            key,
+            items: Vec::new(),
+            rng: StdRng::seed_from_u64(0),
+            running_since: None,
+            elapsed_ms: 0.0,
+            last_practice_metrics: None,
+            last_main_metrics: None,
+            paused_at: None,
+            pause_count: 0,
+            paused_ms: 0.0,
+        }
+    }
+
+    /// Switch to `config` between runs, e.g. after a main run moves the user on
+    /// to the next session's symbol set. Ignored while a block is running.
+    pub fn reconfigure(&mut self, config: DsstConfig) {
+        if self.is_running() {
+            return;
+        }
+        self.key = build_key(&config);
+        self.config = config;
+    }
+
+    /// The symbols paired with the digits 1–9, in digit order.
+    pub fn key(&self) -> &[&'static str; 9] {
+        &self.key
+    }
+
+    pub fn practice_metrics(&self) -> Option<DsstMetrics> {
+        self.last_practice_metrics.clone()
+    }
+
+    pub fn main_metrics(&self) -> Option<DsstMetrics> {
+        self.last_main_metrics.clone()
+    }
+
+    pub fn items(&self) -> &[DsstItem] {
+        &self.items
+    }
+
+    /// The symbol awaiting an answer, if the block is running.
+    pub fn current_item(&self) -> Option<&DsstItem> {
+        match self.state {
+            EngineState::Active { .. } => self.items.last(),
+            _ => None,
+        }
+    }
+
+    /// The last run's answered items, for keeping with it; times are from its
+    /// first symbol.
+    pub fn trial_samples(&self) -> Vec<TrialSample> {
+        let Some(first) = self.items.iter().find_map(|item| item.presented_at) else {
+            return Vec::new();
+        };
+        self.items
+            .iter()
+            .filter_map(|item| {
+                let outcome = match item.outcome {
+                    ItemOutcome::Pending => return None,
+                    ItemOutcome::Correct { .. } => "correct",
+                    ItemOutcome::Error { .. } => "error",
+                };
+                Some(TrialSample {
+                    index: item.index,
+                    iti_ms: None,
+                    onset_ms: item.presented_at.map(|at| timing::duration_ms(first, at)),
+                    response_ms: item
+                        .response
+                        .as_ref()
+                        .map(|r| timing::duration_ms(first, r.timestamp)),
+                    rt_ms: item.response.as_ref().map(|r| r.rt_ms),
+                    outcome: outcome.to_string(),
+                    stimulus: Some(item.symbol.to_string()),
+                    target: None,
+                    lure: None,
+                })
+            })
+            .collect()
+    }
+
+    pub fn start(&mut self, mode: RunMode) -> Option<BlockSchedule> {
+        if self.is_running() {
+            return None;
+        }
+
+        self.run_id = self.run_id.wrapping_add(1);
+        self.items.clear();
+        self.rng = self.seeded_rng(mode);
+        self.running_since = None;
+        self.elapsed_ms = 0.0;
+        self.paused_at = None;
+        self.pause_count = 0;
+        self.paused_ms = 0.0;
+        self.state = EngineState::Waiting { mode };
+
+        Some(self.schedule())
+    }
+
+    pub fn abort(&mut self) {
+        self.state = EngineState::Aborted;
+    }
+
+    /// Show the first symbol after the lead-in and start the block's clock.
+    /// Returns how long the block has left to run, for its end timer.
+    pub fn begin(&mut self, timestamp: InstantStamp) -> Option<u64> {
+        let EngineState::Waiting { mode } = self.state else {
+            return None;
+        };
+
+        match self.items.last_mut() {
+            // Resuming: the voided symbol is shown again.
+            Some(item) if !item.is_completed() => item.presented_at = Some(timestamp),
+            _ => self.push_item(timestamp),
+        }
+        self.running_since = Some(timestamp);
+        self.state = EngineState::Active { mode };
+
+        let remaining = self.config.block_duration_ms(mode) as f64 - self.elapsed_ms;
+        Some(remaining.max(0.0).round() as u64)
+    }
+
+    /// Score an answer to the symbol on screen and put up the next one.
+    /// Answers outside a running block are ignored.
+    pub fn register_response(&mut self, digit: u8, timestamp: InstantStamp) -> ResponseOutcome {
+        if !matches!(self.state, EngineState::Active { .. }) {
+            return ResponseOutcome::Ignored;
+        }
+        let Some(item) = self.items.last_mut() else {
+            return ResponseOutcome::Ignored;
+        };
+        let Some(onset) = item.presented_at else {
+            return ResponseOutcome::Ignored;
+        };
+
+        let rt_ms = timing::duration_ms(onset, timestamp);
+        let correct = digit == item.digit;
+        item.response = Some(ItemResponse {
+            timestamp,
+            rt_ms,
+            digit,
+        });
+        item.outcome = if correct {
+            ItemOutcome::Correct { rt_ms }
+        } else {
+            ItemOutcome::Error { rt_ms }
+        };
+        let item_index = item.index;
+
+        self.push_item(timestamp);
+        ResponseOutcome::Recorded {
+            item_index,
+            correct,
+        }
+    }
+
+    /// End the block when its time is up. The symbol left unanswered is
+    /// dropped. Returns the finished block's mode, or `None` if no block was
+    /// running (e.g. a stale timer from before a pause).
+    pub fn finish(&mut self) -> Option<RunMode> {
+        let EngineState::Active { mode } = self.state else {
+            return None;
+        };
+
+        self.items.retain(|item| item.is_completed());
+        self.running_since = None;
+        self.state = EngineState::Completed { mode };
+        let metrics = DsstMetrics::from_items(
+            &self.items,
+            self.config.block_duration_ms(mode) as f64,
+            self.config.symbol_set,
+        );
+        match mode {
+            RunMode::Practice => self.last_practice_metrics = Some(metrics),
+            RunMode::Main => self.last_main_metrics = Some(metrics),
+        }
+        Some(mode)
+    }
+
+    /// Pause a running block. Its clock stops and the symbol on screen is
+    /// voided so it can be shown again on resume; bumping `run_id` strands the
+    /// block's end timer.
+    pub fn pause(&mut self) -> bool {
+        let mode = match self.state {
+            EngineState::Waiting { mode } | EngineState::Active { mode } => mode,
+            _ => return false,
+        };
+
+        let now = timing::now();
+        if let Some(since) = self.running_since.take() {
+            self.elapsed_ms += timing::duration_ms(since, now);
+        }
+        if let Some(item) = self.items.last_mut() {
+            if !item.is_completed() {
+                item.presented_at = None;
+            }
+        }
+        self.run_id = self.run_id.wrapping_add(1);
+        self.paused_at = Some(now);
+        self.pause_count = self.pause_count.saturating_add(1);
+        self.state = EngineState::Paused { mode };
+        true
+    }
+
+    /// Resume a paused block after the lead-in, with the time it had left.
+    pub fn resume(&mut self) -> Option<BlockSchedule> {
+        let EngineState::Paused { mode } = self.state else {
+            return None;
+        };
+
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_ms += timing::elapsed_ms(paused_at);
+        }
+        self.state = EngineState::Waiting { mode };
+        Some(self.schedule())
+    }
+
+    /// Times the current run was paused.
+    pub fn pause_count(&self) -> u32 {
+        self.pause_count
+    }
+
+    /// Total time the current run has spent paused, including a pause in
+    /// progress.
+    pub fn paused_ms(&self) -> f64 {
+        self.paused_ms + self.paused_at.map(timing::elapsed_ms).unwrap_or_default()
+    }
+
+    fn is_running(&self) -> bool {
+        matches!(
+            self.state,
+            EngineState::Waiting { .. } | EngineState::Active { .. } | EngineState::Paused { .. }
+        )
+    }
+
+    fn schedule(&self) -> BlockSchedule {
+        BlockSchedule {
+            run_id: self.run_id,
+            lead_in_ms: self.config.lead_in_ms,
+        }
+    }
+
+    /// Put up a new symbol, never the same one twice running.
+    fn push_item(&mut self, timestamp: InstantStamp) {
+        let previous = self.items.last().map(|item| item.digit);
+        let digit = loop {
+            let candidate = *[1u8, 2, 3, 4, 5, 6, 7, 8, 9]
+                .choose(&mut self.rng)
+                .unwrap_or(&1);
+            if Some(candidate) != previous {
+                break candidate;
+            }
+        };
+        self.items.push(DsstItem {
+            index: self.items.len(),
+            symbol: self.key[usize::from(digit) - 1],
+            digit,
+            presented_at: Some(timestamp),
+            response: None,
+            outcome: ItemOutcome::Pending,
+        });
+    }
+
+    fn seeded_rng(&self, mode: RunMode) -> StdRng {
+        let combined_seed = self.config.seed ^ mode.seed_tag() ^ self.run_id;
+        StdRng::seed_from_u64(combined_seed)
+    }
+}
+
+impl Default for DsstEngine {
+    fn default() -> Self {
+        Self::new(DsstConfig::default())
+    }
+}
+
+/// Shuffle the configured symbol set into a key. Practice and main blocks of
+/// a session share it.
+fn build_key(config: &DsstConfig) -> [&'static str; 9] {
+    let mut key = SYMBOL_SETS[config.symbol_set % SYMBOL_SETS.len()];
+    let mut rng = StdRng::seed_from_u64(config.seed ^ KEY_SEED_TAG);
+    key.shuffle(&mut rng);
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_rotate_symbol_sets_and_reshuffle_the_key() {
+        let first = DsstEngine::new(DsstConfig::for_session(0));
+        let again = DsstEngine::new(DsstConfig::for_session(0));
+        assert_eq!(first.key(), again.key());
+
+        let second = DsstEngine::new(DsstConfig::for_session(1));
+        let fourth = DsstEngine::new(DsstConfig::for_session(3));
+        assert!(second.key().iter().all(|s| SYMBOL_SETS[1].contains(s)));
+        assert!(fourth.key().iter().all(|s| SYMBOL_SETS[0].contains(s)));
+        assert_ne!(first.key(), fourth.key());
+
+        let mut sorted = *first.key();
+        sorted.sort_unstable();
+        let mut set = SYMBOL_SETS[0];
+        set.sort_unstable();
+        assert_eq!(sorted, set);
+    }
+
+    #[test]
+    fn answers_advance_and_the_timer_ends_the_block() {
+        let mut engine = DsstEngine::default();
+        engine.start(RunMode::Main).expect("schedule");
+        assert!(matches!(
+            engine.register_response(1, timing::now()),
+            ResponseOutcome::Ignored
+        ));
+        assert_eq!(engine.begin(timing::now()), Some(90_000));
+
+        for round in 0..4 {
+            let item = engine.current_item().expect("symbol on screen").clone();
+            assert_eq!(engine.key()[usize::from(item.digit) - 1], item.symbol);
+            let answer = if round == 3 {
+                item.digit % 9 + 1
+            } else {
+                item.digit
+            };
+            let ResponseOutcome::Recorded {
+                item_index,
+                correct,
+            } = engine.register_response(answer, timing::now())
+            else {
+                panic!("response ignored");
+            };
+            assert_eq!((item_index, correct), (round, round != 3));
+            let next = engine.current_item().expect("next symbol");
+            assert_ne!(next.digit, item.digit);
+        }
+
+        assert_eq!(engine.finish(), Some(RunMode::Main));
+        assert_eq!(engine.finish(), None);
+        assert_eq!(engine.items().len(), 4);
+
+        let metrics = engine.main_metrics().expect("main metrics");
+        assert_eq!((metrics.correct, metrics.errors), (3, 1));
+        assert!((metrics.correct_per_minute - 2.0).abs() < 1e-9);
+        let outcomes: Vec<_> = engine
+            .trial_samples()
+            .iter()
+            .map(|s| s.outcome.clone())
+            .collect();
+        assert_eq!(outcomes, ["correct", "correct", "correct", "error"]);
+    }
+
+    #[test]
+    fn pausing_stops_the_clock_and_replays_the_symbol() {
+        let mut engine = DsstEngine::default();
+        let first = engine.start(RunMode::Main).expect("schedule");
+        engine.begin(timing::now());
+        let shown = engine.current_item().expect("symbol").symbol;
+
+        assert!(engine.pause());
+        assert!(!engine.pause());
+        assert!(engine.start(RunMode::Main).is_none());
+        assert_ne!(engine.run_id, first.run_id);
+        // The end timer armed before the pause no longer ends the block.
+        assert_eq!(engine.finish(), None);
+
+        let resumed = engine.resume().expect("reschedule");
+        assert_eq!(resumed.lead_in_ms, engine.config.lead_in_ms);
+        let remaining = engine.begin(timing::now()).expect("running");
+        assert!(remaining <= 90_000);
+        assert_eq!(engine.items().len(), 1);
+        assert_eq!(engine.current_item().expect("symbol").symbol, shown);
+        assert_eq!(engine.pause_count(), 1);
+    }
+}
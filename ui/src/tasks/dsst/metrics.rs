@@ -0,0 +1,147 @@
+//! Metric definitions and aggregation helpers for DSST runs.
+//!
+//! The headline score is correct responses per minute of the block, which
+//! folds speed and accuracy into one processing-speed figure. Reaction times
+//! are over correct responses only.
+
+use serde::{Deserialize, Serialize};
+
+use super::engine::{DsstItem, ItemOutcome};
+
+/// Answers a run needs before its score is taken as a fair sample.
+const MIN_RESPONSES: u32 = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DsstMetrics {
+    /// Which alternate symbol set the key was drawn from.
+    pub symbol_set: usize,
+    pub duration_ms: f64,
+    pub responses: u32,
+    pub correct: u32,
+    pub errors: u32,
+    pub correct_per_minute: f64,
+    pub accuracy: f64,
+    pub mean_rt_ms: f64,
+    pub median_rt_ms: f64,
+    pub meets_min_trial_requirement: bool,
+}
+
+impl DsstMetrics {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Score the answered `items` of a block that ran for `duration_ms`.
+    pub fn from_items(items: &[DsstItem], duration_ms: f64, symbol_set: usize) -> Self {
+        let mut errors = 0u32;
+        let mut rts = Vec::new();
+
+        for item in items {
+            match item.outcome {
+                ItemOutcome::Correct { rt_ms } => rts.push(rt_ms),
+                ItemOutcome::Error { .. } => errors = errors.saturating_add(1),
+                ItemOutcome::Pending => {}
+            }
+        }
+
+        rts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let correct = rts.len() as u32;
+        let responses = correct + errors;
+        let minutes = duration_ms / 60_000.0;
+
+        Self {
+            symbol_set,
+            duration_ms,
+            responses,
+            correct,
+            errors,
+            correct_per_minute: if minutes > 0.0 {
+                correct as f64 / minutes
+            } else {
+                0.0
+            },
+            accuracy: if responses > 0 {
+                correct as f64 / responses as f64
+            } else {
+                0.0
+            },
+            mean_rt_ms: mean(&rts),
+            median_rt_ms: percentile(&rts, 0.5),
+            meets_min_trial_requirement: responses >= MIN_RESPONSES,
+        }
+    }
+}
+
+fn mean(data: &[f64]) -> f64 {
+    if data.is_empty() {
+        0.0
+    } else {
+        data.iter().sum::<f64>() / data.len() as f64
+    }
+}
+
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let clamped_pct = pct.clamp(0.0, 1.0);
+    let rank = clamped_pct * (sorted.len() as f64 - 1.0);
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    if lower == upper {
+        sorted[lower]
+    } else {
+        let weight = rank - lower as f64;
+        sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(index: usize, outcome: ItemOutcome) -> DsstItem {
+        DsstItem {
+            index,
+            symbol: "○",
+            digit: 1,
+            presented_at: None,
+            response: None,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn scores_correct_per_minute_over_the_block() {
+        let items = vec![
+            item(0, ItemOutcome::Correct { rt_ms: 900.0 }),
+            item(1, ItemOutcome::Correct { rt_ms: 1_100.0 }),
+            item(2, ItemOutcome::Error { rt_ms: 700.0 }),
+            item(3, ItemOutcome::Correct { rt_ms: 1_300.0 }),
+        ];
+        let metrics = DsstMetrics::from_items(&items, 90_000.0, 2);
+
+        assert_eq!(
+            (metrics.responses, metrics.correct, metrics.errors),
+            (4, 3, 1)
+        );
+        assert!((metrics.correct_per_minute - 2.0).abs() < 1e-9);
+        assert!((metrics.accuracy - 0.75).abs() < 1e-9);
+        // The error's RT is left out.
+        assert!((metrics.median_rt_ms - 1_100.0).abs() < 1e-9);
+        assert!((metrics.mean_rt_ms - 1_100.0).abs() < 1e-9);
+        assert_eq!(metrics.symbol_set, 2);
+        assert!(!metrics.meets_min_trial_requirement);
+
+        let empty = DsstMetrics::from_items(&[], 0.0, 0);
+        assert_eq!(empty.correct_per_minute, 0.0);
+        assert_eq!(empty.accuracy, 0.0);
+    }
+}
@@ -0,0 +1,8 @@
+mod engine;
+pub use engine::DsstEngine;
+
+mod metrics;
+pub use metrics::DsstMetrics;
+
+mod view;
+pub use view::DsstView;
@@ -0,0 +1,566 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
+
+use crate::core::format;
+use crate::core::qc::QualityFlags;
+use crate::core::readiness::{self, Readiness};
+use crate::core::store::{use_shared_store, SharedStore};
+use crate::core::{platform, storage, timing};
+
+use super::engine::{
+    BlockSchedule, DsstConfig, DsstEngine, EngineState, ItemOutcome, ResponseOutcome, RunMode,
+};
+use super::metrics::DsstMetrics;
+
+const FEEDBACK_HOLD_MS: u64 = 400;
+
+#[component]
+pub fn DsstView() -> Element {
+    // Subscribe to global language code so instructional section re-renders on locale switch.
+    let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
+    let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
+
+    let store = use_shared_store();
+    let engine = {
+        let store = store.clone();
+        use_signal(move || DsstEngine::new(session_config(&store)))
+    };
+    let qc_flags = use_signal(QualityFlags::pristine);
+    let practice_metrics = use_signal(|| Option::<DsstMetrics>::None);
+    let last_metrics = use_signal(|| Option::<DsstMetrics>::None);
+    let last_error = use_signal(|| Option::<String>::None);
+    let feedback_state = use_signal(|| Option::<FeedbackState>::None);
+    let mut readiness_info = use_signal(|| current_readiness(&store));
+
+    let sender_slot: Rc<RefCell<Option<UnboundedSender<DsstEvent>>>> = Rc::new(RefCell::new(None));
+    let sender_slot_for_loop = sender_slot.clone();
+
+    let coroutine = {
+        let engine_ref = engine;
+        let store_ref = store.clone();
+        let qc_ref = qc_flags;
+        let practice_ref = practice_metrics;
+        let last_metrics_ref = last_metrics;
+        let error_ref = last_error;
+        let feedback_ref = feedback_state;
+
+        use_coroutine(move |mut rx: UnboundedReceiver<DsstEvent>| {
+            let sender_slot = sender_slot_for_loop.clone();
+            let store = store_ref.clone();
+            let mut engine = engine_ref;
+            let mut qc_flags = qc_ref;
+            let mut practice_metrics = practice_ref;
+            let mut last_metrics = last_metrics_ref;
+            let mut last_error = error_ref;
+            let mut feedback_signal = feedback_ref;
+
+            async move {
+                while let Some(event) = rx.next().await {
+                    match event {
+                        DsstEvent::StartPractice => {
+                            practice_metrics.set(None);
+                            last_error.set(None);
+                            feedback_signal.set(None);
+                            if let Some(schedule) =
+                                engine.with_mut(|eng| eng.start(RunMode::Practice))
+                            {
+                                queue_block(sender_slot.clone(), schedule);
+                            }
+                        }
+                        DsstEvent::StartMain => {
+                            last_metrics.set(None);
+                            last_error.set(None);
+                            qc_flags.set(QualityFlags::pristine());
+                            feedback_signal.set(None);
+                            if let Some(schedule) = engine.with_mut(|eng| eng.start(RunMode::Main))
+                            {
+                                queue_block(sender_slot.clone(), schedule);
+                            }
+                        }
+                        DsstEvent::Abort => {
+                            engine.with_mut(|eng| eng.abort());
+                        }
+                        DsstEvent::Pause => {
+                            if engine.with_mut(|eng| eng.pause()) {
+                                feedback_signal.set(None);
+                            }
+                        }
+                        DsstEvent::Resume => {
+                            if let Some(schedule) = engine.with_mut(|eng| eng.resume()) {
+                                queue_block(sender_slot.clone(), schedule);
+                            }
+                        }
+                        DsstEvent::BlockReady { run_id } => {
+                            let remaining_ms = engine.with_mut(|eng| {
+                                if eng.run_id == run_id {
+                                    eng.begin(timing::now())
+                                } else {
+                                    None
+                                }
+                            });
+
+                            if let Some(remaining_ms) = remaining_ms {
+                                queue_time_up(sender_slot.clone(), run_id, remaining_ms);
+                            }
+                        }
+                        DsstEvent::Respond { digit, timestamp } => {
+                            let (response, run_id, mode) = engine.with_mut(|eng| {
+                                let mode = match eng.state {
+                                    EngineState::Active { mode } => Some(mode),
+                                    _ => None,
+                                };
+                                (eng.register_response(digit, timestamp), eng.run_id, mode)
+                            });
+
+                            // Only the practice block gives feedback.
+                            if let (
+                                ResponseOutcome::Recorded { correct, .. },
+                                Some(RunMode::Practice),
+                            ) = (response, mode)
+                            {
+                                let feedback = if correct {
+                                    FeedbackState::new(
+                                        crate::t!("dsst-feedback-correct"),
+                                        FeedbackTone::Positive,
+                                    )
+                                } else {
+                                    FeedbackState::new(
+                                        crate::t!("dsst-feedback-error"),
+                                        FeedbackTone::Negative,
+                                    )
+                                };
+                                feedback_signal.set(Some(feedback));
+                                schedule_feedback_clear(
+                                    sender_slot.clone(),
+                                    run_id,
+                                    FEEDBACK_HOLD_MS,
+                                );
+                            }
+                        }
+                        DsstEvent::TimeUp { run_id } => {
+                            let finished = engine.with_mut(|eng| {
+                                if eng.run_id == run_id {
+                                    eng.finish()
+                                } else {
+                                    None
+                                }
+                            });
+
+                            if let Some(mode) = finished {
+                                feedback_signal.set(None);
+                                let recorded = finalize_run(
+                                    &store,
+                                    mode,
+                                    &engine,
+                                    qc_flags,
+                                    practice_metrics,
+                                    last_metrics,
+                                    last_error,
+                                );
+                                if recorded {
+                                    readiness_info.set(current_readiness(&store));
+                                    // The next session gets a fresh symbol set and key.
+                                    let config = session_config(&store);
+                                    engine.with_mut(|eng| eng.reconfigure(config));
+                                }
+                            }
+                        }
+                        DsstEvent::FocusLost => {
+                            if engine.with(|eng| matches!(eng.state, EngineState::Paused { .. })) {
+                                continue;
+                            }
+                            qc_flags.with_mut(|flags| {
+                                flags.log_focus_loss();
+                                flags.log_visibility_blur();
+                            });
+                        }
+                        DsstEvent::ClearFeedback { run_id } => {
+                            let current_run = engine.with(|eng| eng.run_id);
+                            if current_run == run_id {
+                                feedback_signal.set(None);
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    };
+
+    sender_slot.borrow_mut().replace(coroutine.tx());
+
+    let send_event = {
+        let coroutine_handle = coroutine;
+        move |event: DsstEvent| coroutine_handle.send(event)
+    };
+
+    let respond = {
+        let send_event_handle = send_event;
+        move |digit: u8| {
+            send_event_handle(DsstEvent::Respond {
+                digit,
+                timestamp: timing::now(),
+            });
+        }
+    };
+
+    let engine_snapshot = engine();
+
+    let active_mode = match engine_snapshot.state {
+        EngineState::Waiting { mode }
+        | EngineState::Active { mode }
+        | EngineState::Paused { mode } => Some(mode),
+        _ => None,
+    };
+    let current_symbol = engine_snapshot.current_item().map(|item| item.symbol);
+    let key: Vec<(u8, &'static str)> = (1u8..=9)
+        .zip(engine_snapshot.key().iter().copied())
+        .collect();
+
+    let is_running = active_mode.is_some();
+    let is_paused = matches!(engine_snapshot.state, EngineState::Paused { .. });
+    let correct_so_far = engine_snapshot
+        .items()
+        .iter()
+        .filter(|item| matches!(item.outcome, ItemOutcome::Correct { .. }))
+        .count();
+
+    let last_practice = practice_metrics();
+    let latest_metrics = last_metrics();
+    let error_message_cloned = last_error();
+    let feedback = feedback_state();
+
+    let mode_label = active_mode
+        .map(|mode| match mode {
+            RunMode::Practice => crate::t!("dsst-mode-practice"),
+            RunMode::Main => crate::t!("dsst-mode-main"),
+        })
+        .unwrap_or_default();
+
+    rsx! {
+        article { class: "task task-dsst",
+
+            if is_running {
+                section { class: "task-card task-card--canvas task-dsst__canvas",
+
+                    button {
+                        class: "button button--ghost button--compact task-canvas__cancel",
+                        onclick: move |_| send_event(DsstEvent::Abort),
+                        {crate::t!("common-cancel")}
+                    }
+
+                    if !mode_label.is_empty() {
+                        div { class: "task-mode-badge", "{mode_label}" }
+                    }
+
+                    if is_paused {
+                        div { class: "task-canvas__paused",
+                            p { {crate::t!("task-paused-note")} }
+                            button {
+                                r#type: "button",
+                                class: "button button--primary",
+                                autofocus: true,
+                                onclick: move |_| send_event(DsstEvent::Resume),
+                                {crate::t!("common-resume")}
+                            }
+                        }
+                    } else {
+                        button {
+                            class: "button button--ghost button--compact task-canvas__pause",
+                            // Keep focus on the stage so pausing is not logged as a focus loss.
+                            onmousedown: move |evt| evt.prevent_default(),
+                            onclick: move |_| send_event(DsstEvent::Pause),
+                            {crate::t!("common-pause")}
+                        }
+
+                        div {
+                            class: "task-dsst__stage",
+                            tabindex: "0",
+                            autofocus: true,
+                            onkeydown: move |evt| {
+                                let key = evt.key().to_string();
+                                let Some(digit) = key.parse::<u8>().ok().filter(|d| (1..=9).contains(d)) else {
+                                    return;
+                                };
+                                evt.prevent_default();
+                                respond(digit);
+                            },
+                            onfocusout: move |_| send_event(DsstEvent::FocusLost),
+
+                            ul { class: "task-dsst__key", aria_label: crate::t!("dsst-aria-key"),
+                                for (digit , symbol) in key.iter().copied() {
+                                    li { key: "{digit}",
+                                        span { class: "task-dsst__key-symbol", "{symbol}" }
+                                        span { class: "task-dsst__key-digit", "{digit}" }
+                                    }
+                                }
+                            }
+
+                            div { class: "task-dsst__symbol",
+                                if let Some(symbol) = current_symbol {
+                                    "{symbol}"
+                                } else {
+                                    span { class: "task-dsst__ready", {crate::t!("dsst-get-ready")} }
+                                }
+                            }
+
+                            div { class: "task-dsst__keypad",
+                                for digit in 1u8..=9 {
+                                    button {
+                                        key: "{digit}",
+                                        r#type: "button",
+                                        class: "button task-dsst__digit",
+                                        // Answering by tap must not pull focus off the stage.
+                                        onmousedown: move |evt| evt.prevent_default(),
+                                        onclick: move |_| respond(digit),
+                                        "{digit}"
+                                    }
+                                }
+                            }
+
+                            if let Some(feedback) = feedback.clone() {
+                                div { class: format!("task-feedback {}", feedback.css_class()), "{feedback.message}" }
+                            }
+                        }
+                    }
+
+                    div { class: "task-progress task-progress--overlay",
+                        span { {crate::t!("dsst-progress-correct")} }
+                        span { class: "task-progress__value", "{correct_so_far}" }
+                    }
+                }
+            } else {
+                // Readiness advisory banner (non-blocking)
+                {
+                    let r: Readiness = readiness_info();
+                    rsx! {
+                        section { class: format!("task-readiness {}", r.css_class()),
+                            span { class: "task-readiness__status", "{r.status_label()}" }
+                            span { class: "task-readiness__detail", "{r.detail_message()}" }
+                        }
+                    }
+                }
+                section { class: "task-card task-card--instructions task-dsst__controls",
+                    // Hidden i18n marker to force re-render of instruction copy when locale changes
+                    div { style: "display:none", "{_lang_marker}" }
+                    h3 { {crate::t!("dsst-how-summary")} }
+                    ul {
+                        li { {crate::t!("dsst-how-step-rule")} }
+                        li { {crate::t!("dsst-how-step-keys")} }
+                        li { {crate::t!("dsst-how-step-timing")} }
+                        li { {crate::t!("dsst-how-step-practice")} }
+                    }
+
+                    div { class: "task-cta", style: "display:flex; gap:0.75rem; flex-wrap:wrap;",
+                        button {
+                            class: "button button--accent",
+                            onclick: move |_| send_event(DsstEvent::StartPractice),
+                            {crate::t!("dsst-start-practice")}
+                        }
+                        button {
+                            class: "button button--primary",
+                            onclick: move |_| send_event(DsstEvent::StartMain),
+                            {crate::t!("dsst-start-main")}
+                        }
+                    }
+                }
+
+                if let Some(metrics) = last_practice {
+                    section { class: "task-card task-card--subtle task-dsst__practice-summary",
+                        h3 { {crate::t!("dsst-practice-recap")} }
+                        p {
+                            {crate::t!("dsst-metric-correct")} " " {metrics.correct.to_string()}
+                            " • " {crate::t!("dsst-metric-errors")} " " {metrics.errors.to_string()}
+                        }
+                    }
+                }
+
+                if let Some(metrics) = latest_metrics {
+                    section { class: "task-card task-dsst__metrics",
+                        h3 { {crate::t!("dsst-last-session")} }
+                        ul { class: "metrics-grid",
+                            li { {crate::t!("dsst-metric-per-minute")} ": " {format::format_number(metrics.correct_per_minute, 1)} }
+                            li { {crate::t!("dsst-metric-correct")} ": " {metrics.correct.to_string()} }
+                            li { {crate::t!("dsst-metric-errors")} ": " {metrics.errors.to_string()} }
+                            li { {crate::t!("dsst-metric-accuracy")} ": " {(metrics.accuracy * 100.0).round().to_string()} "%" }
+                            li { {crate::t!("dsst-metric-median-rt")} ": " {format::format_ms(metrics.median_rt_ms).to_string()} }
+                        }
+                    }
+                } else {
+                    section { class: "task-card task-dsst__metrics task-metrics--placeholder",
+                        p { {crate::t!("dsst-metrics-placeholder")} }
+                    }
+                }
+
+                if let Some(err) = error_message_cloned {
+                    div { class: "task-error", {crate::t!("dsst-error-generic", message = err.clone())} }
+                }
+            }
+        }
+    }
+}
+
+fn finalize_run(
+    store: &SharedStore,
+    mode: RunMode,
+    engine: &Signal<DsstEngine>,
+    mut qc_flags: Signal<QualityFlags>,
+    mut practice_metrics: Signal<Option<DsstMetrics>>,
+    mut last_metrics: Signal<Option<DsstMetrics>>,
+    mut last_error: Signal<Option<String>>,
+) -> bool {
+    match mode {
+        RunMode::Practice => {
+            if let Some(metrics) = engine.with(|eng| eng.practice_metrics()) {
+                practice_metrics.set(Some(metrics));
+            }
+            false
+        }
+        RunMode::Main => {
+            let Some(metrics) = engine.with(|eng| eng.main_metrics()) else {
+                return false;
+            };
+            let (pause_count, paused_ms) = engine.with(|eng| (eng.pause_count(), eng.paused_ms()));
+            qc_flags.with_mut(|flags| {
+                flags.mark_min_trials(metrics.meets_min_trial_requirement);
+                flags.record_pauses(pause_count, paused_ms);
+            });
+            let qc_snapshot = qc_flags();
+            match serde_json::to_value(&metrics) {
+                Ok(metrics_json) => {
+                    let record = storage::SummaryRecord::new("dsst", metrics_json, qc_snapshot);
+                    let trials = if storage::keep_trials() {
+                        engine.with(|eng| eng.trial_samples())
+                    } else {
+                        Vec::new()
+                    };
+                    if let Err(err) = storage::append_summary(store, &record, &trials) {
+                        last_error.set(Some(format!("Failed to persist summary: {err}")));
+                    } else {
+                        last_error.set(None);
+                    }
+                    last_metrics.set(Some(metrics));
+                    true
+                }
+                Err(err) => {
+                    last_error.set(Some(format!("Failed to serialise metrics: {err}")));
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Cooldown advisory from the most recent stored DSST run.
+fn current_readiness(store: &SharedStore) -> Readiness {
+    match storage::load_summaries(store) {
+        Ok(mut records) => {
+            records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            let last = records.iter().find(|r| r.task == "dsst");
+            readiness::evaluate("dsst", last)
+        }
+        Err(_) => readiness::evaluate("dsst", None),
+    }
+}
+
+/// The key for the user's next session: one on from the runs already stored.
+fn session_config(store: &SharedStore) -> DsstConfig {
+    let completed = storage::load_summaries(store)
+        .map(|records| records.iter().filter(|r| r.task == "dsst").count())
+        .unwrap_or_default();
+    DsstConfig::for_session(completed)
+}
+
+fn queue_block(
+    sender_slot: Rc<RefCell<Option<UnboundedSender<DsstEvent>>>>,
+    schedule: BlockSchedule,
+) {
+    if let Some(sender) = sender_slot.borrow().as_ref().cloned() {
+        platform::spawn_future(async move {
+            timing::sleep_ms(schedule.lead_in_ms).await;
+            let _ = sender.unbounded_send(DsstEvent::BlockReady {
+                run_id: schedule.run_id,
+            });
+        });
+    }
+}
+
+fn queue_time_up(
+    sender_slot: Rc<RefCell<Option<UnboundedSender<DsstEvent>>>>,
+    run_id: u64,
+    wait_ms: u64,
+) {
+    if let Some(sender) = sender_slot.borrow().as_ref().cloned() {
+        platform::spawn_future(async move {
+            timing::sleep_ms(wait_ms).await;
+            let _ = sender.unbounded_send(DsstEvent::TimeUp { run_id });
+        });
+    }
+}
+
+fn schedule_feedback_clear(
+    sender_slot: Rc<RefCell<Option<UnboundedSender<DsstEvent>>>>,
+    run_id: u64,
+    wait_ms: u64,
+) {
+    if let Some(sender) = sender_slot.borrow().as_ref().cloned() {
+        platform::spawn_future(async move {
+            timing::sleep_ms(wait_ms).await;
+            let _ = sender.unbounded_send(DsstEvent::ClearFeedback { run_id });
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+enum DsstEvent {
+    StartPractice,
+    StartMain,
+    Abort,
+    Pause,
+    Resume,
+    BlockReady {
+        run_id: u64,
+    },
+    TimeUp {
+        run_id: u64,
+    },
+    Respond {
+        digit: u8,
+        timestamp: crate::core::timing::InstantStamp,
+    },
+    FocusLost,
+    ClearFeedback {
+        run_id: u64,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct FeedbackState {
+    message: String,
+    tone: FeedbackTone,
+}
+
+impl FeedbackState {
+    fn new<M: Into<String>>(message: M, tone: FeedbackTone) -> Self {
+        Self {
+            message: message.into(),
+            tone,
+        }
+    }
+
+    fn css_class(&self) -> &'static str {
+        match self.tone {
+            FeedbackTone::Positive => "task-feedback--positive",
+            FeedbackTone::Negative => "task-feedback--negative",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FeedbackTone {
+    Positive,
+    Negative,
+}
@@ -0,0 +1,597 @@
+//! Engine managing the Eriksen flanker schedule and response tracking.
+//!
+//! Each trial shows a row of five arrows and the participant reports the
+//! direction of the middle one. Congruent rows flank it with arrows pointing
+//! the same way (`←←←←←`), incongruent rows with the opposite (`←←→←←`). The
+//! seeded schedule places the configured share of congruent trials, with as
+//! many left as right targets in each condition. A trial ends at the first
+//! response or when its response window closes.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::core::storage::TrialSample;
+use crate::core::timing::{self, InstantStamp};
+
+use super::metrics::FlankerMetrics;
+
+/// Arrows either side of the target.
+const FLANKERS_PER_SIDE: usize = 2;
+
+/// Different run phases for the flanker engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunMode {
+    Practice,
+    Main,
+}
+
+impl RunMode {
+    fn seed_tag(self) -> u64 {
+        match self {
+            RunMode::Practice => 0x46_4c50_5241_4354_u64, // "FLPRACT"
+            RunMode::Main => 0x46_4c4d_4149_4e52_u64,     // "FLMAINR"
+        }
+    }
+}
+
+/// Which way an arrow (or a response) points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Left => '←',
+            Direction::Right => '→',
+        }
+    }
+
+    fn opposite(self) -> Self {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// Public configuration knobs for the task.
+#[derive(Debug, Clone)]
+pub struct FlankerConfig {
+    pub total_trials: usize,
+    pub practice_trials: usize,
+    /// Share of the trials whose flankers match the target.
+    pub congruent_ratio: f32,
+    /// Blank (fixation) gap between a trial's end and the next row.
+    pub interstimulus_interval_ms: u64,
+    pub lead_in_ms: u64,
+    /// How long a row stays up waiting for an answer before it is a miss.
+    pub response_window_ms: u64,
+    pub seed: u64,
+}
+
+impl Default for FlankerConfig {
+    fn default() -> Self {
+        Self {
+            total_trials: 48,
+            practice_trials: 8,
+            congruent_ratio: 0.5,
+            interstimulus_interval_ms: 1_000,
+            lead_in_ms: 750,
+            response_window_ms: 1_500,
+            seed: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineState {
+    Idle,
+    Waiting { mode: RunMode, trial_index: usize },
+    StimulusActive { mode: RunMode, trial_index: usize },
+    Paused { mode: RunMode, trial_index: usize },
+    Completed { mode: RunMode },
+    Aborted,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlankerTrial {
+    pub index: usize,
+    /// Direction of the middle arrow: the correct answer.
+    pub target: Direction,
+    pub congruent: bool,
+    pub presented_at: Option<InstantStamp>,
+    pub response: Option<TrialResponse>,
+    pub outcome: TrialOutcome,
+}
+
+impl FlankerTrial {
+    fn new(index: usize, target: Direction, congruent: bool) -> Self {
+        Self {
+            index,
+            target,
+            congruent,
+            presented_at: None,
+            response: None,
+            outcome: TrialOutcome::Pending,
+        }
+    }
+
+    /// The row of arrows shown, e.g. `←←→←←`.
+    pub fn stimulus(&self) -> String {
+        let flanker = if self.congruent {
+            self.target
+        } else {
+            self.target.opposite()
+        };
+        let side: String = std::iter::repeat_n(flanker.arrow(), FLANKERS_PER_SIDE).collect();
+        format!("{side}{}{side}", self.target.arrow())
+    }
+
+    pub fn is_completed(&self) -> bool {
+        !matches!(self.outcome, TrialOutcome::Pending)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrialResponse {
+    pub timestamp: InstantStamp,
+    pub rt_ms: f64,
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrialOutcome {
+    Pending,
+    Correct { rt_ms: f64 },
+    Error { rt_ms: f64 },
+    Miss,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduledStimulus {
+    pub run_id: u64,
+    pub trial_index: usize,
+    pub wait_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduledAdvance {
+    pub run_id: u64,
+    pub trial_index: usize,
+    pub wait_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrialSchedule {
+    pub stimulus: ScheduledStimulus,
+    pub advance: ScheduledAdvance,
+}
+
+#[derive(Debug, Clone)]
+pub enum AdvanceOutcome {
+    Next(TrialSchedule),
+    Completed { mode: RunMode },
+    Ignored,
+}
+
+#[derive(Debug, Clone)]
+pub enum ResponseOutcome {
+    /// The answer was scored; the trial should advance straight away.
+    Recorded { trial_index: usize, correct: bool },
+    Ignored,
+}
+
+#[derive(Debug, Clone)]
+pub struct FlankerEngine {
+    pub config: FlankerConfig,
+    pub state: EngineState,
+    pub run_id: u64,
+    trials: Vec<FlankerTrial>,
+    last_practice_metrics: Option<FlankerMetrics>,
+    last_main_metrics: Option<FlankerMetrics>,
+    paused_at: Option<InstantStamp>,
+    pause_count: u32,
+    paused_ms: f64,
+}
+
+impl FlankerEngine {
+    pub fn new(config: FlankerConfig) -> Self {
+        Self {
+            config,
+            state: EngineState::Idle,
+            run_id: 0,
+            trials: Vec::new(),
+            last_practice_metrics: None,
+            last_main_metrics: None,
+            paused_at: None,
+            pause_count: 0,
+            paused_ms: 0.0,
+        }
+    }
+
+    pub fn practice_metrics(&self) -> Option<FlankerMetrics> {
+        self.last_practice_metrics.clone()
+    }
+
+    pub fn main_metrics(&self) -> Option<FlankerMetrics> {
+        self.last_main_metrics.clone()
+    }
+
+    pub fn trials(&self) -> &[FlankerTrial] {
+        &self.trials
+    }
+
+    /// The last run's finished trials, for keeping with it; times are from its
+    /// first stimulus. The stimulus string carries both the target and the
+    /// condition.
+    pub fn trial_samples(&self) -> Vec<TrialSample> {
+        let Some(first) = self.trials.iter().find_map(|trial| trial.presented_at) else {
+            return Vec::new();
+        };
+        self.trials
+            .iter()
+            .filter_map(|trial| {
+                let outcome = match trial.outcome {
+                    TrialOutcome::Pending => return None,
+                    TrialOutcome::Correct { .. } => "correct",
+                    TrialOutcome::Error { .. } => "error",
+                    TrialOutcome::Miss => "miss",
+                };
+                let wait_ms = if trial.index == 0 {
+                    self.config.lead_in_ms
+                } else {
+                    self.config.interstimulus_interval_ms
+                };
+                Some(TrialSample {
+                    index: trial.index,
+                    iti_ms: Some(wait_ms as f64),
+                    onset_ms: trial.presented_at.map(|at| timing::duration_ms(first, at)),
+                    response_ms: trial
+                        .response
+                        .as_ref()
+                        .map(|r| timing::duration_ms(first, r.timestamp)),
+                    rt_ms: trial.response.as_ref().map(|r| r.rt_ms),
+                    outcome: outcome.to_string(),
+                    stimulus: Some(trial.stimulus()),
+                    target: None,
+                    lure: None,
+                })
+            })
+            .collect()
+    }
+
+    pub fn start(&mut self, mode: RunMode) -> Option<TrialSchedule> {
+        if matches!(
+            self.state,
+            EngineState::Waiting { .. }
+                | EngineState::StimulusActive { .. }
+                | EngineState::Paused { .. }
+        ) {
+            return None;
+        }
+
+        self.run_id = self.run_id.wrapping_add(1);
+        self.trials = self.generate_trials(mode);
+        if self.trials.is_empty() {
+            return None;
+        }
+        self.paused_at = None;
+        self.pause_count = 0;
+        self.paused_ms = 0.0;
+        self.state = EngineState::Waiting {
+            mode,
+            trial_index: 0,
+        };
+
+        Some(self.schedule_current(0))
+    }
+
+    pub fn abort(&mut self) {
+        self.state = EngineState::Aborted;
+    }
+
+    /// Pause a running block. The in-flight trial is voided so it can be shown
+    /// again on resume, and bumping `run_id` strands its scheduled timers.
+    pub fn pause(&mut self) -> bool {
+        let (mode, trial_index) = match self.state {
+            EngineState::Waiting { mode, trial_index }
+            | EngineState::StimulusActive { mode, trial_index } => (mode, trial_index),
+            _ => return false,
+        };
+
+        if let Some(trial) = self.trials.get_mut(trial_index) {
+            trial.presented_at = None;
+            trial.response = None;
+            trial.outcome = TrialOutcome::Pending;
+        }
+        self.run_id = self.run_id.wrapping_add(1);
+        self.paused_at = Some(timing::now());
+        self.pause_count = self.pause_count.saturating_add(1);
+        self.state = EngineState::Paused { mode, trial_index };
+        true
+    }
+
+    /// Resume a paused block from the voided trial, after the lead-in.
+    pub fn resume(&mut self) -> Option<TrialSchedule> {
+        let EngineState::Paused { mode, trial_index } = self.state else {
+            return None;
+        };
+
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_ms += timing::elapsed_ms(paused_at);
+        }
+        self.state = EngineState::Waiting { mode, trial_index };
+
+        let mut schedule = self.schedule_current(trial_index);
+        schedule.stimulus.wait_ms = self.config.lead_in_ms;
+        Some(schedule)
+    }
+
+    /// Times the current run was paused.
+    pub fn pause_count(&self) -> u32 {
+        self.pause_count
+    }
+
+    /// Total time the current run has spent paused, including a pause in
+    /// progress.
+    pub fn paused_ms(&self) -> f64 {
+        self.paused_ms + self.paused_at.map(timing::elapsed_ms).unwrap_or_default()
+    }
+
+    pub fn mark_stimulus_on(&mut self, trial_index: usize, timestamp: InstantStamp) -> bool {
+        match self.state {
+            EngineState::Waiting {
+                mode,
+                trial_index: idx,
+            } if idx == trial_index => {
+                if let Some(trial) = self.trials.get_mut(trial_index) {
+                    trial.presented_at = Some(timestamp);
+                    self.state = EngineState::StimulusActive { mode, trial_index };
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Score an answer to the row on screen. Answers between rows, or a second
+    /// answer to the same row, are ignored.
+    pub fn register_response(
+        &mut self,
+        direction: Direction,
+        timestamp: InstantStamp,
+    ) -> ResponseOutcome {
+        let EngineState::StimulusActive { trial_index, .. } = self.state else {
+            return ResponseOutcome::Ignored;
+        };
+        let Some(trial) = self.trials.get_mut(trial_index) else {
+            return ResponseOutcome::Ignored;
+        };
+        let Some(onset) = trial.presented_at else {
+            return ResponseOutcome::Ignored;
+        };
+        if trial.response.is_some() {
+            return ResponseOutcome::Ignored;
+        }
+
+        let rt_ms = timing::duration_ms(onset, timestamp);
+        let correct = direction == trial.target;
+        trial.response = Some(TrialResponse {
+            timestamp,
+            rt_ms,
+            direction,
+        });
+        trial.outcome = if correct {
+            TrialOutcome::Correct { rt_ms }
+        } else {
+            TrialOutcome::Error { rt_ms }
+        };
+        ResponseOutcome::Recorded {
+            trial_index,
+            correct,
+        }
+    }
+
+    /// End `trial_index` (a miss if it went unanswered) and schedule the next
+    /// one. Stale calls, e.g. the window timer of a row already answered, are
+    /// ignored.
+    pub fn advance(&mut self, trial_index: usize) -> AdvanceOutcome {
+        let (mode, idx) = match self.state {
+            EngineState::StimulusActive {
+                mode,
+                trial_index: idx,
+            } if idx == trial_index => (mode, idx),
+            _ => return AdvanceOutcome::Ignored,
+        };
+
+        if let Some(trial) = self.trials.get_mut(idx) {
+            if trial.outcome == TrialOutcome::Pending {
+                trial.outcome = TrialOutcome::Miss;
+            }
+        }
+
+        let next_index = idx + 1;
+
+        if next_index >= self.trials.len() {
+            self.state = EngineState::Completed { mode };
+            let metrics = FlankerMetrics::from_trials(&self.trials);
+            match mode {
+                RunMode::Practice => self.last_practice_metrics = Some(metrics),
+                RunMode::Main => self.last_main_metrics = Some(metrics),
+            }
+            AdvanceOutcome::Completed { mode }
+        } else {
+            self.state = EngineState::Waiting {
+                mode,
+                trial_index: next_index,
+            };
+            AdvanceOutcome::Next(self.schedule_current(next_index))
+        }
+    }
+
+    fn schedule_current(&self, trial_index: usize) -> TrialSchedule {
+        TrialSchedule {
+            stimulus: ScheduledStimulus {
+                run_id: self.run_id,
+                trial_index,
+                wait_ms: if trial_index == 0 {
+                    self.config.lead_in_ms
+                } else {
+                    self.config.interstimulus_interval_ms
+                },
+            },
+            advance: ScheduledAdvance {
+                run_id: self.run_id,
+                trial_index,
+                wait_ms: self.config.response_window_ms,
+            },
+        }
+    }
+
+    fn generate_trials(&self, mode: RunMode) -> Vec<FlankerTrial> {
+        let length = match mode {
+            RunMode::Practice => self.config.practice_trials,
+            RunMode::Main => self.config.total_trials,
+        };
+
+        let share = length as f32 * self.config.congruent_ratio.clamp(0.0, 1.0);
+        let congruent = (share.round() as usize).min(length);
+
+        // Balance directions within each condition, then shuffle the whole run.
+        let mut conditions: Vec<(Direction, bool)> = Vec::with_capacity(length);
+        for (count, is_congruent) in [(congruent, true), (length - congruent, false)] {
+            for i in 0..count {
+                let target = if i % 2 == 0 {
+                    Direction::Left
+                } else {
+                    Direction::Right
+                };
+                conditions.push((target, is_congruent));
+            }
+        }
+
+        let mut rng = self.seeded_rng(mode);
+        conditions.shuffle(&mut rng);
+
+        conditions
+            .into_iter()
+            .enumerate()
+            .map(|(index, (target, is_congruent))| FlankerTrial::new(index, target, is_congruent))
+            .collect()
+    }
+
+    fn seeded_rng(&self, mode: RunMode) -> StdRng {
+        let combined_seed = self.config.seed ^ mode.seed_tag() ^ self.run_id;
+        StdRng::seed_from_u64(combined_seed)
+    }
+}
+
+impl Default for FlankerEngine {
+    fn default() -> Self {
+        Self::new(FlankerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedule_is_balanced_and_deterministic() {
+        let engine = FlankerEngine {
+            run_id: 3,
+            ..Default::default()
+        };
+        let trials = engine.generate_trials(RunMode::Main);
+        assert_eq!(trials.len(), 48);
+        assert_eq!(trials.iter().filter(|t| t.congruent).count(), 24);
+        for congruent in [true, false] {
+            let lefts = trials
+                .iter()
+                .filter(|t| t.congruent == congruent && t.target == Direction::Left)
+                .count();
+            assert_eq!(lefts, 12);
+        }
+        assert_eq!(trials, engine.generate_trials(RunMode::Main));
+
+        let congruent = FlankerTrial::new(0, Direction::Left, true);
+        let incongruent = FlankerTrial::new(1, Direction::Right, false);
+        assert_eq!(congruent.stimulus(), "←←←←←");
+        assert_eq!(incongruent.stimulus(), "←←→←←");
+    }
+
+    #[test]
+    fn responses_end_the_trial_and_timeouts_are_misses() {
+        let mut engine = FlankerEngine::new(FlankerConfig {
+            total_trials: 3,
+            ..Default::default()
+        });
+        engine.start(RunMode::Main).expect("schedule");
+        assert!(matches!(
+            engine.register_response(Direction::Left, timing::now()),
+            ResponseOutcome::Ignored
+        ));
+
+        // Answer the first row correctly, the second wrongly, miss the third.
+        for index in 0..2 {
+            engine.mark_stimulus_on(index, timing::now());
+            let target = engine.trials()[index].target;
+            let answer = if index == 0 { target } else { target.opposite() };
+            let ResponseOutcome::Recorded {
+                trial_index,
+                correct,
+            } = engine.register_response(answer, timing::now())
+            else {
+                panic!("response ignored");
+            };
+            assert_eq!((trial_index, correct), (index, index == 0));
+            assert!(matches!(
+                engine.register_response(target, timing::now()),
+                ResponseOutcome::Ignored
+            ));
+            assert!(matches!(engine.advance(index), AdvanceOutcome::Next(_)));
+            // The answered row's window timer lands late and is ignored.
+            assert!(matches!(engine.advance(index), AdvanceOutcome::Ignored));
+        }
+        engine.mark_stimulus_on(2, timing::now());
+        assert!(matches!(
+            engine.advance(2),
+            AdvanceOutcome::Completed {
+                mode: RunMode::Main
+            }
+        ));
+
+        let metrics = engine.main_metrics().expect("main metrics");
+        assert_eq!((metrics.correct, metrics.errors, metrics.misses), (1, 1, 1));
+        let samples = engine.trial_samples();
+        let outcomes: Vec<_> = samples.iter().map(|s| s.outcome.as_str()).collect();
+        assert_eq!(outcomes, ["correct", "error", "miss"]);
+        assert_eq!(samples[0].onset_ms, Some(0.0));
+        assert!(samples[2].rt_ms.is_none());
+    }
+
+    #[test]
+    fn pausing_replays_the_voided_trial_on_resume() {
+        let mut engine = FlankerEngine::default();
+        let first = engine.start(RunMode::Main).expect("schedule");
+        engine.mark_stimulus_on(0, timing::now());
+
+        assert!(engine.pause());
+        assert!(!engine.pause());
+        assert!(engine.start(RunMode::Main).is_none());
+        assert_ne!(engine.run_id, first.stimulus.run_id);
+        assert!(!engine.trials()[0].is_completed());
+        assert!(matches!(engine.advance(0), AdvanceOutcome::Ignored));
+
+        let resumed = engine.resume().expect("reschedule");
+        assert_eq!(resumed.stimulus.trial_index, 0);
+        assert_eq!(resumed.stimulus.wait_ms, engine.config.lead_in_ms);
+        assert_eq!(engine.pause_count(), 1);
+    }
+}
@@ -0,0 +1,220 @@
+//! Metric definitions and aggregation helpers for flanker runs.
+//!
+//! Reaction times are over correct responses only. The conflict effect is the
+//! cost of ignoring incongruent flankers: incongruent minus congruent median
+//! correct RT, and congruent minus incongruent accuracy. Both grow as
+//! inhibitory control weakens.
+
+use serde::{Deserialize, Serialize};
+
+use super::engine::{FlankerTrial, TrialOutcome};
+
+/// Correct responses each condition needs for its median RT to count.
+const MIN_CORRECT_PER_CONDITION: u32 = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FlankerMetrics {
+    pub total_trials: usize,
+    pub congruent_trials: usize,
+    pub incongruent_trials: usize,
+    pub correct: u32,
+    pub errors: u32,
+    pub misses: u32,
+    pub congruent_correct: u32,
+    pub incongruent_correct: u32,
+    pub accuracy: f64,
+    pub congruent_accuracy: f64,
+    pub incongruent_accuracy: f64,
+    pub congruent_mean_rt_ms: f64,
+    pub incongruent_mean_rt_ms: f64,
+    pub congruent_median_rt_ms: f64,
+    pub incongruent_median_rt_ms: f64,
+    /// Incongruent minus congruent median correct RT; 0 unless both
+    /// conditions have a correct response.
+    pub conflict_effect_ms: f64,
+    /// Congruent minus incongruent accuracy.
+    pub conflict_effect_accuracy: f64,
+    pub response_count: u32,
+    pub meets_min_trial_requirement: bool,
+}
+
+impl FlankerMetrics {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn from_trials(trials: &[FlankerTrial]) -> Self {
+        let total_trials = trials.len();
+        if total_trials == 0 {
+            return Self::default();
+        }
+
+        let mut congruent_trials = 0usize;
+        let mut incongruent_trials = 0usize;
+        let mut errors = 0u32;
+        let mut misses = 0u32;
+        let mut congruent_rts = Vec::new();
+        let mut incongruent_rts = Vec::new();
+
+        for trial in trials {
+            if trial.congruent {
+                congruent_trials += 1;
+            } else {
+                incongruent_trials += 1;
+            }
+
+            match trial.outcome {
+                TrialOutcome::Correct { rt_ms } => {
+                    if trial.congruent {
+                        congruent_rts.push(rt_ms);
+                    } else {
+                        incongruent_rts.push(rt_ms);
+                    }
+                }
+                TrialOutcome::Error { .. } => {
+                    errors = errors.saturating_add(1);
+                }
+                TrialOutcome::Miss => {
+                    misses = misses.saturating_add(1);
+                }
+                TrialOutcome::Pending => {}
+            }
+        }
+
+        congruent_rts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        incongruent_rts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let congruent_correct = congruent_rts.len() as u32;
+        let incongruent_correct = incongruent_rts.len() as u32;
+        let correct = congruent_correct + incongruent_correct;
+
+        let accuracy = ratio(correct, total_trials);
+        let congruent_accuracy = ratio(congruent_correct, congruent_trials);
+        let incongruent_accuracy = ratio(incongruent_correct, incongruent_trials);
+
+        let congruent_median_rt_ms = percentile(&congruent_rts, 0.5);
+        let incongruent_median_rt_ms = percentile(&incongruent_rts, 0.5);
+        let conflict_effect_ms = if congruent_rts.is_empty() || incongruent_rts.is_empty() {
+            0.0
+        } else {
+            incongruent_median_rt_ms - congruent_median_rt_ms
+        };
+
+        Self {
+            total_trials,
+            congruent_trials,
+            incongruent_trials,
+            correct,
+            errors,
+            misses,
+            congruent_correct,
+            incongruent_correct,
+            accuracy,
+            congruent_accuracy,
+            incongruent_accuracy,
+            congruent_mean_rt_ms: mean(&congruent_rts),
+            incongruent_mean_rt_ms: mean(&incongruent_rts),
+            congruent_median_rt_ms,
+            incongruent_median_rt_ms,
+            conflict_effect_ms,
+            conflict_effect_accuracy: congruent_accuracy - incongruent_accuracy,
+            response_count: correct + errors,
+            meets_min_trial_requirement: congruent_correct >= MIN_CORRECT_PER_CONDITION
+                && incongruent_correct >= MIN_CORRECT_PER_CONDITION,
+        }
+    }
+}
+
+fn ratio(count: u32, total: usize) -> f64 {
+    if total > 0 {
+        count as f64 / total as f64
+    } else {
+        0.0
+    }
+}
+
+fn mean(data: &[f64]) -> f64 {
+    if data.is_empty() {
+        0.0
+    } else {
+        data.iter().sum::<f64>() / data.len() as f64
+    }
+}
+
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let clamped_pct = pct.clamp(0.0, 1.0);
+    let rank = clamped_pct * (sorted.len() as f64 - 1.0);
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    if lower == upper {
+        sorted[lower]
+    } else {
+        let weight = rank - lower as f64;
+        sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::flanker::engine::Direction;
+
+    fn trial(index: usize, congruent: bool, outcome: TrialOutcome) -> FlankerTrial {
+        FlankerTrial {
+            index,
+            target: Direction::Left,
+            congruent,
+            presented_at: None,
+            response: None,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn conflict_effect_compares_conditions() {
+        let trials = vec![
+            trial(0, true, TrialOutcome::Correct { rt_ms: 400.0 }),
+            trial(1, true, TrialOutcome::Correct { rt_ms: 420.0 }),
+            trial(2, true, TrialOutcome::Correct { rt_ms: 440.0 }),
+            trial(3, true, TrialOutcome::Error { rt_ms: 300.0 }),
+            trial(4, false, TrialOutcome::Correct { rt_ms: 480.0 }),
+            trial(5, false, TrialOutcome::Correct { rt_ms: 500.0 }),
+            trial(6, false, TrialOutcome::Error { rt_ms: 350.0 }),
+            trial(7, false, TrialOutcome::Miss),
+        ];
+        let metrics = FlankerMetrics::from_trials(&trials);
+
+        assert_eq!((metrics.congruent_trials, metrics.incongruent_trials), (4, 4));
+        assert_eq!((metrics.correct, metrics.errors, metrics.misses), (5, 2, 1));
+        assert_eq!(metrics.response_count, 7);
+        assert!((metrics.accuracy - 0.625).abs() < 1e-9);
+        assert!((metrics.congruent_accuracy - 0.75).abs() < 1e-9);
+        assert!((metrics.incongruent_accuracy - 0.5).abs() < 1e-9);
+        // Error RTs are left out of the medians.
+        assert!((metrics.congruent_median_rt_ms - 420.0).abs() < 1e-9);
+        assert!((metrics.incongruent_median_rt_ms - 490.0).abs() < 1e-9);
+        assert!((metrics.conflict_effect_ms - 70.0).abs() < 1e-9);
+        assert!((metrics.conflict_effect_accuracy - 0.25).abs() < 1e-9);
+        assert!(!metrics.meets_min_trial_requirement);
+    }
+
+    #[test]
+    fn conflict_effect_needs_both_conditions() {
+        let trials = vec![
+            trial(0, true, TrialOutcome::Correct { rt_ms: 400.0 }),
+            trial(1, false, TrialOutcome::Miss),
+        ];
+        let metrics = FlankerMetrics::from_trials(&trials);
+        assert_eq!(metrics.conflict_effect_ms, 0.0);
+        assert_eq!(FlankerMetrics::from_trials(&[]).total_trials, 0);
+    }
+}
@@ -0,0 +1,8 @@
+mod engine;
+pub use engine::FlankerEngine;
+
+mod metrics;
+pub use metrics::FlankerMetrics;
+
+mod view;
+pub use view::FlankerView;
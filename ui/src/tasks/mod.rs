@@ -1,2 +1,6 @@
+pub mod dsst;
+pub mod flanker;
 pub mod nback;
 pub mod pvt;
+pub mod task_switch;
+pub mod word_recall;
@@ -1,9 +1,16 @@
-//! Engine managing the 2-back stimulus schedule and response tracking.
+//! Engine managing the N-back stimulus schedule and response tracking.
+//!
+//! A trial is a target when its letter matches the one `n` trials back, and a
+//! lure when it instead matches the one `n − 1` or `n + 1` back. Targets and
+//! lures are placed at the configured ratios from the seeded RNG; every other
+//! letter avoids all three, so no lure arises by chance. In adaptive mode `n`
+//! steps up or down after each main block, by its accuracy.
 
 use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::SeedableRng;
 
+use crate::core::storage::TrialSample;
 use crate::core::timing::{self, InstantStamp};
 
 use super::metrics::NBackMetrics;
@@ -12,7 +19,16 @@ const LETTER_POOL: &[char] = &[
     'B', 'C', 'D', 'F', 'G', 'H', 'J', 'K', 'M', 'P', 'Q', 'R', 'S', 'T', 'V', 'W', 'X', 'Y', 'Z',
 ];
 
-/// Different run phases for the 2-back engine.
+/// Lowest and highest N the engine runs.
+pub const MIN_LEVEL: usize = 1;
+pub const MAX_LEVEL: usize = 4;
+
+/// Main-block accuracy at or above which adaptive mode steps N up.
+const ADAPT_UP_ACCURACY: f64 = 0.9;
+/// Main-block accuracy below which adaptive mode steps N down.
+const ADAPT_DOWN_ACCURACY: f64 = 0.75;
+
+/// Different run phases for the N-back engine.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RunMode {
     Practice,
@@ -31,9 +47,15 @@ impl RunMode {
 /// Public configuration knobs for the task.
 #[derive(Debug, Clone)]
 pub struct NBackConfig {
+    /// How many trials back a target matches, `MIN_LEVEL..=MAX_LEVEL`.
+    pub n: usize,
+    /// Step `n` between main blocks by accuracy ([`next_level`]).
+    pub adaptive: bool,
     pub total_trials: usize,
     pub practice_trials: usize,
     pub target_ratio: f32,
+    /// Share of the non-target trials that are lures.
+    pub lure_ratio: f32,
     pub stimulus_ms: u64,
     pub interstimulus_interval_ms: u64,
     pub lead_in_ms: u64,
@@ -44,9 +66,12 @@ pub struct NBackConfig {
 impl Default for NBackConfig {
     fn default() -> Self {
         Self {
+            n: 2,
+            adaptive: false,
             total_trials: 60,
             practice_trials: 12,
             target_ratio: 0.3,
+            lure_ratio: 0.15,
             stimulus_ms: 500,
             interstimulus_interval_ms: 2_500,
             lead_in_ms: 750,
@@ -61,6 +86,7 @@ pub enum EngineState {
     Idle,
     Waiting { mode: RunMode, trial_index: usize },
     StimulusActive { mode: RunMode, trial_index: usize },
+    Paused { mode: RunMode, trial_index: usize },
     Completed { mode: RunMode },
     Aborted,
 }
@@ -156,6 +182,9 @@ pub struct NBackEngine {
     trials: Vec<NBackTrial>,
     last_practice_metrics: Option<NBackMetrics>,
     last_main_metrics: Option<NBackMetrics>,
+    paused_at: Option<InstantStamp>,
+    pause_count: u32,
+    paused_ms: f64,
 }
 
 impl NBackEngine {
@@ -167,6 +196,9 @@ impl NBackEngine {
             trials: Vec::new(),
             last_practice_metrics: None,
             last_main_metrics: None,
+            paused_at: None,
+            pause_count: 0,
+            paused_ms: 0.0,
         }
     }
 
@@ -182,16 +214,60 @@ impl NBackEngine {
         &self.trials
     }
 
+    /// The last run's finished trials, for keeping with it; times are from its
+    /// first stimulus.
+    pub fn trial_samples(&self) -> Vec<TrialSample> {
+        let Some(first) = self.trials.iter().find_map(|trial| trial.presented_at) else {
+            return Vec::new();
+        };
+        self.trials
+            .iter()
+            .filter_map(|trial| {
+                let outcome = match trial.outcome {
+                    TrialOutcome::Pending => return None,
+                    TrialOutcome::Hit { .. } => "hit",
+                    TrialOutcome::Miss => "miss",
+                    TrialOutcome::FalseAlarm { .. } => "false_alarm",
+                    TrialOutcome::CorrectRejection => "correct_rejection",
+                };
+                let wait_ms = if trial.index == 0 {
+                    self.config.lead_in_ms
+                } else {
+                    self.config.interstimulus_interval_ms
+                };
+                Some(TrialSample {
+                    index: trial.index,
+                    iti_ms: Some(wait_ms as f64),
+                    onset_ms: trial.presented_at.map(|at| timing::duration_ms(first, at)),
+                    response_ms: trial
+                        .response
+                        .as_ref()
+                        .map(|r| timing::duration_ms(first, r.timestamp)),
+                    rt_ms: trial.response.as_ref().map(|r| r.rt_ms),
+                    outcome: outcome.to_string(),
+                    stimulus: Some(trial.letter.to_string()),
+                    target: Some(trial.is_target),
+                    lure: Some(trial.is_lure),
+                })
+            })
+            .collect()
+    }
+
     pub fn start(&mut self, mode: RunMode) -> Option<TrialSchedule> {
         if matches!(
             self.state,
-            EngineState::Waiting { .. } | EngineState::StimulusActive { .. }
+            EngineState::Waiting { .. }
+                | EngineState::StimulusActive { .. }
+                | EngineState::Paused { .. }
         ) {
             return None;
         }
 
         self.run_id = self.run_id.wrapping_add(1);
         self.trials = self.generate_trials(mode);
+        self.paused_at = None;
+        self.pause_count = 0;
+        self.paused_ms = 0.0;
         self.state = EngineState::Waiting {
             mode,
             trial_index: 0,
@@ -204,6 +280,54 @@ impl NBackEngine {
         self.state = EngineState::Aborted;
     }
 
+    /// Pause a running block. The in-flight trial is voided so it can be shown
+    /// again on resume, and bumping `run_id` strands its scheduled timers.
+    pub fn pause(&mut self) -> bool {
+        let (mode, trial_index) = match self.state {
+            EngineState::Waiting { mode, trial_index }
+            | EngineState::StimulusActive { mode, trial_index } => (mode, trial_index),
+            _ => return false,
+        };
+
+        if let Some(trial) = self.trials.get_mut(trial_index) {
+            trial.presented_at = None;
+            trial.response = None;
+            trial.outcome = TrialOutcome::Pending;
+        }
+        self.run_id = self.run_id.wrapping_add(1);
+        self.paused_at = Some(timing::now());
+        self.pause_count = self.pause_count.saturating_add(1);
+        self.state = EngineState::Paused { mode, trial_index };
+        true
+    }
+
+    /// Resume a paused block from the voided trial, after the lead-in.
+    pub fn resume(&mut self) -> Option<TrialSchedule> {
+        let EngineState::Paused { mode, trial_index } = self.state else {
+            return None;
+        };
+
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_ms += timing::elapsed_ms(paused_at);
+        }
+        self.state = EngineState::Waiting { mode, trial_index };
+
+        let mut schedule = self.schedule_current(trial_index);
+        schedule.stimulus.wait_ms = self.config.lead_in_ms;
+        Some(schedule)
+    }
+
+    /// Times the current run was paused.
+    pub fn pause_count(&self) -> u32 {
+        self.pause_count
+    }
+
+    /// Total time the current run has spent paused, including a pause in
+    /// progress.
+    pub fn paused_ms(&self) -> f64 {
+        self.paused_ms + self.paused_at.map(timing::elapsed_ms).unwrap_or_default()
+    }
+
     pub fn mark_stimulus_on(&mut self, trial_index: usize, timestamp: InstantStamp) -> bool {
         match self.state {
             EngineState::Waiting {
@@ -276,10 +400,17 @@ impl NBackEngine {
 
         if next_index >= self.trials.len() {
             self.state = EngineState::Completed { mode };
-            let metrics = NBackMetrics::from_trials(&self.trials);
+            let mut metrics = NBackMetrics::from_trials(&self.trials);
+            metrics.n = self.level() as u8;
+            metrics.adaptive = self.config.adaptive;
             match mode {
                 RunMode::Practice => self.last_practice_metrics = Some(metrics),
-                RunMode::Main => self.last_main_metrics = Some(metrics),
+                RunMode::Main => {
+                    if self.config.adaptive {
+                        self.config.n = next_level(self.level(), metrics.accuracy);
+                    }
+                    self.last_main_metrics = Some(metrics);
+                }
             }
             AdvanceOutcome::Completed { mode }
         } else {
@@ -310,7 +441,13 @@ impl NBackEngine {
         }
     }
 
+    /// The configured N, clamped to the supported levels.
+    pub fn level(&self) -> usize {
+        self.config.n.clamp(MIN_LEVEL, MAX_LEVEL)
+    }
+
     fn generate_trials(&self, mode: RunMode) -> Vec<NBackTrial> {
+        let n = self.level();
         let length = match mode {
             RunMode::Practice => self.config.practice_trials,
             RunMode::Main => self.config.total_trials,
@@ -322,20 +459,11 @@ impl NBackEngine {
         }
 
         let mut rng = self.seeded_rng(mode);
-        let mut letters: Vec<char> = Vec::with_capacity(length);
 
-        for i in 0..length {
-            if i < 2 {
-                letters.push(random_letter(&mut rng, None));
-            } else {
-                letters.push(' ');
-            }
-        }
-
-        let target_candidates: Vec<usize> = (2..length).collect();
+        let target_candidates: Vec<usize> = (n..length).collect();
         let max_targets = target_candidates.len();
         let mut target_quota =
-            ((length.saturating_sub(2)) as f32 * self.config.target_ratio).round() as isize;
+            ((length.saturating_sub(n)) as f32 * self.config.target_ratio).round() as isize;
         if max_targets == 0 {
             target_quota = 0;
         } else {
@@ -353,20 +481,50 @@ impl NBackEngine {
         chosen_targets.truncate(target_quota);
         chosen_targets.sort_unstable();
 
-        for idx in 2..length {
-            if chosen_targets.binary_search(&idx).is_ok() {
-                let letter = letters[idx - 2];
-                letters[idx] = letter;
+        // Lures need an N±1-back letter to repeat; a 1-back has only N + 1.
+        let lure_candidates: Vec<usize> = (n..length)
+            .filter(|idx| chosen_targets.binary_search(idx).is_err())
+            .filter(|&idx| n > 1 || idx > n)
+            .collect();
+        let lure_share = lure_candidates.len() as f32 * self.config.lure_ratio.max(0.0);
+        let lure_quota = (lure_share.round() as usize).min(lure_candidates.len());
+        let mut chosen_lures = lure_candidates;
+        chosen_lures.shuffle(&mut rng);
+        chosen_lures.truncate(lure_quota);
+        chosen_lures.sort_unstable();
+
+        let mut letters: Vec<char> = Vec::with_capacity(length);
+        for idx in 0..length {
+            let back = |offset: usize| (offset > 0 && idx >= offset).then(|| letters[idx - offset]);
+            let letter = if chosen_targets.binary_search(&idx).is_ok() {
+                letters[idx - n]
             } else {
-                let previous_two = letters[idx - 2];
-                letters[idx] = random_letter(&mut rng, Some(previous_two));
-            }
+                // A lure repeats the N−1 or N+1 back letter, unless that would
+                // also repeat the N-back one (and so be a target).
+                let lure = if chosen_lures.binary_search(&idx).is_ok() {
+                    let near: Vec<char> = [n - 1, n + 1]
+                        .into_iter()
+                        .filter_map(back)
+                        .filter(|&letter| Some(letter) != back(n))
+                        .collect();
+                    near.choose(&mut rng).copied()
+                } else {
+                    None
+                };
+                lure.unwrap_or_else(|| {
+                    let avoid: Vec<char> = [n - 1, n, n + 1].into_iter().filter_map(back).collect();
+                    random_letter(&mut rng, &avoid)
+                })
+            };
+            letters.push(letter);
         }
 
         for idx in 0..length {
             let letter = letters[idx];
-            let is_target = idx >= 2 && letter == letters[idx - 2];
-            let is_lure = idx >= 1 && letter == letters[idx - 1];
+            let matches_back =
+                |back: usize| back > 0 && idx >= back && letter == letters[idx - back];
+            let is_target = matches_back(n);
+            let is_lure = !is_target && (matches_back(n - 1) || matches_back(n + 1));
             trials.push(NBackTrial::new(idx, letter, is_target, is_lure));
         }
 
@@ -385,10 +543,22 @@ impl Default for NBackEngine {
     }
 }
 
-fn random_letter(rng: &mut StdRng, disallow: Option<char>) -> char {
+/// The N for the block after a main block at `n` scored `accuracy`.
+pub fn next_level(n: usize, accuracy: f64) -> usize {
+    let stepped = if accuracy >= ADAPT_UP_ACCURACY {
+        n + 1
+    } else if accuracy < ADAPT_DOWN_ACCURACY {
+        n.saturating_sub(1)
+    } else {
+        n
+    };
+    stepped.clamp(MIN_LEVEL, MAX_LEVEL)
+}
+
+fn random_letter(rng: &mut StdRng, avoid: &[char]) -> char {
     loop {
         let letter = LETTER_POOL.choose(rng).copied().unwrap_or('A');
-        if disallow.map(|c| c == letter).unwrap_or(false) {
+        if avoid.contains(&letter) {
             continue;
         }
         return letter;
@@ -400,32 +570,97 @@ mod tests {
     use super::*;
 
     #[test]
-    fn generated_sequence_respects_two_back_constraints() {
-        let engine = NBackEngine {
-            run_id: 42,
-            ..Default::default()
-        };
-        let trials = engine.generate_trials(RunMode::Main);
+    fn generated_sequence_respects_n_back_constraints() {
+        for n in MIN_LEVEL..=MAX_LEVEL {
+            let mut engine = NBackEngine {
+                run_id: 42,
+                ..Default::default()
+            };
+            engine.config.n = n;
+            let trials = engine.generate_trials(RunMode::Main);
+
+            assert_eq!(trials.len(), engine.config.total_trials);
+            assert!(trials[..n].iter().all(|trial| !trial.is_target));
+            assert!(trials.iter().any(|trial| trial.is_target));
+
+            for idx in n..trials.len() {
+                let trial = &trials[idx];
+                let n_back = &trials[idx - n];
+                if trial.is_target {
+                    assert_eq!(trial.letter, n_back.letter);
+                    assert!(!trial.is_lure);
+                } else {
+                    assert_ne!(trial.letter, n_back.letter);
+                }
+                let near_miss = [n - 1, n + 1].into_iter().any(|back| {
+                    back > 0 && idx >= back && trials[idx - back].letter == trial.letter
+                });
+                assert_eq!(trial.is_lure, !trial.is_target && near_miss);
+            }
+        }
+    }
 
-        assert_eq!(trials.len(), engine.config.total_trials);
+    #[test]
+    fn lures_follow_the_configured_ratio_deterministically() {
+        for n in MIN_LEVEL..=MAX_LEVEL {
+            let mut engine = NBackEngine {
+                run_id: 7,
+                ..Default::default()
+            };
+            engine.config.n = n;
+            engine.config.lure_ratio = 0.25;
+            let trials = engine.generate_trials(RunMode::Main);
+            let targets = trials.iter().filter(|trial| trial.is_target).count();
+            let candidates = (n..trials.len()).count() - targets - usize::from(n == 1);
+            let quota = (candidates as f32 * 0.25).round() as usize;
+            let lures = trials.iter().filter(|trial| trial.is_lure).count();
+            assert!(lures > 0 && lures <= quota, "n={n}: {lures} lures, quota {quota}");
+            assert_eq!(trials, engine.generate_trials(RunMode::Main));
+
+            engine.config.lure_ratio = 0.0;
+            let trials = engine.generate_trials(RunMode::Main);
+            assert!(trials.iter().all(|trial| !trial.is_lure), "n={n}");
+        }
+    }
 
-        for idx in 2..trials.len() {
-            let trial = &trials[idx];
-            let two_back = &trials[idx - 2];
-            if trial.is_target {
-                assert_eq!(trial.letter, two_back.letter);
-            } else {
-                assert_ne!(trial.letter, two_back.letter);
+    #[test]
+    fn adaptive_level_steps_by_accuracy_within_bounds() {
+        assert_eq!(next_level(2, 0.95), 3);
+        assert_eq!(next_level(2, 0.8), 2);
+        assert_eq!(next_level(2, 0.5), 1);
+        assert_eq!(next_level(MAX_LEVEL, 1.0), MAX_LEVEL);
+        assert_eq!(next_level(MIN_LEVEL, 0.0), MIN_LEVEL);
+
+        // A flawless block steps up once it completes.
+        let mut engine = NBackEngine::new(NBackConfig {
+            n: 3,
+            adaptive: true,
+            total_trials: 8,
+            ..Default::default()
+        });
+        engine.start(RunMode::Main).expect("schedule");
+        for index in 0..8 {
+            engine.mark_stimulus_on(index, timing::now());
+            if engine.trials()[index].is_target {
+                engine.register_response(timing::now());
             }
+            engine.advance(index);
         }
+        let metrics = engine.main_metrics().expect("main metrics");
+        assert_eq!(metrics.n, 3);
+        assert!(metrics.adaptive);
+        assert_eq!(engine.config.n, 4);
     }
 
     #[test]
     fn run_completion_produces_metrics() {
         let mut engine = NBackEngine::new(NBackConfig {
+            n: 2,
+            adaptive: false,
             total_trials: 4,
             practice_trials: 4,
             target_ratio: 0.5,
+            lure_ratio: 0.0,
             stimulus_ms: 300,
             interstimulus_interval_ms: 200,
             lead_in_ms: 10,
@@ -459,5 +694,47 @@ mod tests {
 
         let metrics = engine.practice_metrics().expect("practice metrics");
         assert_eq!(metrics.total_trials, 4);
+
+        let samples = engine.trial_samples();
+        assert_eq!(samples.len(), 4);
+        assert_eq!(samples[0].onset_ms, Some(0.0));
+        assert_eq!(samples[0].iti_ms, Some(10.0));
+        assert!(samples.iter().all(|s| s.rt_ms.is_none()));
+        assert!(samples
+            .iter()
+            .all(|s| matches!(s.outcome.as_str(), "miss" | "correct_rejection")));
+    }
+
+    #[test]
+    fn pausing_replays_the_voided_trial_on_resume() {
+        let mut engine = NBackEngine::default();
+        let first = engine.start(RunMode::Main).expect("schedule");
+        engine.mark_stimulus_on(0, timing::now());
+        assert!(matches!(engine.advance(0), AdvanceOutcome::Next(_)));
+        engine.mark_stimulus_on(1, timing::now());
+        engine.register_response(timing::now());
+
+        assert!(engine.pause());
+        assert!(!engine.pause());
+        assert!(engine.start(RunMode::Main).is_none());
+        assert_ne!(engine.run_id, first.stimulus.run_id);
+        assert!(engine.trials()[1].response.is_none());
+        assert!(!engine.trials()[1].is_completed());
+        // The voided trial's advance timer no longer lands.
+        assert!(matches!(engine.advance(1), AdvanceOutcome::Ignored));
+
+        let resumed = engine.resume().expect("reschedule");
+        assert_eq!(resumed.stimulus.run_id, engine.run_id);
+        assert_eq!(resumed.stimulus.trial_index, 1);
+        assert_eq!(resumed.stimulus.wait_ms, engine.config.lead_in_ms);
+        assert_eq!(
+            engine.state,
+            EngineState::Waiting {
+                mode: RunMode::Main,
+                trial_index: 1
+            }
+        );
+        assert!(engine.resume().is_none());
+        assert_eq!(engine.pause_count(), 1);
     }
 }
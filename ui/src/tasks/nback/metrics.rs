@@ -1,10 +1,15 @@
-//! Metric definitions and aggregation helpers for 2-back runs.
+//! Metric definitions and aggregation helpers for N-back runs.
+//!
+//! False alarms are also split by whether the non-target was a lure (a repeat
+//! of the N±1-back letter). Lure false alarms mark familiarity-based rather
+//! than position-based responding; `lure_d_prime` is d′ with lure false alarms
+//! as the noise, so it falls as a participant confuses lures with targets.
 
 use serde::{Deserialize, Serialize};
 
 use super::engine::{NBackTrial, TrialOutcome};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NBackMetrics {
     pub total_trials: usize,
     pub target_trials: usize,
@@ -24,6 +29,65 @@ pub struct NBackMetrics {
     pub p10_hit_rt_ms: f64,
     pub p90_hit_rt_ms: f64,
     pub response_count: u32,
+    /// Non-target trials that repeated the N±1-back letter.
+    #[serde(default)]
+    pub lure_trials: usize,
+    #[serde(default)]
+    pub lure_false_alarms: u32,
+    #[serde(default)]
+    pub non_lure_false_alarms: u32,
+    #[serde(default)]
+    pub lure_false_alarm_rate: f64,
+    #[serde(default)]
+    pub non_lure_false_alarm_rate: f64,
+    /// d′ of hits against lure false alarms (log-linear corrected).
+    #[serde(default)]
+    pub lure_d_prime: f64,
+    /// The N the run was played at. Runs saved before N-back levels existed
+    /// were all 2-back.
+    #[serde(default = "two_back")]
+    pub n: u8,
+    /// Whether the run was a block of an adaptive session.
+    #[serde(default)]
+    pub adaptive: bool,
+}
+
+fn two_back() -> u8 {
+    2
+}
+
+/// Empty 2-back metrics: the level a stored run without `n` reads as.
+impl Default for NBackMetrics {
+    fn default() -> Self {
+        Self {
+            total_trials: 0,
+            target_trials: 0,
+            non_target_trials: 0,
+            hits: 0,
+            misses: 0,
+            false_alarms: 0,
+            correct_rejections: 0,
+            hit_rate: 0.0,
+            false_alarm_rate: 0.0,
+            accuracy: 0.0,
+            d_prime: 0.0,
+            criterion: 0.0,
+            mean_hit_rt_ms: 0.0,
+            median_hit_rt_ms: 0.0,
+            sd_hit_rt_ms: 0.0,
+            p10_hit_rt_ms: 0.0,
+            p90_hit_rt_ms: 0.0,
+            response_count: 0,
+            lure_trials: 0,
+            lure_false_alarms: 0,
+            non_lure_false_alarms: 0,
+            lure_false_alarm_rate: 0.0,
+            non_lure_false_alarm_rate: 0.0,
+            lure_d_prime: 0.0,
+            n: two_back(),
+            adaptive: false,
+        }
+    }
 }
 
 impl NBackMetrics {
@@ -31,6 +95,21 @@ impl NBackMetrics {
         Self::default()
     }
 
+    /// Whether the run carries the lure split; runs saved before it existed
+    /// read `lure_trials` as zero.
+    pub fn has_lure_measures(&self) -> bool {
+        self.lure_trials > 0
+    }
+
+    /// English name of the run's level, e.g. `3-back (adaptive)`.
+    pub fn level_label(&self) -> String {
+        if self.adaptive {
+            format!("{}-back (adaptive)", self.n)
+        } else {
+            format!("{}-back", self.n)
+        }
+    }
+
     pub fn from_trials(trials: &[NBackTrial]) -> Self {
         let total_trials = trials.len();
         if total_trials == 0 {
@@ -43,6 +122,8 @@ impl NBackMetrics {
         let mut misses = 0u32;
         let mut false_alarms = 0u32;
         let mut correct_rejections = 0u32;
+        let mut lure_trials = 0usize;
+        let mut lure_false_alarms = 0u32;
         let mut hit_rts = Vec::new();
 
         for trial in trials {
@@ -50,6 +131,9 @@ impl NBackMetrics {
                 target_trials += 1;
             } else {
                 non_target_trials += 1;
+                if trial.is_lure {
+                    lure_trials += 1;
+                }
             }
 
             match trial.outcome {
@@ -62,6 +146,9 @@ impl NBackMetrics {
                 }
                 TrialOutcome::FalseAlarm { .. } => {
                     false_alarms = false_alarms.saturating_add(1);
+                    if trial.is_lure {
+                        lure_false_alarms = lure_false_alarms.saturating_add(1);
+                    }
                 }
                 TrialOutcome::CorrectRejection => {
                     correct_rejections = correct_rejections.saturating_add(1);
@@ -101,6 +188,24 @@ impl NBackMetrics {
         let (d_prime, criterion) =
             signal_detection_indices(hits, false_alarms, target_trials, non_target_trials);
 
+        let non_lure_trials = non_target_trials - lure_trials;
+        let non_lure_false_alarms = false_alarms - lure_false_alarms;
+        let lure_false_alarm_rate = if lure_trials > 0 {
+            lure_false_alarms as f64 / lure_trials as f64
+        } else {
+            0.0
+        };
+        let non_lure_false_alarm_rate = if non_lure_trials > 0 {
+            non_lure_false_alarms as f64 / non_lure_trials as f64
+        } else {
+            0.0
+        };
+        let lure_d_prime = if lure_trials > 0 {
+            signal_detection_indices(hits, lure_false_alarms, target_trials, lure_trials).0
+        } else {
+            0.0
+        };
+
         let accuracy = if total_trials > 0 {
             (hits + correct_rejections) as f64 / total_trials as f64
         } else {
@@ -126,6 +231,14 @@ impl NBackMetrics {
             p10_hit_rt_ms,
             p90_hit_rt_ms,
             response_count,
+            lure_trials,
+            lure_false_alarms,
+            non_lure_false_alarms,
+            lure_false_alarm_rate,
+            non_lure_false_alarm_rate,
+            lure_d_prime,
+            n: two_back(),
+            adaptive: false,
         }
     }
 }
@@ -316,4 +429,57 @@ mod tests {
         assert!(metrics.criterion.is_finite());
         assert_eq!(metrics.median_hit_rt_ms, 480.0);
     }
+
+    fn trial(index: usize, is_target: bool, is_lure: bool, outcome: TrialOutcome) -> NBackTrial {
+        NBackTrial {
+            index,
+            letter: 'B',
+            is_target,
+            is_lure,
+            presented_at: None,
+            response: None,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn false_alarms_split_by_lure() {
+        let fa = || TrialOutcome::FalseAlarm { rt_ms: 500.0 };
+        let trials = vec![
+            trial(0, true, false, TrialOutcome::Hit { rt_ms: 450.0 }),
+            trial(1, true, false, TrialOutcome::Hit { rt_ms: 470.0 }),
+            trial(2, false, true, fa()),
+            trial(3, false, true, fa()),
+            trial(4, false, true, TrialOutcome::CorrectRejection),
+            trial(5, false, true, TrialOutcome::CorrectRejection),
+            trial(6, false, false, fa()),
+            trial(7, false, false, TrialOutcome::CorrectRejection),
+            trial(8, false, false, TrialOutcome::CorrectRejection),
+            trial(9, false, false, TrialOutcome::CorrectRejection),
+        ];
+
+        let metrics = NBackMetrics::from_trials(&trials);
+        assert!(metrics.has_lure_measures());
+        assert_eq!(metrics.lure_trials, 4);
+        assert_eq!(metrics.false_alarms, 3);
+        assert_eq!(metrics.lure_false_alarms, 2);
+        assert_eq!(metrics.non_lure_false_alarms, 1);
+        assert_eq!(metrics.lure_false_alarm_rate, 0.5);
+        assert_eq!(metrics.non_lure_false_alarm_rate, 0.25);
+        // Lures are the harder noise, so discrimination against them is lower.
+        assert!(metrics.lure_d_prime < metrics.d_prime);
+
+        let no_lures = NBackMetrics::from_trials(&trials[6..]);
+        assert!(!no_lures.has_lure_measures());
+        assert_eq!(no_lures.lure_d_prime, 0.0);
+    }
+
+    #[test]
+    fn default_and_a_stored_run_without_n_agree_on_two_back() {
+        let mut stored = serde_json::to_value(NBackMetrics::default()).unwrap();
+        stored.as_object_mut().unwrap().remove("n");
+        let read: NBackMetrics = serde_json::from_value(stored).unwrap();
+        assert_eq!(read.n, 2);
+        assert_eq!(NBackMetrics::empty().n, read.n);
+    }
 }
@@ -8,10 +8,12 @@ use futures_util::StreamExt;
 use crate::core::format;
 use crate::core::qc::QualityFlags;
 use crate::core::readiness::{self, Readiness};
+use crate::core::store::{use_shared_store, SharedStore};
 use crate::core::{platform, storage, timing};
 
 use super::engine::{
-    AdvanceOutcome, EngineState, NBackEngine, ResponseOutcome, RunMode, TrialOutcome, TrialSchedule,
+    AdvanceOutcome, EngineState, NBackConfig, NBackEngine, ResponseOutcome, RunMode, TrialOutcome,
+    TrialSchedule, MAX_LEVEL, MIN_LEVEL,
 };
 use super::metrics::NBackMetrics;
 
@@ -26,27 +28,28 @@ pub fn NBackView() -> Element {
     let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
     let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
 
-    let engine = use_signal(NBackEngine::default);
+    let mut engine = use_signal(|| {
+        NBackEngine::new(NBackConfig {
+            n: storage::nback_level().map(usize::from).unwrap_or(2),
+            adaptive: storage::nback_adaptive(),
+            ..Default::default()
+        })
+    });
     let qc_flags = use_signal(QualityFlags::pristine);
     let practice_metrics = use_signal(|| Option::<NBackMetrics>::None);
     let last_metrics = use_signal(|| Option::<NBackMetrics>::None);
     let last_error = use_signal(|| Option::<String>::None);
     let feedback_state = use_signal(|| Option::<FeedbackState>::None);
     // Readiness (cooldown advisory) – compute once per mount; updates only after a run completes (page reload or future reactive trigger).
-    let readiness_info = use_signal(|| match storage::load_summaries() {
-        Ok(mut records) => {
-            records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-            let last = records.iter().find(|r| r.task == "nback2");
-            readiness::evaluate("nback2", last)
-        }
-        Err(_) => readiness::evaluate("nback2", None),
-    });
+    let store = use_shared_store();
+    let mut readiness_info = use_signal(|| current_readiness(&store));
 
     let sender_slot: Rc<RefCell<Option<UnboundedSender<NBackEvent>>>> = Rc::new(RefCell::new(None));
     let sender_slot_for_loop = sender_slot.clone();
 
     let coroutine = {
         let engine_ref = engine;
+        let store_ref = store.clone();
         let qc_ref = qc_flags;
         let practice_ref = practice_metrics;
         let last_metrics_ref = last_metrics;
@@ -55,6 +58,7 @@ pub fn NBackView() -> Element {
 
         use_coroutine(move |mut rx: UnboundedReceiver<NBackEvent>| {
             let sender_slot = sender_slot_for_loop.clone();
+            let store = store_ref.clone();
             let mut engine = engine_ref;
             let mut qc_flags = qc_ref;
             let mut practice_metrics = practice_ref;
@@ -98,6 +102,16 @@ pub fn NBackView() -> Element {
                         NBackEvent::Abort => {
                             engine.with_mut(|eng| eng.abort());
                         }
+                        NBackEvent::Pause => {
+                            if engine.with_mut(|eng| eng.pause()) {
+                                feedback_signal.set(None);
+                            }
+                        }
+                        NBackEvent::Resume => {
+                            if let Some(schedule) = engine.with_mut(|eng| eng.resume()) {
+                                queue_trial(sender_slot.clone(), schedule);
+                            }
+                        }
                         NBackEvent::StimulusReady {
                             run_id,
                             trial_index,
@@ -180,20 +194,28 @@ pub fn NBackView() -> Element {
                                     queue_trial(sender_slot.clone(), schedule);
                                 }
                                 AdvanceOutcome::Completed { mode } => {
-                                    finalize_run(
+                                    let recorded = finalize_run(
+                                        &store,
                                         mode,
                                         &engine,
                                         qc_flags,
                                         practice_metrics,
                                         last_metrics,
                                         last_error,
-                                        readiness_info,
                                     );
+                                    // Refresh readiness advisory immediately so the
+                                    // cooldown banner updates.
+                                    if recorded {
+                                        readiness_info.set(current_readiness(&store));
+                                    }
                                 }
                                 AdvanceOutcome::Ignored => {}
                             }
                         }
                         NBackEvent::FocusLost => {
+                            if engine.with(|eng| matches!(eng.state, EngineState::Paused { .. })) {
+                                continue;
+                            }
                             qc_flags.with_mut(|flags| {
                                 flags.log_focus_loss();
                                 flags.log_visibility_blur();
@@ -230,7 +252,7 @@ pub fn NBackView() -> Element {
     let engine_snapshot = engine();
 
     let (active_mode, current_letter) = match engine_snapshot.state {
-        EngineState::Waiting { mode, .. } => (Some(mode), None),
+        EngineState::Waiting { mode, .. } | EngineState::Paused { mode, .. } => (Some(mode), None),
         EngineState::StimulusActive { mode, trial_index } => {
             let letter = engine_snapshot
                 .trials()
@@ -242,6 +264,9 @@ pub fn NBackView() -> Element {
     };
 
     let is_running = active_mode.is_some();
+    let is_paused = matches!(engine_snapshot.state, EngineState::Paused { .. });
+    let level = engine_snapshot.level();
+    let adaptive = engine_snapshot.config.adaptive;
     let total_trials = match active_mode.unwrap_or(RunMode::Main) {
         RunMode::Practice => engine_snapshot.config.practice_trials,
         RunMode::Main => engine_snapshot.config.total_trials,
@@ -281,31 +306,52 @@ pub fn NBackView() -> Element {
                         div { class: "task-mode-badge", "{mode_label}" }
                     }
 
-                    button {
-                        r#type: "button",
-                        class: "task-nback__hitbox",
-                        aria_label: crate::t!("nback-aria-respond"),
-                        autofocus: true,
-                        onclick: move |_| respond_now(),
-                        onkeydown: move |evt| {
-                            let key = evt.key().to_string().to_lowercase();
-                            if matches!(key.as_str(), " " | "space" | "spacebar" | "enter" | "j") {
-                                evt.prevent_default();
-                                respond_now();
-                            }
-                        },
-                        onfocusout: move |_| send_event(NBackEvent::FocusLost),
-
-                        div { class: "task-nback__glyph",
-                            if let Some(letter) = current_letter {
-                                "{letter}"
-                            } else {
-                                span { {crate::t!("nback-get-ready")} }
+                    if is_paused {
+                        div { class: "task-canvas__paused",
+                            p { {crate::t!("task-paused-note")} }
+                            button {
+                                r#type: "button",
+                                class: "button button--primary",
+                                autofocus: true,
+                                onclick: move |_| send_event(NBackEvent::Resume),
+                                {crate::t!("common-resume")}
                             }
                         }
+                    } else {
+                        button {
+                            class: "button button--ghost button--compact task-canvas__pause",
+                            // Keep focus on the hitbox so pausing is not logged as a focus loss.
+                            onmousedown: move |evt| evt.prevent_default(),
+                            onclick: move |_| send_event(NBackEvent::Pause),
+                            {crate::t!("common-pause")}
+                        }
 
-                        if let Some(feedback) = feedback.clone() {
-                            div { class: format!("task-feedback {}", feedback.css_class()), "{feedback.message}" }
+                        button {
+                            r#type: "button",
+                            class: "task-nback__hitbox",
+                            aria_label: crate::t!("nback-aria-respond"),
+                            autofocus: true,
+                            onclick: move |_| respond_now(),
+                            onkeydown: move |evt| {
+                                let key = evt.key().to_string().to_lowercase();
+                                if matches!(key.as_str(), " " | "space" | "spacebar" | "enter" | "j") {
+                                    evt.prevent_default();
+                                    respond_now();
+                                }
+                            },
+                            onfocusout: move |_| send_event(NBackEvent::FocusLost),
+
+                            div { class: "task-nback__glyph",
+                                if let Some(letter) = current_letter {
+                                    "{letter}"
+                                } else {
+                                    span { {crate::t!("nback-get-ready")} }
+                                }
+                            }
+
+                            if let Some(feedback) = feedback.clone() {
+                                div { class: format!("task-feedback {}", feedback.css_class()), "{feedback.message}" }
+                            }
                         }
                     }
 
@@ -329,9 +375,43 @@ pub fn NBackView() -> Element {
                     // Hidden i18n marker to force re-render of instruction copy when locale changes
                     div { style: "display:none", "{_lang_marker}" }
                     h3 { {crate::t!("nback-how-summary")} }
+                    div { class: "task-nback__level",
+                        label {
+                            {crate::t!("nback-level-label")}
+                            select {
+                                value: level.to_string(),
+                                onchange: move |e| {
+                                    if let Ok(picked) = e.value().parse::<usize>() {
+                                        let picked = picked.clamp(MIN_LEVEL, MAX_LEVEL);
+                                        engine.with_mut(|eng| eng.config.n = picked);
+                                        storage::set_nback_level(picked as u8, adaptive);
+                                    }
+                                },
+                                for option_level in MIN_LEVEL..=MAX_LEVEL {
+                                    option {
+                                        value: option_level.to_string(),
+                                        selected: option_level == level,
+                                        {crate::t!("nback-level-option", n = option_level)}
+                                    }
+                                }
+                            }
+                        }
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: adaptive,
+                                onchange: move |e| {
+                                    let on = e.checked();
+                                    engine.with_mut(|eng| eng.config.adaptive = on);
+                                    storage::set_nback_level(level as u8, on);
+                                },
+                            }
+                            {crate::t!("nback-adaptive-label")}
+                        }
+                    }
                     ul {
                         li { {crate::t!("nback-how-step-timing")} }
-                        li { {crate::t!("nback-how-step-rule")} }
+                        li { {crate::t!("nback-how-step-rule", n = level)} }
                         li { {crate::t!("nback-how-step-practice")} }
                         li { {crate::t!("nback-how-step-strategy")} }
                     }
@@ -368,6 +448,7 @@ pub fn NBackView() -> Element {
                     section { class: "task-card task-nback__metrics",
                         h3 { {crate::t!("nback-last-session")} }
                         ul { class: "metrics-grid",
+                            li { {crate::t!("nback-metric-level")} ": " {crate::t!("nback-level-option", n = metrics.n)} }
                             li { {crate::t!("nback-metric-hits")} ": " {metrics.hits.to_string()} " / " {metrics.target_trials.to_string()} }
                             li { {crate::t!("nback-metric-misses")} ": " {metrics.misses.to_string()} }
                             li { {crate::t!("nback-metric-false-alarms")} ": " {metrics.false_alarms.to_string()} }
@@ -375,10 +456,18 @@ pub fn NBackView() -> Element {
                             li { {crate::t!("nback-metric-accuracy")} ": " {(metrics.accuracy * 100.0).round().to_string()} "%" }
                             li { {crate::t!("nback-metric-dprime")} ": " {format!("{:.2}", metrics.d_prime).to_string()} }
                             li { {crate::t!("nback-metric-criterion")} ": " {format!("{:.2}", metrics.criterion).to_string()} }
+                            if metrics.has_lure_measures() {
+                                li { {crate::t!("nback-metric-lure-false-alarms")} ": " {metrics.lure_false_alarms.to_string()} " / " {metrics.lure_trials.to_string()} }
+                                li { {crate::t!("nback-metric-non-lure-false-alarms")} ": " {metrics.non_lure_false_alarms.to_string()} }
+                                li { {crate::t!("nback-metric-lure-dprime")} ": " {format!("{:.2}", metrics.lure_d_prime).to_string()} }
+                            }
                             li { {crate::t!("nback-metric-median-hit-rt")} ": " {format::format_ms(metrics.median_hit_rt_ms).to_string()} }
                             li { {crate::t!("nback-metric-mean-hit-rt")} ": " {format::format_ms(metrics.mean_hit_rt_ms).to_string()} }
                             li { {crate::t!("nback-metric-hit-rt-p10p90")} ": " {format::format_ms(metrics.p10_hit_rt_ms).to_string()} " / " {format::format_ms(metrics.p90_hit_rt_ms).to_string()} }
                         }
+                        if metrics.adaptive {
+                            p { class: "task-guidance", {crate::t!("nback-next-level", n = level)} }
+                        }
                     }
                 } else {
                     section { class: "task-card task-nback__metrics task-metrics--placeholder",
@@ -395,52 +484,78 @@ pub fn NBackView() -> Element {
 }
 
 fn finalize_run(
+    store: &SharedStore,
     mode: RunMode,
     engine: &Signal<NBackEngine>,
     mut qc_flags: Signal<QualityFlags>,
     mut practice_metrics: Signal<Option<NBackMetrics>>,
     mut last_metrics: Signal<Option<NBackMetrics>>,
     mut last_error: Signal<Option<String>>,
-    mut readiness_info: Signal<Readiness>,
-) {
+) -> bool {
     match mode {
         RunMode::Practice => {
             if let Some(metrics) = engine.with(|eng| eng.practice_metrics()) {
                 practice_metrics.set(Some(metrics));
             }
+            false
         }
         RunMode::Main => {
             if let Some(metrics) = engine.with(|eng| eng.main_metrics()) {
-                qc_flags.with_mut(|flags| flags.mark_min_trials(true));
+                let (pause_count, paused_ms) =
+                    engine.with(|eng| (eng.pause_count(), eng.paused_ms()));
+                // Adaptive mode has already stepped the level; start there next time.
+                if metrics.adaptive {
+                    let n = engine.with(|eng| eng.level());
+                    storage::set_nback_level(n as u8, true);
+                }
+                qc_flags.with_mut(|flags| {
+                    flags.mark_min_trials(true);
+                    flags.record_pauses(pause_count, paused_ms);
+                });
                 let qc_snapshot = qc_flags();
                 match serde_json::to_value(&metrics) {
                     Ok(metrics_json) => {
+                        // Every level keeps the 2-back's task id, so runs stay one
+                        // series; `metrics.n` tells the levels apart.
                         let record =
                             storage::SummaryRecord::new("nback2", metrics_json, qc_snapshot);
-                        if let Err(err) = storage::append_summary(&record) {
+                        let trials = if storage::keep_trials() {
+                            engine.with(|eng| eng.trial_samples())
+                        } else {
+                            Vec::new()
+                        };
+                        if let Err(err) = storage::append_summary(store, &record, &trials) {
                             last_error.set(Some(format!("Failed to persist summary: {err}")));
                         } else {
                             last_error.set(None);
                         }
                         last_metrics.set(Some(metrics));
-
-                        // Refresh readiness advisory immediately so the cooldown banner updates
-                        if let Ok(mut records) = storage::load_summaries() {
-                            records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-                            let last = records.iter().find(|r| r.task == "nback2");
-                            let updated = readiness::evaluate("nback2", last);
-                            readiness_info.set(updated);
-                        }
+                        true
                     }
                     Err(err) => {
                         last_error.set(Some(format!("Failed to serialise metrics: {err}")));
+                        false
                     }
                 }
+            } else {
+                false
             }
         }
     }
 }
 
+/// Cooldown advisory from the most recent stored 2-back run.
+fn current_readiness(store: &SharedStore) -> Readiness {
+    match storage::load_summaries(store) {
+        Ok(mut records) => {
+            records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            let last = records.iter().find(|r| r.task == "nback2");
+            readiness::evaluate("nback2", last)
+        }
+        Err(_) => readiness::evaluate("nback2", None),
+    }
+}
+
 fn queue_trial(
     sender_slot: Rc<RefCell<Option<UnboundedSender<NBackEvent>>>>,
     schedule: TrialSchedule,
@@ -500,6 +615,8 @@ enum NBackEvent {
     StartPractice,
     StartMain,
     Abort,
+    Pause,
+    Resume,
     StimulusReady {
         run_id: u64,
         trial_index: usize,
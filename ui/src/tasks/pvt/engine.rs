@@ -2,15 +2,108 @@
 
 use rand::Rng;
 
+use crate::core::storage::TrialSample;
 use crate::core::timing::{self, InstantStamp};
 
 use super::metrics::PvtMetrics;
 
 const FALSE_START_THRESHOLD_MS: f64 = 100.0;
 
+/// A named PVT protocol. Every run records the preset it used, so runs of
+/// different protocols are never compared with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PvtPreset {
+    /// The original 20-trial block with a 1 s response window.
+    #[default]
+    Quick,
+    /// The 3-minute brief PVT (PVT-B): 1–4 s intervals.
+    PvtB3Min,
+    /// A 5-minute run with 2–10 s intervals.
+    Pvt5Min,
+    /// The standard 10-minute PVT with 2–10 s intervals.
+    Pvt10Min,
+}
+
+impl PvtPreset {
+    pub const ALL: [PvtPreset; 4] = [
+        PvtPreset::Quick,
+        PvtPreset::PvtB3Min,
+        PvtPreset::Pvt5Min,
+        PvtPreset::Pvt10Min,
+    ];
+
+    /// Stable id stored in the run's metrics.
+    pub fn id(self) -> &'static str {
+        match self {
+            PvtPreset::Quick => "quick",
+            PvtPreset::PvtB3Min => "pvt_b_3min",
+            PvtPreset::Pvt5Min => "pvt_5min",
+            PvtPreset::Pvt10Min => "pvt_10min",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| preset.id() == id)
+    }
+
+    /// Short English name, for the results panels.
+    pub fn label(self) -> &'static str {
+        match self {
+            PvtPreset::Quick => "Quick (20 trials)",
+            PvtPreset::PvtB3Min => "PVT-B (3 min)",
+            PvtPreset::Pvt5Min => "5-minute PVT",
+            PvtPreset::Pvt10Min => "10-minute PVT",
+        }
+    }
+
+    /// Reaction time (ms) at or above which a response is a lapse: 355 ms on
+    /// PVT-B (Basner, Mollicone & Dinges 2011), the standard 500 ms otherwise.
+    pub fn lapse_threshold_ms(self) -> f64 {
+        match self {
+            PvtPreset::PvtB3Min => 355.0,
+            PvtPreset::Quick | PvtPreset::Pvt5Min | PvtPreset::Pvt10Min => 500.0,
+        }
+    }
+
+    pub fn config(self) -> PvtConfig {
+        // Time-based protocols give up on a trial only after 30 s, and count a
+        // run as complete with about half the reactions it should collect.
+        let timed = |duration_ms: u64, min_iti_ms: u64, max_iti_ms: u64, min_reaction_trials| {
+            PvtConfig {
+                preset: self,
+                target_trials: 0,
+                duration_ms: Some(duration_ms),
+                min_iti_ms,
+                max_iti_ms,
+                max_response_ms: 30_000,
+                min_reaction_trials,
+            }
+        };
+        match self {
+            PvtPreset::Quick => PvtConfig {
+                preset: self,
+                target_trials: 20,
+                duration_ms: None,
+                min_iti_ms: 2_000,
+                max_iti_ms: 10_000,
+                max_response_ms: 1_000,
+                min_reaction_trials: 12,
+            },
+            PvtPreset::PvtB3Min => timed(180_000, 1_000, 4_000, 30),
+            PvtPreset::Pvt5Min => timed(300_000, 2_000, 10_000, 24),
+            PvtPreset::Pvt10Min => timed(600_000, 2_000, 10_000, 48),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PvtConfig {
+    pub preset: PvtPreset,
+    /// Trials per run; unused when `duration_ms` is set.
     pub target_trials: usize,
+    /// Run until this much time has passed instead of counting trials. No
+    /// stimulus is scheduled past the end.
+    pub duration_ms: Option<u64>,
     pub min_iti_ms: u64,
     pub max_iti_ms: u64,
     pub max_response_ms: u64,
@@ -19,13 +112,7 @@ pub struct PvtConfig {
 
 impl Default for PvtConfig {
     fn default() -> Self {
-        Self {
-            target_trials: 20,
-            min_iti_ms: 2_000,
-            max_iti_ms: 10_000,
-            max_response_ms: 1_000,
-            min_reaction_trials: 12,
-        }
+        PvtPreset::default().config()
     }
 }
 
@@ -38,6 +125,9 @@ pub struct PvtEngine {
     pub run_started_at: Option<InstantStamp>,
     pub run_finished_at: Option<InstantStamp>,
     total_false_starts: u32,
+    paused_at: Option<InstantStamp>,
+    pause_count: u32,
+    paused_ms: f64,
 }
 
 impl Default for PvtEngine {
@@ -56,6 +146,9 @@ impl PvtEngine {
             run_started_at: None,
             run_finished_at: None,
             total_false_starts: 0,
+            paused_at: None,
+            pause_count: 0,
+            paused_ms: 0.0,
         }
     }
 
@@ -65,6 +158,9 @@ impl PvtEngine {
         self.run_started_at = None;
         self.run_finished_at = None;
         self.total_false_starts = 0;
+        self.paused_at = None;
+        self.pause_count = 0;
+        self.paused_ms = 0.0;
     }
 
     pub fn start(&mut self) -> Option<ScheduledStimulus> {
@@ -96,6 +192,63 @@ impl PvtEngine {
         self.run_finished_at = Some(timing::now());
     }
 
+    /// Pause a running block. The in-flight trial is voided, and bumping
+    /// `run_id` strands its scheduled stimulus and timeout.
+    pub fn pause(&mut self) -> bool {
+        let trial_index = match self.state {
+            EngineState::Waiting { trial_index } | EngineState::StimulusActive { trial_index } => {
+                trial_index
+            }
+            _ => return false,
+        };
+
+        if self
+            .trials
+            .get(trial_index)
+            .is_some_and(|trial| !trial.is_completed())
+        {
+            self.trials.truncate(trial_index);
+        }
+        self.run_id = self.run_id.wrapping_add(1);
+        self.paused_at = Some(timing::now());
+        self.pause_count = self.pause_count.saturating_add(1);
+        self.state = EngineState::Paused;
+        true
+    }
+
+    /// Resume a paused block with a fresh trial and interval.
+    pub fn resume(&mut self) -> Option<ScheduledStimulus> {
+        if !matches!(self.state, EngineState::Paused) {
+            return None;
+        }
+
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_ms += timing::elapsed_ms(paused_at);
+        }
+        let next_index = self.trials.len();
+        let iti = self.random_iti();
+        self.trials.push(PvtTrial::new(next_index, iti));
+        self.state = EngineState::Waiting {
+            trial_index: next_index,
+        };
+
+        Some(ScheduledStimulus {
+            run_id: self.run_id,
+            trial_index: next_index,
+            wait_ms: iti,
+        })
+    }
+
+    /// Times this run was paused.
+    pub fn pause_count(&self) -> u32 {
+        self.pause_count
+    }
+
+    /// Total time this run has spent paused, including a pause in progress.
+    pub fn paused_ms(&self) -> f64 {
+        self.paused_ms + self.paused_at.map(timing::elapsed_ms).unwrap_or_default()
+    }
+
     pub fn mark_stimulus_on(&mut self, trial_index: usize, timestamp: InstantStamp) -> bool {
         if !matches!(self.state, EngineState::Waiting { trial_index: idx } if idx == trial_index) {
             return false;
@@ -165,18 +318,67 @@ impl PvtEngine {
             &self.trials,
             self.total_false_starts,
             self.config.min_reaction_trials,
+            self.config.preset,
         ))
     }
 
+    /// Milliseconds of the run spent unpaused, or 0 before it started.
+    pub fn elapsed_ms(&self) -> f64 {
+        self.run_started_at
+            .map(|start| (timing::elapsed_ms(start) - self.paused_ms()).max(0.0))
+            .unwrap_or_default()
+    }
+
+    /// The finished trials, for keeping with the run; times are from the run's
+    /// start. A false start during the stimulus keeps its RT, one before it
+    /// has none.
+    pub fn trial_samples(&self) -> Vec<TrialSample> {
+        let Some(run_start) = self.run_started_at else {
+            return Vec::new();
+        };
+        self.trials
+            .iter()
+            .filter_map(|trial| {
+                let (outcome, rt_ms) = match trial.outcome {
+                    TrialOutcome::Pending => return None,
+                    TrialOutcome::Reaction { rt_ms } => ("reaction", Some(rt_ms)),
+                    TrialOutcome::Lapse => ("lapse", None),
+                    TrialOutcome::FalseStart => (
+                        "false_start",
+                        trial
+                            .stimulus_onset
+                            .zip(trial.response_at)
+                            .map(|(onset, at)| timing::duration_ms(onset, at)),
+                    ),
+                };
+                Some(TrialSample {
+                    index: trial.index,
+                    iti_ms: Some(trial.iti_ms as f64),
+                    onset_ms: trial.onset_since_start_ms,
+                    response_ms: trial.response_at.map(|at| timing::duration_ms(run_start, at)),
+                    rt_ms,
+                    outcome: outcome.to_string(),
+                    stimulus: None,
+                    target: None,
+                    lure: None,
+                })
+            })
+            .collect()
+    }
+
     fn schedule_next(&mut self, _just_finished: usize) -> ResponseOutcome {
-        if self.completed_trial_count() >= self.config.target_trials {
+        let iti = self.random_iti();
+        let finished = match self.config.duration_ms {
+            Some(duration_ms) => self.elapsed_ms() + iti as f64 >= duration_ms as f64,
+            None => self.completed_trial_count() >= self.config.target_trials,
+        };
+        if finished {
             self.state = EngineState::Completed;
             self.run_finished_at = Some(timing::now());
             return ResponseOutcome::RunCompleted;
         }
 
         let next_index = self.trials.len();
-        let iti = self.random_iti();
         self.trials.push(PvtTrial::new(next_index, iti));
         self.state = EngineState::Waiting {
             trial_index: next_index,
@@ -249,6 +451,7 @@ pub enum EngineState {
     Idle,
     Waiting { trial_index: usize },
     StimulusActive { trial_index: usize },
+    Paused,
     Completed,
     Aborted,
 }
@@ -266,3 +469,78 @@ pub enum ResponseOutcome {
     RunCompleted,
     Ignored,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn presets_round_trip_through_their_ids() {
+        for preset in PvtPreset::ALL {
+            assert_eq!(PvtPreset::from_id(preset.id()), Some(preset));
+            assert_eq!(preset.config().preset, preset);
+        }
+        assert_eq!(PvtPreset::from_id("unknown"), None);
+        assert_eq!(PvtConfig::default().target_trials, 20);
+        assert_eq!(PvtPreset::Pvt10Min.config().max_response_ms, 30_000);
+    }
+
+    #[test]
+    fn timed_runs_end_on_the_clock_not_the_trial_count() {
+        let mut engine = PvtEngine::new(PvtPreset::PvtB3Min.config());
+        engine.start().expect("schedule");
+        engine.mark_stimulus_on(0, timing::now());
+        assert!(matches!(
+            engine.register_timeout(0),
+            ResponseOutcome::NextScheduled(_)
+        ));
+
+        // Pretend the run began just short of three minutes ago: the next
+        // interval can't fit, so the run ends and reports its preset.
+        engine.run_started_at = timing::now().checked_sub(Duration::from_millis(179_500));
+        engine.mark_stimulus_on(1, timing::now());
+        assert!(matches!(
+            engine.register_timeout(1),
+            ResponseOutcome::RunCompleted
+        ));
+
+        let metrics = engine.metrics().expect("metrics");
+        assert_eq!(metrics.total_trials, 2);
+        assert_eq!(metrics.preset, "pvt_b_3min");
+    }
+
+    #[test]
+    fn pausing_voids_the_trial_in_flight_and_strands_its_schedule() {
+        let mut engine = PvtEngine::default();
+        let first = engine.start().expect("schedule");
+        engine.mark_stimulus_on(0, timing::now());
+        assert!(matches!(
+            engine.register_timeout(0),
+            ResponseOutcome::NextScheduled(_)
+        ));
+        engine.mark_stimulus_on(1, timing::now());
+
+        assert!(engine.pause());
+        assert!(!engine.pause());
+        assert_eq!(engine.state, EngineState::Paused);
+        assert_eq!(engine.trials.len(), 1);
+        assert_ne!(engine.run_id, first.run_id);
+        // The voided trial's timeout no longer lands.
+        assert!(matches!(
+            engine.register_timeout(1),
+            ResponseOutcome::Ignored
+        ));
+
+        let resumed = engine.resume().expect("reschedule");
+        assert_eq!(resumed.run_id, engine.run_id);
+        assert_eq!(resumed.trial_index, 1);
+        assert_eq!(
+            engine.state,
+            EngineState::Waiting { trial_index: 1 }
+        );
+        assert!(engine.resume().is_none());
+        assert_eq!(engine.pause_count(), 1);
+    }
+}
@@ -1,10 +1,20 @@
 //! Metric definitions and aggregation helpers for PVT summaries.
+//!
+//! Besides the RT distribution this computes the outcome measures of Basner &
+//! Dinges (2011), "Maximizing sensitivity of the PVT to sleep loss": mean
+//! reciprocal RT (response speed, 1/s), the means of the fastest and slowest
+//! 10% of RTs, the lapse probability `lapses / valid stimuli` and the
+//! performance score `1 − (lapses + false starts) / (valid stimuli + false
+//! starts)`. A valid stimulus is one answered or timed out; a false start
+//! ends its trial without one. For these two a lapse is a timeout or an RT at
+//! or above the preset's [`PvtPreset::lapse_threshold_ms`] — 355 ms on PVT-B —
+//! while `lapses_ge_500ms` keeps counting against 500 ms on every preset.
 
 use serde::{Deserialize, Serialize};
 
 use crate::core::timing;
 
-use super::engine::{PvtTrial, TrialOutcome};
+use super::engine::{PvtPreset, PvtTrial, TrialOutcome};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PvtMetrics {
@@ -19,21 +29,68 @@ pub struct PvtMetrics {
     pub minor_lapses_355_499ms: u32,
     pub false_starts: u32,
     pub time_on_task_slope_ms_per_min: f64,
+    /// Mean of `1 / RT` over the reactions, RT in seconds.
+    #[serde(default)]
+    pub mean_reciprocal_rt_per_s: f64,
+    /// Mean of the fastest 10% of reaction times.
+    #[serde(default)]
+    pub fastest_10pct_rt_ms: f64,
+    /// Mean of the slowest 10% of reaction times.
+    #[serde(default)]
+    pub slowest_10pct_rt_ms: f64,
+    /// `lapses / valid stimuli`, against the preset's lapse threshold; 0 for a
+    /// run without any.
+    #[serde(default)]
+    pub lapse_probability: f64,
+    /// `1 − (lapses + false starts) / (valid stimuli + false starts)`: 1 is a
+    /// perfect run.
+    #[serde(default)]
+    pub performance_score: f64,
     pub meets_min_trial_requirement: bool,
+    /// Id of the protocol the run used ([`PvtPreset::id`]). Runs saved before
+    /// presets existed were all the quick block.
+    #[serde(default = "quick_preset_id")]
+    pub preset: String,
+}
+
+fn quick_preset_id() -> String {
+    PvtPreset::Quick.id().to_string()
 }
 
 impl PvtMetrics {
-    pub fn from_trials(trials: &[PvtTrial], false_starts: u32, min_required: usize) -> Self {
+    /// Whether the run carries the reciprocal-RT, 10% tail and lapse probability
+    /// measures; runs saved before they existed read them as zero. (Any run
+    /// with a valid stimulus has a positive lapse probability or performance
+    /// score.)
+    pub fn has_outcome_measures(&self) -> bool {
+        self.lapse_probability + self.performance_score > 0.0
+    }
+
+    /// The protocol behind these metrics; unknown ids read as the quick block.
+    pub fn preset(&self) -> PvtPreset {
+        PvtPreset::from_id(&self.preset).unwrap_or_default()
+    }
+
+    pub fn from_trials(
+        trials: &[PvtTrial],
+        false_starts: u32,
+        min_required: usize,
+        preset: PvtPreset,
+    ) -> Self {
+        let threshold_ms = preset.lapse_threshold_ms();
         let total_trials = trials.iter().filter(|trial| trial.is_completed()).count();
 
         let mut reaction_times = Vec::new();
         let mut reaction_offsets = Vec::new();
         let mut lapses_ge_500ms = 0u32;
         let mut minor_lapses = 0u32;
+        let mut lapses = 0u32;
+        let mut valid_stimuli = 0u32;
 
         for trial in trials {
             match trial.outcome {
                 TrialOutcome::Reaction { rt_ms } => {
+                    valid_stimuli += 1;
                     reaction_times.push(rt_ms);
                     let minutes = trial
                         .onset_since_start_ms
@@ -46,19 +103,41 @@ impl PvtMetrics {
                     } else if (355.0..500.0).contains(&rt_ms) {
                         minor_lapses += 1;
                     }
+                    if rt_ms >= threshold_ms {
+                        lapses += 1;
+                    }
                 }
                 TrialOutcome::Lapse => {
+                    valid_stimuli += 1;
                     lapses_ge_500ms += 1;
+                    lapses += 1;
                 }
                 TrialOutcome::FalseStart | TrialOutcome::Pending => {}
             }
         }
 
+        let lapses = f64::from(lapses);
+        let lapse_probability = if valid_stimuli > 0 {
+            lapses / f64::from(valid_stimuli)
+        } else {
+            0.0
+        };
+        let attempts = f64::from(valid_stimuli) + f64::from(false_starts);
+        let performance_score = if attempts > 0.0 {
+            1.0 - (lapses + f64::from(false_starts)) / attempts
+        } else {
+            0.0
+        };
+
         if reaction_times.is_empty() {
             return Self {
                 total_trials,
+                lapses_ge_500ms,
                 false_starts,
+                lapse_probability,
+                performance_score,
                 meets_min_trial_requirement: false,
+                preset: preset.id().to_string(),
                 ..Default::default()
             };
         }
@@ -68,6 +147,15 @@ impl PvtMetrics {
         let mut sorted_times = reaction_times.clone();
         sorted_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
+        let reciprocal_mean = mean(
+            &reaction_times
+                .iter()
+                .map(|rt| 1_000.0 / rt)
+                .collect::<Vec<_>>(),
+        );
+        let tail = tail_len(sorted_times.len());
+        let fastest = mean(&sorted_times[..tail]);
+        let slowest = mean(&sorted_times[sorted_times.len() - tail..]);
         let mean = mean(&reaction_times);
         let sd = std_dev(&reaction_times, mean);
         let median = percentile(&sorted_times, 0.5);
@@ -87,7 +175,13 @@ impl PvtMetrics {
             minor_lapses_355_499ms: minor_lapses,
             false_starts,
             time_on_task_slope_ms_per_min: slope,
+            mean_reciprocal_rt_per_s: reciprocal_mean,
+            fastest_10pct_rt_ms: fastest,
+            slowest_10pct_rt_ms: slowest,
+            lapse_probability,
+            performance_score,
             meets_min_trial_requirement: reacted_trials >= min_required,
+            preset: preset.id().to_string(),
         }
     }
 }
@@ -100,6 +194,12 @@ fn mean(data: &[f64]) -> f64 {
     }
 }
 
+/// How many RTs make up a 10% tail: a tenth of them, rounded to the nearest
+/// (halves up), and at least one.
+fn tail_len(n: usize) -> usize {
+    ((n as f64 * 0.1).round() as usize).clamp(1, n.max(1))
+}
+
 fn std_dev(data: &[f64], mean: f64) -> f64 {
     let n = data.len();
     if n < 2 {
@@ -160,3 +260,149 @@ fn slope_minutes(xs_minutes: &[f64], ys_ms: &[f64]) -> f64 {
         (n * sum_xy - sum_x * sum_y) / denominator
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trial(index: usize, outcome: TrialOutcome) -> PvtTrial {
+        let mut trial = PvtTrial::new(index, 2_000);
+        trial.onset_since_start_ms = Some(2_000.0 * (index + 1) as f64);
+        trial.outcome = outcome;
+        trial
+    }
+
+    fn reaction(index: usize, rt_ms: f64) -> PvtTrial {
+        trial(index, TrialOutcome::Reaction { rt_ms })
+    }
+
+    #[test]
+    fn outcome_measures_on_a_worked_run() {
+        // Ten reactions (200, 250, 400, 500, 1000 ms, twice), one timeout and
+        // one false start: twelve trials.
+        let mut trials: Vec<PvtTrial> = [200.0, 250.0, 400.0, 500.0, 1_000.0]
+            .iter()
+            .cycle()
+            .take(10)
+            .enumerate()
+            .map(|(index, &rt_ms)| reaction(index, rt_ms))
+            .collect();
+        trials.push(trial(10, TrialOutcome::Lapse));
+        trials.push(trial(11, TrialOutcome::FalseStart));
+
+        let metrics = PvtMetrics::from_trials(&trials, 1, 5, PvtPreset::Quick);
+        assert_eq!(metrics.total_trials, 12);
+        assert_eq!(metrics.lapses_ge_500ms, 5);
+
+        // 1/RT in 1/s: 5, 4, 2.5, 2, 1 (twice) → 29 / 10.
+        assert!((metrics.mean_reciprocal_rt_per_s - 2.9).abs() < 1e-9);
+        // A 10% tail of ten RTs is one RT.
+        assert_eq!(metrics.fastest_10pct_rt_ms, 200.0);
+        assert_eq!(metrics.slowest_10pct_rt_ms, 1_000.0);
+        // 5 lapses / 11 valid stimuli; 1 − (5 + 1 false start) / (11 + 1).
+        assert!((metrics.lapse_probability - 5.0 / 11.0).abs() < 1e-9);
+        assert!((metrics.performance_score - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lapse_probability_and_performance_score_follow_basner_and_dinges() {
+        // Basner & Dinges (2011): lapse probability is lapses over valid
+        // stimuli, false starts excluded; the performance score is 1 minus
+        // lapses and false starts over valid stimuli, false starts included.
+        // A 10-minute run: 100 valid stimuli, of which 5 slow responses and 3
+        // timeouts are lapses, plus 2 false starts.
+        let mut trials: Vec<PvtTrial> = (0..97)
+            .map(|index| reaction(index, if index < 5 { 650.0 } else { 280.0 }))
+            .collect();
+        trials.extend((97..100).map(|index| trial(index, TrialOutcome::Lapse)));
+        trials.extend((100..102).map(|index| trial(index, TrialOutcome::FalseStart)));
+
+        let metrics = PvtMetrics::from_trials(&trials, 2, 30, PvtPreset::Pvt10Min);
+        assert_eq!(metrics.lapses_ge_500ms, 8);
+        assert!((metrics.lapse_probability - 8.0 / 100.0).abs() < 1e-9);
+        assert!((metrics.performance_score - (1.0 - 10.0 / 102.0)).abs() < 1e-9);
+        // 97 reactions: the tails are 10 RTs (9.7 rounded), the slowest
+        // holding all 5 slow responses.
+        assert!((metrics.slowest_10pct_rt_ms - (5.0 * 650.0 + 5.0 * 280.0) / 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ten_percent_tails_round_and_never_go_empty() {
+        assert_eq!(tail_len(1), 1);
+        assert_eq!(tail_len(4), 1);
+        // Halves round up: 0.5 → 1, 1.5 → 2, 2.5 → 3.
+        assert_eq!(tail_len(5), 1);
+        assert_eq!(tail_len(14), 1);
+        assert_eq!(tail_len(15), 2);
+        assert_eq!(tail_len(20), 2);
+        assert_eq!(tail_len(24), 2);
+        assert_eq!(tail_len(25), 3);
+
+        // Twenty RTs 100..=290 ms in 10 ms steps: the tails are two RTs each.
+        let trials: Vec<PvtTrial> = (0..20)
+            .map(|index| reaction(index, 100.0 + 10.0 * index as f64))
+            .collect();
+        let metrics = PvtMetrics::from_trials(&trials, 0, 12, PvtPreset::Quick);
+        assert_eq!(metrics.fastest_10pct_rt_ms, 105.0);
+        assert_eq!(metrics.slowest_10pct_rt_ms, 285.0);
+        assert_eq!(metrics.lapse_probability, 0.0);
+        assert_eq!(metrics.performance_score, 1.0);
+    }
+
+    #[test]
+    fn a_run_of_only_timeouts_scores_zero() {
+        let trials: Vec<PvtTrial> = (0..3)
+            .map(|index| trial(index, TrialOutcome::Lapse))
+            .collect();
+        let metrics = PvtMetrics::from_trials(&trials, 0, 1, PvtPreset::Quick);
+        assert_eq!(metrics.lapses_ge_500ms, 3);
+        assert_eq!(metrics.lapse_probability, 1.0);
+        assert_eq!(metrics.performance_score, 0.0);
+        assert_eq!(metrics.mean_reciprocal_rt_per_s, 0.0);
+    }
+
+    #[test]
+    fn pvt_b_counts_lapses_from_355_ms() {
+        // Basner, Mollicone & Dinges (2011), "Validity and sensitivity of a
+        // brief psychomotor vigilance test (PVT-B)": 3 minutes, 1–4 s
+        // intervals, false starts under 100 ms, and lapses from 355 ms rather
+        // than the 10-minute PVT's 500 ms.
+        let config = PvtPreset::PvtB3Min.config();
+        assert_eq!(config.duration_ms, Some(180_000));
+        assert_eq!((config.min_iti_ms, config.max_iti_ms), (1_000, 4_000));
+        assert_eq!(PvtPreset::PvtB3Min.lapse_threshold_ms(), 355.0);
+        assert_eq!(PvtPreset::Pvt10Min.lapse_threshold_ms(), 500.0);
+
+        // Six reactions around both thresholds, a timeout and a false start.
+        let mut trials: Vec<PvtTrial> = [280.0, 354.0, 355.0, 420.0, 499.0, 500.0]
+            .iter()
+            .enumerate()
+            .map(|(index, &rt_ms)| reaction(index, rt_ms))
+            .collect();
+        trials.push(trial(6, TrialOutcome::Lapse));
+        trials.push(trial(7, TrialOutcome::FalseStart));
+
+        // PVT-B: 355, 420, 499, 500 ms and the timeout are lapses, 5 of 7.
+        let brief = PvtMetrics::from_trials(&trials, 1, 1, PvtPreset::PvtB3Min);
+        assert!((brief.lapse_probability - 5.0 / 7.0).abs() < 1e-9);
+        assert!((brief.performance_score - (1.0 - 6.0 / 8.0)).abs() < 1e-9);
+        // The fixed-threshold counts don't move with the preset.
+        assert_eq!(brief.lapses_ge_500ms, 2);
+        assert_eq!(brief.minor_lapses_355_499ms, 3);
+
+        // The 10-minute PVT: only 500 ms and the timeout, 2 of 7.
+        let standard = PvtMetrics::from_trials(&trials, 1, 1, PvtPreset::Pvt10Min);
+        assert!((standard.lapse_probability - 2.0 / 7.0).abs() < 1e-9);
+        assert!((standard.performance_score - (1.0 - 3.0 / 8.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn metrics_record_the_preset_they_were_scored_for() {
+        let preset = PvtPreset::PvtB3Min;
+        let reacted = PvtMetrics::from_trials(&[reaction(0, 300.0)], 0, 1, preset);
+        let timed_out = PvtMetrics::from_trials(&[trial(0, TrialOutcome::Lapse)], 0, 1, preset);
+        for metrics in [reacted, timed_out] {
+            assert_eq!(metrics.preset(), preset);
+        }
+    }
+}
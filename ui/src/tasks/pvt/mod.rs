@@ -1,5 +1,5 @@
 mod engine;
-pub use engine::PvtEngine;
+pub use engine::{PvtEngine, PvtPreset};
 
 mod metrics;
 pub use metrics::PvtMetrics;
@@ -9,10 +9,13 @@ use futures_util::StreamExt;
 
 use crate::core::qc::QualityFlags;
 use crate::core::readiness::{self, Readiness};
+use crate::core::store::{use_shared_store, SharedStore};
 use crate::core::timing::InstantStamp;
 use crate::core::{format, platform, storage, timing};
 
-use super::engine::{EngineState, PvtEngine, ResponseOutcome, ScheduledStimulus, TrialOutcome};
+use super::engine::{
+    EngineState, PvtEngine, PvtPreset, ResponseOutcome, ScheduledStimulus, TrialOutcome,
+};
 use super::metrics::PvtMetrics;
 
 const TICK_INTERVAL_MS: u64 = 33;
@@ -23,14 +26,20 @@ pub fn PvtView() -> Element {
     // Subscribe to global language signal so instructional subsection re-renders on locale switch.
     let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
     let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
-    let engine = use_signal(PvtEngine::default);
+    let mut engine = use_signal(|| {
+        let preset = storage::pvt_preset()
+            .and_then(|id| PvtPreset::from_id(&id))
+            .unwrap_or_default();
+        PvtEngine::new(preset.config())
+    });
     let qc_flags = use_signal(QualityFlags::pristine);
     let last_metrics = use_signal(|| Option::<PvtMetrics>::None);
     let indicator_text = use_signal(|| "READY".to_string());
     let last_error = use_signal(|| Option::<String>::None);
     let focus_target = use_signal(|| Option::<Rc<MountedData>>::None);
     // Readiness (cooldown advisory) – compute once per mount; updates after a run completes (on next mount for now).
-    let readiness_info = use_signal(|| match storage::load_summaries() {
+    let store = use_shared_store();
+    let readiness_info = use_signal(|| match storage::load_summaries(&store) {
         Ok(mut records) => {
             records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
             let last = records.iter().find(|r| r.task == "pvt");
@@ -44,6 +53,7 @@ pub fn PvtView() -> Element {
 
     let coroutine = {
         let engine_ref = engine;
+        let store_ref = store.clone();
         let qc_ref = qc_flags;
         let metrics_ref = last_metrics;
         let indicator_ref = indicator_text;
@@ -52,6 +62,7 @@ pub fn PvtView() -> Element {
 
         use_coroutine(move |mut rx: UnboundedReceiver<PvtEvent>| {
             let sender_slot = sender_slot_for_loop.clone();
+            let store = store_ref.clone();
             let mut engine_signal = engine_ref;
             let mut qc_signal = qc_ref;
             let mut metrics_signal = metrics_ref;
@@ -80,6 +91,18 @@ pub fn PvtView() -> Element {
                             engine_signal.with_mut(|eng| eng.abort());
                             indicator_signal.set("ABORT".to_string());
                         }
+                        PvtEvent::Pause => {
+                            if engine_signal.with_mut(|eng| eng.pause()) {
+                                indicator_signal.set("PAUSE".to_string());
+                            }
+                        }
+                        PvtEvent::Resume => {
+                            if let Some(schedule) = engine_signal.with_mut(|eng| eng.resume()) {
+                                indicator_signal.set("WAIT".to_string());
+                                queue_stimulus(sender_slot.clone(), schedule);
+                                focus_reaction_target(focus_signal);
+                            }
+                        }
                         PvtEvent::StimulusReady {
                             run_id,
                             trial_index,
@@ -118,9 +141,11 @@ pub fn PvtView() -> Element {
 
                                     match last_outcome {
                                         Some(TrialOutcome::Reaction { rt_ms }) => {
+                                            let ceiling = engine_signal
+                                                .with(|eng| eng.config.max_response_ms as f64);
                                             indicator_signal.set(format!(
                                                 "{:03}",
-                                                rt_ms.round().clamp(0.0, 999.0) as u32
+                                                rt_ms.round().clamp(0.0, ceiling) as u32
                                             ));
                                             let run_id = engine_signal.with(|eng| eng.run_id);
                                             schedule_indicator_reset(sender_slot.clone(), run_id);
@@ -142,6 +167,7 @@ pub fn PvtView() -> Element {
                                 }
                                 ResponseOutcome::RunCompleted => {
                                     finalize_run(
+                                        &store,
                                         &engine_signal,
                                         qc_signal,
                                         metrics_signal,
@@ -176,6 +202,7 @@ pub fn PvtView() -> Element {
                                 ResponseOutcome::RunCompleted => {
                                     indicator_signal.set("LAP".to_string());
                                     finalize_run(
+                                        &store,
                                         &engine_signal,
                                         qc_signal,
                                         metrics_signal,
@@ -197,7 +224,8 @@ pub fn PvtView() -> Element {
                                     if let Some(trial) = eng.trials.get(trial_index) {
                                         if let Some(onset) = trial.stimulus_onset {
                                             let elapsed = timing::duration_ms(onset, timing::now());
-                                            let clamped = elapsed.clamp(0.0, 999.0);
+                                            let clamped = elapsed
+                                                .clamp(0.0, eng.config.max_response_ms as f64);
                                             indicator_signal
                                                 .set(format!("{:03}", clamped.round() as u32));
                                             return true;
@@ -224,6 +252,9 @@ pub fn PvtView() -> Element {
                             }
                         }
                         PvtEvent::FocusLost => {
+                            if engine_signal.with(|eng| eng.state == EngineState::Paused) {
+                                continue;
+                            }
                             qc_signal.with_mut(|flags| {
                                 flags.log_focus_loss();
                                 flags.log_visibility_blur();
@@ -256,8 +287,9 @@ pub fn PvtView() -> Element {
     let engine_snapshot = engine();
     let is_running = matches!(
         engine_snapshot.state,
-        EngineState::Waiting { .. } | EngineState::StimulusActive { .. }
+        EngineState::Waiting { .. } | EngineState::StimulusActive { .. } | EngineState::Paused
     );
+    let is_paused = matches!(engine_snapshot.state, EngineState::Paused);
     let is_stimulus_active = matches!(engine_snapshot.state, EngineState::StimulusActive { .. });
     let trial_progress = engine_snapshot
         .trials
@@ -265,6 +297,18 @@ pub fn PvtView() -> Element {
         .filter(|trial| trial.is_completed())
         .count();
     let total_target = engine_snapshot.config.target_trials;
+    let preset = engine_snapshot.config.preset;
+    let duration_ms = engine_snapshot.config.duration_ms;
+    let progress_value = match duration_ms {
+        Some(total_ms) => format!(
+            "{}/{}",
+            format_clock(engine_snapshot.elapsed_ms().min(total_ms as f64)),
+            format_clock(total_ms as f64)
+        ),
+        None => format!("{trial_progress}/{total_target}"),
+    };
+    let min_iti_s = engine_snapshot.config.min_iti_ms / 1_000;
+    let max_iti_s = engine_snapshot.config.max_iti_ms / 1_000;
 
     let latest_metrics = last_metrics();
     let error_message = last_error();
@@ -275,6 +319,7 @@ pub fn PvtView() -> Element {
         }
         EngineState::Waiting { .. } => crate::t!("pvt-guidance-waiting"),
         EngineState::StimulusActive { .. } => crate::t!("pvt-guidance-active"),
+        EngineState::Paused => crate::t!("pvt-guidance-paused"),
         EngineState::Completed => crate::t!("pvt-guidance-completed"),
         EngineState::Aborted => crate::t!("pvt-guidance-aborted"),
     };
@@ -298,37 +343,58 @@ pub fn PvtView() -> Element {
                         {crate::t!("common-cancel")}
                     }
 
-                    button {
-                        r#type: "button",
-                        class: "task-pvt__hitbox",
-                        aria_label: "PVT reaction target",
-                        autofocus: true,
-                        onmounted: {
-                            let mut focus_signal = focus_target;
-                            move |evt: MountedEvent| {
-                                let mounted = evt.data();
-                                focus_signal.set(Some(mounted));
-                                focus_reaction_target(focus_signal);
+                    if is_paused {
+                        div { class: "task-canvas__paused",
+                            p { {crate::t!("task-paused-note")} }
+                            button {
+                                r#type: "button",
+                                class: "button button--primary",
+                                autofocus: true,
+                                onclick: move |_| send_event(PvtEvent::Resume),
+                                {crate::t!("common-resume")}
                             }
-                        },
-                        onfocusout: move |_| send_event(PvtEvent::FocusLost),
-                        onclick: move |_| respond_now(),
-                        onkeydown: move |evt| {
-                            let key = evt.key().to_string().to_lowercase();
-                            if key == " " || key == "space" || key == "spacebar" || key == "enter" {
-                                evt.prevent_default();
-                                respond_now();
-                            }
-                        },
+                        }
+                    } else {
+                        button {
+                            class: "button button--ghost button--compact task-canvas__pause",
+                            // Keep focus on the hitbox so pausing is not logged as a focus loss.
+                            onmousedown: move |evt| evt.prevent_default(),
+                            onclick: move |_| send_event(PvtEvent::Pause),
+                            {crate::t!("common-pause")}
+                        }
+
+                        button {
+                            r#type: "button",
+                            class: "task-pvt__hitbox",
+                            aria_label: "PVT reaction target",
+                            autofocus: true,
+                            onmounted: {
+                                let mut focus_signal = focus_target;
+                                move |evt: MountedEvent| {
+                                    let mounted = evt.data();
+                                    focus_signal.set(Some(mounted));
+                                    focus_reaction_target(focus_signal);
+                                }
+                            },
+                            onfocusout: move |_| send_event(PvtEvent::FocusLost),
+                            onclick: move |_| respond_now(),
+                            onkeydown: move |evt| {
+                                let key = evt.key().to_string().to_lowercase();
+                                if key == " " || key == "space" || key == "spacebar" || key == "enter" {
+                                    evt.prevent_default();
+                                    respond_now();
+                                }
+                            },
 
-                        div { class: indicator_class, {indicator_value} }
+                            div { class: indicator_class, {indicator_value} }
+                        }
                     }
 
                     div { class: "task-guidance task-guidance--overlay", {guidance_text} }
 
                     div { class: "task-progress task-progress--overlay",
                         span { {crate::t!("pvt-progress-label")} }
-                        span { class: "task-progress__value", "{trial_progress}/{total_target}" }
+                        span { class: "task-progress__value", "{progress_value}" }
                     }
                 }
             } else {
@@ -345,11 +411,34 @@ pub fn PvtView() -> Element {
                     // Hidden i18n marker to force re-render of instruction copy when locale changes
                     div { style: "display:none", "{_lang_marker}" }
                     h3 { {crate::t!("pvt-how-summary")} }
+                    label { class: "task-pvt__preset",
+                        {crate::t!("pvt-preset-label")}
+                        select {
+                            value: preset.id(),
+                            onchange: move |e| {
+                                if let Some(picked) = PvtPreset::from_id(&e.value()) {
+                                    engine.with_mut(|eng| eng.config = picked.config());
+                                    storage::set_pvt_preset(picked.id());
+                                }
+                            },
+                            for option_preset in PvtPreset::ALL {
+                                option {
+                                    value: option_preset.id(),
+                                    selected: option_preset == preset,
+                                    {preset_name(option_preset)}
+                                }
+                            }
+                        }
+                    }
                     ul {
                         li { {crate::t!("pvt-how-step-wait")} }
                         li { {crate::t!("pvt-how-step-respond")} }
-                        li { {crate::t!("pvt-how-step-jitter")} }
-                        li { {crate::t!("pvt-how-step-target", trials = total_target)} }
+                        li { {crate::t!("pvt-how-step-jitter", min = min_iti_s, max = max_iti_s)} }
+                        if let Some(total_ms) = duration_ms {
+                            li { {crate::t!("pvt-how-step-duration", minutes = (total_ms / 60_000))} }
+                        } else {
+                            li { {crate::t!("pvt-how-step-target", trials = total_target)} }
+                        }
                     }
 
                     div { class: "task-cta", style: "display:flex; gap:0.75rem; flex-wrap:wrap; align-items:center;",
@@ -369,6 +458,7 @@ pub fn PvtView() -> Element {
                     section { class: "task-card task-metrics",
                         h3 { {crate::t!("pvt-last-session")} }
                         ul { class: "metrics-grid",
+                            li { {crate::t!("pvt-preset-label")} ": " {preset_name(metrics.preset())} }
                             li { {crate::t!("pvt-metric-median-rt")} ": " {format::format_ms(metrics.median_rt_ms)} }
                             li { {crate::t!("pvt-metric-mean-rt")} ": " {format::format_ms(metrics.mean_rt_ms)} }
                             li { {crate::t!("pvt-metric-sd-rt")} ": " {format::format_ms(metrics.sd_rt_ms)} }
@@ -398,7 +488,23 @@ pub fn PvtView() -> Element {
     }
 }
 
+fn preset_name(preset: PvtPreset) -> String {
+    match preset {
+        PvtPreset::Quick => crate::t!("pvt-preset-quick"),
+        PvtPreset::PvtB3Min => crate::t!("pvt-preset-pvt-b-3min"),
+        PvtPreset::Pvt5Min => crate::t!("pvt-preset-5min"),
+        PvtPreset::Pvt10Min => crate::t!("pvt-preset-10min"),
+    }
+}
+
+/// `m:ss` for the time-based progress counter.
+fn format_clock(ms: f64) -> String {
+    let total_s = (ms.max(0.0) / 1_000.0).floor() as u64;
+    format!("{}:{:02}", total_s / 60, total_s % 60)
+}
+
 fn finalize_run(
+    store: &SharedStore,
     engine: &Signal<PvtEngine>,
     mut qc_flags: Signal<QualityFlags>,
     mut last_metrics: Signal<Option<PvtMetrics>>,
@@ -407,13 +513,22 @@ fn finalize_run(
     mut readiness_info: Signal<Readiness>,
 ) {
     if let Some(metrics) = engine.with(|eng| eng.metrics()) {
-        qc_flags.with_mut(|flags| flags.mark_min_trials(metrics.meets_min_trial_requirement));
+        let (pause_count, paused_ms) = engine.with(|eng| (eng.pause_count(), eng.paused_ms()));
+        qc_flags.with_mut(|flags| {
+            flags.mark_min_trials(metrics.meets_min_trial_requirement);
+            flags.record_pauses(pause_count, paused_ms);
+        });
         let qc_snapshot = qc_flags();
 
         match serde_json::to_value(&metrics) {
             Ok(metrics_json) => {
                 let record = storage::SummaryRecord::new("pvt", metrics_json, qc_snapshot.clone());
-                if let Err(err) = storage::append_summary(&record) {
+                let trials = if storage::keep_trials() {
+                    engine.with(|eng| eng.trial_samples())
+                } else {
+                    Vec::new()
+                };
+                if let Err(err) = storage::append_summary(store, &record, &trials) {
                     last_error.set(Some(format!("Failed to persist summary: {err}")));
                 } else {
                     last_error.set(None);
@@ -429,7 +544,7 @@ fn finalize_run(
 
         // Refresh readiness advisory immediately so the banner reflects the new cooldown
         // without requiring a remount or navigation.
-        if let Ok(mut records) = storage::load_summaries() {
+        if let Ok(mut records) = storage::load_summaries(store) {
             records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
             let last = records.iter().find(|r| r.task == "pvt");
             let updated = readiness::evaluate("pvt", last);
@@ -503,6 +618,8 @@ fn focus_reaction_target(focus_signal: Signal<Option<Rc<MountedData>>>) {
 enum PvtEvent {
     Start,
     Abort,
+    Pause,
+    Resume,
     StimulusReady { run_id: u64, trial_index: usize },
     Timeout { run_id: u64, trial_index: usize },
     Respond { timestamp: InstantStamp },
@@ -0,0 +1,721 @@
+//! Engine managing the cued task-switching schedule and response tracking.
+//!
+//! Each trial shows a digit (1–9, never 5) with a cue naming the rule to apply:
+//! odd or even (parity), or lower or higher than five (magnitude). Both rules
+//! share the two response keys. A main run has a pure block of each rule, in
+//! seeded order, then a mixed block where the cued rule switches on a seeded
+//! share of trials. Pure blocks give the baseline for the mixing cost (repeat
+//! trials in a mixed block against pure trials); the mixed block gives the
+//! switch cost (switch trials against repeat trials). The first trial of every
+//! block is neither and is left out of both.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::core::storage::TrialSample;
+use crate::core::timing::{self, InstantStamp};
+
+use super::metrics::TaskSwitchMetrics;
+
+/// The digits shown; 5 is neither lower nor higher.
+const DIGITS: [u8; 8] = [1, 2, 3, 4, 6, 7, 8, 9];
+
+/// Different run phases for the task-switching engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunMode {
+    Practice,
+    Main,
+}
+
+impl RunMode {
+    fn seed_tag(self) -> u64 {
+        match self {
+            RunMode::Practice => 0x54_5350_5241_4354_u64, // "TSPRACT"
+            RunMode::Main => 0x54_534d_4149_4e52_u64,     // "TSMAINR"
+        }
+    }
+}
+
+/// The rule a cue asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Odd (left) or even (right).
+    Parity,
+    /// Lower than five (left) or higher (right).
+    Magnitude,
+}
+
+impl Rule {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Rule::Parity => "parity",
+            Rule::Magnitude => "magnitude",
+        }
+    }
+
+    /// The correct key for `digit` under this rule.
+    pub fn answer(self, digit: u8) -> Side {
+        let left = match self {
+            Rule::Parity => digit % 2 == 1,
+            Rule::Magnitude => digit < 5,
+        };
+        if left {
+            Side::Left
+        } else {
+            Side::Right
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            Rule::Parity => Rule::Magnitude,
+            Rule::Magnitude => Rule::Parity,
+        }
+    }
+}
+
+/// One of the two response keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// How a trial's rule relates to the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrialKind {
+    /// In a single-rule block.
+    Pure,
+    /// In a mixed block, same rule as the previous trial.
+    Repeat,
+    /// In a mixed block, the other rule.
+    Switch,
+}
+
+impl TrialKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrialKind::Pure => "pure",
+            TrialKind::Repeat => "repeat",
+            TrialKind::Switch => "switch",
+        }
+    }
+}
+
+/// Public configuration knobs for the task.
+#[derive(Debug, Clone)]
+pub struct TaskSwitchConfig {
+    /// Trials in each of the two pure blocks.
+    pub pure_block_trials: usize,
+    pub mixed_block_trials: usize,
+    /// Practice is one short mixed block.
+    pub practice_trials: usize,
+    /// Share of mixed-block trials (after the first) that switch rule.
+    pub switch_ratio: f32,
+    /// Blank gap between a trial's end and the next digit.
+    pub interstimulus_interval_ms: u64,
+    pub lead_in_ms: u64,
+    /// Pause before the first trial of each later block, while its rule is
+    /// announced.
+    pub block_break_ms: u64,
+    /// How long a digit stays up waiting for an answer before it is a miss.
+    pub response_window_ms: u64,
+    pub seed: u64,
+}
+
+impl Default for TaskSwitchConfig {
+    fn default() -> Self {
+        Self {
+            pure_block_trials: 17,
+            mixed_block_trials: 49,
+            practice_trials: 12,
+            switch_ratio: 0.5,
+            interstimulus_interval_ms: 600,
+            lead_in_ms: 750,
+            block_break_ms: 2_500,
+            response_window_ms: 3_000,
+            seed: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineState {
+    Idle,
+    Waiting { mode: RunMode, trial_index: usize },
+    StimulusActive { mode: RunMode, trial_index: usize },
+    Paused { mode: RunMode, trial_index: usize },
+    Completed { mode: RunMode },
+    Aborted,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwitchTrial {
+    pub index: usize,
+    pub block: usize,
+    pub rule: Rule,
+    pub digit: u8,
+    pub kind: TrialKind,
+    /// First trial of its block; scored, but left out of the costs.
+    pub first_in_block: bool,
+    pub presented_at: Option<InstantStamp>,
+    pub response: Option<TrialResponse>,
+    pub outcome: TrialOutcome,
+}
+
+impl SwitchTrial {
+    fn new(index: usize, block: usize, rule: Rule, digit: u8, kind: TrialKind) -> Self {
+        Self {
+            index,
+            block,
+            rule,
+            digit,
+            kind,
+            first_in_block: false,
+            presented_at: None,
+            response: None,
+            outcome: TrialOutcome::Pending,
+        }
+    }
+
+    pub fn is_completed(&self) -> bool {
+        !matches!(self.outcome, TrialOutcome::Pending)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrialResponse {
+    pub timestamp: InstantStamp,
+    pub rt_ms: f64,
+    pub side: Side,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrialOutcome {
+    Pending,
+    Correct { rt_ms: f64 },
+    Error { rt_ms: f64 },
+    Miss,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduledStimulus {
+    pub run_id: u64,
+    pub trial_index: usize,
+    pub wait_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduledAdvance {
+    pub run_id: u64,
+    pub trial_index: usize,
+    pub wait_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrialSchedule {
+    pub stimulus: ScheduledStimulus,
+    pub advance: ScheduledAdvance,
+}
+
+#[derive(Debug, Clone)]
+pub enum AdvanceOutcome {
+    Next(TrialSchedule),
+    Completed { mode: RunMode },
+    Ignored,
+}
+
+#[derive(Debug, Clone)]
+pub enum ResponseOutcome {
+    /// The answer was scored; the trial should advance straight away.
+    Recorded {
+        trial_index: usize,
+        correct: bool,
+    },
+    Ignored,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskSwitchEngine {
+    pub config: TaskSwitchConfig,
+    pub state: EngineState,
+    pub run_id: u64,
+    trials: Vec<SwitchTrial>,
+    last_practice_metrics: Option<TaskSwitchMetrics>,
+    last_main_metrics: Option<TaskSwitchMetrics>,
+    paused_at: Option<InstantStamp>,
+    pause_count: u32,
+    paused_ms: f64,
+}
+
+impl TaskSwitchEngine {
+    pub fn new(config: TaskSwitchConfig) -> Self {
+        Self {
+            config,
+            state: EngineState::Idle,
+            run_id: 0,
+            trials: Vec::new(),
+            last_practice_metrics: None,
+            last_main_metrics: None,
+            paused_at: None,
+            pause_count: 0,
+            paused_ms: 0.0,
+        }
+    }
+
+    pub fn practice_metrics(&self) -> Option<TaskSwitchMetrics> {
+        self.last_practice_metrics.clone()
+    }
+
+    pub fn main_metrics(&self) -> Option<TaskSwitchMetrics> {
+        self.last_main_metrics.clone()
+    }
+
+    pub fn trials(&self) -> &[SwitchTrial] {
+        &self.trials
+    }
+
+    pub fn total_trials(&self, mode: RunMode) -> usize {
+        match mode {
+            RunMode::Practice => self.config.practice_trials,
+            RunMode::Main => 2 * self.config.pure_block_trials + self.config.mixed_block_trials,
+        }
+    }
+
+    /// The last run's finished trials, for keeping with it; times are from its
+    /// first stimulus. The stimulus records rule, digit and trial kind, e.g.
+    /// `parity:7:switch`.
+    pub fn trial_samples(&self) -> Vec<TrialSample> {
+        let Some(first) = self.trials.iter().find_map(|trial| trial.presented_at) else {
+            return Vec::new();
+        };
+        self.trials
+            .iter()
+            .filter_map(|trial| {
+                let outcome = match trial.outcome {
+                    TrialOutcome::Pending => return None,
+                    TrialOutcome::Correct { .. } => "correct",
+                    TrialOutcome::Error { .. } => "error",
+                    TrialOutcome::Miss => "miss",
+                };
+                Some(TrialSample {
+                    index: trial.index,
+                    iti_ms: Some(self.wait_before(trial.index) as f64),
+                    onset_ms: trial.presented_at.map(|at| timing::duration_ms(first, at)),
+                    response_ms: trial
+                        .response
+                        .as_ref()
+                        .map(|r| timing::duration_ms(first, r.timestamp)),
+                    rt_ms: trial.response.as_ref().map(|r| r.rt_ms),
+                    outcome: outcome.to_string(),
+                    stimulus: Some(format!(
+                        "{}:{}:{}",
+                        trial.rule.as_str(),
+                        trial.digit,
+                        trial.kind.as_str()
+                    )),
+                    target: None,
+                    lure: None,
+                })
+            })
+            .collect()
+    }
+
+    pub fn start(&mut self, mode: RunMode) -> Option<TrialSchedule> {
+        if matches!(
+            self.state,
+            EngineState::Waiting { .. }
+                | EngineState::StimulusActive { .. }
+                | EngineState::Paused { .. }
+        ) {
+            return None;
+        }
+
+        self.run_id = self.run_id.wrapping_add(1);
+        self.trials = self.generate_trials(mode);
+        if self.trials.is_empty() {
+            return None;
+        }
+        self.paused_at = None;
+        self.pause_count = 0;
+        self.paused_ms = 0.0;
+        self.state = EngineState::Waiting {
+            mode,
+            trial_index: 0,
+        };
+
+        Some(self.schedule_current(0))
+    }
+
+    pub fn abort(&mut self) {
+        self.state = EngineState::Aborted;
+    }
+
+    /// Pause a running block. The in-flight trial is voided so it can be shown
+    /// again on resume, and bumping `run_id` strands its scheduled timers.
+    pub fn pause(&mut self) -> bool {
+        let (mode, trial_index) = match self.state {
+            EngineState::Waiting { mode, trial_index }
+            | EngineState::StimulusActive { mode, trial_index } => (mode, trial_index),
+            _ => return false,
+        };
+
+        if let Some(trial) = self.trials.get_mut(trial_index) {
+            trial.presented_at = None;
+            trial.response = None;
+            trial.outcome = TrialOutcome::Pending;
+        }
+        self.run_id = self.run_id.wrapping_add(1);
+        self.paused_at = Some(timing::now());
+        self.pause_count = self.pause_count.saturating_add(1);
+        self.state = EngineState::Paused { mode, trial_index };
+        true
+    }
+
+    /// Resume a paused block from the voided trial, after the lead-in.
+    pub fn resume(&mut self) -> Option<TrialSchedule> {
+        let EngineState::Paused { mode, trial_index } = self.state else {
+            return None;
+        };
+
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused_ms += timing::elapsed_ms(paused_at);
+        }
+        self.state = EngineState::Waiting { mode, trial_index };
+
+        let mut schedule = self.schedule_current(trial_index);
+        schedule.stimulus.wait_ms = self.config.lead_in_ms;
+        Some(schedule)
+    }
+
+    /// Times the current run was paused.
+    pub fn pause_count(&self) -> u32 {
+        self.pause_count
+    }
+
+    /// Total time the current run has spent paused, including a pause in
+    /// progress.
+    pub fn paused_ms(&self) -> f64 {
+        self.paused_ms + self.paused_at.map(timing::elapsed_ms).unwrap_or_default()
+    }
+
+    pub fn mark_stimulus_on(&mut self, trial_index: usize, timestamp: InstantStamp) -> bool {
+        match self.state {
+            EngineState::Waiting {
+                mode,
+                trial_index: idx,
+            } if idx == trial_index => {
+                if let Some(trial) = self.trials.get_mut(trial_index) {
+                    trial.presented_at = Some(timestamp);
+                    self.state = EngineState::StimulusActive { mode, trial_index };
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Score an answer to the digit on screen. Answers between digits, or a
+    /// second answer to the same one, are ignored.
+    pub fn register_response(&mut self, side: Side, timestamp: InstantStamp) -> ResponseOutcome {
+        let EngineState::StimulusActive { trial_index, .. } = self.state else {
+            return ResponseOutcome::Ignored;
+        };
+        let Some(trial) = self.trials.get_mut(trial_index) else {
+            return ResponseOutcome::Ignored;
+        };
+        let Some(onset) = trial.presented_at else {
+            return ResponseOutcome::Ignored;
+        };
+        if trial.response.is_some() {
+            return ResponseOutcome::Ignored;
+        }
+
+        let rt_ms = timing::duration_ms(onset, timestamp);
+        let correct = side == trial.rule.answer(trial.digit);
+        trial.response = Some(TrialResponse {
+            timestamp,
+            rt_ms,
+            side,
+        });
+        trial.outcome = if correct {
+            TrialOutcome::Correct { rt_ms }
+        } else {
+            TrialOutcome::Error { rt_ms }
+        };
+        ResponseOutcome::Recorded {
+            trial_index,
+            correct,
+        }
+    }
+
+    /// End `trial_index` (a miss if it went unanswered) and schedule the next
+    /// one. Stale calls, e.g. the window timer of a digit already answered, are
+    /// ignored.
+    pub fn advance(&mut self, trial_index: usize) -> AdvanceOutcome {
+        let (mode, idx) = match self.state {
+            EngineState::StimulusActive {
+                mode,
+                trial_index: idx,
+            } if idx == trial_index => (mode, idx),
+            _ => return AdvanceOutcome::Ignored,
+        };
+
+        if let Some(trial) = self.trials.get_mut(idx) {
+            if trial.outcome == TrialOutcome::Pending {
+                trial.outcome = TrialOutcome::Miss;
+            }
+        }
+
+        let next_index = idx + 1;
+
+        if next_index >= self.trials.len() {
+            self.state = EngineState::Completed { mode };
+            let metrics = TaskSwitchMetrics::from_trials(&self.trials);
+            match mode {
+                RunMode::Practice => self.last_practice_metrics = Some(metrics),
+                RunMode::Main => self.last_main_metrics = Some(metrics),
+            }
+            AdvanceOutcome::Completed { mode }
+        } else {
+            self.state = EngineState::Waiting {
+                mode,
+                trial_index: next_index,
+            };
+            AdvanceOutcome::Next(self.schedule_current(next_index))
+        }
+    }
+
+    /// Gap before `trial_index`: the lead-in for the run's first trial, a
+    /// block break before each later block's first.
+    fn wait_before(&self, trial_index: usize) -> u64 {
+        if trial_index == 0 {
+            self.config.lead_in_ms
+        } else if self
+            .trials
+            .get(trial_index)
+            .is_some_and(|trial| trial.first_in_block)
+        {
+            self.config.block_break_ms
+        } else {
+            self.config.interstimulus_interval_ms
+        }
+    }
+
+    fn schedule_current(&self, trial_index: usize) -> TrialSchedule {
+        TrialSchedule {
+            stimulus: ScheduledStimulus {
+                run_id: self.run_id,
+                trial_index,
+                wait_ms: self.wait_before(trial_index),
+            },
+            advance: ScheduledAdvance {
+                run_id: self.run_id,
+                trial_index,
+                wait_ms: self.config.response_window_ms,
+            },
+        }
+    }
+
+    fn generate_trials(&self, mode: RunMode) -> Vec<SwitchTrial> {
+        let mut rng = self.seeded_rng(mode);
+        let first_rule = if rng.gen_bool(0.5) {
+            Rule::Parity
+        } else {
+            Rule::Magnitude
+        };
+
+        let mut blocks: Vec<Vec<(Rule, TrialKind)>> = Vec::new();
+        match mode {
+            RunMode::Practice => {
+                blocks.push(mixed_block(
+                    &mut rng,
+                    first_rule,
+                    self.config.practice_trials,
+                    self.config.switch_ratio,
+                ));
+            }
+            RunMode::Main => {
+                for rule in [first_rule, first_rule.other()] {
+                    blocks.push(vec![(rule, TrialKind::Pure); self.config.pure_block_trials]);
+                }
+                blocks.push(mixed_block(
+                    &mut rng,
+                    first_rule,
+                    self.config.mixed_block_trials,
+                    self.config.switch_ratio,
+                ));
+            }
+        }
+
+        let mut trials = Vec::new();
+        for (block, cues) in blocks.into_iter().enumerate() {
+            let digits = balanced_digits(&mut rng, cues.len());
+            for (position, ((rule, kind), digit)) in cues.into_iter().zip(digits).enumerate() {
+                let mut trial = SwitchTrial::new(trials.len(), block, rule, digit, kind);
+                trial.first_in_block = position == 0;
+                trials.push(trial);
+            }
+        }
+        trials
+    }
+
+    fn seeded_rng(&self, mode: RunMode) -> StdRng {
+        let combined_seed = self.config.seed ^ mode.seed_tag() ^ self.run_id;
+        StdRng::seed_from_u64(combined_seed)
+    }
+}
+
+impl Default for TaskSwitchEngine {
+    fn default() -> Self {
+        Self::new(TaskSwitchConfig::default())
+    }
+}
+
+/// Cues for a mixed block: the first trial sets the rule, then the given
+/// share of the rest switch, in shuffled order.
+fn mixed_block(
+    rng: &mut StdRng,
+    first_rule: Rule,
+    length: usize,
+    switch_ratio: f32,
+) -> Vec<(Rule, TrialKind)> {
+    if length == 0 {
+        return Vec::new();
+    }
+    let transitions = length - 1;
+    let share = transitions as f32 * switch_ratio.clamp(0.0, 1.0);
+    let switches = (share.round() as usize).min(transitions);
+    let mut kinds: Vec<TrialKind> = std::iter::repeat_n(TrialKind::Switch, switches)
+        .chain(std::iter::repeat_n(
+            TrialKind::Repeat,
+            transitions - switches,
+        ))
+        .collect();
+    kinds.shuffle(rng);
+
+    let mut rule = first_rule;
+    let mut cues = vec![(rule, TrialKind::Repeat)];
+    for kind in kinds {
+        if kind == TrialKind::Switch {
+            rule = rule.other();
+        }
+        cues.push((rule, kind));
+    }
+    cues
+}
+
+/// `length` digits drawn evenly from [`DIGITS`], in shuffled order.
+fn balanced_digits(rng: &mut StdRng, length: usize) -> Vec<u8> {
+    let mut digits: Vec<u8> = DIGITS.iter().copied().cycle().take(length).collect();
+    digits.shuffle(rng);
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn main_run_has_two_pure_blocks_then_a_balanced_mixed_block() {
+        let engine = TaskSwitchEngine {
+            run_id: 5,
+            ..Default::default()
+        };
+        let trials = engine.generate_trials(RunMode::Main);
+        assert_eq!(trials.len(), engine.total_trials(RunMode::Main));
+        assert_eq!(trials, engine.generate_trials(RunMode::Main));
+
+        let pure: Vec<_> = trials.iter().filter(|t| t.block < 2).collect();
+        assert!(pure.iter().all(|t| t.kind == TrialKind::Pure));
+        assert_ne!(pure[0].rule, pure[17].rule);
+        assert!(pure.iter().all(|t| t.rule
+            == if t.block == 0 {
+                pure[0].rule
+            } else {
+                pure[17].rule
+            }));
+
+        let mixed: Vec<_> = trials.iter().filter(|t| t.block == 2).collect();
+        let switches = mixed.iter().filter(|t| t.kind == TrialKind::Switch).count();
+        assert_eq!(switches, 24);
+        for pair in mixed.windows(2) {
+            assert_eq!(
+                pair[1].kind == TrialKind::Switch,
+                pair[0].rule != pair[1].rule
+            );
+        }
+        let firsts: Vec<_> = trials
+            .iter()
+            .filter(|t| t.first_in_block)
+            .map(|t| t.index)
+            .collect();
+        assert_eq!(firsts, [0, 17, 34]);
+        assert!(trials.iter().all(|t| t.digit != 5));
+    }
+
+    #[test]
+    fn rules_map_digits_to_keys() {
+        assert_eq!(Rule::Parity.answer(7), Side::Left);
+        assert_eq!(Rule::Parity.answer(8), Side::Right);
+        assert_eq!(Rule::Magnitude.answer(3), Side::Left);
+        assert_eq!(Rule::Magnitude.answer(6), Side::Right);
+    }
+
+    #[test]
+    fn block_breaks_precede_later_blocks_and_pause_replays_the_trial() {
+        let mut engine = TaskSwitchEngine::new(TaskSwitchConfig {
+            pure_block_trials: 2,
+            mixed_block_trials: 3,
+            ..Default::default()
+        });
+        let first = engine.start(RunMode::Main).expect("schedule");
+        assert_eq!(first.stimulus.wait_ms, engine.config.lead_in_ms);
+
+        engine.mark_stimulus_on(0, timing::now());
+        let digit = engine.trials()[0].digit;
+        let answer = engine.trials()[0].rule.answer(digit);
+        assert!(matches!(
+            engine.register_response(answer, timing::now()),
+            ResponseOutcome::Recorded { correct: true, .. }
+        ));
+        let AdvanceOutcome::Next(second) = engine.advance(0) else {
+            panic!("run ended early");
+        };
+        assert_eq!(
+            second.stimulus.wait_ms,
+            engine.config.interstimulus_interval_ms
+        );
+        engine.mark_stimulus_on(1, timing::now());
+        let AdvanceOutcome::Next(third) = engine.advance(1) else {
+            panic!("run ended early");
+        };
+        assert_eq!(third.stimulus.wait_ms, engine.config.block_break_ms);
+
+        assert!(engine.pause());
+        let resumed = engine.resume().expect("reschedule");
+        assert_eq!(resumed.stimulus.trial_index, 2);
+        assert_eq!(resumed.stimulus.wait_ms, engine.config.lead_in_ms);
+
+        for index in 2..7 {
+            engine.mark_stimulus_on(index, timing::now());
+            engine.advance(index);
+        }
+        assert_eq!(
+            engine.state,
+            EngineState::Completed {
+                mode: RunMode::Main
+            }
+        );
+        let outcomes: Vec<_> = engine
+            .trial_samples()
+            .iter()
+            .map(|s| s.outcome.clone())
+            .collect();
+        assert_eq!(outcomes[..2], ["correct", "miss"]);
+        assert_eq!(engine.main_metrics().expect("metrics").misses, 6);
+    }
+}
@@ -0,0 +1,255 @@
+//! Metric definitions and aggregation helpers for task-switching runs.
+//!
+//! Reaction times are over correct responses only, and error rates count
+//! errors and misses alike. The first trial of each block is scored but left
+//! out of the per-kind figures, since it is neither a repeat nor a switch. The
+//! switch cost is switch minus repeat trials within the mixed block; the
+//! mixing cost is mixed-block repeats minus pure-block trials, i.e. the price
+//! of having to keep both rules ready.
+
+use serde::{Deserialize, Serialize};
+
+use super::engine::{SwitchTrial, TrialKind, TrialOutcome};
+
+/// Correct responses each trial kind needs for its median RT to count.
+const MIN_CORRECT_PER_KIND: u32 = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TaskSwitchMetrics {
+    pub total_trials: usize,
+    pub correct: u32,
+    pub errors: u32,
+    pub misses: u32,
+    pub accuracy: f64,
+    pub pure_trials: usize,
+    pub repeat_trials: usize,
+    pub switch_trials: usize,
+    pub pure_median_rt_ms: f64,
+    pub repeat_median_rt_ms: f64,
+    pub switch_median_rt_ms: f64,
+    pub pure_error_rate: f64,
+    pub repeat_error_rate: f64,
+    pub switch_error_rate: f64,
+    /// Switch minus repeat median correct RT; 0 unless both kinds have a
+    /// correct response.
+    pub switch_cost_ms: f64,
+    /// Repeat minus pure median correct RT; 0 unless both kinds have a correct
+    /// response.
+    pub mixing_cost_ms: f64,
+    /// Switch minus repeat error rate.
+    pub switch_cost_error_rate: f64,
+    /// Repeat minus pure error rate.
+    pub mixing_cost_error_rate: f64,
+    pub response_count: u32,
+    pub meets_min_trial_requirement: bool,
+}
+
+impl TaskSwitchMetrics {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn from_trials(trials: &[SwitchTrial]) -> Self {
+        let total_trials = trials.len();
+        if total_trials == 0 {
+            return Self::default();
+        }
+
+        let mut correct = 0u32;
+        let mut errors = 0u32;
+        let mut misses = 0u32;
+        let mut pure = KindTally::default();
+        let mut repeat = KindTally::default();
+        let mut switch = KindTally::default();
+
+        for trial in trials {
+            let rt = match trial.outcome {
+                TrialOutcome::Correct { rt_ms } => {
+                    correct = correct.saturating_add(1);
+                    Some(rt_ms)
+                }
+                TrialOutcome::Error { .. } => {
+                    errors = errors.saturating_add(1);
+                    None
+                }
+                TrialOutcome::Miss => {
+                    misses = misses.saturating_add(1);
+                    None
+                }
+                TrialOutcome::Pending => continue,
+            };
+            if trial.first_in_block {
+                continue;
+            }
+            let tally = match trial.kind {
+                TrialKind::Pure => &mut pure,
+                TrialKind::Repeat => &mut repeat,
+                TrialKind::Switch => &mut switch,
+            };
+            tally.trials += 1;
+            tally.rts.extend(rt);
+        }
+
+        for tally in [&mut pure, &mut repeat, &mut switch] {
+            tally.rts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        }
+
+        let pure_median_rt_ms = percentile(&pure.rts, 0.5);
+        let repeat_median_rt_ms = percentile(&repeat.rts, 0.5);
+        let switch_median_rt_ms = percentile(&switch.rts, 0.5);
+        let pure_error_rate = pure.error_rate();
+        let repeat_error_rate = repeat.error_rate();
+        let switch_error_rate = switch.error_rate();
+
+        Self {
+            total_trials,
+            correct,
+            errors,
+            misses,
+            accuracy: ratio(correct, total_trials),
+            pure_trials: pure.trials,
+            repeat_trials: repeat.trials,
+            switch_trials: switch.trials,
+            pure_median_rt_ms,
+            repeat_median_rt_ms,
+            switch_median_rt_ms,
+            pure_error_rate,
+            repeat_error_rate,
+            switch_error_rate,
+            switch_cost_ms: if repeat.rts.is_empty() || switch.rts.is_empty() {
+                0.0
+            } else {
+                switch_median_rt_ms - repeat_median_rt_ms
+            },
+            mixing_cost_ms: if pure.rts.is_empty() || repeat.rts.is_empty() {
+                0.0
+            } else {
+                repeat_median_rt_ms - pure_median_rt_ms
+            },
+            switch_cost_error_rate: switch_error_rate - repeat_error_rate,
+            mixing_cost_error_rate: repeat_error_rate - pure_error_rate,
+            response_count: correct + errors,
+            meets_min_trial_requirement: [&pure, &repeat, &switch]
+                .iter()
+                .all(|tally| tally.rts.len() as u32 >= MIN_CORRECT_PER_KIND),
+        }
+    }
+}
+
+#[derive(Default)]
+struct KindTally {
+    trials: usize,
+    /// Correct RTs.
+    rts: Vec<f64>,
+}
+
+impl KindTally {
+    fn error_rate(&self) -> f64 {
+        if self.trials > 0 {
+            1.0 - ratio(self.rts.len() as u32, self.trials)
+        } else {
+            0.0
+        }
+    }
+}
+
+fn ratio(count: u32, total: usize) -> f64 {
+    if total > 0 {
+        count as f64 / total as f64
+    } else {
+        0.0
+    }
+}
+
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let clamped_pct = pct.clamp(0.0, 1.0);
+    let rank = clamped_pct * (sorted.len() as f64 - 1.0);
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    if lower == upper {
+        sorted[lower]
+    } else {
+        let weight = rank - lower as f64;
+        sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::task_switch::engine::Rule;
+
+    fn trial(index: usize, kind: TrialKind, outcome: TrialOutcome) -> SwitchTrial {
+        SwitchTrial {
+            index,
+            block: 0,
+            rule: Rule::Parity,
+            digit: 3,
+            kind,
+            first_in_block: false,
+            presented_at: None,
+            response: None,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn costs_compare_trial_kinds() {
+        let mut first = trial(0, TrialKind::Pure, TrialOutcome::Correct { rt_ms: 900.0 });
+        first.first_in_block = true;
+        let trials = vec![
+            first,
+            trial(1, TrialKind::Pure, TrialOutcome::Correct { rt_ms: 500.0 }),
+            trial(2, TrialKind::Pure, TrialOutcome::Correct { rt_ms: 520.0 }),
+            trial(3, TrialKind::Repeat, TrialOutcome::Correct { rt_ms: 600.0 }),
+            trial(4, TrialKind::Repeat, TrialOutcome::Error { rt_ms: 400.0 }),
+            trial(5, TrialKind::Switch, TrialOutcome::Correct { rt_ms: 700.0 }),
+            trial(6, TrialKind::Switch, TrialOutcome::Correct { rt_ms: 760.0 }),
+            trial(7, TrialKind::Switch, TrialOutcome::Miss),
+            trial(8, TrialKind::Switch, TrialOutcome::Error { rt_ms: 450.0 }),
+        ];
+        let metrics = TaskSwitchMetrics::from_trials(&trials);
+
+        assert_eq!((metrics.correct, metrics.errors, metrics.misses), (6, 2, 1));
+        assert_eq!(metrics.response_count, 8);
+        // The block's first trial counts overall but not per kind.
+        assert_eq!(
+            (
+                metrics.pure_trials,
+                metrics.repeat_trials,
+                metrics.switch_trials
+            ),
+            (2, 2, 4)
+        );
+        assert!((metrics.pure_median_rt_ms - 510.0).abs() < 1e-9);
+        assert!((metrics.repeat_median_rt_ms - 600.0).abs() < 1e-9);
+        assert!((metrics.switch_median_rt_ms - 730.0).abs() < 1e-9);
+        assert!((metrics.switch_cost_ms - 130.0).abs() < 1e-9);
+        assert!((metrics.mixing_cost_ms - 90.0).abs() < 1e-9);
+        assert!((metrics.repeat_error_rate - 0.5).abs() < 1e-9);
+        assert!((metrics.mixing_cost_error_rate - 0.5).abs() < 1e-9);
+        assert!(metrics.switch_cost_error_rate.abs() < 1e-9);
+        assert!(!metrics.meets_min_trial_requirement);
+    }
+
+    #[test]
+    fn costs_need_both_kinds() {
+        let trials = vec![
+            trial(0, TrialKind::Repeat, TrialOutcome::Correct { rt_ms: 600.0 }),
+            trial(1, TrialKind::Switch, TrialOutcome::Miss),
+        ];
+        let metrics = TaskSwitchMetrics::from_trials(&trials);
+        assert_eq!(metrics.switch_cost_ms, 0.0);
+        assert_eq!(metrics.mixing_cost_ms, 0.0);
+        assert_eq!(TaskSwitchMetrics::from_trials(&[]).total_trials, 0);
+    }
+}
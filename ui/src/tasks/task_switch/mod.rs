@@ -0,0 +1,8 @@
+mod engine;
+pub use engine::TaskSwitchEngine;
+
+mod metrics;
+pub use metrics::TaskSwitchMetrics;
+
+mod view;
+pub use view::TaskSwitchView;
@@ -0,0 +1,657 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
+
+use crate::core::format;
+use crate::core::qc::QualityFlags;
+use crate::core::readiness::{self, Readiness};
+use crate::core::store::{use_shared_store, SharedStore};
+use crate::core::{platform, storage, timing};
+
+use super::engine::{
+    AdvanceOutcome, EngineState, ResponseOutcome, Rule, RunMode, Side, SwitchTrial,
+    TaskSwitchConfig, TaskSwitchEngine, TrialKind, TrialOutcome, TrialSchedule,
+};
+use super::metrics::TaskSwitchMetrics;
+
+const FEEDBACK_HOLD_MS: u64 = 650;
+
+#[component]
+pub fn TaskSwitchView() -> Element {
+    // Subscribe to global language code so instructional section re-renders on locale switch.
+    let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
+    let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
+
+    let engine = use_signal(|| TaskSwitchEngine::new(TaskSwitchConfig::default()));
+    let qc_flags = use_signal(QualityFlags::pristine);
+    let practice_metrics = use_signal(|| Option::<TaskSwitchMetrics>::None);
+    let last_metrics = use_signal(|| Option::<TaskSwitchMetrics>::None);
+    let last_error = use_signal(|| Option::<String>::None);
+    let feedback_state = use_signal(|| Option::<FeedbackState>::None);
+    let store = use_shared_store();
+    let mut readiness_info = use_signal(|| current_readiness(&store));
+
+    let sender_slot: Rc<RefCell<Option<UnboundedSender<TaskSwitchEvent>>>> =
+        Rc::new(RefCell::new(None));
+    let sender_slot_for_loop = sender_slot.clone();
+
+    let coroutine = {
+        let engine_ref = engine;
+        let store_ref = store.clone();
+        let qc_ref = qc_flags;
+        let practice_ref = practice_metrics;
+        let last_metrics_ref = last_metrics;
+        let error_ref = last_error;
+        let feedback_ref = feedback_state;
+
+        use_coroutine(move |mut rx: UnboundedReceiver<TaskSwitchEvent>| {
+            let sender_slot = sender_slot_for_loop.clone();
+            let store = store_ref.clone();
+            let mut engine = engine_ref;
+            let mut qc_flags = qc_ref;
+            let mut practice_metrics = practice_ref;
+            let mut last_metrics = last_metrics_ref;
+            let mut last_error = error_ref;
+            let mut feedback_signal = feedback_ref;
+
+            async move {
+                while let Some(event) = rx.next().await {
+                    match event {
+                        TaskSwitchEvent::StartPractice => {
+                            practice_metrics.set(None);
+                            last_error.set(None);
+                            feedback_signal.set(None);
+                            if let Some(schedule) =
+                                engine.with_mut(|eng| eng.start(RunMode::Practice))
+                            {
+                                queue_trial(sender_slot.clone(), schedule);
+                            }
+                        }
+                        TaskSwitchEvent::StartMain => {
+                            last_metrics.set(None);
+                            last_error.set(None);
+                            qc_flags.set(QualityFlags::pristine());
+                            feedback_signal.set(None);
+                            if let Some(schedule) = engine.with_mut(|eng| eng.start(RunMode::Main))
+                            {
+                                queue_trial(sender_slot.clone(), schedule);
+                            }
+                        }
+                        TaskSwitchEvent::Abort => {
+                            engine.with_mut(|eng| eng.abort());
+                        }
+                        TaskSwitchEvent::Pause => {
+                            if engine.with_mut(|eng| eng.pause()) {
+                                feedback_signal.set(None);
+                            }
+                        }
+                        TaskSwitchEvent::Resume => {
+                            if let Some(schedule) = engine.with_mut(|eng| eng.resume()) {
+                                queue_trial(sender_slot.clone(), schedule);
+                            }
+                        }
+                        TaskSwitchEvent::StimulusReady {
+                            run_id,
+                            trial_index,
+                            advance_wait_ms,
+                        } => {
+                            let should_schedule = engine.with_mut(|eng| {
+                                if eng.run_id == run_id {
+                                    eng.mark_stimulus_on(trial_index, timing::now())
+                                } else {
+                                    false
+                                }
+                            });
+
+                            if should_schedule {
+                                queue_advance(
+                                    sender_slot.clone(),
+                                    run_id,
+                                    trial_index,
+                                    advance_wait_ms,
+                                );
+                            }
+                        }
+                        TaskSwitchEvent::Respond { side, timestamp } => {
+                            let (response, run_id, mode) = engine.with_mut(|eng| {
+                                let mode = match eng.state {
+                                    EngineState::StimulusActive { mode, .. } => Some(mode),
+                                    _ => None,
+                                };
+                                (eng.register_response(side, timestamp), eng.run_id, mode)
+                            });
+
+                            if let ResponseOutcome::Recorded {
+                                trial_index,
+                                correct,
+                            } = response
+                            {
+                                // Only the practice block gives feedback.
+                                if mode == Some(RunMode::Practice) {
+                                    let feedback = if correct {
+                                        FeedbackState::new(
+                                            crate::t!("task-switch-feedback-correct"),
+                                            FeedbackTone::Positive,
+                                        )
+                                    } else {
+                                        FeedbackState::new(
+                                            crate::t!("task-switch-feedback-error"),
+                                            FeedbackTone::Negative,
+                                        )
+                                    };
+                                    feedback_signal.set(Some(feedback));
+                                    schedule_feedback_clear(
+                                        sender_slot.clone(),
+                                        run_id,
+                                        FEEDBACK_HOLD_MS,
+                                    );
+                                }
+                                // An answer ends the trial.
+                                queue_advance(sender_slot.clone(), run_id, trial_index, 0);
+                            }
+                        }
+                        TaskSwitchEvent::Advance {
+                            run_id,
+                            trial_index,
+                        } => {
+                            let (outcome, trial_snapshot) = engine.with_mut(|eng| {
+                                if eng.run_id == run_id {
+                                    let result = eng.advance(trial_index);
+                                    let trial = eng
+                                        .trials()
+                                        .get(trial_index)
+                                        .map(|trial| trial.outcome.clone());
+                                    (result, trial)
+                                } else {
+                                    (AdvanceOutcome::Ignored, None)
+                                }
+                            });
+
+                            let practicing = matches!(
+                                engine.with(|eng| eng.state),
+                                EngineState::Waiting {
+                                    mode: RunMode::Practice,
+                                    ..
+                                } | EngineState::Completed {
+                                    mode: RunMode::Practice
+                                }
+                            );
+                            if practicing
+                                && !matches!(outcome, AdvanceOutcome::Ignored)
+                                && matches!(trial_snapshot, Some(TrialOutcome::Miss))
+                            {
+                                feedback_signal.set(Some(FeedbackState::new(
+                                    crate::t!("task-switch-feedback-miss"),
+                                    FeedbackTone::Negative,
+                                )));
+                                schedule_feedback_clear(
+                                    sender_slot.clone(),
+                                    run_id,
+                                    FEEDBACK_HOLD_MS,
+                                );
+                            }
+
+                            match outcome {
+                                AdvanceOutcome::Next(schedule) => {
+                                    queue_trial(sender_slot.clone(), schedule);
+                                }
+                                AdvanceOutcome::Completed { mode } => {
+                                    let recorded = finalize_run(
+                                        &store,
+                                        mode,
+                                        &engine,
+                                        qc_flags,
+                                        practice_metrics,
+                                        last_metrics,
+                                        last_error,
+                                    );
+                                    if recorded {
+                                        readiness_info.set(current_readiness(&store));
+                                    }
+                                }
+                                AdvanceOutcome::Ignored => {}
+                            }
+                        }
+                        TaskSwitchEvent::FocusLost => {
+                            if engine.with(|eng| matches!(eng.state, EngineState::Paused { .. })) {
+                                continue;
+                            }
+                            qc_flags.with_mut(|flags| {
+                                flags.log_focus_loss();
+                                flags.log_visibility_blur();
+                            });
+                        }
+                        TaskSwitchEvent::ClearFeedback { run_id } => {
+                            let current_run = engine.with(|eng| eng.run_id);
+                            if current_run == run_id {
+                                feedback_signal.set(None);
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    };
+
+    sender_slot.borrow_mut().replace(coroutine.tx());
+
+    let send_event = {
+        let coroutine_handle = coroutine;
+        move |event: TaskSwitchEvent| coroutine_handle.send(event)
+    };
+
+    let respond = {
+        let send_event_handle = send_event;
+        move |side: Side| {
+            send_event_handle(TaskSwitchEvent::Respond {
+                side,
+                timestamp: timing::now(),
+            });
+        }
+    };
+
+    let engine_snapshot = engine();
+
+    // While waiting on a block's first trial, announce the block; while a
+    // digit is up, show it with its cue.
+    let (active_mode, block_notice, current_trial) = match engine_snapshot.state {
+        EngineState::Waiting { mode, trial_index } => {
+            let notice = engine_snapshot
+                .trials()
+                .get(trial_index)
+                .filter(|trial| trial.first_in_block)
+                .map(block_label);
+            (Some(mode), notice, None)
+        }
+        EngineState::Paused { mode, .. } => (Some(mode), None, None),
+        EngineState::StimulusActive { mode, trial_index } => {
+            let trial = engine_snapshot
+                .trials()
+                .get(trial_index)
+                .map(|trial| (trial.rule, trial.digit));
+            (Some(mode), None, trial)
+        }
+        _ => (None, None, None),
+    };
+
+    let is_running = active_mode.is_some();
+    let is_paused = matches!(engine_snapshot.state, EngineState::Paused { .. });
+    let started = engine_snapshot
+        .trials()
+        .iter()
+        .any(|trial| trial.presented_at.is_some());
+    let total_trials = engine_snapshot.total_trials(active_mode.unwrap_or(RunMode::Main));
+    let completed_trials = engine_snapshot
+        .trials()
+        .iter()
+        .filter(|trial| trial.is_completed())
+        .count();
+
+    let last_practice = practice_metrics();
+    let latest_metrics = last_metrics();
+    let error_message_cloned = last_error();
+    let feedback = feedback_state();
+
+    let mode_label = active_mode
+        .map(|mode| match mode {
+            RunMode::Practice => crate::t!("task-switch-mode-practice"),
+            RunMode::Main => crate::t!("task-switch-mode-main"),
+        })
+        .unwrap_or_default();
+
+    rsx! {
+        article { class: "task task-switch",
+
+            if is_running {
+                section { class: "task-card task-card--canvas task-switch__canvas",
+
+                    button {
+                        class: "button button--ghost button--compact task-canvas__cancel",
+                        onclick: move |_| send_event(TaskSwitchEvent::Abort),
+                        {crate::t!("common-cancel")}
+                    }
+
+                    if !mode_label.is_empty() {
+                        div { class: "task-mode-badge", "{mode_label}" }
+                    }
+
+                    if is_paused {
+                        div { class: "task-canvas__paused",
+                            p { {crate::t!("task-paused-note")} }
+                            button {
+                                r#type: "button",
+                                class: "button button--primary",
+                                autofocus: true,
+                                onclick: move |_| send_event(TaskSwitchEvent::Resume),
+                                {crate::t!("common-resume")}
+                            }
+                        }
+                    } else {
+                        button {
+                            class: "button button--ghost button--compact task-canvas__pause",
+                            // Keep focus on the stage so pausing is not logged as a focus loss.
+                            onmousedown: move |evt| evt.prevent_default(),
+                            onclick: move |_| send_event(TaskSwitchEvent::Pause),
+                            {crate::t!("common-pause")}
+                        }
+
+                        div {
+                            class: "task-switch__stage",
+                            tabindex: "0",
+                            autofocus: true,
+                            onkeydown: move |evt| {
+                                let key = evt.key().to_string().to_lowercase();
+                                let side = match key.as_str() {
+                                    "arrowleft" | "f" => Side::Left,
+                                    "arrowright" | "j" => Side::Right,
+                                    _ => return,
+                                };
+                                evt.prevent_default();
+                                respond(side);
+                            },
+                            onfocusout: move |_| send_event(TaskSwitchEvent::FocusLost),
+
+                            if let Some((rule, digit)) = current_trial {
+                                div { class: format!("task-switch__cue task-switch__cue--{}", rule.as_str()), "{cue_label(rule)}" }
+                                div { class: "task-switch__digit", "{digit}" }
+                            } else if let Some(notice) = block_notice.clone() {
+                                div { class: "task-switch__cue" }
+                                div { class: "task-switch__block", "{notice}" }
+                            } else if started {
+                                div { class: "task-switch__cue" }
+                                div { class: "task-switch__digit task-switch__fixation", "+" }
+                            } else {
+                                div { class: "task-switch__cue" }
+                                div { class: "task-switch__ready", {crate::t!("task-switch-get-ready")} }
+                            }
+
+                            div { class: "task-switch__responses",
+                                button {
+                                    r#type: "button",
+                                    class: "button task-switch__response",
+                                    aria_label: crate::t!("task-switch-aria-left"),
+                                    // Answering by tap must not pull focus off the stage.
+                                    onmousedown: move |evt| evt.prevent_default(),
+                                    onclick: move |_| respond(Side::Left),
+                                    {crate::t!("task-switch-response-left")}
+                                }
+                                button {
+                                    r#type: "button",
+                                    class: "button task-switch__response",
+                                    aria_label: crate::t!("task-switch-aria-right"),
+                                    onmousedown: move |evt| evt.prevent_default(),
+                                    onclick: move |_| respond(Side::Right),
+                                    {crate::t!("task-switch-response-right")}
+                                }
+                            }
+
+                            if let Some(feedback) = feedback.clone() {
+                                div { class: format!("task-feedback {}", feedback.css_class()), "{feedback.message}" }
+                            }
+                        }
+                    }
+
+                    div { class: "task-progress task-progress--overlay",
+                        span { {crate::t!("common-progress")} }
+                        span { class: "task-progress__value", "{completed_trials}/{total_trials}" }
+                    }
+                }
+            } else {
+                // Readiness advisory banner (non-blocking)
+                {
+                    let r: Readiness = readiness_info();
+                    rsx! {
+                        section { class: format!("task-readiness {}", r.css_class()),
+                            span { class: "task-readiness__status", "{r.status_label()}" }
+                            span { class: "task-readiness__detail", "{r.detail_message()}" }
+                        }
+                    }
+                }
+                section { class: "task-card task-card--instructions task-switch__controls",
+                    // Hidden i18n marker to force re-render of instruction copy when locale changes
+                    div { style: "display:none", "{_lang_marker}" }
+                    h3 { {crate::t!("task-switch-how-summary")} }
+                    ul {
+                        li { {crate::t!("task-switch-how-step-rules")} }
+                        li { {crate::t!("task-switch-how-step-keys")} }
+                        li { {crate::t!("task-switch-how-step-blocks")} }
+                        li { {crate::t!("task-switch-how-step-practice")} }
+                    }
+
+                    div { class: "task-cta", style: "display:flex; gap:0.75rem; flex-wrap:wrap;",
+                        button {
+                            class: "button button--accent",
+                            onclick: move |_| send_event(TaskSwitchEvent::StartPractice),
+                            {crate::t!("task-switch-start-practice")}
+                        }
+                        button {
+                            class: "button button--primary",
+                            onclick: move |_| send_event(TaskSwitchEvent::StartMain),
+                            {crate::t!("task-switch-start-main")}
+                        }
+                    }
+                }
+
+                if let Some(metrics) = last_practice {
+                    section { class: "task-card task-card--subtle task-switch__practice-summary",
+                        h3 { {crate::t!("task-switch-practice-recap")} }
+                        p {
+                            {crate::t!("task-switch-metric-accuracy")} " " {(metrics.accuracy * 100.0).round().to_string()} "%"
+                            " • " {crate::t!("task-switch-metric-errors")} " " {metrics.errors.to_string()}
+                            " • " {crate::t!("task-switch-metric-misses")} " " {metrics.misses.to_string()}
+                        }
+                    }
+                }
+
+                if let Some(metrics) = latest_metrics {
+                    section { class: "task-card task-switch__metrics",
+                        h3 { {crate::t!("task-switch-last-session")} }
+                        ul { class: "metrics-grid",
+                            li { {crate::t!("task-switch-metric-accuracy")} ": " {(metrics.accuracy * 100.0).round().to_string()} "%" }
+                            li { {crate::t!("task-switch-metric-pure-rt")} ": " {format::format_ms(metrics.pure_median_rt_ms).to_string()} }
+                            li { {crate::t!("task-switch-metric-repeat-rt")} ": " {format::format_ms(metrics.repeat_median_rt_ms).to_string()} }
+                            li { {crate::t!("task-switch-metric-switch-rt")} ": " {format::format_ms(metrics.switch_median_rt_ms).to_string()} }
+                            li { {crate::t!("task-switch-metric-switch-cost")} ": " {format!("{:+.0} ms", metrics.switch_cost_ms)} }
+                            li { {crate::t!("task-switch-metric-mixing-cost")} ": " {format!("{:+.0} ms", metrics.mixing_cost_ms)} }
+                            li { {crate::t!("task-switch-metric-errors")} ": " {metrics.errors.to_string()} }
+                            li { {crate::t!("task-switch-metric-misses")} ": " {metrics.misses.to_string()} }
+                        }
+                    }
+                } else {
+                    section { class: "task-card task-switch__metrics task-metrics--placeholder",
+                        p { {crate::t!("task-switch-metrics-placeholder")} }
+                    }
+                }
+
+                if let Some(err) = error_message_cloned {
+                    div { class: "task-error", {crate::t!("task-switch-error-generic", message = err.clone())} }
+                }
+            }
+        }
+    }
+}
+
+fn cue_label(rule: Rule) -> String {
+    match rule {
+        Rule::Parity => crate::t!("task-switch-cue-parity"),
+        Rule::Magnitude => crate::t!("task-switch-cue-magnitude"),
+    }
+}
+
+/// What the block starting at `trial` asks for.
+fn block_label(trial: &SwitchTrial) -> String {
+    match (trial.kind, trial.rule) {
+        (TrialKind::Pure, Rule::Parity) => crate::t!("task-switch-block-parity"),
+        (TrialKind::Pure, Rule::Magnitude) => crate::t!("task-switch-block-magnitude"),
+        _ => crate::t!("task-switch-block-mixed"),
+    }
+}
+
+fn finalize_run(
+    store: &SharedStore,
+    mode: RunMode,
+    engine: &Signal<TaskSwitchEngine>,
+    mut qc_flags: Signal<QualityFlags>,
+    mut practice_metrics: Signal<Option<TaskSwitchMetrics>>,
+    mut last_metrics: Signal<Option<TaskSwitchMetrics>>,
+    mut last_error: Signal<Option<String>>,
+) -> bool {
+    match mode {
+        RunMode::Practice => {
+            if let Some(metrics) = engine.with(|eng| eng.practice_metrics()) {
+                practice_metrics.set(Some(metrics));
+            }
+            false
+        }
+        RunMode::Main => {
+            let Some(metrics) = engine.with(|eng| eng.main_metrics()) else {
+                return false;
+            };
+            let (pause_count, paused_ms) = engine.with(|eng| (eng.pause_count(), eng.paused_ms()));
+            qc_flags.with_mut(|flags| {
+                flags.mark_min_trials(metrics.meets_min_trial_requirement);
+                flags.record_pauses(pause_count, paused_ms);
+            });
+            let qc_snapshot = qc_flags();
+            match serde_json::to_value(&metrics) {
+                Ok(metrics_json) => {
+                    let record =
+                        storage::SummaryRecord::new("task_switch", metrics_json, qc_snapshot);
+                    let trials = if storage::keep_trials() {
+                        engine.with(|eng| eng.trial_samples())
+                    } else {
+                        Vec::new()
+                    };
+                    if let Err(err) = storage::append_summary(store, &record, &trials) {
+                        last_error.set(Some(format!("Failed to persist summary: {err}")));
+                    } else {
+                        last_error.set(None);
+                    }
+                    last_metrics.set(Some(metrics));
+                    true
+                }
+                Err(err) => {
+                    last_error.set(Some(format!("Failed to serialise metrics: {err}")));
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Cooldown advisory from the most recent stored task-switching run.
+fn current_readiness(store: &SharedStore) -> Readiness {
+    match storage::load_summaries(store) {
+        Ok(mut records) => {
+            records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            let last = records.iter().find(|r| r.task == "task_switch");
+            readiness::evaluate("task_switch", last)
+        }
+        Err(_) => readiness::evaluate("task_switch", None),
+    }
+}
+
+fn queue_trial(
+    sender_slot: Rc<RefCell<Option<UnboundedSender<TaskSwitchEvent>>>>,
+    schedule: TrialSchedule,
+) {
+    let stimulus = schedule.stimulus;
+    let advance_wait_ms = schedule.advance.wait_ms;
+    if let Some(sender) = sender_slot.borrow().as_ref().cloned() {
+        platform::spawn_future(async move {
+            timing::sleep_ms(stimulus.wait_ms).await;
+            let _ = sender.unbounded_send(TaskSwitchEvent::StimulusReady {
+                run_id: stimulus.run_id,
+                trial_index: stimulus.trial_index,
+                advance_wait_ms,
+            });
+        });
+    }
+}
+
+fn queue_advance(
+    sender_slot: Rc<RefCell<Option<UnboundedSender<TaskSwitchEvent>>>>,
+    run_id: u64,
+    trial_index: usize,
+    wait_ms: u64,
+) {
+    if let Some(sender) = sender_slot.borrow().as_ref().cloned() {
+        platform::spawn_future(async move {
+            timing::sleep_ms(wait_ms).await;
+            let _ = sender.unbounded_send(TaskSwitchEvent::Advance {
+                run_id,
+                trial_index,
+            });
+        });
+    }
+}
+
+fn schedule_feedback_clear(
+    sender_slot: Rc<RefCell<Option<UnboundedSender<TaskSwitchEvent>>>>,
+    run_id: u64,
+    wait_ms: u64,
+) {
+    if let Some(sender) = sender_slot.borrow().as_ref().cloned() {
+        platform::spawn_future(async move {
+            timing::sleep_ms(wait_ms).await;
+            let _ = sender.unbounded_send(TaskSwitchEvent::ClearFeedback { run_id });
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+enum TaskSwitchEvent {
+    StartPractice,
+    StartMain,
+    Abort,
+    Pause,
+    Resume,
+    StimulusReady {
+        run_id: u64,
+        trial_index: usize,
+        advance_wait_ms: u64,
+    },
+    Advance {
+        run_id: u64,
+        trial_index: usize,
+    },
+    Respond {
+        side: Side,
+        timestamp: crate::core::timing::InstantStamp,
+    },
+    FocusLost,
+    ClearFeedback {
+        run_id: u64,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct FeedbackState {
+    message: String,
+    tone: FeedbackTone,
+}
+
+impl FeedbackState {
+    fn new<M: Into<String>>(message: M, tone: FeedbackTone) -> Self {
+        Self {
+            message: message.into(),
+            tone,
+        }
+    }
+
+    fn css_class(&self) -> &'static str {
+        match self.tone {
+            FeedbackTone::Positive => "task-feedback--positive",
+            FeedbackTone::Negative => "task-feedback--negative",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FeedbackTone {
+    Positive,
+    Negative,
+}
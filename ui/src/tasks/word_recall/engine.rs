@@ -0,0 +1,571 @@
+//! Engine for the delayed word-list recall task.
+//!
+//! A run shows a list of words one at a time (study), then asks for as many
+//! of them as can be remembered, in any order (immediate recall). A main run
+//! can then wait while the participant does something else and come back for
+//! a second, delayed recall of the same list, which is where episodic memory
+//! loss shows most.
+//!
+//! Answers are typed, or picked from a word bank mixing the list with as many
+//! foils. Each answer is scored as it comes in: a list word not yet given is
+//! correct, a word off the list an intrusion, and anything already given in
+//! the same recall a repetition.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::core::storage::TrialSample;
+use crate::core::timing::{self, InstantStamp};
+
+use super::metrics::WordRecallMetrics;
+use super::pool::normalize;
+
+/// Different run phases for the recall engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunMode {
+    Practice,
+    Main,
+}
+
+impl RunMode {
+    fn seed_tag(self) -> u64 {
+        match self {
+            RunMode::Practice => 0x57_5250_5241_4354_u64, // "WRPRACT"
+            RunMode::Main => 0x57_524d_4149_4e52_u64,     // "WRMAINR"
+        }
+    }
+}
+
+/// How answers are given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseMode {
+    #[default]
+    Typed,
+    /// Picked from a word bank; easier than free recall, so scores are only
+    /// comparable between runs in the same mode.
+    Selected,
+}
+
+impl ResponseMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResponseMode::Typed => "typed",
+            ResponseMode::Selected => "selected",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecallPhase {
+    Immediate,
+    Delayed,
+}
+
+/// Public configuration knobs for the task.
+#[derive(Debug, Clone)]
+pub struct WordRecallConfig {
+    /// How long each word stays on screen during study.
+    pub word_ms: u64,
+    pub lead_in_ms: u64,
+    /// Suggested wait between immediate and delayed recall.
+    pub delay_ms: u64,
+    pub seed: u64,
+}
+
+impl Default for WordRecallConfig {
+    fn default() -> Self {
+        Self {
+            word_ms: 2_000,
+            lead_in_ms: 750,
+            delay_ms: 10 * 60_000,
+            seed: 1,
+        }
+    }
+}
+
+/// The words of one run.
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    /// Where the list came from, e.g. `en-US/v1/A`; stored with the run.
+    pub form_id: String,
+    pub words: Vec<String>,
+    /// Off-list words mixed into the word bank.
+    pub foils: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineState {
+    Idle,
+    /// `shown` words have been put up; the last of them is on screen.
+    Study {
+        mode: RunMode,
+        shown: usize,
+    },
+    Recall {
+        mode: RunMode,
+        phase: RecallPhase,
+    },
+    /// Immediate recall is done and stored; delayed recall can start.
+    AwaitingDelayed,
+    Completed {
+        mode: RunMode,
+    },
+    Aborted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseKind {
+    Correct,
+    Intrusion,
+    Repetition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecallResponse {
+    pub phase: RecallPhase,
+    /// The answer as given.
+    pub text: String,
+    pub kind: ResponseKind,
+    /// Time from the recall opening to this answer.
+    pub latency_ms: f64,
+    pub timestamp: InstantStamp,
+}
+
+#[derive(Debug, Clone)]
+pub enum StudyOutcome {
+    /// Another word is up; tick again after `wait_ms`.
+    Next {
+        run_id: u64,
+        wait_ms: u64,
+    },
+    RecallOpen,
+    Ignored,
+}
+
+#[derive(Debug, Clone)]
+pub enum SubmitOutcome {
+    Recorded(ResponseKind),
+    Ignored,
+}
+
+#[derive(Debug, Clone)]
+pub struct WordRecallEngine {
+    pub config: WordRecallConfig,
+    pub state: EngineState,
+    pub run_id: u64,
+    response_mode: ResponseMode,
+    list: WordList,
+    bank: Vec<String>,
+    responses: Vec<RecallResponse>,
+    study_started_at: Option<InstantStamp>,
+    recall_opened_at: Option<InstantStamp>,
+    immediate_closed_at: Option<InstantStamp>,
+    delay_ms: Option<f64>,
+    last_practice_metrics: Option<WordRecallMetrics>,
+    last_main_metrics: Option<WordRecallMetrics>,
+}
+
+impl WordRecallEngine {
+    pub fn new(config: WordRecallConfig) -> Self {
+        Self {
+            config,
+            state: EngineState::Idle,
+            run_id: 0,
+            response_mode: ResponseMode::default(),
+            list: WordList::default(),
+            bank: Vec::new(),
+            responses: Vec::new(),
+            study_started_at: None,
+            recall_opened_at: None,
+            immediate_closed_at: None,
+            delay_ms: None,
+            last_practice_metrics: None,
+            last_main_metrics: None,
+        }
+    }
+
+    pub fn practice_metrics(&self) -> Option<WordRecallMetrics> {
+        self.last_practice_metrics.clone()
+    }
+
+    /// The main run's scores; before delayed recall, only the immediate ones.
+    pub fn main_metrics(&self) -> Option<WordRecallMetrics> {
+        self.last_main_metrics.clone()
+    }
+
+    pub fn response_mode(&self) -> ResponseMode {
+        self.response_mode
+    }
+
+    pub fn list(&self) -> &WordList {
+        &self.list
+    }
+
+    /// The list and its foils in a fixed shuffled order, for picking answers.
+    pub fn word_bank(&self) -> &[String] {
+        &self.bank
+    }
+
+    pub fn responses(&self) -> &[RecallResponse] {
+        &self.responses
+    }
+
+    /// The word on screen during study.
+    pub fn current_word(&self) -> Option<&str> {
+        match self.state {
+            EngineState::Study { shown, .. } if shown > 0 => {
+                self.list.words.get(shown - 1).map(String::as_str)
+            }
+            _ => None,
+        }
+    }
+
+    /// Time since immediate recall closed, while delayed recall is pending.
+    pub fn since_immediate_ms(&self) -> Option<f64> {
+        match self.state {
+            EngineState::AwaitingDelayed => self.immediate_closed_at.map(timing::elapsed_ms),
+            _ => None,
+        }
+    }
+
+    /// The run's answers, for keeping with it: `response_ms` is from the first
+    /// word of study, `rt_ms` from the opening of the recall it belongs to.
+    pub fn trial_samples(&self) -> Vec<TrialSample> {
+        let Some(first) = self.study_started_at else {
+            return Vec::new();
+        };
+        self.responses
+            .iter()
+            .enumerate()
+            .map(|(index, response)| {
+                let outcome = match (response.phase, response.kind) {
+                    (RecallPhase::Immediate, ResponseKind::Correct) => "recalled",
+                    (RecallPhase::Immediate, ResponseKind::Intrusion) => "intrusion",
+                    (RecallPhase::Immediate, ResponseKind::Repetition) => "repetition",
+                    (RecallPhase::Delayed, ResponseKind::Correct) => "delayed_recalled",
+                    (RecallPhase::Delayed, ResponseKind::Intrusion) => "delayed_intrusion",
+                    (RecallPhase::Delayed, ResponseKind::Repetition) => "delayed_repetition",
+                };
+                TrialSample {
+                    index,
+                    iti_ms: None,
+                    onset_ms: None,
+                    response_ms: Some(timing::duration_ms(first, response.timestamp)),
+                    rt_ms: Some(response.latency_ms),
+                    outcome: outcome.to_string(),
+                    stimulus: Some(response.text.clone()),
+                    target: Some(self.on_list(&response.text)),
+                    lure: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Start a run on `list`. Refused while a run is underway, including one
+    /// waiting for its delayed recall.
+    pub fn start(
+        &mut self,
+        mode: RunMode,
+        list: WordList,
+        response_mode: ResponseMode,
+    ) -> Option<StudyOutcome> {
+        if matches!(
+            self.state,
+            EngineState::Study { .. } | EngineState::Recall { .. } | EngineState::AwaitingDelayed
+        ) || list.words.is_empty()
+        {
+            return None;
+        }
+
+        self.run_id = self.run_id.wrapping_add(1);
+        let mut bank: Vec<String> = list.words.iter().chain(&list.foils).cloned().collect();
+        bank.shuffle(&mut self.seeded_rng(mode));
+        self.bank = bank;
+        self.list = list;
+        self.response_mode = response_mode;
+        self.responses.clear();
+        self.study_started_at = None;
+        self.recall_opened_at = None;
+        self.immediate_closed_at = None;
+        self.delay_ms = None;
+        self.state = EngineState::Study { mode, shown: 0 };
+
+        Some(StudyOutcome::Next {
+            run_id: self.run_id,
+            wait_ms: self.config.lead_in_ms,
+        })
+    }
+
+    /// Put up the next study word, or open immediate recall after the last.
+    pub fn advance_study(&mut self, timestamp: InstantStamp) -> StudyOutcome {
+        let EngineState::Study { mode, shown } = self.state else {
+            return StudyOutcome::Ignored;
+        };
+
+        if shown < self.list.words.len() {
+            if shown == 0 {
+                self.study_started_at = Some(timestamp);
+            }
+            self.state = EngineState::Study {
+                mode,
+                shown: shown + 1,
+            };
+            StudyOutcome::Next {
+                run_id: self.run_id,
+                wait_ms: self.config.word_ms,
+            }
+        } else {
+            self.recall_opened_at = Some(timestamp);
+            self.state = EngineState::Recall {
+                mode,
+                phase: RecallPhase::Immediate,
+            };
+            StudyOutcome::RecallOpen
+        }
+    }
+
+    /// Score one answer. Blank answers, and answers outside a recall, are
+    /// ignored.
+    pub fn submit(&mut self, text: &str, timestamp: InstantStamp) -> SubmitOutcome {
+        let EngineState::Recall { phase, .. } = self.state else {
+            return SubmitOutcome::Ignored;
+        };
+        let answer = normalize(text);
+        if answer.is_empty() {
+            return SubmitOutcome::Ignored;
+        }
+
+        let repeated = self
+            .responses
+            .iter()
+            .any(|response| response.phase == phase && normalize(&response.text) == answer);
+        let kind = if repeated {
+            ResponseKind::Repetition
+        } else if self.on_list(&answer) {
+            ResponseKind::Correct
+        } else {
+            ResponseKind::Intrusion
+        };
+
+        let latency_ms = self
+            .recall_opened_at
+            .map(|opened| timing::duration_ms(opened, timestamp))
+            .unwrap_or_default();
+        self.responses.push(RecallResponse {
+            phase,
+            text: text.trim().to_string(),
+            kind,
+            latency_ms,
+            timestamp,
+        });
+        SubmitOutcome::Recorded(kind)
+    }
+
+    /// Close the open recall and score the run so far. Returns the state the
+    /// run moved to: `AwaitingDelayed` after a main run's immediate recall,
+    /// otherwise `Completed`.
+    pub fn close_recall(&mut self, timestamp: InstantStamp) -> Option<EngineState> {
+        let EngineState::Recall { mode, phase } = self.state else {
+            return None;
+        };
+
+        let delayed_done = phase == RecallPhase::Delayed;
+        let metrics = WordRecallMetrics::from_responses(
+            &self.responses,
+            &self.list.form_id,
+            self.list.words.len(),
+            self.response_mode,
+            delayed_done.then_some(self.delay_ms.unwrap_or_default()),
+        );
+
+        self.state = match (mode, phase) {
+            (RunMode::Practice, _) => {
+                self.last_practice_metrics = Some(metrics);
+                EngineState::Completed { mode }
+            }
+            (RunMode::Main, RecallPhase::Immediate) => {
+                self.last_main_metrics = Some(metrics);
+                self.immediate_closed_at = Some(timestamp);
+                EngineState::AwaitingDelayed
+            }
+            (RunMode::Main, RecallPhase::Delayed) => {
+                self.last_main_metrics = Some(metrics);
+                EngineState::Completed { mode }
+            }
+        };
+        Some(self.state)
+    }
+
+    /// Open delayed recall of the stored list.
+    pub fn begin_delayed(&mut self, timestamp: InstantStamp) -> bool {
+        if self.state != EngineState::AwaitingDelayed {
+            return false;
+        }
+        self.delay_ms = self
+            .immediate_closed_at
+            .map(|closed| timing::duration_ms(closed, timestamp));
+        self.recall_opened_at = Some(timestamp);
+        self.state = EngineState::Recall {
+            mode: RunMode::Main,
+            phase: RecallPhase::Delayed,
+        };
+        true
+    }
+
+    /// Finish a main run without its delayed recall; the immediate scores
+    /// stand.
+    pub fn skip_delayed(&mut self) -> bool {
+        if self.state != EngineState::AwaitingDelayed {
+            return false;
+        }
+        self.state = EngineState::Completed {
+            mode: RunMode::Main,
+        };
+        true
+    }
+
+    /// Abandon the run. Cancelling delayed recall only drops its answers, so
+    /// it can be started again.
+    pub fn abort(&mut self) {
+        if let EngineState::Recall {
+            phase: RecallPhase::Delayed,
+            ..
+        } = self.state
+        {
+            self.responses
+                .retain(|response| response.phase == RecallPhase::Immediate);
+            self.state = EngineState::AwaitingDelayed;
+        } else {
+            self.state = EngineState::Aborted;
+        }
+    }
+
+    fn on_list(&self, word: &str) -> bool {
+        let word = normalize(word);
+        self.list
+            .words
+            .iter()
+            .any(|listed| normalize(listed) == word)
+    }
+
+    fn seeded_rng(&self, mode: RunMode) -> StdRng {
+        let combined_seed = self.config.seed ^ mode.seed_tag() ^ self.run_id;
+        StdRng::seed_from_u64(combined_seed)
+    }
+}
+
+impl Default for WordRecallEngine {
+    fn default() -> Self {
+        Self::new(WordRecallConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list() -> WordList {
+        WordList {
+            form_id: "en-US/v1/A".into(),
+            words: vec!["apple".into(), "river".into(), "café".into()],
+            foils: vec!["bread".into(), "forest".into(), "mirror".into()],
+        }
+    }
+
+    fn study(engine: &mut WordRecallEngine) {
+        let mut shown = Vec::new();
+        loop {
+            match engine.advance_study(timing::now()) {
+                StudyOutcome::Next { .. } => shown.extend(engine.current_word().map(String::from)),
+                StudyOutcome::RecallOpen => break,
+                StudyOutcome::Ignored => panic!("not studying"),
+            }
+        }
+        assert_eq!(shown, engine.list().words);
+    }
+
+    #[test]
+    fn answers_are_scored_as_they_come_in() {
+        let mut engine = WordRecallEngine::default();
+        engine
+            .start(RunMode::Main, list(), ResponseMode::Typed)
+            .expect("schedule");
+        assert!(matches!(
+            engine.submit("apple", timing::now()),
+            SubmitOutcome::Ignored
+        ));
+        study(&mut engine);
+        assert_eq!(engine.word_bank().len(), 6);
+
+        let kinds: Vec<_> = [" Apple", "cafe", "bread", "APPLE", "bread", "  "]
+            .iter()
+            .filter_map(|answer| match engine.submit(answer, timing::now()) {
+                SubmitOutcome::Recorded(kind) => Some(kind),
+                SubmitOutcome::Ignored => None,
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ResponseKind::Correct,
+                ResponseKind::Correct,
+                ResponseKind::Intrusion,
+                ResponseKind::Repetition,
+                ResponseKind::Repetition,
+            ]
+        );
+
+        assert_eq!(
+            engine.close_recall(timing::now()),
+            Some(EngineState::AwaitingDelayed)
+        );
+        let immediate = engine.main_metrics().expect("immediate scores");
+        assert_eq!(immediate.immediate_correct, 2);
+        assert_eq!(immediate.delayed_correct, None);
+        assert!(engine
+            .start(RunMode::Practice, list(), ResponseMode::Typed)
+            .is_none());
+    }
+
+    #[test]
+    fn delayed_recall_scores_separately_and_can_be_cancelled() {
+        let mut engine = WordRecallEngine::default();
+        engine.start(RunMode::Main, list(), ResponseMode::Selected);
+        study(&mut engine);
+        engine.submit("river", timing::now());
+        engine.close_recall(timing::now());
+
+        assert!(engine.begin_delayed(timing::now()));
+        engine.submit("apple", timing::now());
+        engine.abort();
+        assert_eq!(engine.state, EngineState::AwaitingDelayed);
+        assert_eq!(engine.responses().len(), 1);
+
+        assert!(engine.begin_delayed(timing::now()));
+        // Words given at immediate recall are not repetitions now.
+        engine.submit("river", timing::now());
+        engine.submit("mirror", timing::now());
+        assert_eq!(
+            engine.close_recall(timing::now()),
+            Some(EngineState::Completed {
+                mode: RunMode::Main
+            })
+        );
+
+        let metrics = engine.main_metrics().expect("scores");
+        assert_eq!(metrics.immediate_correct, 1);
+        assert_eq!(metrics.delayed_correct, Some(1));
+        assert_eq!(metrics.delayed_intrusions, Some(1));
+        assert_eq!(metrics.response_mode, "selected");
+        let outcomes: Vec<_> = engine
+            .trial_samples()
+            .iter()
+            .map(|s| s.outcome.clone())
+            .collect();
+        assert_eq!(
+            outcomes,
+            ["recalled", "delayed_recalled", "delayed_intrusion"]
+        );
+    }
+}
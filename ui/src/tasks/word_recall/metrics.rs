@@ -0,0 +1,129 @@
+//! Metric definitions for word-recall runs.
+//!
+//! Each recall is scored on its own: words correctly recalled, intrusions
+//! (words off the list) and repetitions (an answer given twice in the same
+//! recall). Delayed fields stay empty for runs that skipped delayed recall.
+
+use serde::{Deserialize, Serialize};
+
+use super::engine::{RecallPhase, RecallResponse, ResponseKind, ResponseMode};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WordRecallMetrics {
+    /// Locale, pool version and form of the list, e.g. `en-US/v1/A`. Only runs
+    /// on the same pool version are comparable.
+    pub form_id: String,
+    /// `typed` or `selected` (from a word bank).
+    pub response_mode: String,
+    pub list_length: usize,
+    pub immediate_correct: u32,
+    pub immediate_intrusions: u32,
+    pub immediate_repetitions: u32,
+    pub delayed_correct: Option<u32>,
+    pub delayed_intrusions: Option<u32>,
+    pub delayed_repetitions: Option<u32>,
+    /// Time between the end of immediate recall and the start of delayed
+    /// recall.
+    pub delay_ms: Option<f64>,
+    /// Delayed over immediate correct; empty without both.
+    pub retention: Option<f64>,
+}
+
+impl WordRecallMetrics {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Score `responses`. `delay_ms` is `Some` once delayed recall is done.
+    pub fn from_responses(
+        responses: &[RecallResponse],
+        form_id: &str,
+        list_length: usize,
+        response_mode: ResponseMode,
+        delay_ms: Option<f64>,
+    ) -> Self {
+        let (immediate_correct, immediate_intrusions, immediate_repetitions) =
+            tally(responses, RecallPhase::Immediate);
+        let delayed = delay_ms.map(|_| tally(responses, RecallPhase::Delayed));
+        let retention = delayed
+            .filter(|_| immediate_correct > 0)
+            .map(|(correct, _, _)| correct as f64 / immediate_correct as f64);
+
+        Self {
+            form_id: form_id.to_string(),
+            response_mode: response_mode.as_str().to_string(),
+            list_length,
+            immediate_correct,
+            immediate_intrusions,
+            immediate_repetitions,
+            delayed_correct: delayed.map(|(correct, _, _)| correct),
+            delayed_intrusions: delayed.map(|(_, intrusions, _)| intrusions),
+            delayed_repetitions: delayed.map(|(_, _, repetitions)| repetitions),
+            delay_ms,
+            retention,
+        }
+    }
+}
+
+/// Correct, intrusions and repetitions within one recall.
+fn tally(responses: &[RecallResponse], phase: RecallPhase) -> (u32, u32, u32) {
+    responses
+        .iter()
+        .filter(|response| response.phase == phase)
+        .fold(
+            (0, 0, 0),
+            |(correct, intrusions, repetitions), response| match response.kind {
+                ResponseKind::Correct => (correct + 1, intrusions, repetitions),
+                ResponseKind::Intrusion => (correct, intrusions + 1, repetitions),
+                ResponseKind::Repetition => (correct, intrusions, repetitions + 1),
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::timing;
+
+    fn response(phase: RecallPhase, kind: ResponseKind) -> RecallResponse {
+        RecallResponse {
+            phase,
+            text: String::new(),
+            kind,
+            latency_ms: 0.0,
+            timestamp: timing::now(),
+        }
+    }
+
+    #[test]
+    fn retention_compares_delayed_with_immediate() {
+        let responses = vec![
+            response(RecallPhase::Immediate, ResponseKind::Correct),
+            response(RecallPhase::Immediate, ResponseKind::Correct),
+            response(RecallPhase::Immediate, ResponseKind::Correct),
+            response(RecallPhase::Immediate, ResponseKind::Correct),
+            response(RecallPhase::Immediate, ResponseKind::Repetition),
+            response(RecallPhase::Delayed, ResponseKind::Correct),
+            response(RecallPhase::Delayed, ResponseKind::Intrusion),
+        ];
+
+        let immediate_only =
+            WordRecallMetrics::from_responses(&responses, "x", 12, ResponseMode::Typed, None);
+        assert_eq!(immediate_only.immediate_correct, 4);
+        assert_eq!(immediate_only.immediate_repetitions, 1);
+        assert_eq!(immediate_only.delayed_correct, None);
+        assert_eq!(immediate_only.retention, None);
+
+        let full = WordRecallMetrics::from_responses(
+            &responses,
+            "x",
+            12,
+            ResponseMode::Typed,
+            Some(600_000.0),
+        );
+        assert_eq!(full.delayed_correct, Some(1));
+        assert_eq!(full.delayed_intrusions, Some(1));
+        assert_eq!(full.delayed_repetitions, Some(0));
+        assert_eq!(full.retention, Some(0.25));
+    }
+}
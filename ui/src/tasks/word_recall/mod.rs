@@ -0,0 +1,10 @@
+mod engine;
+pub use engine::WordRecallEngine;
+
+mod metrics;
+pub use metrics::WordRecallMetrics;
+
+mod pool;
+
+mod view;
+pub use view::WordRecallView;
@@ -0,0 +1,125 @@
+//! Word pools for the recall task.
+//!
+//! The lists live in the i18n resources (`word-recall-v{POOL_VERSION}-*`), so
+//! each locale has its own pool rather than translations of one list: every
+//! pool holds four alternate forms of twelve concrete, common nouns, matched
+//! for length and frequency within the locale, and no word appears in two
+//! forms. Editing a list changes what a score means, so any change to a pool
+//! goes into a new version with new message ids, never into the old ones.
+
+/// Version of the word pools; part of every message id and stored with each
+/// run.
+pub const POOL_VERSION: u32 = 1;
+
+/// One of the alternate, equivalent lists. Sessions rotate through them so a
+/// repeat test never reuses the words of the one before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Form {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl Form {
+    pub const ALL: [Form; 4] = [Form::A, Form::B, Form::C, Form::D];
+
+    /// The form for a user's `session`-th main run (counting from zero).
+    pub fn for_session(session: usize) -> Self {
+        Self::ALL[session % Self::ALL.len()]
+    }
+
+    /// The form whose words serve as foils in a word bank for this one.
+    pub fn foil_form(self) -> Self {
+        let index = Self::ALL.iter().position(|form| *form == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Form::A => "A",
+            Form::B => "B",
+            Form::C => "C",
+            Form::D => "D",
+        }
+    }
+}
+
+/// Split a pool message (`word, word, …`) into its words.
+pub fn parse_pool(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|word| word.trim().to_string())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Fold a word for matching: trimmed, lower-case, inner whitespace collapsed
+/// and accents dropped, so `Árbol ` matches `arbol`.
+pub fn normalize(word: &str) -> String {
+    word.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .chars()
+        .map(fold_accent)
+        .collect()
+}
+
+fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ä' | 'ã' => 'a',
+        'ç' => 'c',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ò' | 'ó' | 'ô' | 'ö' | 'õ' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ÿ' => 'y',
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCALES: [(&str, &str); 3] = [
+        ("en-US", include_str!("../../../i18n/en-US/looplace-ui.ftl")),
+        ("es-ES", include_str!("../../../i18n/es-ES/looplace-ui.ftl")),
+        ("fr-FR", include_str!("../../../i18n/fr-FR/looplace-ui.ftl")),
+    ];
+
+    fn message<'a>(ftl: &'a str, id: &str) -> Option<&'a str> {
+        ftl.lines()
+            .find_map(|line| line.strip_prefix(id)?.trim_start().strip_prefix('='))
+    }
+
+    #[test]
+    fn every_locale_has_four_disjoint_forms_of_twelve() {
+        for (locale, ftl) in LOCALES {
+            let mut seen = std::collections::HashSet::new();
+            for form in Form::ALL {
+                let id = format!(
+                    "word-recall-v{POOL_VERSION}-form-{}",
+                    form.label().to_lowercase()
+                );
+                let words = parse_pool(message(ftl, &id).unwrap_or_default());
+                assert_eq!(words.len(), 12, "{locale} {id}");
+                for word in &words {
+                    assert!(seen.insert(normalize(word)), "{locale}: {word} repeats");
+                }
+            }
+            let practice = format!("word-recall-v{POOL_VERSION}-practice");
+            assert!(!parse_pool(message(ftl, &practice).unwrap_or_default()).is_empty());
+        }
+    }
+
+    #[test]
+    fn matching_ignores_case_spacing_and_accents() {
+        assert_eq!(normalize("  Árbol "), "arbol");
+        assert_eq!(normalize("Île"), "ile");
+        assert_eq!(normalize("ice  cream"), "ice cream");
+        assert_eq!(Form::for_session(5), Form::B);
+        assert_eq!(Form::D.foil_form(), Form::A);
+    }
+}
@@ -0,0 +1,633 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use dioxus::prelude::*;
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
+
+use crate::core::qc::QualityFlags;
+use crate::core::readiness::{self, Readiness};
+use crate::core::store::{use_shared_store, SharedStore};
+use crate::core::{platform, storage, timing};
+
+use super::engine::{
+    EngineState, RecallPhase, ResponseKind, ResponseMode, RunMode, StudyOutcome, WordList,
+    WordRecallEngine,
+};
+use super::metrics::WordRecallMetrics;
+use super::pool::{normalize, parse_pool, Form, POOL_VERSION};
+
+/// A main run whose immediate recall is stored and whose delayed recall is
+/// still to come. Kept outside the component so the participant can leave
+/// the page for the delay; it lasts as long as the app does.
+struct PendingDelayed {
+    engine: WordRecallEngine,
+    record: storage::SummaryRecord,
+}
+
+static PENDING: Mutex<Option<PendingDelayed>> = Mutex::new(None);
+
+#[component]
+pub fn WordRecallView() -> Element {
+    // Subscribe to global language code so instructional section re-renders on locale switch.
+    let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
+    let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
+
+    let store = use_shared_store();
+    let engine = use_signal(|| {
+        pending_lock()
+            .as_ref()
+            .map(|pending| pending.engine.clone())
+            .unwrap_or_default()
+    });
+    let qc_flags = use_signal(QualityFlags::pristine);
+    let mut response_mode = use_signal(ResponseMode::default);
+    let mut draft = use_signal(String::new);
+    let practice_metrics = use_signal(|| Option::<WordRecallMetrics>::None);
+    let last_metrics = use_signal(|| {
+        pending_lock()
+            .as_ref()
+            .and_then(|pending| pending.engine.main_metrics())
+    });
+    let last_error = use_signal(|| Option::<String>::None);
+    let mut readiness_info = use_signal(|| current_readiness(&store));
+
+    let sender_slot: Rc<RefCell<Option<UnboundedSender<WordRecallEvent>>>> =
+        Rc::new(RefCell::new(None));
+    let sender_slot_for_loop = sender_slot.clone();
+
+    let coroutine = {
+        let engine_ref = engine;
+        let store_ref = store.clone();
+        let qc_ref = qc_flags;
+        let practice_ref = practice_metrics;
+        let last_metrics_ref = last_metrics;
+        let error_ref = last_error;
+
+        use_coroutine(move |mut rx: UnboundedReceiver<WordRecallEvent>| {
+            let sender_slot = sender_slot_for_loop.clone();
+            let store = store_ref.clone();
+            let mut engine = engine_ref;
+            let mut qc_flags = qc_ref;
+            let mut practice_metrics = practice_ref;
+            let mut last_metrics = last_metrics_ref;
+            let mut last_error = error_ref;
+
+            async move {
+                while let Some(event) = rx.next().await {
+                    match event {
+                        WordRecallEvent::StartPractice => {
+                            practice_metrics.set(None);
+                            last_error.set(None);
+                            let list = practice_list();
+                            let mode = response_mode();
+                            if let Some(outcome) =
+                                engine.with_mut(|eng| eng.start(RunMode::Practice, list, mode))
+                            {
+                                queue_study(sender_slot.clone(), outcome);
+                            }
+                        }
+                        WordRecallEvent::StartMain => {
+                            last_metrics.set(None);
+                            last_error.set(None);
+                            qc_flags.set(QualityFlags::pristine());
+                            let list = main_list(&store);
+                            let mode = response_mode();
+                            if let Some(outcome) =
+                                engine.with_mut(|eng| eng.start(RunMode::Main, list, mode))
+                            {
+                                queue_study(sender_slot.clone(), outcome);
+                            }
+                        }
+                        WordRecallEvent::Abort => {
+                            engine.with_mut(|eng| eng.abort());
+                        }
+                        WordRecallEvent::StudyTick { run_id } => {
+                            let outcome = engine.with_mut(|eng| {
+                                if eng.run_id == run_id {
+                                    eng.advance_study(timing::now())
+                                } else {
+                                    StudyOutcome::Ignored
+                                }
+                            });
+                            queue_study(sender_slot.clone(), outcome);
+                        }
+                        WordRecallEvent::Submit { text, timestamp } => {
+                            engine.with_mut(|eng| eng.submit(&text, timestamp));
+                        }
+                        WordRecallEvent::Done => {
+                            let closed = engine.with_mut(|eng| eng.close_recall(timing::now()));
+                            let recorded = match closed {
+                                Some(EngineState::Completed {
+                                    mode: RunMode::Practice,
+                                }) => {
+                                    practice_metrics.set(engine.with(|eng| eng.practice_metrics()));
+                                    false
+                                }
+                                Some(EngineState::AwaitingDelayed) => save_immediate(
+                                    &store,
+                                    &engine,
+                                    qc_flags,
+                                    last_metrics,
+                                    last_error,
+                                ),
+                                Some(EngineState::Completed {
+                                    mode: RunMode::Main,
+                                }) => save_delayed(&store, &engine, last_metrics, last_error),
+                                _ => false,
+                            };
+                            if recorded {
+                                readiness_info.set(current_readiness(&store));
+                            }
+                        }
+                        WordRecallEvent::BeginDelayed => {
+                            engine.with_mut(|eng| eng.begin_delayed(timing::now()));
+                        }
+                        WordRecallEvent::SkipDelayed => {
+                            if engine.with_mut(|eng| eng.skip_delayed()) {
+                                *pending_lock() = None;
+                            }
+                        }
+                        WordRecallEvent::FocusLost => {
+                            qc_flags.with_mut(|flags| {
+                                flags.log_focus_loss();
+                                flags.log_visibility_blur();
+                            });
+                        }
+                    }
+                }
+            }
+        })
+    };
+
+    sender_slot.borrow_mut().replace(coroutine.tx());
+
+    let send_event = {
+        let coroutine_handle = coroutine;
+        move |event: WordRecallEvent| coroutine_handle.send(event)
+    };
+
+    let mut submit_draft = {
+        let send_event_handle = send_event;
+        move || {
+            let text = draft();
+            if !text.trim().is_empty() {
+                send_event_handle(WordRecallEvent::Submit {
+                    text,
+                    timestamp: timing::now(),
+                });
+            }
+            draft.set(String::new());
+        }
+    };
+
+    let engine_snapshot = engine();
+
+    let (active_mode, recall_phase) = match engine_snapshot.state {
+        EngineState::Study { mode, .. } => (Some(mode), None),
+        EngineState::Recall { mode, phase } => (Some(mode), Some(phase)),
+        _ => (None, None),
+    };
+    let current_word = engine_snapshot.current_word().map(str::to_string);
+    let awaiting_delayed = engine_snapshot.state == EngineState::AwaitingDelayed;
+    let minutes_since = engine_snapshot
+        .since_immediate_ms()
+        .map(|ms| timing::ms_to_minutes(ms).floor() as u64)
+        .unwrap_or_default();
+    let suggested_minutes = engine_snapshot.config.delay_ms / 60_000;
+
+    // Answers of the open recall; in practice they are marked right or wrong.
+    let given: Vec<(String, Option<ResponseKind>)> = engine_snapshot
+        .responses()
+        .iter()
+        .filter(|response| Some(response.phase) == recall_phase)
+        .map(|response| {
+            let kind = (active_mode == Some(RunMode::Practice)).then_some(response.kind);
+            (response.text.clone(), kind)
+        })
+        .collect();
+    let bank: Vec<(String, bool)> = engine_snapshot
+        .word_bank()
+        .iter()
+        .map(|word| {
+            let picked = given
+                .iter()
+                .any(|(text, _)| normalize(text) == normalize(word));
+            (word.clone(), picked)
+        })
+        .collect();
+    let given_count = given.len();
+    let selecting = engine_snapshot.response_mode() == ResponseMode::Selected;
+    let chosen_mode = response_mode();
+
+    let last_practice = practice_metrics();
+    let latest_metrics = last_metrics();
+    let error_message_cloned = last_error();
+
+    let mode_label = match (active_mode, recall_phase) {
+        (Some(RunMode::Practice), _) => crate::t!("word-recall-mode-practice"),
+        (Some(RunMode::Main), Some(RecallPhase::Delayed)) => crate::t!("word-recall-mode-delayed"),
+        (Some(RunMode::Main), _) => crate::t!("word-recall-mode-main"),
+        (None, _) => String::new(),
+    };
+
+    rsx! {
+        article { class: "task task-word-recall",
+
+            if active_mode.is_some() {
+                section { class: "task-card task-card--canvas task-word-recall__canvas",
+
+                    button {
+                        class: "button button--ghost button--compact task-canvas__cancel",
+                        onclick: move |_| send_event(WordRecallEvent::Abort),
+                        {crate::t!("common-cancel")}
+                    }
+
+                    if !mode_label.is_empty() {
+                        div { class: "task-mode-badge", "{mode_label}" }
+                    }
+
+                    if recall_phase.is_none() {
+                        div {
+                            class: "task-word-recall__stage",
+                            tabindex: "0",
+                            autofocus: true,
+                            onfocusout: move |_| send_event(WordRecallEvent::FocusLost),
+                            div { class: "task-word-recall__word",
+                                if let Some(word) = current_word.clone() {
+                                    "{word}"
+                                } else {
+                                    span { class: "task-word-recall__ready", {crate::t!("word-recall-get-ready")} }
+                                }
+                            }
+                        }
+                    } else {
+                        div { class: "task-word-recall__recall",
+                            p { class: "task-word-recall__prompt", {crate::t!("word-recall-prompt")} }
+
+                            if selecting {
+                                div { class: "task-word-recall__bank",
+                                    for (word , picked) in bank.iter().cloned() {
+                                        button {
+                                            key: "{word}",
+                                            r#type: "button",
+                                            class: if picked { "button task-word-recall__choice task-word-recall__choice--picked" } else { "button task-word-recall__choice" },
+                                            disabled: picked,
+                                            onclick: move |_| {
+                                                send_event(WordRecallEvent::Submit {
+                                                    text: word.clone(),
+                                                    timestamp: timing::now(),
+                                                })
+                                            },
+                                            "{word}"
+                                        }
+                                    }
+                                }
+                            } else {
+                                form {
+                                    class: "task-word-recall__entry",
+                                    onsubmit: move |evt| {
+                                        evt.prevent_default();
+                                        submit_draft();
+                                    },
+                                    input {
+                                        r#type: "text",
+                                        autofocus: true,
+                                        autocomplete: "off",
+                                        aria_label: crate::t!("word-recall-aria-entry"),
+                                        value: "{draft}",
+                                        oninput: move |e| draft.set(e.value()),
+                                    }
+                                    button { r#type: "submit", class: "button", {crate::t!("word-recall-add")} }
+                                }
+                            }
+
+                            ul { class: "task-word-recall__given",
+                                for (index , (text , kind)) in given.iter().cloned().enumerate() {
+                                    li {
+                                        key: "{index}",
+                                        class: match kind {
+                                            Some(ResponseKind::Correct) => "task-word-recall__answer--correct",
+                                            Some(_) => "task-word-recall__answer--wrong",
+                                            None => "",
+                                        },
+                                        "{text}"
+                                    }
+                                }
+                            }
+
+                            button {
+                                r#type: "button",
+                                class: "button button--primary",
+                                onclick: move |_| send_event(WordRecallEvent::Done),
+                                {crate::t!("word-recall-done")}
+                            }
+                        }
+                    }
+
+                    div { class: "task-progress task-progress--overlay",
+                        span { {crate::t!("word-recall-progress-given")} }
+                        span { class: "task-progress__value", "{given_count}" }
+                    }
+                }
+            } else {
+                // Readiness advisory banner (non-blocking)
+                {
+                    let r: Readiness = readiness_info();
+                    rsx! {
+                        section { class: format!("task-readiness {}", r.css_class()),
+                            span { class: "task-readiness__status", "{r.status_label()}" }
+                            span { class: "task-readiness__detail", "{r.detail_message()}" }
+                        }
+                    }
+                }
+
+                if awaiting_delayed {
+                    section { class: "task-card task-word-recall__delayed",
+                        h3 { {crate::t!("word-recall-delayed-title")} }
+                        p {
+                            {crate::t!(
+                                "word-recall-delayed-waiting",
+                                minutes = minutes_since,
+                                suggested = suggested_minutes
+                            )}
+                        }
+                        div { class: "task-cta", style: "display:flex; gap:0.75rem; flex-wrap:wrap;",
+                            button {
+                                class: "button button--primary",
+                                onclick: move |_| send_event(WordRecallEvent::BeginDelayed),
+                                {crate::t!("word-recall-start-delayed")}
+                            }
+                            button {
+                                class: "button button--ghost",
+                                onclick: move |_| send_event(WordRecallEvent::SkipDelayed),
+                                {crate::t!("word-recall-skip-delayed")}
+                            }
+                        }
+                    }
+                } else {
+                    section { class: "task-card task-card--instructions task-word-recall__controls",
+                        // Hidden i18n marker to force re-render of instruction copy when locale changes
+                        div { style: "display:none", "{_lang_marker}" }
+                        h3 { {crate::t!("word-recall-how-summary")} }
+                        ul {
+                            li { {crate::t!("word-recall-how-step-study")} }
+                            li { {crate::t!("word-recall-how-step-recall")} }
+                            li { {crate::t!("word-recall-how-step-delayed")} }
+                            li { {crate::t!("word-recall-how-step-forms")} }
+                        }
+
+                        fieldset { class: "task-word-recall__mode",
+                            legend { {crate::t!("word-recall-response-mode")} }
+                            label {
+                                input {
+                                    r#type: "radio",
+                                    name: "word-recall-mode",
+                                    checked: chosen_mode == ResponseMode::Typed,
+                                    onchange: move |_| response_mode.set(ResponseMode::Typed),
+                                }
+                                " "
+                                {crate::t!("word-recall-mode-typed")}
+                            }
+                            label {
+                                input {
+                                    r#type: "radio",
+                                    name: "word-recall-mode",
+                                    checked: chosen_mode == ResponseMode::Selected,
+                                    onchange: move |_| response_mode.set(ResponseMode::Selected),
+                                }
+                                " "
+                                {crate::t!("word-recall-mode-selected")}
+                            }
+                        }
+
+                        div { class: "task-cta", style: "display:flex; gap:0.75rem; flex-wrap:wrap;",
+                            button {
+                                class: "button button--accent",
+                                onclick: move |_| send_event(WordRecallEvent::StartPractice),
+                                {crate::t!("word-recall-start-practice")}
+                            }
+                            button {
+                                class: "button button--primary",
+                                onclick: move |_| send_event(WordRecallEvent::StartMain),
+                                {crate::t!("word-recall-start-main")}
+                            }
+                        }
+                    }
+                }
+
+                if let Some(metrics) = last_practice {
+                    section { class: "task-card task-card--subtle task-word-recall__practice-summary",
+                        h3 { {crate::t!("word-recall-practice-recap")} }
+                        p {
+                            {crate::t!("word-recall-metric-correct")} " "
+                            {metrics.immediate_correct.to_string()} " / " {metrics.list_length.to_string()}
+                        }
+                    }
+                }
+
+                if let Some(metrics) = latest_metrics {
+                    section { class: "task-card task-word-recall__metrics",
+                        h3 { {crate::t!("word-recall-last-session")} }
+                        ul { class: "metrics-grid",
+                            li {
+                                {crate::t!("word-recall-metric-immediate")} ": "
+                                {metrics.immediate_correct.to_string()} " / " {metrics.list_length.to_string()}
+                            }
+                            if let Some(delayed) = metrics.delayed_correct {
+                                li {
+                                    {crate::t!("word-recall-metric-delayed")} ": "
+                                    {delayed.to_string()} " / " {metrics.list_length.to_string()}
+                                }
+                            }
+                            li {
+                                {crate::t!("word-recall-metric-intrusions")} ": "
+                                {(metrics.immediate_intrusions + metrics.delayed_intrusions.unwrap_or_default()).to_string()}
+                            }
+                            li {
+                                {crate::t!("word-recall-metric-repetitions")} ": "
+                                {(metrics.immediate_repetitions + metrics.delayed_repetitions.unwrap_or_default()).to_string()}
+                            }
+                        }
+                    }
+                } else {
+                    section { class: "task-card task-word-recall__metrics task-metrics--placeholder",
+                        p { {crate::t!("word-recall-metrics-placeholder")} }
+                    }
+                }
+
+                if let Some(err) = error_message_cloned {
+                    div { class: "task-error", {crate::t!("word-recall-error-generic", message = err.clone())} }
+                }
+            }
+        }
+    }
+}
+
+/// Store the main run once immediate recall is done, and hold it for the
+/// delayed recall.
+fn save_immediate(
+    store: &SharedStore,
+    engine: &Signal<WordRecallEngine>,
+    mut qc_flags: Signal<QualityFlags>,
+    mut last_metrics: Signal<Option<WordRecallMetrics>>,
+    mut last_error: Signal<Option<String>>,
+) -> bool {
+    let Some(metrics) = engine.with(|eng| eng.main_metrics()) else {
+        return false;
+    };
+    qc_flags.with_mut(|flags| flags.mark_min_trials(metrics.list_length > 0));
+    let metrics_json = match serde_json::to_value(&metrics) {
+        Ok(json) => json,
+        Err(err) => {
+            last_error.set(Some(format!("Failed to serialise metrics: {err}")));
+            return false;
+        }
+    };
+
+    let record = storage::SummaryRecord::new("word_recall", metrics_json, qc_flags());
+    let trials = if storage::keep_trials() {
+        engine.with(|eng| eng.trial_samples())
+    } else {
+        Vec::new()
+    };
+    if let Err(err) = storage::append_summary(store, &record, &trials) {
+        last_error.set(Some(format!("Failed to persist summary: {err}")));
+        return false;
+    }
+
+    last_error.set(None);
+    last_metrics.set(Some(metrics));
+    *pending_lock() = Some(PendingDelayed {
+        engine: engine.with(|eng| eng.clone()),
+        record,
+    });
+    true
+}
+
+/// Replace the stored run with one carrying the delayed scores, under the
+/// same id and timestamp. The immediate run stays pending until the write
+/// succeeds, so a failed save can be retried.
+fn save_delayed(
+    store: &SharedStore,
+    engine: &Signal<WordRecallEngine>,
+    mut last_metrics: Signal<Option<WordRecallMetrics>>,
+    mut last_error: Signal<Option<String>>,
+) -> bool {
+    let Some(mut record) = pending_lock().as_ref().map(|p| p.record.clone()) else {
+        return false;
+    };
+    let Some(metrics) = engine.with(|eng| eng.main_metrics()) else {
+        return false;
+    };
+    record.metrics = match serde_json::to_value(&metrics) {
+        Ok(json) => json,
+        Err(err) => {
+            last_error.set(Some(format!("Failed to serialise metrics: {err}")));
+            return false;
+        }
+    };
+    let trials = if storage::keep_trials() {
+        engine.with(|eng| eng.trial_samples())
+    } else {
+        Vec::new()
+    };
+
+    // Same id, same metric keys: the write replaces the immediate run in place.
+    if let Err(err) = storage::append_summary(store, &record, &trials) {
+        last_error.set(Some(format!("Failed to persist summary: {err}")));
+        return false;
+    }
+
+    *pending_lock() = None;
+    last_error.set(None);
+    last_metrics.set(Some(metrics));
+    true
+}
+
+fn pending_lock() -> std::sync::MutexGuard<'static, Option<PendingDelayed>> {
+    PENDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The next main run's list: forms rotate with the runs already stored, and
+/// the following form supplies the word-bank foils.
+fn main_list(store: &SharedStore) -> WordList {
+    let completed = storage::load_summaries(store)
+        .map(|records| records.iter().filter(|r| r.task == "word_recall").count())
+        .unwrap_or_default();
+    let form = Form::for_session(completed);
+    WordList {
+        form_id: format!(
+            "{}/v{POOL_VERSION}/{}",
+            crate::i18n::current_language(),
+            form.label()
+        ),
+        words: form_words(form),
+        foils: form_words(form.foil_form()),
+    }
+}
+
+fn practice_list() -> WordList {
+    WordList {
+        form_id: "practice".to_string(),
+        words: parse_pool(&crate::t!("word-recall-v1-practice")),
+        foils: parse_pool(&crate::t!("word-recall-v1-practice-foils")),
+    }
+}
+
+fn form_words(form: Form) -> Vec<String> {
+    parse_pool(&match form {
+        Form::A => crate::t!("word-recall-v1-form-a"),
+        Form::B => crate::t!("word-recall-v1-form-b"),
+        Form::C => crate::t!("word-recall-v1-form-c"),
+        Form::D => crate::t!("word-recall-v1-form-d"),
+    })
+}
+
+/// Cooldown advisory from the most recent stored recall run.
+fn current_readiness(store: &SharedStore) -> Readiness {
+    match storage::load_summaries(store) {
+        Ok(mut records) => {
+            records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            let last = records.iter().find(|r| r.task == "word_recall");
+            readiness::evaluate("word_recall", last)
+        }
+        Err(_) => readiness::evaluate("word_recall", None),
+    }
+}
+
+fn queue_study(
+    sender_slot: Rc<RefCell<Option<UnboundedSender<WordRecallEvent>>>>,
+    outcome: StudyOutcome,
+) {
+    let StudyOutcome::Next { run_id, wait_ms } = outcome else {
+        return;
+    };
+    if let Some(sender) = sender_slot.borrow().as_ref().cloned() {
+        platform::spawn_future(async move {
+            timing::sleep_ms(wait_ms).await;
+            let _ = sender.unbounded_send(WordRecallEvent::StudyTick { run_id });
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+enum WordRecallEvent {
+    StartPractice,
+    StartMain,
+    Abort,
+    StudyTick {
+        run_id: u64,
+    },
+    Submit {
+        text: String,
+        timestamp: crate::core::timing::InstantStamp,
+    },
+    Done,
+    BeginDelayed,
+    SkipDelayed,
+    FocusLost,
+}
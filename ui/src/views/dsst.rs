@@ -0,0 +1,21 @@
+use dioxus::prelude::*;
+
+use crate::tasks::dsst::DsstView;
+
+#[component]
+pub fn Dsst() -> Element {
+    // Subscribe to global language code (if provided) so this view re-renders
+    // when the user switches language elsewhere (e.g. while staying on this page).
+    let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
+    let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
+
+    rsx! {
+        // Hidden marker node retains reactive dependency on language signal.
+        div { style: "display:none", "{_lang_marker}" }
+        section { class: "page page-dsst",
+            h1 { {crate::t!("page-dsst-title")} }
+            p { {crate::t!("page-dsst-intro")} }
+            DsstView {}
+        }
+    }
+}
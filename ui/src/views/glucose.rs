@@ -4,10 +4,19 @@
 //! On desktop, "Sync from reader" pulls the FreeStyle Libre 2 over USB on a shared
 //! device thread (so the UI never freezes) and writes the local store. On
 //! web/mobile the view shows a desktop-only note instead of a sync button.
+//!
+//! An encrypted store shows as locked until the passphrase is entered; a
+//! plaintext one offers to encrypt itself. Journal entries are drawn on the
+//! chart as dashed markers.
+//!
+//! Data comes from the app's shared store handle; syncs, unlocks, and journal
+//! edits arrive through its change feed and refresh the view in place.
 
 use dioxus::prelude::*;
 
-use crate::core::glucose::{self, GlucoseData, GlucosePoint, GlucoseSettings};
+use crate::core::glucose::{self, GlucosePoint, GlucoseSettings, StoreEncryption};
+use crate::core::journal::{self, JournalItem};
+use crate::core::store::{use_shared_store, use_store_changes, SharedStore};
 
 #[derive(Clone, PartialEq)]
 enum SyncStatus {
@@ -28,10 +37,23 @@ pub fn Glucose() -> Element {
     let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
     let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
 
-    let data = use_signal(glucose::load);
+    let store = use_shared_store();
+    let mut data = use_signal(|| glucose::load(&store));
     let sync_status = use_signal(|| SyncStatus::Idle);
     let settings = use_signal(glucose::load_settings);
     let hovered = use_signal(|| None::<usize>);
+    let passphrase = use_signal(String::new);
+    let unlock_error = use_signal(|| None::<String>);
+    let mut journal_items = use_signal(|| journal::load(&store).items);
+    use_store_changes({
+        let store = store.clone();
+        move |changes| {
+            glucose::refresh(&store, &mut data.write(), changes);
+            if journal::affected(changes) {
+                journal_items.set(journal::load(&store).items);
+            }
+        }
+    });
 
     let snapshot = data();
     let count = snapshot.points.len();
@@ -42,7 +64,7 @@ pub fn Glucose() -> Element {
         section { class: "page page-glucose",
             div { class: "results__header",
                 h1 { {crate::t!("glucose-title")} }
-                {sync_action(data, sync_status)}
+                {sync_action(store.clone(), sync_status)}
             }
             p { class: "results__intro", {crate::t!("glucose-intro")} }
 
@@ -56,18 +78,23 @@ pub fn Glucose() -> Element {
 
             if !snapshot.supported {
                 div { class: "results__alert", {crate::t!("glucose-desktop-only")} }
+            } else if snapshot.encryption == StoreEncryption::Locked {
+                div { class: "results__alert", {crate::t!("glucose-locked")} }
             } else if count == 0 && snapshot.error.is_none() {
                 div { class: "results__alert", {crate::t!("glucose-empty")} }
             }
+            if snapshot.supported {
+                {encryption_panel(store.clone(), snapshot.encryption, passphrase, unlock_error)}
+            }
 
             if let Some(p) = latest {
-                {latest_card(&p, &snapshot.unit)}
+                {latest_card(&p, &snapshot.label, &snapshot.unit)}
             }
             if snapshot.supported && count > 0 {
                 {normal_range_editor(settings, snapshot.unit.clone())}
             }
             if count >= 2 {
-                {glucose_chart(&snapshot.points, &snapshot.unit, settings(), hovered)}
+                {glucose_chart(&snapshot.points, &journal_items(), &snapshot.unit, settings(), hovered)}
             }
             if count > 0 {
                 {recent_list(&snapshot.points, &snapshot.unit)}
@@ -108,10 +135,11 @@ fn badge(text: &str) -> Element {
     }
 }
 
-fn latest_card(p: &GlucosePoint, unit: &str) -> Element {
+fn latest_card(p: &GlucosePoint, label: &str, unit: &str) -> Element {
     rsx! {
         div {
             style: "display:flex;align-items:baseline;gap:0.75rem;flex-wrap:wrap;margin:1rem 0 0.25rem;",
+            span { style: "font-size:0.95rem;color:#667085;", "{label}" }
             span { style: "font-size:3rem;font-weight:700;line-height:1;", "{p.value:.0}" }
             span { style: "font-size:1rem;color:#667085;", "{unit}" }
             span { style: "font-size:0.95rem;color:#667085;", "· {p.ts_label}" }
@@ -128,6 +156,66 @@ fn latest_card(p: &GlucosePoint, unit: &str) -> Element {
     }
 }
 
+/// Lock state of the store: a passphrase prompt when locked, a lock button when
+/// unlocked, and an opt-in "encrypt" form for a plaintext store.
+fn encryption_panel(
+    store: SharedStore,
+    state: StoreEncryption,
+    mut pass: Signal<String>,
+    mut error: Signal<Option<String>>,
+) -> Element {
+    // The view reloads from the feed's reset; only errors need handling here.
+    let submit = {
+        let store = store.clone();
+        move |_| {
+            let result = match state {
+                StoreEncryption::Locked => store.unlock(&pass()),
+                _ => store.encrypt(&pass()),
+            };
+            pass.set(String::new());
+            error.set(result.err());
+        }
+    };
+    let lock = move |_| store.lock();
+    let action = match state {
+        StoreEncryption::Locked => crate::t!("glucose-unlock"),
+        _ => crate::t!("glucose-encrypt"),
+    };
+
+    rsx! {
+        div {
+            style: "display:flex;align-items:center;gap:0.5rem;margin:0.75rem 0 0.25rem;font-size:0.9rem;color:#667085;flex-wrap:wrap;",
+            if state == StoreEncryption::Unlocked {
+                span { {crate::t!("glucose-unlocked")} }
+                button { r#type: "button", class: "button", onclick: lock, {crate::t!("glucose-lock")} }
+            } else {
+                input {
+                    r#type: "password",
+                    placeholder: crate::t!("glucose-passphrase"),
+                    style: "width:14rem;padding:0.2rem 0.4rem;",
+                    value: "{pass}",
+                    oninput: move |e| pass.set(e.value()),
+                }
+                button {
+                    r#type: "button",
+                    class: "button",
+                    disabled: pass().is_empty(),
+                    onclick: submit,
+                    "{action}"
+                }
+                if state == StoreEncryption::Plaintext {
+                    span { style: "font-size:0.8rem;", {crate::t!("glucose-encrypt-hint")} }
+                }
+            }
+        }
+        if let Some(err) = error() {
+            div { class: "results__alert results__alert--error",
+                {crate::t!("results-error-prefix")} " {err}"
+            }
+        }
+    }
+}
+
 /// Editor for the personal normal range; commits + persists on change.
 fn normal_range_editor(mut settings: Signal<GlucoseSettings>, unit: String) -> Element {
     let low = settings().normal_low;
@@ -213,6 +301,7 @@ fn date_label(unix_secs: i64) -> Option<String> {
 
 fn glucose_chart(
     points: &[GlucosePoint],
+    entries: &[JournalItem],
     unit: &str,
     settings: GlucoseSettings,
     mut hovered: Signal<Option<usize>>,
@@ -315,9 +404,21 @@ fn glucose_chart(
             }
         })
         .collect();
-    let poly = plotted
+    // Journal entries within the plotted span: (x, glyph, hover title).
+    let markers: Vec<(f64, &str, String)> = entries
         .iter()
-        .map(|pp| format!("{:.1},{:.1}", pp.x, pp.y))
+        .filter(|e| (tmin..=tmax).contains(&e.start_unix))
+        .map(|e| {
+            let title = format!("{} · {}", e.category.label(), e.when_label());
+            (map_x(e.start_unix), e.category.glyph(), title)
+        })
+        .collect();
+    let marker_glyph_y = py0 + 11.0;
+
+    // The trace follows 15-minute means; the dots stay on the raw readings.
+    let poly = glucose::trace(points)
+        .iter()
+        .map(|&(t, v)| format!("{:.1},{:.1}", map_x(t), map_y(v)))
         .collect::<Vec<_>>()
         .join(" ");
 
@@ -396,6 +497,22 @@ fn glucose_chart(
                     }
                 }
 
+                // Journal markers (behind the trace).
+                for (i , (x , glyph , title)) in markers.iter().enumerate() {
+                    g { key: "j{i}",
+                        title { "{title}" }
+                        line {
+                            x1: "{x}", y1: "{py0}", x2: "{x}", y2: "{py1}",
+                            style: "stroke:#a78bfa;stroke-width:1;stroke-dasharray:3 3;",
+                        }
+                        text {
+                            x: "{x}", y: "{marker_glyph_y}",
+                            style: "text-anchor:middle;font-size:12px;",
+                            "{glyph}"
+                        }
+                    }
+                }
+
                 // The glucose trace.
                 polyline {
                     points: "{poly}",
@@ -465,6 +582,12 @@ fn glucose_chart(
                 }
                 span { "🍎 food" }
                 span { "🏃 exercise" }
+                if !markers.is_empty() {
+                    span {
+                        span { style: "color:#a78bfa;", "┊" }
+                        " journal"
+                    }
+                }
                 span {
                     span { style: "display:inline-block;width:0.8rem;height:0.8rem;background:#ef4444;opacity:0.30;border-radius:2px;vertical-align:middle;margin-right:0.2rem;" }
                     "out of range"
@@ -493,7 +616,7 @@ fn recent_list(points: &[GlucosePoint], unit: &str) -> Element {
 // ---- Sync button: desktop wires the reader; web/mobile renders nothing -----
 
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
-fn sync_action(mut data: Signal<GlucoseData>, mut status: Signal<SyncStatus>) -> Element {
+fn sync_action(store: SharedStore, mut status: Signal<SyncStatus>) -> Element {
     let running = matches!(&*status.read(), SyncStatus::Running);
     let onclick = move |_| {
         if matches!(&*status.peek(), SyncStatus::Running) {
@@ -502,10 +625,10 @@ fn sync_action(mut data: Signal<GlucoseData>, mut status: Signal<SyncStatus>) ->
         status.set(SyncStatus::Running);
         // All device I/O runs on the shared, long-lived device thread (macOS pins
         // hidapi to one CFRunLoop); we just await its result on the UI task.
+        let reply = glucose::request_sync(&store);
         spawn(async move {
-            match glucose::request_sync().await {
+            match reply.await {
                 Ok(Ok(report)) => {
-                    data.set(glucose::load());
                     status.set(SyncStatus::Done {
                         serial: report.serial,
                         added: report.added,
@@ -533,6 +656,6 @@ fn sync_action(mut data: Signal<GlucoseData>, mut status: Signal<SyncStatus>) ->
 }
 
 #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-fn sync_action(_data: Signal<GlucoseData>, _status: Signal<SyncStatus>) -> Element {
+fn sync_action(_store: SharedStore, _status: Signal<SyncStatus>) -> Element {
     rsx! {}
 }
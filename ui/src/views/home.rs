@@ -31,6 +31,10 @@ pub fn Home() -> Element {
             ul { class: "page-home__features",
                 li { {crate::t!("home-feature-pvt")} }
                 li { {crate::t!("home-feature-nback")} }
+                li { {crate::t!("home-feature-flanker")} }
+                li { {crate::t!("home-feature-dsst")} }
+                li { {crate::t!("home-feature-word-recall")} }
+                li { {crate::t!("home-feature-task-switch")} }
                 li { {crate::t!("home-feature-glucose")} }
                 li { {crate::t!("home-feature-local")} }
             }
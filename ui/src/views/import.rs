@@ -0,0 +1,671 @@
+//! The Import view: bring history from other apps into the local store.
+//!
+//! Apple Health exports are streamed from the chosen `export.xml` on a
+//! background thread with a progress bar. Spreadsheets (CSV/JSON) are mapped
+//! column by column, previewed as a dry run, then imported; the mapping can be
+//! saved and picked again next time. A bundle of the whole store (or one kind
+//! of data) can be exported to move machines or share, and imported back.
+//! Every sync and import is listed with the rows it still holds, and a bad
+//! one can be rolled back. Imported rows reach the other views through the
+//! store's change feed. Imports are desktop-only; on web/mobile the view shows
+//! a note instead.
+
+use dioxus::prelude::*;
+
+use crate::core::import::{self, ImportStatus};
+use crate::core::store::{use_shared_store, use_store_changes, SharedStore, StoreEncryption};
+
+#[component]
+pub fn Import() -> Element {
+    // Subscribe to the global language code (if provided) so the view re-renders
+    // when the locale changes — mirrors Results.
+    let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
+    let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
+
+    let store = use_shared_store();
+    let mut encryption = use_signal(|| store.encryption());
+    let health_status = use_signal(ImportStatus::default);
+    use_store_changes({
+        let store = store.clone();
+        move |_| encryption.set(store.encryption())
+    });
+
+    rsx! {
+        div { style: "display:none", "{_lang_marker}" }
+        section { class: "page page-import",
+            div { class: "results__header",
+                h1 { {crate::t!("import-title")} }
+            }
+            p { class: "results__intro", {crate::t!("import-intro")} }
+
+            if !import::supported() {
+                div { class: "results__alert", {crate::t!("import-desktop-only")} }
+            } else if encryption() == StoreEncryption::Locked {
+                div { class: "results__alert", {crate::t!("import-locked")} }
+            } else {
+                {apple_health_panel(store.clone(), health_status)}
+                MappedImport {}
+                BundlePanel {}
+                HistoryPanel {}
+            }
+        }
+    }
+}
+
+fn apple_health_panel(store: SharedStore, status: Signal<ImportStatus>) -> Element {
+    rsx! {
+        div { style: "border-top:1px solid #e4e7ec;margin-top:1rem;padding-top:0.75rem;",
+            h2 { style: "font-size:1.1rem;margin:0 0 0.25rem;", {crate::t!("import-apple-health")} }
+            p { style: "font-size:0.9rem;color:#667085;margin:0 0 0.5rem;",
+                {crate::t!("import-apple-health-hint")}
+            }
+            {file_picker(store, status)}
+            {status_line(&status())}
+        }
+    }
+}
+
+/// Progress while running, then the outcome.
+fn status_line(status: &ImportStatus) -> Element {
+    match status {
+        ImportStatus::Idle => rsx! {},
+        ImportStatus::Running { imported, .. } => {
+            let percent = (status.fraction() * 100.0).round() as u32;
+            rsx! {
+                div { style: "display:flex;align-items:center;gap:0.75rem;margin-top:0.5rem;font-size:0.9rem;",
+                    progress { style: "flex:1;max-width:24rem;", max: "1", value: "{status.fraction()}" }
+                    span { {crate::t!("import-running", percent = percent, imported = (*imported))} }
+                }
+            }
+        }
+        ImportStatus::Done { imported, skipped } => rsx! {
+            div { class: "results__alert results__alert--success",
+                {crate::t!("import-done", imported = (*imported), skipped = (*skipped))}
+            }
+        },
+        ImportStatus::Failed(err) => rsx! {
+            div { class: "results__alert results__alert--error",
+                {crate::t!("results-error-prefix")} " {err}"
+            }
+        },
+    }
+}
+
+// ---- File picker: desktop reads the chosen path; web/mobile renders nothing --
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn file_picker(store: SharedStore, status: Signal<ImportStatus>) -> Element {
+    let running = status.read().is_running();
+    let onchange = move |evt: FormEvent| {
+        if status.peek().is_running() {
+            return;
+        }
+        // The desktop webview hands back real paths, so the export is streamed
+        // from disk rather than read into memory.
+        let path = evt.files().and_then(|f| f.files().into_iter().next());
+        if let Some(path) = path {
+            import::apple_health(&store, path.into(), status);
+        }
+    };
+    rsx! {
+        input {
+            r#type: "file",
+            accept: ".xml",
+            disabled: running,
+            onchange: onchange,
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+fn file_picker(_store: SharedStore, _status: Signal<ImportStatus>) -> Element {
+    rsx! {}
+}
+
+// ---- Spreadsheet import: desktop only (the mapping types live in the store) -
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+#[component]
+fn MappedImport() -> Element {
+    use crate::core::import::{ImportMapping, MappedFile, ParsedReadings};
+
+    let store = use_shared_store();
+    let mut file = use_signal(|| None::<MappedFile>);
+    let mut draft = use_signal(import::new_mapping);
+    let mut saved = use_signal(import::load_mappings);
+    let mut preview = use_signal(|| None::<Result<ParsedReadings, String>>);
+    let mut status = use_signal(ImportStatus::default);
+
+    // Any change to the file or mapping invalidates the last dry run.
+    let mut edit = move |f: &dyn Fn(&mut ImportMapping)| {
+        f(&mut draft.write());
+        preview.set(None);
+        status.set(ImportStatus::Idle);
+    };
+    // A complete mapping previews as soon as it has a file, so a saved
+    // mapping plus a new file is ready to import straight away.
+    let mut auto_preview = move || {
+        if let Some(f) = &*file.peek() {
+            if draft.peek().check().is_ok() {
+                preview.set(Some(import::preview(f, &draft.peek())));
+            }
+        }
+    };
+    let pick_file = move |evt: FormEvent| {
+        let Some(path) = evt.files().and_then(|f| f.files().into_iter().next()) else {
+            return;
+        };
+        preview.set(None);
+        status.set(ImportStatus::Idle);
+        match import::read_mapped_file(std::path::Path::new(&path)) {
+            Ok(f) => {
+                file.set(Some(f));
+                auto_preview();
+            }
+            Err(e) => {
+                file.set(None);
+                status.set(ImportStatus::Failed(e));
+            }
+        }
+    };
+    let use_saved = move |evt: FormEvent| {
+        let name = evt.value();
+        let chosen = saved.peek().iter().find(|m| m.name == name).cloned();
+        edit(&|d| *d = chosen.clone().unwrap_or_else(import::new_mapping));
+        auto_preview();
+    };
+    let run_preview = move |_| {
+        let result = match &*file.read() {
+            Some(f) => import::preview(f, &draft.read()),
+            None => return,
+        };
+        preview.set(Some(result));
+    };
+    let run_import = move |_| {
+        let Some(Ok(parsed)) = preview() else {
+            return;
+        };
+        let Some(source) = file() else {
+            return;
+        };
+        status.set(match import::write_mapped(&store, &source, &parsed) {
+            Ok(_) => ImportStatus::Done {
+                imported: parsed.observations.len(),
+                skipped: parsed.skipped,
+            },
+            Err(e) => ImportStatus::Failed(e),
+        });
+        preview.set(None);
+    };
+    let save = move |_| match import::save_mapping(&draft.read()) {
+        Ok(all) => saved.set(all),
+        Err(e) => status.set(ImportStatus::Failed(e)),
+    };
+    let forget = move |_| {
+        let name = draft.read().name.clone();
+        match import::delete_mapping(&name) {
+            Ok(all) => saved.set(all),
+            Err(e) => status.set(ImportStatus::Failed(e)),
+        }
+    };
+
+    let current = draft();
+    let columns = file
+        .read()
+        .as_ref()
+        .map(|f| f.columns.clone())
+        .unwrap_or_default();
+    let is_saved = saved.read().iter().any(|m| m.name == current.name);
+    let ready = matches!(&*preview.read(), Some(Ok(p)) if !p.observations.is_empty());
+    let field = "display:flex;flex-direction:column;gap:0.2rem;font-size:0.85rem;color:#475467;";
+
+    rsx! {
+        div { style: "border-top:1px solid #e4e7ec;margin-top:1.25rem;padding-top:0.75rem;",
+            h2 { style: "font-size:1.1rem;margin:0 0 0.25rem;", {crate::t!("import-mapped")} }
+            p { style: "font-size:0.9rem;color:#667085;margin:0 0 0.5rem;",
+                {crate::t!("import-mapped-hint")}
+            }
+            div { style: "display:flex;gap:0.75rem;flex-wrap:wrap;align-items:flex-end;",
+                input { r#type: "file", accept: ".csv,.json,.txt", onchange: pick_file }
+                label { style: field,
+                    {crate::t!("import-mapped-saved")}
+                    select { value: "{current.name}", onchange: use_saved,
+                        option { value: "", {crate::t!("import-mapped-none")} }
+                        for m in saved() {
+                            option { key: "{m.name}", value: "{m.name}", "{m.name}" }
+                        }
+                    }
+                }
+            }
+            div { style: "display:grid;grid-template-columns:repeat(auto-fill,minmax(12rem,1fr));gap:0.6rem;margin-top:0.75rem;",
+                label { style: field,
+                    {crate::t!("import-mapped-time-column")}
+                    {column_select(&columns, &current.timestamp_column, move |v| edit(&|d| d.timestamp_column = v.clone()))}
+                }
+                label { style: field,
+                    {crate::t!("import-mapped-time-format")}
+                    {time_format_select(&current.timestamp_format, move |v| edit(&|d| d.timestamp_format = v.clone()))}
+                }
+                label { style: field,
+                    {crate::t!("import-mapped-value-column")}
+                    {column_select(&columns, &current.value_column, move |v| edit(&|d| d.value_column = v.clone()))}
+                }
+                label { style: field,
+                    {crate::t!("import-mapped-stream")}
+                    input {
+                        value: "{current.stream}",
+                        placeholder: crate::t!("import-mapped-stream-hint"),
+                        oninput: move |e| edit(&|d| d.stream = e.value()),
+                    }
+                }
+                label { style: field,
+                    {crate::t!("import-mapped-unit")}
+                    input { value: "{current.unit}", oninput: move |e| edit(&|d| d.unit = e.value()) }
+                }
+                label { style: field,
+                    {crate::t!("import-mapped-tz")}
+                    input { value: "{current.tz}", oninput: move |e| edit(&|d| d.tz = e.value()) }
+                }
+                label { style: field,
+                    {crate::t!("import-mapped-name")}
+                    input { value: "{current.name}", oninput: move |e| edit(&|d| d.name = e.value()) }
+                }
+            }
+            div { style: "display:flex;gap:0.5rem;flex-wrap:wrap;margin-top:0.75rem;",
+                button {
+                    r#type: "button",
+                    class: "button",
+                    disabled: file.read().is_none(),
+                    onclick: run_preview,
+                    {crate::t!("import-mapped-preview")}
+                }
+                button {
+                    r#type: "button",
+                    class: "button button--primary",
+                    disabled: !ready,
+                    onclick: run_import,
+                    {crate::t!("import-mapped-import")}
+                }
+                button { r#type: "button", class: "button", onclick: save, {crate::t!("import-mapped-save")} }
+                if is_saved {
+                    button { r#type: "button", class: "button", onclick: forget, {crate::t!("import-mapped-delete")} }
+                }
+            }
+            match preview() {
+                Some(Ok(parsed)) => preview_table(&parsed),
+                Some(Err(err)) => rsx! {
+                    div { class: "results__alert results__alert--error",
+                        {crate::t!("results-error-prefix")} " {err}"
+                    }
+                },
+                None => rsx! {},
+            }
+            {status_line(&status())}
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+#[component]
+fn MappedImport() -> Element {
+    rsx! {}
+}
+
+// ---- Bundles: export/import the store itself, desktop only ----------------
+
+/// Export scopes: bundle name, label key, and the stream domains included
+/// (none = everything, prefs too).
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+const BUNDLE_SCOPES: &[(&str, &str, &[&str])] = &[
+    ("all", "import-bundle-scope-all", &[]),
+    ("glucose", "import-bundle-scope-glucose", &["glucose", "ketone"]),
+    ("health", "import-bundle-scope-health", &["health"]),
+    ("journal", "import-bundle-scope-journal", &["journal"]),
+    (
+        "cognition",
+        "import-bundle-scope-cognition",
+        &[
+            "pvt",
+            "nback2",
+            "flanker",
+            "dsst",
+            "word_recall",
+            "task_switch",
+        ],
+    ),
+];
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+#[component]
+fn BundlePanel() -> Element {
+    let store = use_shared_store();
+    let mut scope = use_signal(|| BUNDLE_SCOPES[0].0);
+    let mut since = use_signal(String::new);
+    let mut outcome = use_signal(|| None::<Result<String, String>>);
+
+    let export = {
+        let store = store.clone();
+        move |_| {
+            let (name, _, domains) = BUNDLE_SCOPES
+                .iter()
+                .find(|s| s.0 == scope())
+                .copied()
+                .unwrap_or(BUNDLE_SCOPES[0]);
+            let from = time::Date::parse(
+                &since(),
+                time::macros::format_description!("[year]-[month]-[day]"),
+            )
+            .ok();
+            outcome.set(Some(import::export_bundle(&store, name, domains, from).map(|path| {
+                crate::t!("import-bundle-exported", path = path.display().to_string())
+            })));
+        }
+    };
+    let pick = move |evt: FormEvent| {
+        let Some(path) = evt.files().and_then(|f| f.files().into_iter().next()) else {
+            return;
+        };
+        outcome.set(Some(import::import_bundle(&store, std::path::Path::new(&path)).map(
+            |report| {
+                crate::t!(
+                    "import-bundle-imported",
+                    observations = report.observations_inserted,
+                    sessions = report.sessions_inserted
+                )
+            },
+        )));
+    };
+    let field = "display:flex;flex-direction:column;gap:0.2rem;font-size:0.85rem;color:#475467;";
+
+    rsx! {
+        div { style: "border-top:1px solid #e4e7ec;margin-top:1.25rem;padding-top:0.75rem;",
+            h2 { style: "font-size:1.1rem;margin:0 0 0.25rem;", {crate::t!("import-bundle")} }
+            p { style: "font-size:0.9rem;color:#667085;margin:0 0 0.5rem;",
+                {crate::t!("import-bundle-hint")}
+            }
+            div { style: "display:flex;gap:0.75rem;flex-wrap:wrap;align-items:flex-end;",
+                label { style: field,
+                    {crate::t!("import-bundle-scope")}
+                    select {
+                        value: "{scope}",
+                        onchange: move |e| {
+                            let chosen = e.value();
+                            if let Some(s) = BUNDLE_SCOPES.iter().find(|s| s.0 == chosen) {
+                                scope.set(s.0);
+                            }
+                        },
+                        for (name, label, _) in BUNDLE_SCOPES.iter().copied() {
+                            option { key: "{name}", value: name, {crate::i18n::tr(label)} }
+                        }
+                    }
+                }
+                label { style: field,
+                    {crate::t!("import-bundle-since")}
+                    input { r#type: "date", value: "{since}", oninput: move |e| since.set(e.value()) }
+                }
+                button { r#type: "button", class: "button button--primary", onclick: export,
+                    {crate::t!("import-bundle-export")}
+                }
+                label { style: field,
+                    {crate::t!("import-bundle-import")}
+                    input { r#type: "file", accept: ".zip", onchange: pick }
+                }
+            }
+            match outcome() {
+                Some(Ok(message)) => rsx! {
+                    div { class: "results__alert results__alert--success", "{message}" }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "results__alert results__alert--error",
+                        {crate::t!("results-error-prefix")} " {err}"
+                    }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+#[component]
+fn BundlePanel() -> Element {
+    rsx! {}
+}
+
+// ---- History: every sync and import, with rollback, desktop only ----------
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+#[component]
+fn HistoryPanel() -> Element {
+    let store = use_shared_store();
+    let mut batches = use_signal(|| import::history(&store));
+    // The first click on "Roll back" arms it; the second removes the rows.
+    let mut confirming = use_signal(|| None::<String>);
+    let mut outcome = use_signal(|| None::<Result<usize, String>>);
+    use_store_changes({
+        let store = store.clone();
+        move |_| batches.set(import::history(&store))
+    });
+
+    let rows = match batches() {
+        Ok(rows) => rows,
+        Err(err) => {
+            return rsx! {
+                div { class: "results__alert results__alert--error",
+                    {crate::t!("results-error-prefix")} " {err}"
+                }
+            }
+        }
+    };
+    let cell = "padding:0.2rem 0.5rem;text-align:left;";
+
+    rsx! {
+        div { style: "border-top:1px solid #e4e7ec;margin-top:1.25rem;padding-top:0.75rem;",
+            h2 { style: "font-size:1.1rem;margin:0 0 0.25rem;", {crate::t!("import-history")} }
+            p { style: "font-size:0.9rem;color:#667085;margin:0 0 0.5rem;",
+                {crate::t!("import-history-hint")}
+            }
+            if rows.is_empty() {
+                p { style: "font-size:0.9rem;", {crate::t!("import-history-empty")} }
+            } else {
+                table { style: "font-size:0.85rem;border-collapse:collapse;",
+                    thead {
+                        tr {
+                            th { style: cell, {crate::t!("import-history-when")} }
+                            th { style: cell, {crate::t!("import-history-kind")} }
+                            th { style: cell, {crate::t!("import-history-source")} }
+                            th { style: cell, {crate::t!("import-history-rows")} }
+                            th { style: cell }
+                        }
+                    }
+                    tbody {
+                        for summary in rows {
+                            tr { key: "{summary.batch.id}", title: "{summary.batch.app_version}",
+                                td { style: cell, {batch_time(&summary.batch)} }
+                                td { style: cell, {kind_label(&summary.batch.kind)} }
+                                td { style: cell, {summary.batch.detail.clone().unwrap_or_default()} }
+                                td { style: cell, "{summary.rows}" }
+                                td { style: cell,
+                                    if summary.batch.rolled_back_at.is_some() {
+                                        {crate::t!("import-history-rolled-back")}
+                                    } else if summary.rows > 0 {
+                                        button {
+                                            r#type: "button",
+                                            class: "button button--ghost",
+                                            onclick: {
+                                                let id = summary.batch.id.clone();
+                                                let store = store.clone();
+                                                move |_| {
+                                                    if confirming.peek().as_deref() != Some(id.as_str()) {
+                                                        confirming.set(Some(id.clone()));
+                                                        return;
+                                                    }
+                                                    confirming.set(None);
+                                                    outcome.set(Some(import::rollback(&store, &id)));
+                                                }
+                                            },
+                                            if confirming().as_deref() == Some(summary.batch.id.as_str()) {
+                                                {crate::t!("import-history-confirm")}
+                                            } else {
+                                                {crate::t!("import-history-rollback")}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            match outcome() {
+                Some(Ok(removed)) => rsx! {
+                    div { class: "results__alert results__alert--success",
+                        {crate::t!("import-history-removed", removed = removed)}
+                    }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "results__alert results__alert--error",
+                        {crate::t!("results-error-prefix")} " {err}"
+                    }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+/// When a batch ran, as recorded (UTC).
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn batch_time(batch: &looplace_store::IngestBatch) -> String {
+    batch
+        .created_at
+        .format(time::macros::format_description!(
+            "[year]-[month]-[day] [hour]:[minute] UTC"
+        ))
+        .unwrap_or_default()
+}
+
+/// A batch kind's label; kinds this build doesn't know show as recorded.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn kind_label(kind: &str) -> String {
+    use looplace_store::provenance::{
+        APPLE_HEALTH, DEXCOM_CSV, LIBREVIEW_CSV, MAPPED_FILE, NIGHTSCOUT_JSON, READER_SYNC,
+    };
+
+    let key = match kind {
+        READER_SYNC => "import-history-kind-reader",
+        LIBREVIEW_CSV => "import-history-kind-libreview",
+        DEXCOM_CSV => "import-history-kind-dexcom",
+        NIGHTSCOUT_JSON => "import-history-kind-nightscout",
+        APPLE_HEALTH => "import-history-kind-apple-health",
+        MAPPED_FILE => "import-history-kind-mapped",
+        other => return other.to_string(),
+    };
+    crate::i18n::tr(key)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+#[component]
+fn HistoryPanel() -> Element {
+    rsx! {}
+}
+
+/// The file's columns, keeping a saved mapping's column even before a file
+/// is chosen.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn column_select(
+    columns: &[String],
+    current: &str,
+    mut on_pick: impl FnMut(String) + 'static,
+) -> Element {
+    let mut options = columns.to_vec();
+    if !current.is_empty() && !options.iter().any(|c| c == current) {
+        options.insert(0, current.to_string());
+    }
+    rsx! {
+        select { value: "{current}", onchange: move |e| on_pick(e.value()),
+            option { value: "", {crate::t!("import-mapped-choose-column")} }
+            for c in options {
+                option { key: "{c}", value: "{c}", "{c}" }
+            }
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn time_format_select(
+    current: &import::TimestampFormat,
+    mut on_pick: impl FnMut(import::TimestampFormat) + Clone + 'static,
+) -> Element {
+    use import::TimestampFormat;
+
+    let key = match current {
+        TimestampFormat::Pattern(_) => "pattern",
+        TimestampFormat::Rfc3339 => "rfc3339",
+        TimestampFormat::UnixSeconds => "unix_seconds",
+        TimestampFormat::UnixMillis => "unix_millis",
+    };
+    let pattern = match current {
+        TimestampFormat::Pattern(p) => Some(p.clone()),
+        _ => None,
+    };
+    let mut on_pattern = on_pick.clone();
+    rsx! {
+        select {
+            value: "{key}",
+            onchange: move |e| {
+                on_pick(match e.value().as_str() {
+                    "rfc3339" => TimestampFormat::Rfc3339,
+                    "unix_seconds" => TimestampFormat::UnixSeconds,
+                    "unix_millis" => TimestampFormat::UnixMillis,
+                    _ => TimestampFormat::Pattern("[year]-[month]-[day] [hour]:[minute]".into()),
+                })
+            },
+            option { value: "pattern", {crate::t!("import-mapped-format-pattern")} }
+            option { value: "rfc3339", {crate::t!("import-mapped-format-rfc3339")} }
+            option { value: "unix_seconds", {crate::t!("import-mapped-format-unix-s")} }
+            option { value: "unix_millis", {crate::t!("import-mapped-format-unix-ms")} }
+        }
+        if let Some(p) = pattern {
+            input {
+                value: "{p}",
+                title: crate::t!("import-mapped-pattern-hint"),
+                oninput: move |e| on_pattern(TimestampFormat::Pattern(e.value())),
+            }
+        }
+    }
+}
+
+/// Dry-run result: counts, then the first few rows as they'd be stored.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn preview_table(parsed: &import::ParsedReadings) -> Element {
+    use time::macros::format_description;
+
+    let fmt = format_description!("[year]-[month]-[day] [hour]:[minute]");
+    let ready = parsed.observations.len();
+    let skipped = parsed.skipped;
+    let rows: Vec<(String, String)> = parsed
+        .observations
+        .iter()
+        .take(5)
+        .map(|o| {
+            let at = o.timestamp.format(&fmt).unwrap_or_default();
+            (at, format!("{} {}", o.value, o.unit))
+        })
+        .collect();
+    rsx! {
+        div { class: "results__alert results__alert--info",
+            {crate::t!("import-mapped-ready", ready = ready, skipped = skipped)}
+        }
+        div { style: "font-size:0.9rem;",
+            for (i , (at , value)) in rows.into_iter().enumerate() {
+                div {
+                    key: "{i}",
+                    style: "display:flex;justify-content:space-between;max-width:24rem;padding:0.25rem 0;border-bottom:1px solid #f2f4f7;",
+                    span { style: "color:#475467;", "{at}" }
+                    span { style: "font-weight:600;", "{value}" }
+                }
+            }
+        }
+    }
+}
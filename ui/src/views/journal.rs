@@ -0,0 +1,222 @@
+//! The Journal view: log meals, medication, exercise, sleep, and notes, and
+//! edit or delete past entries. Entries show up as markers on the glucose chart
+//! and the cognition trend.
+//!
+//! Like glucose, the journal lives in the desktop app's local store (read
+//! through the shared handle, refreshed from its change feed); on web/mobile
+//! the view shows a desktop-only note.
+
+use dioxus::prelude::*;
+
+use crate::core::journal::{self, JournalCategory, JournalDraft, JournalItem};
+use crate::core::store::{use_shared_store, use_store_changes, SharedStore};
+
+#[component]
+pub fn Journal() -> Element {
+    // Subscribe to the global language code (if provided) so the view re-renders
+    // when the locale changes — mirrors Results.
+    let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
+    let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
+
+    let store = use_shared_store();
+    let mut data = use_signal(|| journal::load(&store));
+    let draft = use_signal(JournalDraft::starting_now);
+    let error = use_signal(|| None::<String>);
+    use_store_changes({
+        let store = store.clone();
+        move |changes| {
+            if journal::affected(changes) {
+                data.set(journal::load(&store));
+            }
+        }
+    });
+
+    let snapshot = data();
+
+    rsx! {
+        div { style: "display:none", "{_lang_marker}" }
+        section { class: "page page-journal",
+            div { class: "results__header",
+                h1 { {crate::t!("journal-title")} }
+            }
+            p { class: "results__intro", {crate::t!("journal-intro")} }
+
+            if let Some(err) = snapshot.error.clone() {
+                div { class: "results__alert results__alert--error",
+                    {crate::t!("results-error-prefix")} " {err}"
+                }
+            }
+
+            if !snapshot.supported {
+                div { class: "results__alert", {crate::t!("journal-desktop-only")} }
+            } else if snapshot.locked {
+                div { class: "results__alert", {crate::t!("journal-locked")} }
+            } else {
+                {entry_form(store.clone(), draft, error)}
+                if snapshot.items.is_empty() {
+                    div { class: "results__alert", {crate::t!("journal-empty")} }
+                } else {
+                    {entry_list(&snapshot.items, store.clone(), draft, error)}
+                }
+            }
+        }
+    }
+}
+
+/// Add/edit form. Saving a draft opened from an entry replaces that entry.
+fn entry_form(
+    store: SharedStore,
+    mut draft: Signal<JournalDraft>,
+    mut error: Signal<Option<String>>,
+) -> Element {
+    let current = draft();
+    let editing = current.id.is_some();
+    let unit = journal::dose_unit(current.category);
+    let save = move |_| match journal::save(&store, &draft()) {
+        Ok(()) => {
+            error.set(None);
+            draft.set(JournalDraft::starting_now());
+        }
+        Err(e) => error.set(Some(e)),
+    };
+    let cancel = move |_| {
+        error.set(None);
+        draft.set(JournalDraft::starting_now());
+    };
+    let field = "display:flex;flex-direction:column;gap:0.2rem;font-size:0.85rem;color:#667085;";
+
+    rsx! {
+        div {
+            style: "display:flex;gap:0.75rem;flex-wrap:wrap;align-items:flex-end;margin:1rem 0 0.5rem;",
+            label { style: field,
+                {crate::t!("journal-category")}
+                select {
+                    value: current.category.key(),
+                    onchange: move |e| {
+                        if let Some(category) = JournalCategory::from_key(&e.value()) {
+                            draft.write().category = category;
+                        }
+                    },
+                    for category in JournalCategory::ALL {
+                        option {
+                            value: category.key(),
+                            selected: category == current.category,
+                            "{category.glyph()} {category.label()}"
+                        }
+                    }
+                }
+            }
+            label { style: field,
+                {crate::t!("journal-start")}
+                input {
+                    r#type: "datetime-local",
+                    value: "{current.start}",
+                    oninput: move |e| draft.write().start = e.value(),
+                }
+            }
+            label { style: field,
+                {crate::t!("journal-end")}
+                input {
+                    r#type: "datetime-local",
+                    value: "{current.end}",
+                    oninput: move |e| draft.write().end = e.value(),
+                }
+            }
+            label { style: field,
+                {crate::t!("journal-amount")}
+                span {
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        step: "any",
+                        style: "width:6rem;padding:0.2rem 0.4rem;",
+                        value: "{current.dose}",
+                        oninput: move |e| draft.write().dose = e.value(),
+                    }
+                    " {unit}"
+                }
+            }
+            label { style: "{field}flex:1 1 14rem;",
+                {crate::t!("journal-notes")}
+                input {
+                    r#type: "text",
+                    style: "padding:0.2rem 0.4rem;",
+                    value: "{current.notes}",
+                    oninput: move |e| draft.write().notes = e.value(),
+                }
+            }
+            button {
+                r#type: "button",
+                class: "button button--primary",
+                onclick: save,
+                if editing {
+                    {crate::t!("journal-update")}
+                } else {
+                    {crate::t!("journal-add")}
+                }
+            }
+            if editing {
+                button { r#type: "button", class: "button", onclick: cancel, {crate::t!("journal-cancel")} }
+            }
+        }
+        if let Some(err) = error() {
+            div { class: "results__alert results__alert--error",
+                {crate::t!("results-error-prefix")} " {err}"
+            }
+        }
+    }
+}
+
+/// Entries, newest first, each with edit and delete actions.
+fn entry_list(
+    items: &[JournalItem],
+    store: SharedStore,
+    mut draft: Signal<JournalDraft>,
+    mut error: Signal<Option<String>>,
+) -> Element {
+    rsx! {
+        div { style: "border-top:1px solid #e4e7ec;margin-top:0.5rem;",
+            for item in items.iter().rev().cloned() {
+                div {
+                    key: "{item.id}",
+                    style: "display:flex;align-items:baseline;gap:0.75rem;padding:0.4rem 0;border-bottom:1px solid #f2f4f7;font-size:0.9rem;flex-wrap:wrap;",
+                    span { style: "color:#475467;min-width:11rem;", "{item.when_label()}" }
+                    span { style: "font-weight:600;", "{item.category.glyph()} {item.category.label()}" }
+                    if let Some(dose) = item.dose {
+                        span { "{dose} {journal::dose_unit(item.category)}" }
+                    }
+                    span { style: "color:#667085;flex:1 1 10rem;", "{item.notes}" }
+                    button {
+                        r#type: "button",
+                        class: "button button--ghost",
+                        onclick: {
+                            let item = item.clone();
+                            move |_| {
+                                error.set(None);
+                                draft.set(JournalDraft::from_item(&item));
+                            }
+                        },
+                        {crate::t!("journal-edit")}
+                    }
+                    button {
+                        r#type: "button",
+                        class: "button button--ghost",
+                        onclick: {
+                            let id = item.id.clone();
+                            let store = store.clone();
+                            move |_| match journal::delete(&store, &id) {
+                                Ok(()) => {
+                                    if draft.peek().id.as_deref() == Some(id.as_str()) {
+                                        draft.set(JournalDraft::starting_now());
+                                    }
+                                }
+                                Err(e) => error.set(Some(e)),
+                            }
+                        },
+                        {crate::t!("journal-delete")}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -7,8 +7,26 @@ pub use pvt::Pvt;
 mod nback;
 pub use nback::NBack2;
 
+mod flanker;
+pub use flanker::Flanker;
+
+mod dsst;
+pub use dsst::Dsst;
+
+mod word_recall;
+pub use word_recall::WordRecall;
+
+mod task_switch;
+pub use task_switch::TaskSwitch;
+
 mod results;
 pub use results::Results;
 
 mod glucose;
 pub use glucose::Glucose;
+
+mod journal;
+pub use journal::Journal;
+
+mod import;
+pub use import::Import;
@@ -1,5 +1,9 @@
 use dioxus::prelude::*;
 
+use crate::core::journal;
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+use crate::core::storage;
+use crate::core::store::{use_shared_store, use_store_changes};
 use crate::results::{
     ResultsDetailPanel, ResultsExportPanel, ResultsList, ResultsSparklines, ResultsState,
 };
@@ -11,7 +15,22 @@ pub fn Results() -> Element {
     let _lang_code: Option<Signal<String>> = try_use_context::<Signal<String>>();
     let _lang_marker = _lang_code.as_ref().map(|s| s()).unwrap_or_default();
 
-    let results_state = use_signal(ResultsState::load);
+    let store = use_shared_store();
+    let mut results_state = use_signal(|| ResultsState::load(&store));
+    // Journal entries to mark on the trends.
+    let mut journal_items = use_signal(|| journal::load(&store).items);
+    // Runs finished or deleted elsewhere, and journal edits, arrive via the feed.
+    use_store_changes({
+        let store = store.clone();
+        move |changes| {
+            if changes.iter().any(|c| c.touches_sessions()) {
+                results_state.set(ResultsState::load(&store));
+            }
+            if journal::affected(changes) {
+                journal_items.set(journal::load(&store).items);
+            }
+        }
+    });
     // Initialize the selection to the first run *once*, via a non-subscribing
     // peek in the initializer — never write to the signal during render.
     let selected_id = use_signal(|| {
@@ -39,7 +58,7 @@ pub fn Results() -> Element {
     let refresh = {
         let (mut state, mut selection) = (results_state, selected_id);
         move |_| {
-            let reloaded = ResultsState::load();
+            let reloaded = ResultsState::load(&store);
             selection.set(reloaded.records.first().map(|record| record.id.clone()));
             state.set(reloaded);
         }
@@ -61,6 +80,7 @@ pub fn Results() -> Element {
             p { class: "results__intro",
                 {crate::t!("results-page-intro")}
             }
+            KeepTrialsToggle {}
 
             if let Some(err) = snapshot.error.clone() {
                 div { class: "results__alert results__alert--error", {crate::t!("results-error-prefix")} " {err}" }
@@ -79,8 +99,37 @@ pub fn Results() -> Element {
                 }
             }
 
-            ResultsSparklines { records: snapshot.records.clone() }
+            ResultsSparklines { records: snapshot.records.clone(), journal: journal_items() }
             ResultsExportPanel { records: snapshot.records }
         }
     }
 }
+
+/// The opt-in for keeping each new run's raw trials (desktop only).
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+#[component]
+fn KeepTrialsToggle() -> Element {
+    let mut keep = use_signal(storage::keep_trials);
+    rsx! {
+        label { class: "results__option",
+            input {
+                r#type: "checkbox",
+                checked: keep(),
+                onchange: move |e| {
+                    let on = e.checked();
+                    storage::set_keep_trials(on);
+                    keep.set(on);
+                },
+            }
+            " "
+            {crate::t!("results-keep-trials")}
+        }
+        p { class: "results__hint", {crate::t!("results-keep-trials-hint")} }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+#[component]
+fn KeepTrialsToggle() -> Element {
+    rsx! {}
+}
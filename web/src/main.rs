@@ -2,7 +2,9 @@ use dioxus::prelude::*;
 
 use ui::components::app_navbar::{register_nav, NavBuilder};
 use ui::components::AppNavbar;
-use ui::views::{Glucose, Home, NBack2, Pvt, Results};
+use ui::views::{
+    Dsst, Flanker, Glucose, Home, Import, Journal, NBack2, Pvt, Results, TaskSwitch, WordRecall,
+};
 
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
@@ -14,10 +16,22 @@ enum Route {
     Pvt {},
     #[route("/test/nback")]
     NBack2 {},
+    #[route("/test/flanker")]
+    Flanker {},
+    #[route("/test/dsst")]
+    Dsst {},
+    #[route("/test/word-recall")]
+    WordRecall {},
+    #[route("/test/task-switch")]
+    TaskSwitch {},
     #[route("/results")]
     Results {},
     #[route("/glucose")]
     Glucose {},
+    #[route("/journal")]
+    Journal {},
+    #[route("/import")]
+    Import {},
 }
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
@@ -44,6 +58,34 @@ fn nav_nback(label: &str) -> Element {
         "{label}"
     })
 }
+fn nav_flanker(label: &str) -> Element {
+    rsx!(Link {
+        class: "navbar__link",
+        to: Route::Flanker {},
+        "{label}"
+    })
+}
+fn nav_dsst(label: &str) -> Element {
+    rsx!(Link {
+        class: "navbar__link",
+        to: Route::Dsst {},
+        "{label}"
+    })
+}
+fn nav_word_recall(label: &str) -> Element {
+    rsx!(Link {
+        class: "navbar__link",
+        to: Route::WordRecall {},
+        "{label}"
+    })
+}
+fn nav_task_switch(label: &str) -> Element {
+    rsx!(Link {
+        class: "navbar__link",
+        to: Route::TaskSwitch {},
+        "{label}"
+    })
+}
 fn nav_results(label: &str) -> Element {
     rsx!(Link {
         class: "navbar__link",
@@ -58,6 +100,20 @@ fn nav_glucose(label: &str) -> Element {
         "{label}"
     })
 }
+fn nav_journal(label: &str) -> Element {
+    rsx!(Link {
+        class: "navbar__link",
+        to: Route::Journal {},
+        "{label}"
+    })
+}
+fn nav_import(label: &str) -> Element {
+    rsx!(Link {
+        class: "navbar__link",
+        to: Route::Import {},
+        "{label}"
+    })
+}
 
 fn main() {
     dioxus::launch(App);
@@ -80,13 +136,23 @@ fn App() -> Element {
     }
     use_context_provider(|| lang_code);
 
+    // One long-lived handle on the local store (and its change feed) for
+    // every view; the store stays open for the app's lifetime.
+    ui::core::store::use_store_provider();
+
     // Register localized navigation builder (Option A)
     register_nav(NavBuilder {
         home: nav_home,
         pvt: nav_pvt,
         nback: nav_nback,
+        flanker: nav_flanker,
+        dsst: nav_dsst,
+        word_recall: nav_word_recall,
+        task_switch: nav_task_switch,
         results: nav_results,
         glucose: nav_glucose,
+        journal: nav_journal,
+        import: nav_import,
     });
 
     rsx! {